@@ -0,0 +1,53 @@
+mod common;
+
+use common::bench;
+use csc263::cache::{replay, Cache};
+use csc263::clock_cache::ClockCache;
+use csc263::lfu_cache::LfuCache;
+use csc263::lru_cache::LruCache;
+use csc263::rng::Rng;
+
+const N: usize = 200_000;
+
+/// Keys drawn with probability falling off like `1 / rank`, roughly Zipf distributed
+fn zipf(keys: u64, rng: &mut Rng) -> Vec<u64> {
+    // Inverting the continuous approximation `rank = keys^u`
+    (0..N).map(|_| (keys as f64).powf(rng.next_f64()) as u64 - 1).collect()
+}
+
+/// A working set that slowly moves, mixed with one-off scans through cold keys
+fn shifting(rng: &mut Rng) -> Vec<u64> {
+    let mut trace = Vec::with_capacity(N);
+    let mut cold = 1_000_000;
+    while trace.len() < N {
+        let base = trace.len() as u64 / 1000;
+        for _ in 0..900 {
+            trace.push(base + rng.gen_range(200));
+        }
+        for _ in 0..100 {
+            trace.push(cold);
+            cold += 1;
+        }
+    }
+    trace
+}
+
+/// Prints the hit rate of a cache on a trace, and times the replay
+fn run<C: Cache<u64, u64>, F: Fn() -> C>(name: &str, trace: &[u64], new: F) {
+    let stats = replay(&mut new(), trace, |&k| k);
+    bench(&format!("{} hit rate {:.3}", name, stats.hit_rate()), 3, || replay(&mut new(), trace, |&k| k));
+}
+
+fn main() {
+    let mut rng = Rng::new(1);
+    let traces = [("zipf over 100000 keys", zipf(100_000, &mut rng)), ("shifting working set with scans", shifting(&mut rng))];
+    for (name, trace) in traces.iter() {
+        for &capacity in [100, 1000].iter() {
+            println!("{}, capacity {}", name, capacity);
+            run("lru", trace, || LruCache::new(capacity));
+            run("lfu", trace, || LfuCache::new(capacity));
+            run("clock", trace, || ClockCache::new(capacity));
+            println!();
+        }
+    }
+}
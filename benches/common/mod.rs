@@ -0,0 +1,23 @@
+use std::time::{Duration, Instant};
+
+/// Runs a closure a number of times and prints the mean time per run
+///
+/// A small stand-in for a benchmarking harness, these benches run with `harness = false`.
+///
+/// # Arguments
+///
+/// * `name` - Label printed with the result
+/// * `runs` - Number of times to run `f`
+/// * `f` - Code to time
+pub fn bench<R, F: FnMut() -> R>(name: &str, runs: u32, mut f: F) -> Duration {
+    // Warm up caches and the branch predictor before timing
+    std::hint::black_box(f());
+
+    let start = Instant::now();
+    for _ in 0..runs {
+        std::hint::black_box(f());
+    }
+    let mean = start.elapsed() / runs;
+    println!("{:<48} {:>12.3?}", name, mean);
+    mean
+}
@@ -0,0 +1,51 @@
+mod common;
+
+use common::bench;
+use csc263::bloom_filter::BloomFilter;
+use csc263::cuckoo_filter::CuckooFilter;
+
+const N: u64 = 100_000;
+
+fn main() {
+    for &p in [0.01, 0.001, 0.0001].iter() {
+        println!("false positive rate {}", p);
+
+        bench("bloom insert", 10, || {
+            let mut filter = BloomFilter::with_seed(N as usize, p, 1);
+            for i in 0..N {
+                filter.insert(&i);
+            }
+            filter
+        });
+        bench("cuckoo insert", 10, || {
+            let mut filter = CuckooFilter::with_seed(N as usize, p, 1);
+            for i in 0..N {
+                filter.insert(&i);
+            }
+            filter
+        });
+
+        let mut bloom = BloomFilter::with_seed(N as usize, p, 1);
+        let mut cuckoo = CuckooFilter::with_seed(N as usize, p, 1);
+        for i in 0..N {
+            bloom.insert(&i);
+            cuckoo.insert(&i);
+        }
+        bench("bloom contains", 10, || (0..2 * N).filter(|i| bloom.contains(i)).count());
+        bench("cuckoo contains", 10, || (0..2 * N).filter(|i| cuckoo.contains(i)).count());
+
+        let bloom_fp = (N..11 * N).filter(|i| bloom.contains(i)).count() as f64 / (10 * N) as f64;
+        let cuckoo_fp = (N..11 * N).filter(|i| cuckoo.contains(i)).count() as f64 / (10 * N) as f64;
+        println!(
+            "bloom  {:>6.2} bits/item, measured rate {:.5}",
+            bloom.num_bits() as f64 / N as f64,
+            bloom_fp
+        );
+        println!(
+            "cuckoo {:>6.2} bits/item, measured rate {:.5}",
+            cuckoo.num_bits() as f64 / N as f64,
+            cuckoo_fp
+        );
+        println!();
+    }
+}
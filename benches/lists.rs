@@ -0,0 +1,90 @@
+mod common;
+
+use common::bench;
+use csc263::rng::Rng;
+use csc263::unrolled_list::UnrolledList;
+use std::collections::LinkedList;
+
+const N: usize = 20_000;
+
+/// Random operations of a mixed workload, an index and whether to insert or remove there
+fn workload(rng: &mut Rng) -> Vec<(usize, bool)> {
+    let mut len = N;
+    (0..N)
+        .map(|_| {
+            let insert = len == 0 || rng.gen_bool(0.6);
+            let i = rng.gen_range(len as u64 + insert as u64) as usize;
+            if insert {
+                len += 1;
+            } else {
+                len -= 1;
+            }
+            (i, insert)
+        })
+        .collect()
+}
+
+/// Inserts into the middle of a `LinkedList`, which has to walk to `i` and relink the halves
+fn linked_insert(list: &mut LinkedList<usize>, i: usize, item: usize) {
+    let mut back = list.split_off(i);
+    list.push_back(item);
+    list.append(&mut back);
+}
+
+/// Removes from the middle of a `LinkedList` the same way
+fn linked_remove(list: &mut LinkedList<usize>, i: usize) {
+    let mut back = list.split_off(i);
+    back.pop_front();
+    list.append(&mut back);
+}
+
+fn main() {
+    let mut rng = Rng::new(1);
+    let operations = workload(&mut rng);
+    println!("{} items, {} random inserts and removes, then a full scan", N, N);
+
+    bench("vec", 3, || {
+        let mut list: Vec<usize> = (0..N).collect();
+        for &(i, insert) in &operations {
+            if insert {
+                list.insert(i, i);
+            } else {
+                list.remove(i);
+            }
+        }
+        list.iter().sum::<usize>()
+    });
+    for capacity in [16, 64, 256] {
+        bench(&format!("unrolled list, {} items per node", capacity), 3, || {
+            let mut list = UnrolledList::with_node_capacity(capacity);
+            list.extend(0..N);
+            for &(i, insert) in &operations {
+                if insert {
+                    list.insert(i, i);
+                } else {
+                    list.remove(i);
+                }
+            }
+            list.iter().sum::<usize>()
+        });
+    }
+    bench("linked list", 3, || {
+        let mut list: LinkedList<usize> = (0..N).collect();
+        for &(i, insert) in &operations {
+            if insert {
+                linked_insert(&mut list, i, i);
+            } else {
+                linked_remove(&mut list, i);
+            }
+        }
+        list.iter().sum::<usize>()
+    });
+    println!();
+
+    let vec: Vec<usize> = (0..N).collect();
+    let unrolled: UnrolledList<usize> = (0..N).collect();
+    let linked: LinkedList<usize> = (0..N).collect();
+    bench("vec scan", 10, || vec.iter().sum::<usize>());
+    bench("unrolled list scan", 10, || unrolled.iter().sum::<usize>());
+    bench("linked list scan", 10, || linked.iter().sum::<usize>());
+}
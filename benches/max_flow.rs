@@ -0,0 +1,56 @@
+mod common;
+
+use common::bench;
+use csc263::dinic::dinic;
+use csc263::edmonds_karp::edmonds_karp;
+use csc263::flow_network::FlowNetwork;
+use csc263::rng::Rng;
+
+/// Builds a network of `layers` layers of `width` nodes, with random edges between neighbouring
+/// layers, a source feeding the first layer, and a sink draining the last
+fn layered(layers: usize, width: usize, degree: usize, rng: &mut Rng) -> FlowNetwork<u64> {
+    let mut network = FlowNetwork::new(layers * width + 2);
+    let (s, t) = (layers * width, layers * width + 1);
+    for x in 0..width {
+        network.add_edge(s, x, 1000);
+        network.add_edge((layers - 1) * width + x, t, 1000);
+    }
+    for l in 0..layers - 1 {
+        for x in 0..width {
+            for _ in 0..degree {
+                let y = rng.gen_range(width as u64) as usize;
+                network.add_edge(l * width + x, (l + 1) * width + y, 1 + rng.gen_range(100));
+            }
+        }
+    }
+    network
+}
+
+/// Times both algorithms on the same network, clearing the flow before every run
+fn compare(name: &str, network: &FlowNetwork<u64>, s: usize, t: usize, runs: u32) {
+    println!("{}", name);
+    let mut net = network.clone();
+    bench("edmonds-karp", runs, || {
+        net.reset_flow();
+        edmonds_karp(&mut net, s, t).value
+    });
+    bench("dinic", runs, || {
+        net.reset_flow();
+        dinic(&mut net, s, t).value
+    });
+    println!();
+}
+
+fn main() {
+    let mut rng = Rng::new(1);
+    for &(n, m) in [(1_000, 10_000), (5_000, 50_000)].iter() {
+        let mut network = FlowNetwork::new(n);
+        for _ in 0..m {
+            network.add_edge(rng.gen_range(n as u64) as usize, rng.gen_range(n as u64) as usize, 1 + rng.gen_range(1000));
+        }
+        compare(&format!("random network, {} nodes, {} edges", n, m), &network, 0, n - 1, 5);
+    }
+
+    let network = layered(50, 100, 4, &mut rng);
+    compare("layered network, 50 layers of 100 nodes", &network, 5_000, 5_001, 3);
+}
@@ -0,0 +1,45 @@
+mod common;
+
+use common::bench;
+use csc263::intro_sort::intro_sort;
+use csc263::merge_sort::merge_sort;
+use csc263::parallel_sort::{par_heapify_by, par_merge_sort, par_quick_sort};
+use csc263::rng::Rng;
+
+/// Sorts and heapifies four million random integers with the parallel sorts on rayon pools of more
+/// and more threads, next to the sequential sorts they fall back on
+fn main() {
+    let mut rng = Rng::new(1);
+    let input: Vec<u64> = (0..4_000_000).map(|_| rng.next_u64()).collect();
+    let cores = std::thread::available_parallelism().map_or(1, |n| n.get());
+    println!("4000000 random u64s, {} cores available", cores);
+
+    bench("mergesort", 3, || {
+        let mut v = input.clone();
+        merge_sort(&mut v);
+        v
+    });
+    bench("introsort", 3, || {
+        let mut v = input.clone();
+        intro_sort(&mut v);
+        v
+    });
+    let mut threads = 1;
+    while threads <= cores.max(8) {
+        let pool = rayon::ThreadPoolBuilder::new().num_threads(threads).build().unwrap();
+        bench(&format!("parallel mergesort, {} threads", threads), 3, || {
+            let mut v = input.clone();
+            pool.install(|| par_merge_sort(&mut v));
+            v
+        });
+        bench(&format!("parallel quicksort, {} threads", threads), 3, || {
+            let mut v = input.clone();
+            pool.install(|| par_quick_sort(&mut v));
+            v
+        });
+        bench(&format!("parallel heapify, {} threads", threads), 3, || {
+            pool.install(|| par_heapify_by(input.clone(), u64::cmp))
+        });
+        threads *= 2;
+    }
+}
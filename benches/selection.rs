@@ -0,0 +1,35 @@
+mod common;
+
+use common::bench;
+use csc263::median_of_medians::deterministic_partition_at;
+use csc263::quickselect::partition_at;
+use csc263::rng::Rng;
+
+/// Finds the median of a million random integers with random and median-of-medians pivots, to
+/// measure what the linear worst case of median of medians costs in the average case
+fn main() {
+    let mut rng = Rng::new(1);
+    let input: Vec<u64> = (0..1_000_000).map(|_| rng.next_u64()).collect();
+    let k = input.len() / 2;
+    println!("median of 1000000 random u64s");
+
+    bench("quickselect", 10, || {
+        let mut v = input.clone();
+        partition_at(&mut v, k);
+        v[k]
+    });
+    bench("median of medians", 10, || {
+        let mut v = input.clone();
+        deterministic_partition_at(&mut v, k);
+        v[k]
+    });
+    bench("stdlib select_nth_unstable", 10, || {
+        let mut v = input.clone();
+        *v.select_nth_unstable(k).1
+    });
+    bench("stdlib sort_unstable", 10, || {
+        let mut v = input.clone();
+        v.sort_unstable();
+        v[k]
+    });
+}
@@ -0,0 +1,55 @@
+mod common;
+
+use common::bench;
+use csc263::rng::Rng;
+use csc263::sparse_set::SparseSet;
+use std::collections::HashSet;
+
+const UNIVERSE: usize = 1 << 16;
+const ROUNDS: usize = 10_000;
+
+/// Fills a set with a few random elements, probes it, and clears it, many times over, like the
+/// visited set of a search that runs again and again on a small graph
+fn main() {
+    let mut rng = Rng::new(1);
+    let rounds: Vec<Vec<usize>> =
+        (0..ROUNDS).map(|_| (0..32).map(|_| rng.gen_range(UNIVERSE as u64) as usize).collect()).collect();
+    println!("{} rounds of 32 inserts and probes then a clear, universe of {}", ROUNDS, UNIVERSE);
+
+    bench("sparse set", 5, || {
+        let mut set = SparseSet::new(UNIVERSE);
+        let mut found = 0;
+        for round in &rounds {
+            for &x in round {
+                set.insert(x);
+            }
+            found += round.iter().filter(|&&x| set.contains(x + 1)).count();
+            set.clear();
+        }
+        found
+    });
+    bench("hash set", 5, || {
+        let mut set = HashSet::new();
+        let mut found = 0;
+        for round in &rounds {
+            for &x in round {
+                set.insert(x);
+            }
+            found += round.iter().filter(|&&x| set.contains(&(x + 1))).count();
+            set.clear();
+        }
+        found
+    });
+    bench("vec of bools", 5, || {
+        let mut set = vec![false; UNIVERSE + 1];
+        let mut found = 0;
+        for round in &rounds {
+            for &x in round {
+                set[x] = true;
+            }
+            found += round.iter().filter(|&&x| set[x + 1]).count();
+            set.iter_mut().for_each(|b| *b = false);
+        }
+        found
+    });
+}
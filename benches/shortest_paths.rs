@@ -0,0 +1,52 @@
+mod common;
+
+use common::bench;
+use csc263::astar::astar_with_check;
+use csc263::bidirectional_dijkstra::bidirectional_dijkstra;
+use csc263::dijkstra::{dijkstra, dijkstra_decrease_key, dijkstra_with_queue};
+use csc263::graph::Graph;
+use csc263::property_map::EdgeData;
+use csc263::rng::Rng;
+use std::collections::BinaryHeap;
+
+/// Builds a `w x w` grid graph with random weights in `1..=10`
+fn grid(w: usize, rng: &mut Rng) -> Graph<(), u64> {
+    let mut graph = Graph::grid(w, w);
+    for e in 0..graph.edge_bound() {
+        *graph.edge_mut(e).unwrap() = 1 + rng.gen_range(10);
+    }
+    graph
+}
+
+/// Times single pair queries with one-sided and bidirectional searches
+fn point_to_point(name: &str, graph: &Graph<(), u64>, rng: &mut Rng) {
+    println!("{}, 20 random pairs", name);
+    let n = graph.node_count() as u64;
+    let pairs: Vec<_> = (0..20).map(|_| (rng.gen_range(n) as usize, rng.gen_range(n) as usize)).collect();
+
+    bench("dijkstra from source", 3, || pairs.iter().map(|&(s, t)| dijkstra(graph, s).unwrap().distance(t)).collect::<Vec<_>>());
+    bench("dijkstra stopping at target", 3, || {
+        pairs.iter().map(|&(s, t)| astar_with_check(graph, s, t, |_| 0, false).unwrap()).collect::<Vec<_>>()
+    });
+    bench("bidirectional dijkstra", 3, || pairs.iter().map(|&(s, t)| bidirectional_dijkstra(graph, s, t).unwrap()).collect::<Vec<_>>());
+    println!();
+}
+
+fn main() {
+    let mut rng = Rng::new(1);
+    for &(n, m) in [(10_000, 50_000), (10_000, 500_000), (100_000, 500_000)].iter() {
+        println!("{} nodes, {} edges", n, m);
+        let edges = (0..m).map(|_| (rng.gen_range(n) as usize, rng.gen_range(n) as usize, rng.gen_range(1000)));
+        let graph: Graph<(), u64> = Graph::from_edges(true, n as usize, edges);
+
+        bench("dijkstra lazy deletion (MaxHeap)", 10, || dijkstra(&graph, 0));
+        bench("dijkstra lazy deletion (BinaryHeap)", 10, || dijkstra_with_queue(&graph, 0, &EdgeData, BinaryHeap::new()));
+        bench("dijkstra decrease-key (IndexedMinHeap)", 10, || dijkstra_decrease_key(&graph, 0));
+        println!();
+    }
+
+    let edges = (0..400_000).map(|_| (rng.gen_range(100_000) as usize, rng.gen_range(100_000) as usize, rng.gen_range(1000)));
+    let random: Graph<(), u64> = Graph::from_edges(false, 100_000, edges);
+    point_to_point("random graph, 100000 nodes", &random, &mut rng);
+    point_to_point("grid graph, 300 x 300", &grid(300, &mut rng), &mut rng);
+}
@@ -0,0 +1,67 @@
+mod common;
+
+use common::bench;
+use csc263::elementary_sorts::{insertion_sort, selection_sort, shell_sort, Gaps};
+use csc263::intro_sort::intro_sort;
+use csc263::max_heap::MaxHeap;
+use csc263::merge_sort::{bottom_up_merge_sort, merge_sort};
+use csc263::natural_merge_sort::natural_merge_sort;
+use csc263::quick_sort::{quick_sort, three_way_quick_sort, Pivot};
+use csc263::radix_sort::{lsd_radix_sort, msd_radix_sort};
+use csc263::rng::Rng;
+
+/// Times a sort on a fresh copy of each input, including the copy
+fn bench_sort<F: FnMut(&mut [u64])>(name: &str, inputs: &[Vec<u64>], runs: u32, mut sort: F) {
+    bench(name, runs, || {
+        for input in inputs {
+            let mut v = input.clone();
+            sort(&mut v);
+            std::hint::black_box(&v);
+        }
+    });
+}
+
+/// Sorts many small random slices, where the elementary sorts compete, then a few large ones,
+/// where only the `O(n*log(n))` sorts and shell sort are worth timing, and then a million
+/// uniformly random integers, where radix sort's linear time shows
+fn main() {
+    let mut rng = Rng::new(1);
+    for n in [8, 32] {
+        let inputs: Vec<Vec<u64>> = (0..10_000).map(|_| (0..n).map(|_| rng.next_u64()).collect()).collect();
+        println!("10000 random slices of {} items", n);
+        bench_sort("insertion sort", &inputs, 10, insertion_sort);
+        bench_sort("selection sort", &inputs, 10, selection_sort);
+        bench_sort("shell sort, Ciura gaps", &inputs, 10, |v| shell_sort(v, Gaps::Ciura));
+        bench_sort("mergesort", &inputs, 10, merge_sort);
+        bench_sort("quicksort, median of three", &inputs, 10, |v| quick_sort(v, Pivot::MedianOfThree));
+        bench_sort("stdlib sort_unstable", &inputs, 10, |v| v.sort_unstable());
+    }
+
+    let inputs: Vec<Vec<u64>> = (0..4).map(|_| (0..100_000).map(|_| rng.next_u64()).collect()).collect();
+    println!("4 random slices of 100000 items");
+    bench_sort("shell sort, Shell gaps", &inputs, 5, |v| shell_sort(v, Gaps::Shell));
+    bench_sort("shell sort, Knuth gaps", &inputs, 5, |v| shell_sort(v, Gaps::Knuth));
+    bench_sort("shell sort, Ciura gaps", &inputs, 5, |v| shell_sort(v, Gaps::Ciura));
+    bench_sort("mergesort", &inputs, 5, merge_sort);
+    bench_sort("bottom-up mergesort", &inputs, 5, bottom_up_merge_sort);
+    bench_sort("natural mergesort", &inputs, 5, |v| {
+        natural_merge_sort(v);
+    });
+    bench_sort("quicksort, random pivot", &inputs, 5, |v| quick_sort(v, Pivot::Random));
+    bench_sort("quicksort, median of three", &inputs, 5, |v| quick_sort(v, Pivot::MedianOfThree));
+    bench_sort("three-way quicksort, median of three", &inputs, 5, |v| three_way_quick_sort(v, Pivot::MedianOfThree));
+    bench_sort("introsort", &inputs, 5, intro_sort);
+    bench_sort("stdlib sort", &inputs, 5, |v| v.sort());
+
+    let inputs = vec![(0..1_000_000).map(|_| rng.next_u64()).collect::<Vec<u64>>()];
+    println!("1000000 uniformly random u64s");
+    bench("heapsort", 5, || MaxHeap::heapsort(inputs[0].clone()));
+    bench_sort("quicksort, random pivot", &inputs, 5, |v| quick_sort(v, Pivot::Random));
+    for radix_bits in [4, 8, 11, 16] {
+        bench_sort(&format!("LSD radix sort, {} bit digits", radix_bits), &inputs, 5, |v| lsd_radix_sort(v, radix_bits));
+    }
+    for radix_bits in [8, 11, 16] {
+        bench_sort(&format!("MSD radix sort, {} bit digits", radix_bits), &inputs, 5, |v| msd_radix_sort(v, radix_bits));
+    }
+    bench_sort("stdlib sort_unstable", &inputs, 5, |v| v.sort_unstable());
+}
@@ -0,0 +1,41 @@
+mod common;
+
+use common::bench;
+use csc263::horspool::Horspool;
+use csc263::kmp::FailureFunction;
+use csc263::rabin_karp::rabin_karp;
+use csc263::rng::Rng;
+use csc263::z_algorithm::z_search;
+
+/// Internal function to time each substring search on a text and pattern
+fn bench_search(label: &str, text: &[u8], pattern: &[u8]) {
+    println!("{}", label);
+    let kmp = FailureFunction::new(pattern);
+    let horspool = Horspool::new(pattern);
+    bench("Knuth-Morris-Pratt", 10, || kmp.find_all(text).count());
+    bench("Rabin-Karp", 10, || rabin_karp(text, pattern).len());
+    bench("Z-algorithm", 10, || z_search(text, pattern).len());
+    bench("Boyer-Moore-Horspool", 10, || horspool.find_all(text).len());
+    bench("stdlib str::matches", 10, || {
+        let text = std::str::from_utf8(text).unwrap();
+        text.matches(std::str::from_utf8(pattern).unwrap()).count()
+    });
+}
+
+/// Searches 4MB of English-like text and of DNA for a pattern near its end, where Horspool's
+/// skips should win on the large alphabet and shrink on the small one
+fn main() {
+    let mut rng = Rng::new(1);
+    let words = ["the", "of", "and", "to", "in", "algorithm", "data", "structure", "heap", "tree", "graph", "search", "string", "pattern", "is", "a"];
+    let mut english = Vec::new();
+    while english.len() < 4_000_000 {
+        english.extend_from_slice(words[rng.gen_range(words.len() as u64) as usize].as_bytes());
+        english.push(b' ');
+    }
+    english.extend_from_slice(b"knuth morris pratt");
+    bench_search("4MB of words, 18 byte pattern", &english, b"knuth morris pratt");
+
+    let mut dna: Vec<u8> = (0..4_000_000).map(|_| b"ACGT"[rng.gen_range(4) as usize]).collect();
+    dna.extend_from_slice(b"GATTACAGATTACAGA");
+    bench_search("4MB of DNA, 16 byte pattern", &dna, b"GATTACAGATTACAGA");
+}
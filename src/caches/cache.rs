@@ -0,0 +1,74 @@
+/// Counts of the lookups and evictions a cache has made
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct CacheStats {
+    /// Lookups that found their key
+    pub hits: usize,
+    /// Lookups that didn't find their key
+    pub misses: usize,
+    /// Entries dropped to make room for new ones
+    pub evictions: usize,
+}
+
+impl CacheStats {
+
+    /// Fraction of lookups that found their key, 0 if there haven't been any
+    pub fn hit_rate(&self) -> f64 {
+        let lookups = self.hits + self.misses;
+        if lookups == 0 {
+            0.0
+        } else {
+            self.hits as f64 / lookups as f64
+        }
+    }
+}
+
+/// A cache of a bounded number of entries, evicting by some policy when full
+///
+/// Implemented by `LruCache`, `LfuCache`, and `ClockCache`, so code can be run against any policy.
+pub trait Cache<K, V> {
+
+    /// Gets the value under a key, counting a hit or miss and marking the entry as used
+    fn get(&mut self, key: &K) -> Option<&V>;
+
+    /// Inserts a value under a key, evicting an entry first if the cache is full and the key is new, and returns the value it replaced
+    fn put(&mut self, key: K, value: V) -> Option<V>;
+
+    /// Removes a key, and returns its value
+    fn remove(&mut self, key: &K) -> Option<V>;
+
+    /// Checks if a key is in the cache, without marking it as used
+    fn contains_key(&self, key: &K) -> bool;
+
+    /// Number of entries in the cache
+    fn len(&self) -> usize;
+
+    /// Checks if the cache is empty
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Most entries the cache holds
+    fn capacity(&self) -> usize;
+
+    /// Hits, misses, and evictions so far
+    fn stats(&self) -> CacheStats;
+}
+
+/// Runs a trace of key lookups through a cache and returns its statistics
+///
+/// Every key is looked up, and on a miss it's loaded and inserted, like a cache in front of slower
+/// storage. Replaying the same recorded trace through several caches compares their policies.
+///
+/// # Arguments
+///
+/// * `cache` - Cache to run the trace through
+/// * `trace` - Keys in the order they are looked up
+/// * `load` - Fetches the value for a key on a miss
+pub fn replay<K: Clone, V, C: Cache<K, V>, F: FnMut(&K) -> V>(cache: &mut C, trace: &[K], mut load: F) -> CacheStats {
+    for key in trace {
+        if cache.get(key).is_none() {
+            cache.put(key.clone(), load(key));
+        }
+    }
+    cache.stats()
+}
@@ -0,0 +1,298 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::hash::Hash;
+
+use crate::cache::{Cache, CacheStats};
+
+/// Internal slot storage, a key and value with the bit set whenever it's used
+#[derive(Clone, Debug)]
+struct Slot<K, V> {
+    key: K,
+    value: V,
+    referenced: bool,
+}
+
+/// A cache of at most `capacity` entries that approximates least recently used eviction
+///
+/// The entries sit in a circle of `capacity` slots, each with a reference bit set whenever its
+/// entry is used. To make room, a clock hand sweeps the circle: an entry with its bit set gets a
+/// second chance, its bit cleared and the hand moving on, and the first entry found with a clear
+/// bit is evicted. An entry used since the hand last passed it survives, so recently used entries
+/// tend to stay, but a use only sets a bit instead of moving the entry to the front of a list,
+/// which is why operating systems use this to pick pages to swap out. A sweep can pass every slot
+/// once, but each one it passes is cleared, so an eviction takes amortized `O(1)` time.
+///
+/// An eviction callback, if set, is given every entry the cache drops to make room.
+pub struct ClockCache<K: Hash + Eq + Clone, V> {
+    map: HashMap<K, usize>,
+    slots: Vec<Option<Slot<K, V>>>,
+    free: Vec<usize>,
+    hand: usize,
+    capacity: usize,
+    stats: CacheStats,
+    on_evict: Option<Box<dyn FnMut(K, V)>>,
+}
+
+impl<K: Hash + Eq + Clone, V> ClockCache<K, V> {
+
+    /// Creates a new empty `ClockCache`
+    ///
+    /// # Arguments
+    ///
+    /// * `capacity` - Most entries the cache holds
+    ///
+    /// # Panics
+    ///
+    /// If `capacity` is zero
+    pub fn new(capacity: usize) -> ClockCache<K, V> {
+        assert!(capacity > 0, "a cache must hold at least one entry");
+        ClockCache {
+            map: HashMap::with_capacity(capacity),
+            slots: Vec::with_capacity(capacity),
+            free: Vec::new(),
+            hand: 0,
+            capacity,
+            stats: CacheStats::default(),
+            on_evict: None,
+        }
+    }
+
+    /// Sets a function to call with every entry evicted to make room, replacing any set before
+    ///
+    /// Entries taken out by `remove` or replaced by `put` aren't evictions.
+    ///
+    /// # Arguments
+    ///
+    /// * `on_evict` - Function given the key and value of each evicted entry
+    pub fn set_eviction_callback<F: FnMut(K, V) + 'static>(&mut self, on_evict: F) {
+        self.on_evict = Some(Box::new(on_evict));
+    }
+
+    /// Number of entries in the cache
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    /// Checks if the cache is empty
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    /// Most entries the cache holds
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Hits, misses, and evictions so far
+    pub fn stats(&self) -> CacheStats {
+        self.stats
+    }
+
+    /// Gets the value under a key and sets its reference bit, counting a hit or miss
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - Key to look up
+    pub fn get(&mut self, key: &K) -> Option<&V> {
+        self.get_mut(key).map(|v| &*v)
+    }
+
+    /// Gets the value under a key mutably and sets its reference bit, counting a hit or miss
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - Key to look up
+    pub fn get_mut(&mut self, key: &K) -> Option<&mut V> {
+        let i = match self.map.get(key) {
+            Some(&i) => i,
+            None => {
+                self.stats.misses += 1;
+                return None;
+            }
+        };
+        self.stats.hits += 1;
+        let slot = self.slot_mut(i);
+        slot.referenced = true;
+        Some(&mut slot.value)
+    }
+
+    /// Gets the value under a key without setting its reference bit or counting a hit or miss
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - Key to look up
+    pub fn peek(&self, key: &K) -> Option<&V> {
+        let &i = self.map.get(key)?;
+        self.slots[i].as_ref().map(|slot| &slot.value)
+    }
+
+    /// Checks if a key is in the cache, without setting its reference bit
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - Key to look for
+    pub fn contains_key(&self, key: &K) -> bool {
+        self.map.contains_key(key)
+    }
+
+    /// Inserts a value under a key with its reference bit set, and returns the value it replaced
+    ///
+    /// Fills an empty slot if there is one, or else sweeps the clock hand to evict an entry.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - Key to insert under
+    /// * `value` - Value to insert
+    pub fn put(&mut self, key: K, value: V) -> Option<V> {
+        if let Some(&i) = self.map.get(&key) {
+            let slot = self.slot_mut(i);
+            slot.referenced = true;
+            return Some(std::mem::replace(&mut slot.value, value));
+        }
+
+        let slot = Some(Slot { key: key.clone(), value, referenced: true });
+        let i = if let Some(i) = self.free.pop() {
+            i
+        } else if self.slots.len() < self.capacity {
+            self.slots.push(None);
+            self.slots.len() - 1
+        } else {
+            self.evict()
+        };
+        self.slots[i] = slot;
+        self.map.insert(key, i);
+        None
+    }
+
+    /// Removes a key, and returns its value
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - Key to remove
+    pub fn remove(&mut self, key: &K) -> Option<V> {
+        let i = self.map.remove(key)?;
+        self.free.push(i);
+        self.slots[i].take().map(|slot| slot.value)
+    }
+
+    /// Internal function to sweep the hand to an entry with a clear reference bit, clearing the
+    /// bits it passes, then evict that entry and return its empty slot
+    fn evict(&mut self) -> usize {
+        loop {
+            let i = self.hand;
+            self.hand = (self.hand + 1) % self.capacity;
+            let slot = self.slot_mut(i);
+            if slot.referenced {
+                slot.referenced = false;
+                continue;
+            }
+
+            let Slot { key, value, .. } = self.slots[i].take().unwrap();
+            self.map.remove(&key);
+            self.stats.evictions += 1;
+            if let Some(on_evict) = self.on_evict.as_mut() {
+                on_evict(key, value);
+            }
+            return i;
+        }
+    }
+
+    /// Internal function to get a slot that holds an entry mutably
+    fn slot_mut(&mut self, i: usize) -> &mut Slot<K, V> {
+        self.slots[i].as_mut().unwrap()
+    }
+}
+
+impl<K: Hash + Eq + Clone, V> Cache<K, V> for ClockCache<K, V> {
+    fn get(&mut self, key: &K) -> Option<&V> {
+        ClockCache::get(self, key)
+    }
+
+    fn put(&mut self, key: K, value: V) -> Option<V> {
+        ClockCache::put(self, key, value)
+    }
+
+    fn remove(&mut self, key: &K) -> Option<V> {
+        ClockCache::remove(self, key)
+    }
+
+    fn contains_key(&self, key: &K) -> bool {
+        ClockCache::contains_key(self, key)
+    }
+
+    fn len(&self) -> usize {
+        ClockCache::len(self)
+    }
+
+    fn capacity(&self) -> usize {
+        ClockCache::capacity(self)
+    }
+
+    fn stats(&self) -> CacheStats {
+        ClockCache::stats(self)
+    }
+}
+
+impl<K: Hash + Eq + Clone + fmt::Debug, V: fmt::Debug> fmt::Debug for ClockCache<K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let entries = self.slots.iter().flatten().map(|slot| (&slot.key, &slot.value));
+        f.debug_map().entries(entries).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cache::replay;
+    use crate::lfu_cache::LfuCache;
+    use crate::lru_cache::LruCache;
+    use crate::rng::Rng;
+
+    #[test]
+    fn test_second_chance() {
+        let mut cache = ClockCache::new(3);
+        for key in [1, 2, 3] {
+            cache.put(key, ());
+        }
+        // Every bit is set, so the hand clears them all and comes back round to 1
+        cache.put(4, ());
+        assert!(!cache.contains_key(&1));
+        // 2 is used again and gets a second chance, so 3 goes
+        cache.get(&2);
+        cache.put(5, ());
+        assert!(!cache.contains_key(&3));
+        assert!(cache.contains_key(&2));
+        assert_eq!(2, cache.stats().evictions);
+    }
+
+    #[test]
+    fn test_remove_reuses_slot() {
+        let mut cache = ClockCache::new(2);
+        cache.put("a", 1);
+        cache.put("b", 2);
+        assert_eq!(Some(1), cache.remove(&"a"));
+        assert_eq!(None, cache.put("c", 3));
+        assert_eq!(0, cache.stats().evictions);
+        assert_eq!(Some(2), cache.put("b", 20));
+        assert_eq!(Some(&20), cache.peek(&"b"));
+        assert_eq!(2, cache.len());
+    }
+
+    #[test]
+    fn test_compare_policies() {
+        let mut rng = Rng::new(113);
+        // Mostly a few hot keys, with some uniformly random cold ones
+        let trace: Vec<u64> =
+            (0..20_000).map(|_| if rng.gen_bool(0.8) { rng.gen_range(10) } else { 10 + rng.gen_range(1000) }).collect();
+        let clock = replay(&mut ClockCache::new(20), &trace, |&k| k);
+        let lru = replay(&mut LruCache::new(20), &trace, |&k| k);
+        let lfu = replay(&mut LfuCache::new(20), &trace, |&k| k);
+        assert_eq!(trace.len(), clock.hits + clock.misses);
+        // Every policy keeps the hot keys most of the time
+        for stats in [clock, lru, lfu] {
+            assert!(stats.hit_rate() > 0.7);
+        }
+        // Clock approximates LRU closely
+        assert!((clock.hit_rate() - lru.hit_rate()).abs() < 0.05);
+    }
+}
@@ -0,0 +1,484 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::hash::Hash;
+
+use crate::cache::{Cache, CacheStats};
+
+/// Internal entry storage, a key and value in the bucket for its use count, linked to the entries
+/// in the same bucket used just before and after it
+#[derive(Clone, Debug)]
+struct Entry<K, V> {
+    key: K,
+    value: V,
+    bucket: usize,
+    newer: Option<usize>,
+    older: Option<usize>,
+}
+
+/// Internal frequency bucket, the ends of the list of entries used `frequency` times, most recent
+/// at the head, linked to the buckets with the next smaller and larger counts
+///
+/// Only a bucket that was just made and is about to get its first entry is empty.
+#[derive(Clone, Copy, Debug)]
+struct Bucket {
+    frequency: usize,
+    head: Option<usize>,
+    tail: Option<usize>,
+    lower: Option<usize>,
+    higher: Option<usize>,
+}
+
+/// A cache of at most `capacity` entries that evicts the least frequently used one
+///
+/// Every entry counts how many times it has been inserted or looked up. The entries with each
+/// count are kept on their own doubly linked list in order of use, a frequency bucket, and the
+/// nonempty buckets are kept on a doubly linked list in order of count. A use moves an entry from
+/// its bucket to the head of the bucket with one more use, which is the next bucket or a new one
+/// linked in after it, and an eviction takes the tail of the first bucket, so ties in frequency
+/// are broken by evicting the least recently used. No operation searches, so every one takes
+/// expected `O(1)` time, the hash map lookup being the only part that isn't worst case.
+///
+/// An eviction callback, if set, is given every entry the cache drops to make room.
+pub struct LfuCache<K: Hash + Eq + Clone, V> {
+    map: HashMap<K, usize>,
+    entries: Vec<Option<Entry<K, V>>>,
+    free: Vec<usize>,
+    buckets: Vec<Option<Bucket>>,
+    free_buckets: Vec<usize>,
+    lowest: Option<usize>,
+    capacity: usize,
+    stats: CacheStats,
+    on_evict: Option<Box<dyn FnMut(K, V)>>,
+}
+
+impl<K: Hash + Eq + Clone, V> LfuCache<K, V> {
+
+    /// Creates a new empty `LfuCache`
+    ///
+    /// # Arguments
+    ///
+    /// * `capacity` - Most entries the cache holds
+    ///
+    /// # Panics
+    ///
+    /// If `capacity` is zero
+    pub fn new(capacity: usize) -> LfuCache<K, V> {
+        assert!(capacity > 0, "a cache must hold at least one entry");
+        LfuCache {
+            map: HashMap::with_capacity(capacity),
+            entries: Vec::with_capacity(capacity),
+            free: Vec::new(),
+            buckets: Vec::new(),
+            free_buckets: Vec::new(),
+            lowest: None,
+            capacity,
+            stats: CacheStats::default(),
+            on_evict: None,
+        }
+    }
+
+    /// Sets a function to call with every entry evicted to make room, replacing any set before
+    ///
+    /// Entries taken out by `remove` or replaced by `put` aren't evictions.
+    ///
+    /// # Arguments
+    ///
+    /// * `on_evict` - Function given the key and value of each evicted entry
+    pub fn set_eviction_callback<F: FnMut(K, V) + 'static>(&mut self, on_evict: F) {
+        self.on_evict = Some(Box::new(on_evict));
+    }
+
+    /// Number of entries in the cache
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    /// Checks if the cache is empty
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    /// Most entries the cache holds
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Hits, misses, and evictions so far
+    pub fn stats(&self) -> CacheStats {
+        self.stats
+    }
+
+    /// Number of times a key has been inserted or looked up since it entered the cache, or None if
+    /// it isn't in the cache
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - Key to look up
+    pub fn frequency(&self, key: &K) -> Option<usize> {
+        let &i = self.map.get(key)?;
+        Some(self.bucket(self.entry(i).bucket).frequency)
+    }
+
+    /// Gets the value under a key and counts a use of it, counting a hit or miss
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - Key to look up
+    pub fn get(&mut self, key: &K) -> Option<&V> {
+        self.get_mut(key).map(|v| &*v)
+    }
+
+    /// Gets the value under a key mutably and counts a use of it, counting a hit or miss
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - Key to look up
+    pub fn get_mut(&mut self, key: &K) -> Option<&mut V> {
+        let i = match self.map.get(key) {
+            Some(&i) => i,
+            None => {
+                self.stats.misses += 1;
+                return None;
+            }
+        };
+        self.stats.hits += 1;
+        self.touch(i);
+        Some(&mut self.entry_mut(i).value)
+    }
+
+    /// Gets the value under a key without counting a use or a hit or miss
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - Key to look up
+    pub fn peek(&self, key: &K) -> Option<&V> {
+        let &i = self.map.get(key)?;
+        Some(&self.entry(i).value)
+    }
+
+    /// Checks if a key is in the cache, without counting a use
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - Key to look for
+    pub fn contains_key(&self, key: &K) -> bool {
+        self.map.contains_key(key)
+    }
+
+    /// Inserts a value under a key and counts a use of it, and returns the value it replaced
+    ///
+    /// Evicts the least frequently used entry first if the cache is full and the key is new,
+    /// breaking ties by evicting the least recently used.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - Key to insert under
+    /// * `value` - Value to insert
+    pub fn put(&mut self, key: K, value: V) -> Option<V> {
+        if let Some(&i) = self.map.get(&key) {
+            self.touch(i);
+            return Some(std::mem::replace(&mut self.entry_mut(i).value, value));
+        }
+
+        if self.map.len() == self.capacity {
+            self.evict();
+        }
+        let bucket = match self.lowest {
+            Some(b) if self.bucket(b).frequency == 1 => b,
+            _ => self.new_bucket(1, None),
+        };
+        let entry = Some(Entry { key: key.clone(), value, bucket, newer: None, older: None });
+        let i = match self.free.pop() {
+            Some(i) => {
+                self.entries[i] = entry;
+                i
+            }
+            None => {
+                self.entries.push(entry);
+                self.entries.len() - 1
+            }
+        };
+        self.map.insert(key, i);
+        self.push_head(i);
+        None
+    }
+
+    /// Removes a key, and returns its value
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - Key to remove
+    pub fn remove(&mut self, key: &K) -> Option<V> {
+        let i = self.map.remove(key)?;
+        Some(self.take(i).1)
+    }
+
+    /// Internal function to count a use of an entry, moving it to the bucket with one more use
+    fn touch(&mut self, i: usize) {
+        let b = self.entry(i).bucket;
+        let Bucket { frequency, higher, .. } = *self.bucket(b);
+        let target = match higher {
+            Some(h) if self.bucket(h).frequency == frequency + 1 => h,
+            _ => self.new_bucket(frequency + 1, Some(b)),
+        };
+        self.unlink(i);
+        self.entry_mut(i).bucket = target;
+        self.push_head(i);
+    }
+
+    /// Internal function to drop the least recently used of the least frequently used entries and
+    /// pass it to the callback
+    fn evict(&mut self) {
+        if let Some(b) = self.lowest {
+            let (key, value) = self.take(self.bucket(b).tail.unwrap());
+            self.map.remove(&key);
+            self.stats.evictions += 1;
+            if let Some(on_evict) = self.on_evict.as_mut() {
+                on_evict(key, value);
+            }
+        }
+    }
+
+    /// Internal function to unlink an entry and free its slot, returning its key and value
+    fn take(&mut self, i: usize) -> (K, V) {
+        self.unlink(i);
+        let entry = self.entries[i].take().unwrap();
+        self.free.push(i);
+        (entry.key, entry.value)
+    }
+
+    /// Internal function to link an unlinked entry in at the head of its bucket
+    fn push_head(&mut self, i: usize) {
+        let b = self.entry(i).bucket;
+        let head = self.bucket(b).head;
+        let entry = self.entry_mut(i);
+        entry.newer = None;
+        entry.older = head;
+        if let Some(h) = head {
+            self.entry_mut(h).newer = Some(i);
+        }
+        let bucket = self.buckets[b].as_mut().unwrap();
+        bucket.head = Some(i);
+        bucket.tail = bucket.tail.or(Some(i));
+    }
+
+    /// Internal function to unlink an entry from its bucket, dropping the bucket if it empties
+    fn unlink(&mut self, i: usize) {
+        let Entry { bucket: b, newer, older, .. } = *self.entry(i);
+        match newer {
+            Some(n) => self.entry_mut(n).older = older,
+            None => match older {
+                Some(o) => self.buckets[b].as_mut().unwrap().head = Some(o),
+                None => self.drop_bucket(b),
+            },
+        }
+        match older {
+            Some(o) => self.entry_mut(o).newer = newer,
+            None => {
+                if let Some(n) = newer {
+                    self.buckets[b].as_mut().unwrap().tail = Some(n);
+                }
+            }
+        }
+    }
+
+    /// Internal function to make an empty bucket for a count, linked after `lower` or first if
+    /// `lower` is None
+    fn new_bucket(&mut self, frequency: usize, lower: Option<usize>) -> usize {
+        let higher = match lower {
+            Some(l) => self.bucket(l).higher,
+            None => self.lowest,
+        };
+        let bucket = Some(Bucket { frequency, head: None, tail: None, lower, higher });
+        let b = match self.free_buckets.pop() {
+            Some(b) => {
+                self.buckets[b] = bucket;
+                b
+            }
+            None => {
+                self.buckets.push(bucket);
+                self.buckets.len() - 1
+            }
+        };
+        match lower {
+            Some(l) => self.buckets[l].as_mut().unwrap().higher = Some(b),
+            None => self.lowest = Some(b),
+        }
+        if let Some(h) = higher {
+            self.buckets[h].as_mut().unwrap().lower = Some(b);
+        }
+        b
+    }
+
+    /// Internal function to unlink an empty bucket and free its slot
+    fn drop_bucket(&mut self, b: usize) {
+        let Bucket { lower, higher, .. } = self.buckets[b].take().unwrap();
+        match lower {
+            Some(l) => self.buckets[l].as_mut().unwrap().higher = higher,
+            None => self.lowest = higher,
+        }
+        if let Some(h) = higher {
+            self.buckets[h].as_mut().unwrap().lower = lower;
+        }
+        self.free_buckets.push(b);
+    }
+
+    /// Internal function to get a bucket that is in the list
+    fn bucket(&self, b: usize) -> &Bucket {
+        self.buckets[b].as_ref().unwrap()
+    }
+
+    /// Internal function to get an entry that is in the cache
+    fn entry(&self, i: usize) -> &Entry<K, V> {
+        self.entries[i].as_ref().unwrap()
+    }
+
+    /// Internal function to get an entry that is in the cache mutably
+    fn entry_mut(&mut self, i: usize) -> &mut Entry<K, V> {
+        self.entries[i].as_mut().unwrap()
+    }
+}
+
+impl<K: Hash + Eq + Clone, V> Cache<K, V> for LfuCache<K, V> {
+    fn get(&mut self, key: &K) -> Option<&V> {
+        LfuCache::get(self, key)
+    }
+
+    fn put(&mut self, key: K, value: V) -> Option<V> {
+        LfuCache::put(self, key, value)
+    }
+
+    fn remove(&mut self, key: &K) -> Option<V> {
+        LfuCache::remove(self, key)
+    }
+
+    fn contains_key(&self, key: &K) -> bool {
+        LfuCache::contains_key(self, key)
+    }
+
+    fn len(&self) -> usize {
+        LfuCache::len(self)
+    }
+
+    fn capacity(&self) -> usize {
+        LfuCache::capacity(self)
+    }
+
+    fn stats(&self) -> CacheStats {
+        LfuCache::stats(self)
+    }
+}
+
+impl<K: Hash + Eq + Clone + fmt::Debug, V: fmt::Debug> fmt::Debug for LfuCache<K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let entries = self.entries.iter().flatten().map(|e| (&e.key, (&e.value, self.bucket(e.bucket).frequency)));
+        f.debug_map().entries(entries).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lru_cache::LruCache;
+    use crate::rng::Rng;
+
+    #[test]
+    fn test_evicts_least_frequent() {
+        let mut cache = LfuCache::new(2);
+        cache.put(1, "a");
+        cache.put(2, "b");
+        assert_eq!(Some(&"a"), cache.get(&1));
+        assert_eq!(Some(2), cache.frequency(&1));
+        // 2 has been used once, 1 twice
+        cache.put(3, "c");
+        assert!(!cache.contains_key(&2));
+        // 3 has been used once, 1 twice
+        cache.put(4, "d");
+        assert!(!cache.contains_key(&3));
+        assert!(cache.contains_key(&1));
+        assert_eq!(Some("a"), cache.remove(&1));
+        assert_eq!(None, cache.frequency(&1));
+        assert_eq!(2, cache.stats().evictions);
+    }
+
+    #[test]
+    fn test_ties_evict_least_recent() {
+        let mut cache = LfuCache::new(3);
+        for key in [1, 2, 3] {
+            cache.put(key, ());
+        }
+        for key in [3, 1, 2] {
+            cache.get(&key);
+        }
+        // All used twice, and 3 the longest ago
+        cache.put(4, ());
+        assert!(!cache.contains_key(&3));
+        // 4 is the only entry used once
+        cache.put(5, ());
+        assert!(!cache.contains_key(&4));
+    }
+
+    #[test]
+    fn test_matches_naive() {
+        let mut rng = Rng::new(109);
+        let mut cache = LfuCache::new(6);
+        // Keys, values, counts, and the time of the last use
+        let mut expected: Vec<(u64, u64, usize, usize)> = Vec::new();
+        for time in 0..5000 {
+            let key = rng.gen_range(15);
+            let position = expected.iter().position(|e| e.0 == key);
+            match rng.gen_range(5) {
+                0 => {
+                    assert_eq!(position.map(|p| expected.remove(p).1), cache.remove(&key));
+                }
+                1 | 2 => {
+                    let found = position.map(|p| {
+                        expected[p].2 += 1;
+                        expected[p].3 = time;
+                        expected[p].1
+                    });
+                    assert_eq!(found, cache.get(&key).copied());
+                }
+                _ => match position {
+                    Some(p) => {
+                        expected[p].2 += 1;
+                        expected[p].3 = time;
+                        let old = std::mem::replace(&mut expected[p].1, time as u64);
+                        assert_eq!(Some(old), cache.put(key, time as u64));
+                    }
+                    None => {
+                        if expected.len() == 6 {
+                            let victim = (0..6).min_by_key(|&p| (expected[p].2, expected[p].3)).unwrap();
+                            expected.remove(victim);
+                        }
+                        expected.push((key, time as u64, 1, time));
+                        assert_eq!(None, cache.put(key, time as u64));
+                    }
+                },
+            }
+            for e in &expected {
+                assert_eq!(Some(e.2), cache.frequency(&e.0));
+            }
+            assert_eq!(expected.len(), cache.len());
+        }
+    }
+
+    #[test]
+    fn test_cache_trait() {
+        fn hits<C: Cache<u64, u64>>(mut cache: C, trace: &[u64]) -> usize {
+            for &key in trace {
+                if cache.get(&key).is_none() {
+                    cache.put(key, key);
+                }
+            }
+            cache.stats().hits
+        }
+        // A few hot keys with a scan through cold ones, which flushes an LRU cache
+        let mut trace = Vec::new();
+        for round in 0..50 {
+            trace.extend([1, 2, 3, 1, 2, 3]);
+            trace.extend((0..4).map(|i| 100 + 4 * round + i));
+        }
+        assert!(hits(LfuCache::new(4), &trace) > hits(LruCache::new(4), &trace));
+    }
+}
@@ -0,0 +1,357 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::hash::Hash;
+
+use crate::cache::{Cache, CacheStats};
+
+/// Internal entry storage, a key and value linked to the entries used just before and after it
+#[derive(Clone, Debug)]
+struct Entry<K, V> {
+    key: K,
+    value: V,
+    newer: Option<usize>,
+    older: Option<usize>,
+}
+
+/// A cache of at most `capacity` entries that evicts the least recently used one
+///
+/// A hash map finds the entry for a key, and the entries are threaded on a doubly linked list in
+/// order of use, the most recent at the head. Looking up a key unlinks its entry and relinks it at
+/// the head, and inserting into a full cache evicts the entry at the tail, all in expected `O(1)`
+/// time. The links live in the entries themselves, which sit in a vector and point to each other
+/// by index, so the map only holds an index per key.
+///
+/// An eviction callback, if set, is given every entry the cache drops to make room.
+pub struct LruCache<K: Hash + Eq + Clone, V> {
+    map: HashMap<K, usize>,
+    entries: Vec<Option<Entry<K, V>>>,
+    free: Vec<usize>,
+    head: Option<usize>,
+    tail: Option<usize>,
+    capacity: usize,
+    stats: CacheStats,
+    on_evict: Option<Box<dyn FnMut(K, V)>>,
+}
+
+impl<K: Hash + Eq + Clone, V> LruCache<K, V> {
+
+    /// Creates a new empty `LruCache`
+    ///
+    /// # Arguments
+    ///
+    /// * `capacity` - Most entries the cache holds
+    ///
+    /// # Panics
+    ///
+    /// If `capacity` is zero
+    pub fn new(capacity: usize) -> LruCache<K, V> {
+        assert!(capacity > 0, "a cache must hold at least one entry");
+        LruCache {
+            map: HashMap::with_capacity(capacity),
+            entries: Vec::with_capacity(capacity),
+            free: Vec::new(),
+            head: None,
+            tail: None,
+            capacity,
+            stats: CacheStats::default(),
+            on_evict: None,
+        }
+    }
+
+    /// Sets a function to call with every entry evicted to make room, replacing any set before
+    ///
+    /// Entries taken out by `remove` or replaced by `put` aren't evictions.
+    ///
+    /// # Arguments
+    ///
+    /// * `on_evict` - Function given the key and value of each evicted entry
+    pub fn set_eviction_callback<F: FnMut(K, V) + 'static>(&mut self, on_evict: F) {
+        self.on_evict = Some(Box::new(on_evict));
+    }
+
+    /// Number of entries in the cache
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    /// Checks if the cache is empty
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    /// Most entries the cache holds
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Hits, misses, and evictions so far
+    pub fn stats(&self) -> CacheStats {
+        self.stats
+    }
+
+    /// Gets the value under a key and marks it as the most recently used, counting a hit or miss
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - Key to look up
+    pub fn get(&mut self, key: &K) -> Option<&V> {
+        self.get_mut(key).map(|v| &*v)
+    }
+
+    /// Gets the value under a key mutably and marks it as the most recently used, counting a hit
+    /// or miss
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - Key to look up
+    pub fn get_mut(&mut self, key: &K) -> Option<&mut V> {
+        let i = match self.map.get(key) {
+            Some(&i) => i,
+            None => {
+                self.stats.misses += 1;
+                return None;
+            }
+        };
+        self.stats.hits += 1;
+        self.unlink(i);
+        self.push_head(i);
+        Some(&mut self.entry_mut(i).value)
+    }
+
+    /// Gets the value under a key without marking it as used or counting a hit or miss
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - Key to look up
+    pub fn peek(&self, key: &K) -> Option<&V> {
+        let &i = self.map.get(key)?;
+        Some(&self.entry(i).value)
+    }
+
+    /// Checks if a key is in the cache, without marking it as used
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - Key to look for
+    pub fn contains_key(&self, key: &K) -> bool {
+        self.map.contains_key(key)
+    }
+
+    /// Inserts a value under a key as the most recently used, and returns the value it replaced
+    ///
+    /// Evicts the least recently used entry first if the cache is full and the key is new.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - Key to insert under
+    /// * `value` - Value to insert
+    pub fn put(&mut self, key: K, value: V) -> Option<V> {
+        if let Some(&i) = self.map.get(&key) {
+            self.unlink(i);
+            self.push_head(i);
+            return Some(std::mem::replace(&mut self.entry_mut(i).value, value));
+        }
+
+        if self.map.len() == self.capacity {
+            self.evict();
+        }
+        let entry = Some(Entry { key: key.clone(), value, newer: None, older: None });
+        let i = match self.free.pop() {
+            Some(i) => {
+                self.entries[i] = entry;
+                i
+            }
+            None => {
+                self.entries.push(entry);
+                self.entries.len() - 1
+            }
+        };
+        self.map.insert(key, i);
+        self.push_head(i);
+        None
+    }
+
+    /// Removes a key, and returns its value
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - Key to remove
+    pub fn remove(&mut self, key: &K) -> Option<V> {
+        let i = self.map.remove(key)?;
+        Some(self.take(i).1)
+    }
+
+    /// Returns an iterator over the keys and values, from the most to the least recently used
+    pub fn iter(&self) -> impl Iterator<Item = (&K, &V)> + '_ {
+        let mut at = self.head;
+        std::iter::from_fn(move || {
+            let entry = self.entry(at?);
+            at = entry.older;
+            Some((&entry.key, &entry.value))
+        })
+    }
+
+    /// Internal function to drop the least recently used entry and pass it to the callback
+    fn evict(&mut self) {
+        if let Some(i) = self.tail {
+            let (key, value) = self.take(i);
+            self.map.remove(&key);
+            self.stats.evictions += 1;
+            if let Some(on_evict) = self.on_evict.as_mut() {
+                on_evict(key, value);
+            }
+        }
+    }
+
+    /// Internal function to unlink an entry and free its slot, returning its key and value
+    fn take(&mut self, i: usize) -> (K, V) {
+        self.unlink(i);
+        let entry = self.entries[i].take().unwrap();
+        self.free.push(i);
+        (entry.key, entry.value)
+    }
+
+    /// Internal function to link an unlinked entry in at the head of the list
+    fn push_head(&mut self, i: usize) {
+        let head = self.head;
+        let entry = self.entry_mut(i);
+        entry.newer = None;
+        entry.older = head;
+        match head {
+            Some(h) => self.entry_mut(h).newer = Some(i),
+            None => self.tail = Some(i),
+        }
+        self.head = Some(i);
+    }
+
+    /// Internal function to unlink an entry from the list, joining its neighbours
+    fn unlink(&mut self, i: usize) {
+        let (newer, older) = {
+            let entry = self.entry(i);
+            (entry.newer, entry.older)
+        };
+        match newer {
+            Some(n) => self.entry_mut(n).older = older,
+            None => self.head = older,
+        }
+        match older {
+            Some(o) => self.entry_mut(o).newer = newer,
+            None => self.tail = newer,
+        }
+    }
+
+    /// Internal function to get an entry that is in the cache
+    fn entry(&self, i: usize) -> &Entry<K, V> {
+        self.entries[i].as_ref().unwrap()
+    }
+
+    /// Internal function to get an entry that is in the cache mutably
+    fn entry_mut(&mut self, i: usize) -> &mut Entry<K, V> {
+        self.entries[i].as_mut().unwrap()
+    }
+}
+
+impl<K: Hash + Eq + Clone, V> Cache<K, V> for LruCache<K, V> {
+    fn get(&mut self, key: &K) -> Option<&V> {
+        LruCache::get(self, key)
+    }
+
+    fn put(&mut self, key: K, value: V) -> Option<V> {
+        LruCache::put(self, key, value)
+    }
+
+    fn remove(&mut self, key: &K) -> Option<V> {
+        LruCache::remove(self, key)
+    }
+
+    fn contains_key(&self, key: &K) -> bool {
+        LruCache::contains_key(self, key)
+    }
+
+    fn len(&self) -> usize {
+        LruCache::len(self)
+    }
+
+    fn capacity(&self) -> usize {
+        LruCache::capacity(self)
+    }
+
+    fn stats(&self) -> CacheStats {
+        LruCache::stats(self)
+    }
+}
+
+impl<K: Hash + Eq + Clone + fmt::Debug, V: fmt::Debug> fmt::Debug for LruCache<K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::Rng;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    #[test]
+    fn test_evicts_least_recent() {
+        let mut cache = LruCache::new(2);
+        cache.put(1, "a");
+        cache.put(2, "b");
+        assert_eq!(Some(&"a"), cache.get(&1));
+        // 2 is now the least recently used
+        cache.put(3, "c");
+        assert!(!cache.contains_key(&2));
+        assert_eq!(Some("a"), cache.put(1, "A"));
+        cache.put(4, "d");
+        assert!(!cache.contains_key(&3));
+        assert_eq!(vec![(&4, &"d"), (&1, &"A")], cache.iter().collect::<Vec<_>>());
+        assert_eq!(Some("A"), cache.remove(&1));
+        assert_eq!(1, cache.len());
+    }
+
+    #[test]
+    fn test_stats_and_callback() {
+        let evicted = Rc::new(RefCell::new(Vec::new()));
+        let mut cache = LruCache::new(3);
+        let log = Rc::clone(&evicted);
+        cache.set_eviction_callback(move |k, v| log.borrow_mut().push((k, v)));
+        for i in 0..5 {
+            cache.put(i, i * 10);
+        }
+        assert_eq!(None, cache.get(&0));
+        assert_eq!(Some(&20), cache.get(&2));
+        // Peeking doesn't count or change the order
+        assert_eq!(Some(&30), cache.peek(&3));
+        cache.put(5, 50);
+        assert_eq!(vec![(0, 0), (1, 10), (3, 30)], *evicted.borrow());
+        assert_eq!(CacheStats { hits: 1, misses: 1, evictions: 3 }, cache.stats());
+        assert_eq!(0.5, cache.stats().hit_rate());
+    }
+
+    #[test]
+    fn test_matches_naive() {
+        let mut rng = Rng::new(107);
+        let mut cache = LruCache::new(8);
+        // Keys from least to most recently used
+        let mut expected: Vec<(u64, u64)> = Vec::new();
+        for i in 0..5000 {
+            let key = rng.gen_range(20);
+            let position = expected.iter().position(|&(k, _)| k == key);
+            if rng.gen_bool(0.5) {
+                let found = position.map(|p| expected.remove(p));
+                assert_eq!(found.map(|(_, v)| v), cache.get(&key).copied());
+                expected.extend(found);
+            } else {
+                assert_eq!(position.map(|p| expected.remove(p).1), cache.put(key, i));
+                if expected.len() == 8 {
+                    expected.remove(0);
+                }
+                expected.push((key, i));
+            }
+        }
+        let order: Vec<(u64, u64)> = cache.iter().map(|(&k, &v)| (k, v)).collect();
+        assert_eq!(expected.into_iter().rev().collect::<Vec<_>>(), order);
+    }
+}
@@ -0,0 +1,228 @@
+use std::cmp::Reverse;
+
+use crate::graph::{EdgeId, Graph, NodeId};
+use crate::max_heap::MaxHeap;
+
+/// A matching no edge can be added to, with the cover certifying it's at least half the maximum
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MaximalMatching {
+    /// Edges of the matching, in increasing order, no two sharing an endpoint
+    pub edges: Vec<EdgeId>,
+    /// Endpoints of the matching, in increasing order. Every edge touches one, since otherwise it
+    /// could have been added, so no matching has more edges than this has nodes
+    pub cover: Vec<NodeId>,
+}
+
+/// A vertex cover with the matching certifying it's at most twice the minimum
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct VertexCover {
+    /// Nodes of the cover, in increasing order, at least one endpoint of every edge
+    pub nodes: Vec<NodeId>,
+    /// Edges sharing no endpoint, each needing its own node in any cover, so the minimum cover has
+    /// at least this many nodes
+    pub matching: Vec<EdgeId>,
+}
+
+/// Sets covering a universe, with prices certifying how far from the minimum they can be
+#[derive(Clone, Debug, PartialEq)]
+pub struct SetCover {
+    /// Indexes of the chosen sets, in the order they were chosen
+    pub sets: Vec<usize>,
+    /// Price of each element, `1 / k` if it was covered by a set covering `k` new elements, which
+    /// sum to the number of sets chosen
+    pub prices: Vec<f64>,
+    /// Harmonic number of the largest set size. No set's elements cost more than this in total, so
+    /// the prices divided by it are a feasible dual solution
+    pub ratio: f64,
+}
+
+impl SetCover {
+
+    /// Lower bound on the number of sets in a minimum cover, the number chosen over `ratio`
+    pub fn lower_bound(&self) -> f64 {
+        self.sets.len() as f64 / self.ratio
+    }
+}
+
+/// Finds a maximal matching greedily, taking each edge in order if neither endpoint is matched
+///
+/// A maximal matching has at least half as many edges as a maximum one, since the endpoints of the
+/// maximal matching touch every edge and each can only be in one edge of the maximum matching.
+/// Takes `O(V + E)` time. Edge directions are ignored, and self-loops are skipped.
+///
+/// # Arguments
+///
+/// * `graph` - Graph to match
+pub fn maximal_matching<N, E>(graph: &Graph<N, E>) -> MaximalMatching {
+    let mut matched = vec![false; graph.node_bound()];
+    let mut edges = Vec::new();
+    for e in graph.edges() {
+        if e.source != e.target && !matched[e.source] && !matched[e.target] {
+            matched[e.source] = true;
+            matched[e.target] = true;
+            edges.push(e.id);
+        }
+    }
+    let cover = (0..matched.len()).filter(|&u| matched[u]).collect();
+    MaximalMatching { edges, cover }
+}
+
+/// Finds a vertex cover at most twice the minimum, the endpoints of a maximal matching
+///
+/// Finding a minimum vertex cover is NP-hard. Any cover needs a different node for each edge of a
+/// matching, and the matched nodes cover every edge since the matching is maximal, so the cover is
+/// exactly twice the matching, and at most twice the minimum. Takes `O(V + E)` time. Edge
+/// directions are ignored, and a self-loop is covered by its node.
+///
+/// # Arguments
+///
+/// * `graph` - Graph to cover
+pub fn vertex_cover<N, E>(graph: &Graph<N, E>) -> VertexCover {
+    let MaximalMatching { edges, mut cover } = maximal_matching(graph);
+    // A self-loop on an unmatched node isn't covered by the matching
+    let mut loops: Vec<NodeId> = graph.edges().filter(|e| e.source == e.target).map(|e| e.source).collect();
+    if !loops.is_empty() {
+        cover.append(&mut loops);
+        cover.sort_unstable();
+        cover.dedup();
+    }
+    VertexCover { nodes: cover, matching: edges }
+}
+
+/// Covers the elements `0..universe` greedily, choosing the set covering the most new elements
+///
+/// Finding a minimum set cover is NP-hard, and the greedy choice is within a factor of `H(d)` of
+/// it, where `d` is the size of the largest set and `H(d) = 1 + 1/2 + ... + 1/d <= ln(d) + 1`.
+/// Set sizes only shrink, so they're kept in a `MaxHeap` that may overestimate them, and a popped
+/// set is pushed back with its real count until one is up to date, taking `O(N log(S))` time for
+/// `N` elements across `S` sets. Ties go to the lower index.
+///
+/// Returns None if some element isn't in any set.
+///
+/// # Arguments
+///
+/// * `universe` - Number of elements
+/// * `sets` - Elements of each set
+///
+/// # Panics
+///
+/// If a set contains an element not less than `universe`
+pub fn greedy_set_cover(universe: usize, sets: &[Vec<usize>]) -> Option<SetCover> {
+    let mut covered = vec![false; universe];
+    let mut prices = vec![0.0; universe];
+    let mut remaining = universe;
+    let mut heap = MaxHeap::new();
+    for (i, set) in sets.iter().enumerate() {
+        assert!(set.iter().all(|&x| x < universe), "set {} has an element outside the universe", i);
+        heap.insert((set.len(), Reverse(i)));
+    }
+    let largest = sets.iter().map(|set| set.len()).max().unwrap_or(0);
+    let mut result = SetCover { sets: Vec::new(), prices: Vec::new(), ratio: (1..=largest).map(|k| 1.0 / k as f64).sum() };
+
+    while remaining > 0 {
+        let (count, Reverse(i)) = heap.pop()?;
+        let mut new: Vec<usize> = sets[i].iter().copied().filter(|&x| !covered[x]).collect();
+        new.sort_unstable();
+        new.dedup();
+        if new.is_empty() {
+            continue;
+        }
+        if new.len() < count {
+            heap.insert((new.len(), Reverse(i)));
+            continue;
+        }
+        for &x in new.iter() {
+            covered[x] = true;
+            prices[x] = 1.0 / new.len() as f64;
+        }
+        remaining -= new.len();
+        result.sets.push(i);
+    }
+    result.prices = prices;
+    Some(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::Rng;
+
+    #[test]
+    fn test_vertex_cover() {
+        // A path 0 - 1 - 2 - 3 where matching (0, 1) first leaves (2, 3), a cover of four for a
+        // minimum of two
+        let g: Graph<(), ()> = Graph::from_edges(false, 4, vec![(0, 1, ()), (1, 2, ()), (2, 3, ())]);
+        let m = maximal_matching(&g);
+        assert_eq!(vec![0, 2], m.edges);
+        assert_eq!(vec![0, 1, 2, 3], m.cover);
+        let c = vertex_cover(&g);
+        assert_eq!(vec![0, 1, 2, 3], c.nodes);
+        assert_eq!(vec![0, 2], c.matching);
+
+        let g: Graph<(), ()> = Graph::from_edges(true, 3, vec![(2, 2, ()), (0, 1, ())]);
+        assert_eq!(vec![0, 1, 2], vertex_cover(&g).nodes);
+    }
+
+    #[test]
+    fn test_random_certificates() {
+        let mut rng = Rng::new(47);
+        let edges: Vec<_> = (0..200)
+            .map(|_| (rng.gen_range(80) as usize, rng.gen_range(80) as usize, ()))
+            .filter(|&(u, v, _)| u != v)
+            .collect();
+        let g: Graph<(), ()> = Graph::from_edges(false, 80, edges);
+        let m = maximal_matching(&g);
+        let mut seen = [false; 80];
+        for &e in m.edges.iter() {
+            let (u, v) = g.endpoints(e).unwrap();
+            assert!(!seen[u] && !seen[v] && u != v);
+            seen[u] = true;
+            seen[v] = true;
+        }
+
+        let c = vertex_cover(&g);
+        assert_eq!(m.edges, c.matching);
+        assert_eq!(2 * c.matching.len(), c.nodes.len());
+        assert!(g.edges().all(|e| c.nodes.binary_search(&e.source).is_ok() || c.nodes.binary_search(&e.target).is_ok()));
+    }
+
+    #[test]
+    fn test_greedy_set_cover() {
+        // From CLRS, greedy picks S1, S4, S5, S3 where S3, S4, S5 is optimal
+        let sets = vec![
+            vec![1, 2, 3, 4, 5, 6],
+            vec![5, 6, 8, 9],
+            vec![1, 4, 7, 10],
+            vec![2, 5, 7, 8, 11],
+            vec![3, 6, 9, 12],
+            vec![10, 11],
+        ];
+        assert_eq!(None, greedy_set_cover(13, &sets));
+
+        let sets: Vec<Vec<usize>> = sets.iter().map(|s| s.iter().map(|&x| x - 1).collect()).collect();
+        let r = greedy_set_cover(12, &sets).unwrap();
+        assert_eq!(vec![0, 3, 4, 2], r.sets);
+        assert!((r.prices.iter().sum::<f64>() - 4.0).abs() < 1e-9);
+        assert!(r.lower_bound() <= 3.0);
+    }
+
+    #[test]
+    fn test_random_set_cover() {
+        let mut rng = Rng::new(53);
+        let mut sets: Vec<Vec<usize>> = (0..40).map(|_| (0..rng.gen_range(10) + 1).map(|_| rng.gen_range(60) as usize).collect()).collect();
+        sets.extend((0..60).map(|x| vec![x]));
+        let r = greedy_set_cover(60, &sets).unwrap();
+        let mut covered = [false; 60];
+        for &i in r.sets.iter() {
+            sets[i].iter().for_each(|&x| covered[x] = true);
+        }
+        assert!(covered.iter().all(|&c| c));
+        // The prices scaled down by the ratio never charge a set more than one
+        for set in sets.iter() {
+            let mut set = set.clone();
+            set.sort_unstable();
+            set.dedup();
+            assert!(set.iter().map(|&x| r.prices[x]).sum::<f64>() <= r.ratio + 1e-9);
+        }
+    }
+}
@@ -0,0 +1,214 @@
+use crate::dijkstra::check_weights;
+use crate::graph::{Graph, NodeId};
+use crate::indexed_min_heap::IndexedMinHeap;
+use crate::property_map::EdgeData;
+use crate::shortest_paths::{NegativeWeightError, ShortestPaths, Weight};
+
+/// A* search for a shortest path between two nodes, with edge data as weights
+///
+/// Like Dijkstra, but settles nodes in order of their distance from `start` plus the heuristic's
+/// estimate of their distance to `goal`, so a good heuristic settles far fewer nodes. Returns the
+/// cost and nodes of the path, or None if `goal` isn't reachable, and an error if any edge has a
+/// negative weight, or a weight that doesn't compare, like `NaN`.
+///
+/// The heuristic must be consistent, `h(u) <= w(u, v) + h(v)` for every edge and `h(goal) = 0`,
+/// for the path to be shortest. In debug builds every edge the search relaxes is checked, see
+/// `astar_with_check`.
+///
+/// # Arguments
+///
+/// * `graph` - Graph to search, edges are followed in both directions if undirected
+/// * `start` - Node to start from
+/// * `goal` - Node to find a path to
+/// * `heuristic` - Lower bound on the distance from a node to `goal`
+///
+/// # Panics
+///
+/// If `start` or `goal` isn't a node in the graph, or in debug builds, if the heuristic isn't consistent
+pub fn astar<N, W: Weight, H: FnMut(NodeId) -> W>(graph: &Graph<N, W>, start: NodeId, goal: NodeId, heuristic: H) -> Result<Option<(W, Vec<NodeId>)>, NegativeWeightError> {
+    astar_with_check(graph, start, goal, heuristic, cfg!(debug_assertions))
+}
+
+/// A* search, with the heuristic's consistency check turned on or off
+///
+/// When checking, panics if `h(goal) > 0` or the search relaxes an edge with `h(u) > w(u, v) + h(v)`,
+/// either of which can make A* return a path that isn't shortest. Every consistent heuristic is
+/// also admissible, never overestimating the distance to `goal`.
+///
+/// # Arguments
+///
+/// * `graph` - Graph to search, edges are followed in both directions if undirected
+/// * `start` - Node to start from
+/// * `goal` - Node to find a path to
+/// * `heuristic` - Lower bound on the distance from a node to `goal`
+/// * `check` - If the heuristic is checked
+///
+/// # Panics
+///
+/// If `start` or `goal` isn't a node in the graph, or `check` is set and the heuristic isn't consistent
+pub fn astar_with_check<N, W: Weight, H: FnMut(NodeId) -> W>(
+    graph: &Graph<N, W>,
+    start: NodeId,
+    goal: NodeId,
+    heuristic: H,
+    check: bool,
+) -> Result<Option<(W, Vec<NodeId>)>, NegativeWeightError> {
+    let paths = search(graph, start, goal, heuristic, check)?;
+    Ok(paths.distance(goal).zip(paths.path_to(goal)))
+}
+
+/// A* search, returning the tree of shortest paths it settled on the way to `goal`
+///
+/// Gives the same path to `goal` as `astar`, in the same `ShortestPaths` type as the single source
+/// algorithms. Only nodes settled before `goal`, whose distances are final, are reached, so
+/// `path_to` gives a shortest path to every one of them. The heuristic is checked in debug builds.
+///
+/// # Arguments
+///
+/// * `graph` - Graph to search, edges are followed in both directions if undirected
+/// * `start` - Node to start from
+/// * `goal` - Node to find a path to
+/// * `heuristic` - Lower bound on the distance from a node to `goal`
+///
+/// # Panics
+///
+/// If `start` or `goal` isn't a node in the graph, or in debug builds, if the heuristic isn't consistent
+pub fn astar_paths<N, W: Weight, H: FnMut(NodeId) -> W>(graph: &Graph<N, W>, start: NodeId, goal: NodeId, heuristic: H) -> Result<ShortestPaths<W>, NegativeWeightError> {
+    search(graph, start, goal, heuristic, cfg!(debug_assertions))
+}
+
+/// Internal function for the A* search, stopping when `goal` is settled
+///
+/// Nodes left in the heap only have tentative distances, and are removed from the result. Settled
+/// nodes are never revisited, so negative weights are rejected before searching.
+fn search<N, W: Weight, H: FnMut(NodeId) -> W>(
+    graph: &Graph<N, W>,
+    start: NodeId,
+    goal: NodeId,
+    mut heuristic: H,
+    check: bool,
+) -> Result<ShortestPaths<W>, NegativeWeightError> {
+    assert!(graph.contains_node(start) && graph.contains_node(goal), "start and goal must be nodes in the graph");
+    check_weights(graph, &EdgeData)?;
+    if check {
+        assert!(heuristic(goal) <= W::zero(), "heuristic must be 0 at the goal");
+    }
+
+    let n = graph.node_bound();
+    let mut paths = ShortestPaths::new(start, n, W::zero());
+    let mut estimate: Vec<Option<W>> = vec![None; n];
+    let mut settled = vec![false; n];
+    let mut heap = IndexedMinHeap::with_capacity(n);
+
+    let h = heuristic(start);
+    estimate[start] = Some(h);
+    heap.push(start, h);
+
+    while let Some((u, _)) = heap.pop() {
+        settled[u] = true;
+        if u == goal {
+            break;
+        }
+        let d = paths.distance[u].unwrap();
+
+        for e in graph.edges_from(u) {
+            let v = e.target;
+            let hv = *estimate[v].get_or_insert_with(|| heuristic(v));
+            if check {
+                let hu = estimate[u].unwrap();
+                assert!(hu <= *e.data + hv, "heuristic is not consistent on the edge from {} to {}", u, v);
+            }
+            if settled[v] {
+                continue;
+            }
+            let nd = d + *e.data;
+            if paths.distance[v].is_none_or(|dv| nd < dv) {
+                paths.distance[v] = Some(nd);
+                paths.predecessor[v] = Some(u);
+                if heap.contains(v) {
+                    heap.decrease_key(v, nd + hv);
+                } else {
+                    heap.push(v, nd + hv);
+                }
+            }
+        }
+    }
+
+    for v in (0..n).filter(|&v| !settled[v]) {
+        paths.distance[v] = None;
+        paths.predecessor[v] = None;
+    }
+    Ok(paths)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dijkstra::dijkstra;
+
+    #[test]
+    fn test_manhattan() {
+        let (w, h) = (20, 10);
+        let mut g: Graph<(), u32> = Graph::grid(w, h);
+        for e in 0..g.edge_bound() {
+            *g.edge_mut(e).unwrap() = 1;
+        }
+        // A wall down column 10 with a gap in the last row
+        for y in 0..h - 1 {
+            for e in g.edges_from(y * w + 10).map(|e| e.id).collect::<Vec<_>>() {
+                g.remove_edge(e);
+            }
+        }
+        let goal = 19;
+        let manhattan = |u: NodeId| ((u % w) as i64 - 19).unsigned_abs() as u32 + (u / w) as u32;
+
+        let (cost, path) = astar(&g, 0, goal, manhattan).unwrap().unwrap();
+        assert_eq!(dijkstra(&g, 0).unwrap().distance(goal), Some(cost));
+        assert_eq!(cost as usize + 1, path.len());
+        assert_eq!((0, goal), (path[0], path[path.len() - 1]));
+    }
+
+    #[test]
+    fn test_zero_heuristic() {
+        let edges = vec![(0, 1, 4.0), (0, 2, 1.0), (2, 1, 2.0), (1, 3, 1.0)];
+        let g: Graph<(), f64> = Graph::from_edges(true, 4, edges);
+        assert_eq!(Ok(Some((4.0, vec![0, 2, 1, 3]))), astar(&g, 0, 3, |_| 0.0));
+        assert_eq!(Ok(Some((0.0, vec![2]))), astar(&g, 2, 2, |_| 0.0));
+        assert_eq!(Ok(None), astar(&g, 3, 0, |_| 0.0));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_inconsistent() {
+        let g: Graph<(), u32> = Graph::from_edges(true, 3, vec![(0, 1, 1), (1, 2, 1)]);
+        let _ = astar_with_check(&g, 0, 2, |u| if u == 1 { 10 } else { 0 }, true);
+    }
+
+    #[test]
+    fn test_astar_paths() {
+        let edges = vec![(0, 1, 4.0), (0, 2, 1.0), (2, 1, 2.0), (1, 3, 1.0), (0, 3, 10.0)];
+        let g: Graph<(), f64> = Graph::from_edges(true, 4, edges);
+        let paths = astar_paths(&g, 0, 1, |_| 0.0).unwrap();
+        assert_eq!(Some(vec![0, 2, 1]), paths.path_to(1));
+        // Node 3 was still in the heap with a tentative distance, so it isn't reached
+        assert_eq!(vec![Some(0.0), Some(3.0), Some(1.0), None], paths.distance);
+        assert_eq!(vec![(2, 1), (0, 2)], paths.tree().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_unchecked() {
+        let g: Graph<(), u32> = Graph::from_edges(true, 3, vec![(0, 1, 1), (1, 2, 1)]);
+        let result = astar_with_check(&g, 0, 2, |u| if u == 1 { 10 } else { 0 }, false);
+        assert_eq!(Ok(Some((2, vec![0, 1, 2]))), result);
+    }
+
+    #[test]
+    fn test_negative_weight() {
+        // Settling 1 before reaching it through 2 would miss the shorter path 0 -> 2 -> 1
+        let g: Graph<(), i32> = Graph::from_edges(true, 3, vec![(0, 1, 1), (0, 2, 3), (2, 1, -5)]);
+        assert_eq!(Err(NegativeWeightError { edge: 2 }), astar(&g, 0, 1, |_| 0));
+        assert_eq!(Err(NegativeWeightError { edge: 2 }), astar_paths(&g, 0, 1, |_| 0).map(|p| p.distance));
+        let g: Graph<(), f64> = Graph::from_edges(true, 2, vec![(0, 1, f64::NAN)]);
+        assert_eq!(Err(NegativeWeightError { edge: 0 }), astar(&g, 0, 1, |_| 0.0));
+    }
+}
@@ -0,0 +1,185 @@
+use std::fmt;
+
+use crate::graph::{Graph, NodeId};
+use crate::property_map::{EdgeData, EdgeMap};
+use crate::shortest_paths::{ShortestPaths, Weight};
+
+/// Error for a negative weight cycle reachable from the source, so some paths have no shortest
+///
+/// `cycle` lists the nodes of the cycle in order, with an edge from each node to the next and from
+/// the last node back to the first.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NegativeCycleError {
+    pub cycle: Vec<NodeId>,
+}
+
+impl fmt::Display for NegativeCycleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "graph has a negative cycle through nodes {:?}", self.cycle)
+    }
+}
+
+impl std::error::Error for NegativeCycleError {}
+
+/// Bellman-Ford single source shortest paths, with edge data as weights
+///
+/// Handles negative weights by relaxing every edge up to `V - 1` times, stopping early once a
+/// round changes nothing, in `O(V * E)` time. If an edge can still be relaxed after that, a
+/// negative cycle is reachable from the source, and it's returned as an error. In an undirected
+/// graph a negative edge is itself a negative cycle, since it can be crossed back and forth.
+///
+/// # Arguments
+///
+/// * `graph` - Graph to search, edges are followed in both directions if undirected
+/// * `source` - Node to find paths from
+///
+/// # Panics
+///
+/// If the source isn't a node in the graph
+pub fn bellman_ford<N, W: Weight>(graph: &Graph<N, W>, source: NodeId) -> Result<ShortestPaths<W>, NegativeCycleError> {
+    bellman_ford_with(graph, source, &EdgeData)
+}
+
+/// Bellman-Ford single source shortest paths, with weights read from an edge map
+///
+/// The same as `bellman_ford`, but weights can come from a vector indexed by `EdgeId` or a closure.
+///
+/// # Arguments
+///
+/// * `graph` - Graph to search, edges are followed in both directions if undirected
+/// * `source` - Node to find paths from
+/// * `weight` - Weight of each edge
+///
+/// # Panics
+///
+/// If the source isn't a node in the graph
+pub fn bellman_ford_with<N, E, W, M>(graph: &Graph<N, E>, source: NodeId, weight: &M) -> Result<ShortestPaths<W>, NegativeCycleError>
+where
+    W: Weight,
+    M: EdgeMap<E, Value = W> + ?Sized,
+{
+    assert!(graph.contains_node(source), "source must be a node in the graph");
+    let mut paths = ShortestPaths::new(source, graph.node_bound(), W::zero());
+
+    for _ in 1..graph.node_count() {
+        if relax_all(graph, weight, &mut paths).is_none() {
+            return Ok(paths);
+        }
+    }
+    match relax_all(graph, weight, &mut paths) {
+        None => Ok(paths),
+        Some(v) => Err(NegativeCycleError { cycle: extract_cycle(&paths, v, graph.node_count()) }),
+    }
+}
+
+/// Internal function to relax every edge once, returning the last node whose distance changed
+fn relax_all<N, E, W: Weight, M: EdgeMap<E, Value = W> + ?Sized>(graph: &Graph<N, E>, weight: &M, paths: &mut ShortestPaths<W>) -> Option<NodeId> {
+    let mut changed = None;
+    for u in graph.node_ids() {
+        let d = match paths.distance[u] {
+            Some(d) => d,
+            None => continue,
+        };
+        for e in graph.edges_from(u) {
+            let nd = d + weight.edge_value(e);
+            if paths.distance[e.target].is_none_or(|dv| nd < dv) {
+                paths.distance[e.target] = Some(nd);
+                paths.predecessor[e.target] = Some(u);
+                changed = Some(e.target);
+            }
+        }
+    }
+    changed
+}
+
+/// Internal function to find the negative cycle behind a node relaxed in the `V`-th round
+///
+/// The node's predecessors lead back into the cycle, and after `V` steps they must be on it.
+fn extract_cycle<W>(paths: &ShortestPaths<W>, v: NodeId, n: usize) -> Vec<NodeId> {
+    let mut u = v;
+    for _ in 0..n {
+        u = paths.predecessor[u].unwrap();
+    }
+    let mut cycle = vec![u];
+    let mut w = paths.predecessor[u].unwrap();
+    while w != u {
+        cycle.push(w);
+        w = paths.predecessor[w].unwrap();
+    }
+    cycle.reverse();
+    cycle
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::EdgeRef;
+
+    /// Internal function to check that a cycle is made of edges in the graph with a negative total
+    fn is_negative_cycle(graph: &Graph<(), i32>, cycle: &[NodeId]) -> bool {
+        let mut total = 0;
+        for i in 0..cycle.len() {
+            let (u, v) = (cycle[i], cycle[(i + 1) % cycle.len()]);
+            match graph.edges_from(u).filter(|e| e.target == v).map(|e| *e.data).min() {
+                Some(w) => total += w,
+                None => return false,
+            }
+        }
+        total < 0
+    }
+
+    #[test]
+    fn test_bellman_ford() {
+        // CLRS Figure 24.4
+        let (s, t, x, y, z) = (0, 1, 2, 3, 4);
+        let edges = vec![
+            (s, t, 6), (s, y, 7), (t, x, 5), (t, y, 8), (t, z, -4), (x, t, -2), (y, x, -3), (y, z, 9), (z, s, 2), (z, x, 7),
+        ];
+        let g: Graph<(), i32> = Graph::from_edges(true, 5, edges);
+        let paths = bellman_ford(&g, s).unwrap();
+        assert_eq!(vec![Some(0), Some(2), Some(4), Some(7), Some(-2)], paths.distance);
+        assert_eq!(Some(vec![s, y, x, t, z]), paths.path_to(z));
+    }
+
+    #[test]
+    fn test_negative_cycle() {
+        let edges = vec![(0, 1, 1), (1, 2, 2), (2, 3, -4), (3, 1, 1), (3, 4, 1)];
+        let g: Graph<(), i32> = Graph::from_edges(true, 5, edges);
+        let err = bellman_ford(&g, 0).unwrap_err();
+        assert_eq!(3, err.cycle.len());
+        assert!(is_negative_cycle(&g, &err.cycle));
+    }
+
+    #[test]
+    fn test_unreachable_cycle() {
+        let g: Graph<(), i32> = Graph::from_edges(true, 4, vec![(0, 1, 3), (2, 3, -1), (3, 2, -1)]);
+        let paths = bellman_ford(&g, 0).unwrap();
+        assert_eq!(vec![Some(0), Some(3), None, None], paths.distance);
+    }
+
+    #[test]
+    fn test_self_loop() {
+        let g: Graph<(), i32> = Graph::from_edges(true, 2, vec![(0, 1, 3), (1, 1, -1)]);
+        assert_eq!(vec![1], bellman_ford(&g, 0).unwrap_err().cycle);
+    }
+
+    #[test]
+    fn test_edge_map() {
+        // Negating every weight turns shortest paths into longest, here with no cycles to break it
+        let g: Graph<(), i32> = Graph::from_edges(true, 3, vec![(0, 1, 3), (1, 2, 4), (0, 2, 5)]);
+        let negated = |e: EdgeRef<'_, i32>| -*e.data;
+        assert_eq!(Some(-7), bellman_ford_with(&g, 0, &negated).unwrap().distance(2));
+        assert_eq!(Some(5), bellman_ford(&g, 0).unwrap().distance(2));
+    }
+
+    #[test]
+    fn test_undirected() {
+        let g: Graph<(), i32> = Graph::from_edges(false, 3, vec![(0, 1, 2), (1, 2, 3)]);
+        assert_eq!(Some(5), bellman_ford(&g, 0).unwrap().distance(2));
+
+        let g: Graph<(), i32> = Graph::from_edges(false, 3, vec![(0, 1, 2), (1, 2, -3)]);
+        let cycle = bellman_ford(&g, 0).unwrap_err().cycle;
+        assert_eq!(2, cycle.len());
+        assert!(is_negative_cycle(&g, &cycle));
+    }
+}
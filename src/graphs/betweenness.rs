@@ -0,0 +1,224 @@
+use std::cmp::Reverse;
+use std::collections::VecDeque;
+
+use crate::dijkstra::check_weights;
+use crate::graph::{Graph, NodeId};
+use crate::max_heap::MaxHeap;
+use crate::property_map::EdgeData;
+use crate::rng::Rng;
+use crate::shortest_paths::{NegativeWeightError, Weight};
+
+/// Shortest paths from one source, as Brandes' accumulation needs them
+struct PathCounts {
+    /// Reached nodes in order of increasing distance
+    order: Vec<NodeId>,
+    /// Predecessors of each node on its shortest paths
+    predecessors: Vec<Vec<NodeId>>,
+    /// Number of shortest paths to each node
+    sigma: Vec<f64>,
+}
+
+/// Betweenness centrality of every node, from shortest paths by edge count
+///
+/// The betweenness of `v` sums, over every pair of other nodes `s` and `t`, the fraction of
+/// shortest paths from `s` to `t` that go through `v`, so nodes that many paths depend on, like
+/// bridges between communities, score highly. Brandes' algorithm runs one breadth-first search per
+/// source, counting shortest paths on the way out, then adds up each node's dependencies from the
+/// farthest nodes back, taking `O(V * E)` time rather than the `O(V^3)` of counting every pair.
+///
+/// The scores aren't normalized. An undirected graph counts each pair once rather than in both
+/// directions. Removed nodes score `0.0`.
+///
+/// # Arguments
+///
+/// * `graph` - Graph to score
+pub fn betweenness_centrality<N, E>(graph: &Graph<N, E>) -> Vec<f64> {
+    brandes(graph, graph.node_ids(), 1.0, |s| bfs_counts(graph, s))
+}
+
+/// Betweenness centrality of every node, from shortest paths by edge weight
+///
+/// The same as `betweenness_centrality`, with a Dijkstra search from each source in place of the
+/// breadth-first search, taking `O(V * E * log(E))` time. Paths count as equally short when their
+/// weights compare equal, so floating point weights may split ties that should be equal. Returns
+/// an error if any edge has a negative weight, or a weight that doesn't compare, like `NaN`.
+///
+/// # Arguments
+///
+/// * `graph` - Graph to score
+pub fn weighted_betweenness_centrality<N, W: Weight>(graph: &Graph<N, W>) -> Result<Vec<f64>, NegativeWeightError> {
+    check_weights(graph, &EdgeData)?;
+    Ok(brandes(graph, graph.node_ids(), 1.0, |s| dijkstra_counts(graph, s)))
+}
+
+/// Estimates the betweenness centrality of every node from a sample of sources
+///
+/// Exact betweenness needs a search from every node, which is too slow for large graphs. This runs
+/// `samples` searches from sources chosen uniformly at random, with replacement, and scales the
+/// dependencies by `V / samples`, which gives an unbiased estimate of `betweenness_centrality` in
+/// `O(samples * E)` time.
+///
+/// # Arguments
+///
+/// * `graph` - Graph to score
+/// * `samples` - Number of sources to search from
+/// * `seed` - Seed for choosing the sources
+///
+/// # Panics
+///
+/// If `samples` is zero
+pub fn approximate_betweenness_centrality<N, E>(graph: &Graph<N, E>, samples: usize, seed: u64) -> Vec<f64> {
+    assert!(samples > 0, "samples must be positive");
+    let nodes: Vec<NodeId> = graph.node_ids().collect();
+    if nodes.is_empty() {
+        return vec![0.0; graph.node_bound()];
+    }
+    let mut rng = Rng::new(seed);
+    let sources: Vec<NodeId> = (0..samples).map(|_| nodes[rng.gen_range(nodes.len() as u64) as usize]).collect();
+    brandes(graph, sources.into_iter(), nodes.len() as f64 / samples as f64, |s| bfs_counts(graph, s))
+}
+
+/// Internal function to add up the dependencies of every node on the paths from each source
+fn brandes<N, E, I, F>(graph: &Graph<N, E>, sources: I, scale: f64, mut counts: F) -> Vec<f64>
+where
+    I: Iterator<Item = NodeId>,
+    F: FnMut(NodeId) -> PathCounts,
+{
+    let mut centrality = vec![0.0; graph.node_bound()];
+    let mut delta = vec![0.0; graph.node_bound()];
+    for s in sources {
+        let paths = counts(s);
+        // Each node passes its dependency back to its predecessors, split by their path counts
+        for &w in paths.order.iter().rev() {
+            for &v in paths.predecessors[w].iter() {
+                delta[v] += paths.sigma[v] / paths.sigma[w] * (1.0 + delta[w]);
+            }
+            if w != s {
+                centrality[w] += delta[w];
+            }
+        }
+        for &w in paths.order.iter() {
+            delta[w] = 0.0;
+        }
+    }
+
+    let scale = if graph.is_directed() { scale } else { scale / 2.0 };
+    centrality.iter_mut().for_each(|c| *c *= scale);
+    centrality
+}
+
+/// Internal function to count the shortest paths by edge count from a source
+fn bfs_counts<N, E>(graph: &Graph<N, E>, source: NodeId) -> PathCounts {
+    let n = graph.node_bound();
+    let mut paths = PathCounts { order: Vec::new(), predecessors: vec![Vec::new(); n], sigma: vec![0.0; n] };
+    let mut distance: Vec<Option<usize>> = vec![None; n];
+    distance[source] = Some(0);
+    paths.sigma[source] = 1.0;
+    let mut queue = VecDeque::new();
+    queue.push_back(source);
+
+    while let Some(u) = queue.pop_front() {
+        paths.order.push(u);
+        let d = distance[u].unwrap();
+        for v in graph.neighbors(u) {
+            if distance[v].is_none() {
+                distance[v] = Some(d + 1);
+                queue.push_back(v);
+            }
+            if distance[v] == Some(d + 1) {
+                paths.sigma[v] += paths.sigma[u];
+                paths.predecessors[v].push(u);
+            }
+        }
+    }
+    paths
+}
+
+/// Internal function to count the shortest paths by edge weight from a source
+fn dijkstra_counts<N, W: Weight>(graph: &Graph<N, W>, source: NodeId) -> PathCounts {
+    let n = graph.node_bound();
+    let mut paths = PathCounts { order: Vec::new(), predecessors: vec![Vec::new(); n], sigma: vec![0.0; n] };
+    let mut distance: Vec<Option<W>> = vec![None; n];
+    let mut settled = vec![false; n];
+    distance[source] = Some(W::zero());
+    paths.sigma[source] = 1.0;
+    let mut heap = MaxHeap::new();
+    heap.insert((Reverse(W::zero()), source));
+
+    while let Some((Reverse(d), u)) = heap.pop() {
+        if settled[u] {
+            continue;
+        }
+        settled[u] = true;
+        paths.order.push(u);
+        for e in graph.edges_from(u) {
+            let v = e.target;
+            let nd = d + *e.data;
+            if settled[v] {
+                continue;
+            }
+            match distance[v] {
+                Some(dv) if nd == dv => {
+                    paths.sigma[v] += paths.sigma[u];
+                    paths.predecessors[v].push(u);
+                }
+                Some(dv) if nd > dv => {}
+                _ => {
+                    distance[v] = Some(nd);
+                    paths.sigma[v] = paths.sigma[u];
+                    paths.predecessors[v] = vec![u];
+                    heap.insert((Reverse(nd), v));
+                }
+            }
+        }
+    }
+    paths
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_betweenness_centrality() {
+        // A path 0 - 1 - 2 - 3 - 4, where 2 is between the most pairs
+        let g: Graph<(), ()> = Graph::path(5);
+        assert_eq!(vec![0.0, 3.0, 4.0, 3.0, 0.0], betweenness_centrality(&g));
+
+        // The same path directed only counts pairs in one direction
+        let g: Graph<(), ()> = Graph::from_edges(true, 5, (1..5).map(|u| (u - 1, u, ())));
+        assert_eq!(vec![0.0, 3.0, 4.0, 3.0, 0.0], betweenness_centrality(&g));
+
+        // A 4-cycle has two shortest paths between opposite nodes, each through a different node
+        let g: Graph<(), ()> = Graph::cycle(4);
+        assert_eq!(vec![0.5; 4], betweenness_centrality(&g));
+    }
+
+    #[test]
+    fn test_weighted() {
+        // The direct edge 0 - 2 is longer than going through 1
+        let g: Graph<(), u32> = Graph::from_edges(false, 3, vec![(0, 1, 1), (1, 2, 1), (0, 2, 5)]);
+        assert_eq!(vec![0.0, 1.0, 0.0], weighted_betweenness_centrality(&g).unwrap());
+        assert_eq!(vec![0.0; 3], betweenness_centrality(&g));
+
+        let g: Graph<(), i32> = Graph::from_edges(true, 2, vec![(0, 1, -1)]);
+        assert_eq!(Err(NegativeWeightError { edge: 0 }), weighted_betweenness_centrality(&g));
+    }
+
+    #[test]
+    fn test_matches_unit_weights() {
+        let mut rng = Rng::new(67);
+        let edges: Vec<_> = (0..150).map(|_| (rng.gen_range(40) as usize, rng.gen_range(40) as usize, 1u32)).collect();
+        let g: Graph<(), u32> = Graph::from_edges(false, 40, edges);
+        let exact = betweenness_centrality(&g);
+        let weighted = weighted_betweenness_centrality(&g).unwrap();
+        for u in 0..40 {
+            assert!((exact[u] - weighted[u]).abs() < 1e-9);
+        }
+
+        let approximate = approximate_betweenness_centrality(&g, 4000, 7);
+        let total: f64 = exact.iter().sum();
+        let estimate: f64 = approximate.iter().sum();
+        assert!((total - estimate).abs() < 0.05 * total);
+    }
+}
@@ -0,0 +1,164 @@
+use crate::deque::Deque;
+use crate::graph::{Graph, NodeId};
+use crate::shortest_paths::ShortestPaths;
+
+/// Breadth-first search for the paths with fewest edges from a source
+///
+/// Visits nodes in order of their distance from the source, so the first time a node is reached
+/// is along a path with the fewest edges. Distances count edges, and edge data is ignored. Takes
+/// `O(V + E)` time.
+///
+/// # Arguments
+///
+/// * `graph` - Graph to search, edges are followed in both directions if undirected
+/// * `source` - Node to find paths from
+///
+/// # Panics
+///
+/// If the source isn't a node in the graph
+pub fn bfs<N, E>(graph: &Graph<N, E>, source: NodeId) -> ShortestPaths<usize> {
+    assert!(graph.contains_node(source), "source must be a node in the graph");
+    let mut paths = ShortestPaths::new(source, graph.node_bound(), 0);
+    let mut queue = Deque::new();
+    queue.push_back(source);
+    while let Some(u) = queue.pop_front() {
+        let d = paths.distance[u].unwrap();
+        for v in graph.neighbors(u) {
+            if paths.distance[v].is_none() {
+                paths.distance[v] = Some(d + 1);
+                paths.predecessor[v] = Some(u);
+                queue.push_back(v);
+            }
+        }
+    }
+    paths
+}
+
+/// Lazy breadth-first search, an iterator over the nodes reachable from a start node
+///
+/// Yields nodes in order of their distance from the start, searching only as far as the iterator
+/// is advanced, so a search can stop as soon as it finds what it's looking for. Between steps, the
+/// distances, parents and frontier found so far can be inspected.
+#[derive(Clone, Debug)]
+pub struct Bfs<'a, N, E> {
+    graph: &'a Graph<N, E>,
+    queue: Deque<NodeId>,
+    distance: Vec<Option<usize>>,
+    parent: Vec<Option<NodeId>>,
+}
+
+impl<'a, N, E> Bfs<'a, N, E> {
+
+    /// Starts a breadth-first search, without visiting any nodes yet
+    ///
+    /// # Arguments
+    ///
+    /// * `graph` - Graph to search, edges are followed in both directions if undirected
+    /// * `start` - Node to search from
+    ///
+    /// # Panics
+    ///
+    /// If the start isn't a node in the graph
+    pub fn new(graph: &'a Graph<N, E>, start: NodeId) -> Bfs<'a, N, E> {
+        assert!(graph.contains_node(start), "start must be a node in the graph");
+        let mut distance = vec![None; graph.node_bound()];
+        distance[start] = Some(0);
+        let mut queue = Deque::new();
+        queue.push_back(start);
+        Bfs { graph, queue, distance, parent: vec![None; graph.node_bound()] }
+    }
+
+    /// Checks if a node has been discovered, which happens before it's yielded
+    ///
+    /// # Arguments
+    ///
+    /// * `u` - Node to check
+    pub fn is_discovered(&self, u: NodeId) -> bool {
+        self.distance[u].is_some()
+    }
+
+    /// Number of edges from the start to a node, or None if it hasn't been discovered
+    ///
+    /// # Arguments
+    ///
+    /// * `u` - Node to get the distance of
+    pub fn distance(&self, u: NodeId) -> Option<usize> {
+        self.distance[u]
+    }
+
+    /// Node a node was discovered from, or None for the start and undiscovered nodes
+    ///
+    /// # Arguments
+    ///
+    /// * `u` - Node to get the parent of
+    pub fn parent(&self, u: NodeId) -> Option<NodeId> {
+        self.parent[u]
+    }
+
+    /// Returns an iterator over the discovered nodes that haven't been yielded, in the order they
+    /// will be
+    pub fn frontier(&self) -> impl Iterator<Item = NodeId> + '_ {
+        self.queue.iter().copied()
+    }
+}
+
+impl<'a, N, E> Iterator for Bfs<'a, N, E> {
+    type Item = NodeId;
+
+    fn next(&mut self) -> Option<NodeId> {
+        let u = self.queue.pop_front()?;
+        let d = self.distance[u].unwrap();
+        for v in self.graph.neighbors(u) {
+            if self.distance[v].is_none() {
+                self.distance[v] = Some(d + 1);
+                self.parent[v] = Some(u);
+                self.queue.push_back(v);
+            }
+        }
+        Some(u)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dijkstra::dijkstra;
+    use crate::rng::Rng;
+
+    #[test]
+    fn test_bfs() {
+        // CLRS Figure 22.3
+        let (r, s, t, u, v, w, x, y) = (0, 1, 2, 3, 4, 5, 6, 7);
+        let edges = vec![(r, s, ()), (r, v, ()), (s, w, ()), (w, t, ()), (w, x, ()), (t, x, ()), (t, u, ()), (x, u, ()), (x, y, ()), (u, y, ())];
+        let g: Graph<(), ()> = Graph::from_edges(false, 8, edges);
+        let paths = bfs(&g, s);
+        let distances: Vec<_> = (0..8).map(|i| paths.distance(i).unwrap()).collect();
+        assert_eq!(vec![1, 0, 2, 3, 2, 1, 2, 3], distances);
+        assert_eq!(Some(vec![s, w, x, y]), paths.path_to(y));
+    }
+
+    #[test]
+    fn test_matches_dijkstra() {
+        let mut rng = Rng::new(41);
+        let edges: Vec<_> = (0..300).map(|_| (rng.gen_range(100) as usize, rng.gen_range(100) as usize, 1usize)).collect();
+        let g: Graph<(), usize> = Graph::from_edges(true, 100, edges);
+        assert_eq!(dijkstra(&g, 0).unwrap().distance, bfs(&g, 0).distance);
+    }
+
+    #[test]
+    fn test_lazy_bfs() {
+        let g: Graph<(), ()> = Graph::grid(4, 4);
+        let order: Vec<NodeId> = Bfs::new(&g, 0).collect();
+        assert_eq!(16, order.len());
+        let paths = bfs(&g, 0);
+        assert!(order.windows(2).all(|w| paths.distance(w[0]) <= paths.distance(w[1])));
+
+        // Stop at the first node three edges away
+        let mut search = Bfs::new(&g, 0);
+        let found = search.by_ref().find(|&u| u % 4 + u / 4 == 3).unwrap();
+        assert_eq!(3, found);
+        assert_eq!(Some(2), search.parent(found));
+        assert!(!search.is_discovered(15));
+        assert!(search.frontier().all(|u| search.distance(u) >= Some(3)));
+    }
+}
@@ -0,0 +1,215 @@
+use crate::dfs::adjacency;
+use crate::graph::{EdgeId, Graph, NodeId};
+
+/// Bridges, articulation points and biconnected components of an undirected graph
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Biconnectivity {
+    /// Edges whose removal disconnects their component, in increasing order
+    pub bridges: Vec<EdgeId>,
+    /// Nodes whose removal disconnects their component, in increasing order
+    pub articulation_points: Vec<NodeId>,
+    /// Edges of each maximal subgraph that stays connected after removing any one node, a single
+    /// bridge being a component of its own
+    pub components: Vec<Vec<EdgeId>>,
+}
+
+/// Finds the bridges, articulation points and biconnected components of an undirected graph
+///
+/// A single depth-first search, iterative so long paths don't overflow the stack, computes the
+/// low-link of every node: the earliest discovery time reachable from its subtree with at most one
+/// back edge. A tree edge `(u, v)` is a bridge if `low[v] > disc[u]`, and `u` separates `v`'s
+/// subtree from the rest if `low[v] >= disc[u]`, which makes a non-root `u` an articulation point,
+/// and the root one if it has two or more children. Edges are kept on a stack as they're explored,
+/// and popped as a biconnected component each time a subtree is separated. Takes `O(V + E)` time.
+///
+/// The edge to the parent is skipped by index rather than by node, so one of two parallel edges is
+/// never a bridge. Self-loops can't disconnect anything and are ignored.
+///
+/// # Arguments
+///
+/// * `graph` - Graph to search
+///
+/// # Panics
+///
+/// If the graph is directed
+pub fn biconnectivity<N, E>(graph: &Graph<N, E>) -> Biconnectivity {
+    assert!(!graph.is_directed(), "biconnectivity requires an undirected graph");
+    let adjacency = adjacency(graph);
+    let n = graph.node_bound();
+    let mut disc: Vec<Option<usize>> = vec![None; n];
+    let mut low = vec![0; n];
+    let mut time = 0;
+    let mut is_cut = vec![false; n];
+    let mut result = Biconnectivity { bridges: Vec::new(), articulation_points: Vec::new(), components: Vec::new() };
+    let mut edges: Vec<EdgeId> = Vec::new();
+
+    for root in graph.node_ids() {
+        if disc[root].is_some() {
+            continue;
+        }
+        disc[root] = Some(time);
+        low[root] = time;
+        time += 1;
+        let mut children = 0;
+        // Node, edge from its parent, and index of the next edge to explore
+        let mut stack: Vec<(NodeId, Option<EdgeId>, usize)> = vec![(root, None, 0)];
+
+        while let Some(&mut (u, parent_edge, ref mut i)) = stack.last_mut() {
+            if *i < adjacency[u].len() {
+                let (e, v) = adjacency[u][*i];
+                *i += 1;
+                if Some(e) == parent_edge || v == u {
+                    continue;
+                }
+                match disc[v] {
+                    None => {
+                        edges.push(e);
+                        disc[v] = Some(time);
+                        low[v] = time;
+                        time += 1;
+                        stack.push((v, Some(e), 0));
+                    }
+                    // A back edge to an ancestor, the other end of one from a descendant is skipped
+                    Some(dv) if dv < disc[u].unwrap() => {
+                        edges.push(e);
+                        low[u] = low[u].min(dv);
+                    }
+                    Some(_) => {}
+                }
+                continue;
+            }
+
+            stack.pop();
+            let p = match stack.last() {
+                Some(&(p, _, _)) => p,
+                None => continue,
+            };
+            let e = parent_edge.unwrap();
+            low[p] = low[p].min(low[u]);
+            if low[u] > disc[p].unwrap() {
+                result.bridges.push(e);
+            }
+            if low[u] >= disc[p].unwrap() {
+                if p == root {
+                    children += 1;
+                    is_cut[p] = children > 1;
+                } else {
+                    is_cut[p] = true;
+                }
+                let start = edges.iter().rposition(|&f| f == e).unwrap();
+                result.components.push(edges.split_off(start));
+            }
+        }
+    }
+
+    result.bridges.sort_unstable();
+    result.articulation_points = (0..n).filter(|&u| is_cut[u]).collect();
+    result
+}
+
+/// Edges whose removal disconnects their component, see `biconnectivity`
+///
+/// # Arguments
+///
+/// * `graph` - Graph to search
+///
+/// # Panics
+///
+/// If the graph is directed
+pub fn bridges<N, E>(graph: &Graph<N, E>) -> Vec<EdgeId> {
+    biconnectivity(graph).bridges
+}
+
+/// Nodes whose removal disconnects their component, see `biconnectivity`
+///
+/// # Arguments
+///
+/// * `graph` - Graph to search
+///
+/// # Panics
+///
+/// If the graph is directed
+pub fn articulation_points<N, E>(graph: &Graph<N, E>) -> Vec<NodeId> {
+    biconnectivity(graph).articulation_points
+}
+
+/// Edges of each biconnected component, see `biconnectivity`
+///
+/// # Arguments
+///
+/// * `graph` - Graph to search
+///
+/// # Panics
+///
+/// If the graph is directed
+pub fn biconnected_components<N, E>(graph: &Graph<N, E>) -> Vec<Vec<EdgeId>> {
+    biconnectivity(graph).components
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::connectivity::connected_components;
+    use crate::rng::Rng;
+
+    #[test]
+    fn test_biconnectivity() {
+        // Two triangles joined by the bridge (2, 3), with a pendant edge (5, 6)
+        let edges = vec![(0, 1, ()), (1, 2, ()), (2, 0, ()), (2, 3, ()), (3, 4, ()), (4, 5, ()), (5, 3, ()), (5, 6, ())];
+        let g: Graph<(), ()> = Graph::from_edges(false, 7, edges);
+        let r = biconnectivity(&g);
+        assert_eq!(vec![3, 7], r.bridges);
+        assert_eq!(vec![2, 3, 5], r.articulation_points);
+
+        let mut components: Vec<Vec<EdgeId>> = r.components.into_iter().map(|mut c| {
+            c.sort_unstable();
+            c
+        }).collect();
+        components.sort();
+        assert_eq!(vec![vec![0, 1, 2], vec![3], vec![4, 5, 6], vec![7]], components);
+    }
+
+    #[test]
+    fn test_parallel_edges() {
+        let g: Graph<(), ()> = Graph::from_edges(false, 3, vec![(0, 1, ()), (1, 0, ()), (1, 2, ()), (2, 2, ())]);
+        assert_eq!(vec![2], bridges(&g));
+        assert_eq!(vec![1], articulation_points(&g));
+        assert_eq!(2, biconnected_components(&g).len());
+    }
+
+    #[test]
+    fn test_matches_brute_force() {
+        let mut rng = Rng::new(37);
+        let edges: Vec<_> = (0..70).map(|_| (rng.gen_range(60) as usize, rng.gen_range(60) as usize, ())).collect();
+        let g: Graph<(), ()> = Graph::from_edges(false, 60, edges);
+        let count = connected_components(&g).len();
+
+        let expected: Vec<EdgeId> = (0..g.edge_count())
+            .filter(|&e| {
+                let mut h = g.clone();
+                h.remove_edge(e);
+                connected_components(&h).len() > count
+            })
+            .collect();
+        assert_eq!(expected, bridges(&g));
+
+        // Removing a node leaves one fewer node, so it separates if more components remain
+        let expected: Vec<NodeId> = (0..60)
+            .filter(|&u| {
+                let mut h = g.clone();
+                h.remove_node(u);
+                connected_components(&h).len() > count - usize::from(g.degree(u) == 0)
+            })
+            .collect();
+        assert_eq!(expected, articulation_points(&g));
+    }
+
+    #[test]
+    fn test_long_path() {
+        let n = 100_000;
+        let g: Graph<(), ()> = Graph::from_edges(false, n, (1..n).map(|u| (u - 1, u, ())));
+        let r = biconnectivity(&g);
+        assert_eq!(n - 1, r.bridges.len());
+        assert_eq!(n - 2, r.articulation_points.len());
+    }
+}
@@ -0,0 +1,164 @@
+use crate::dijkstra::check_weights;
+use crate::graph::{Graph, NodeId};
+use crate::indexed_min_heap::IndexedMinHeap;
+use crate::property_map::EdgeData;
+use crate::shortest_paths::{NegativeWeightError, Weight};
+
+/// Bidirectional Dijkstra search for a shortest path between two nodes, with edge data as weights
+///
+/// Runs Dijkstra forwards from `start` and backwards from `goal` along reversed edges, always
+/// expanding the side with the smaller next distance, and keeps the shortest path found where the
+/// searches meet. It stops once the two next distances add up to at least that path, usually after
+/// settling far fewer nodes than a one-sided search, since two balls of half the radius are smaller
+/// than one of the full radius. Returns the cost and nodes of the path, or None if `goal` isn't
+/// reachable, and an error if any edge has a negative weight, or a weight that doesn't compare,
+/// like `NaN`, since the stopping rule relies on distances only growing along a path.
+///
+/// # Arguments
+///
+/// * `graph` - Graph to search, edges are followed in both directions if undirected
+/// * `start` - Node to start from
+/// * `goal` - Node to find a path to
+///
+/// # Panics
+///
+/// If `start` or `goal` isn't a node in the graph
+pub fn bidirectional_dijkstra<N, W: Weight>(graph: &Graph<N, W>, start: NodeId, goal: NodeId) -> Result<Option<(W, Vec<NodeId>)>, NegativeWeightError> {
+    assert!(graph.contains_node(start) && graph.contains_node(goal), "start and goal must be nodes in the graph");
+    check_weights(graph, &EdgeData)?;
+    let mut forward = Search::new(graph.node_bound(), start);
+    let mut backward = Search::new(graph.node_bound(), goal);
+    let mut best: Option<(W, NodeId)> = if start == goal { Some((W::zero(), start)) } else { None };
+
+    // Once either side runs out, every path through the other side's frontier has been tried
+    while let (Some((_, &f)), Some((_, &b))) = (forward.heap.peek(), backward.heap.peek()) {
+        if best.is_some_and(|(mu, _)| f + b >= mu) {
+            break;
+        }
+
+        if f <= b {
+            let (u, d) = forward.heap.pop().unwrap();
+            for e in graph.edges_from(u) {
+                forward.relax(u, e.target, d + *e.data, &backward, &mut best);
+            }
+        } else {
+            let (u, d) = backward.heap.pop().unwrap();
+            for e in graph.edges_to(u) {
+                backward.relax(u, e.source, d + *e.data, &forward, &mut best);
+            }
+        }
+    }
+
+    let (cost, meet) = match best {
+        Some(best) => best,
+        None => return Ok(None),
+    };
+    let mut path = forward.path_to(meet);
+    path.reverse();
+    path.extend(backward.path_to(meet).into_iter().skip(1));
+    Ok(Some((cost, path)))
+}
+
+/// Internal state of one side of a bidirectional search
+struct Search<W: Weight> {
+    distance: Vec<Option<W>>,
+    predecessor: Vec<Option<NodeId>>,
+    heap: IndexedMinHeap<W>,
+}
+
+impl<W: Weight> Search<W> {
+
+    /// Internal function to create a search from a node
+    fn new(n: usize, source: NodeId) -> Search<W> {
+        let mut distance = vec![None; n];
+        distance[source] = Some(W::zero());
+        let mut heap = IndexedMinHeap::with_capacity(n);
+        heap.push(source, W::zero());
+        Search { distance, predecessor: vec![None; n], heap }
+    }
+
+    /// Internal function to relax the edge to `v`, and update the best path through it
+    fn relax(&mut self, u: NodeId, v: NodeId, d: W, other: &Search<W>, best: &mut Option<(W, NodeId)>) {
+        if self.distance[v].is_none_or(|dv| d < dv) {
+            let first = self.distance[v].is_none();
+            self.distance[v] = Some(d);
+            self.predecessor[v] = Some(u);
+            if first {
+                self.heap.push(v, d);
+            } else if self.heap.contains(v) {
+                self.heap.decrease_key(v, d);
+            }
+        }
+        if let (Some(dv), Some(ov)) = (self.distance[v], other.distance[v]) {
+            if best.is_none_or(|(mu, _)| dv + ov < mu) {
+                *best = Some((dv + ov, v));
+            }
+        }
+    }
+
+    /// Internal function for the nodes from a node back to this search's source
+    fn path_to(&self, mut v: NodeId) -> Vec<NodeId> {
+        let mut path = vec![v];
+        while let Some(p) = self.predecessor[v] {
+            path.push(p);
+            v = p;
+        }
+        path
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dijkstra::dijkstra;
+    use crate::rng::Rng;
+
+    /// Internal function to check that a path is made of edges in the graph and find its cost
+    fn path_cost(graph: &Graph<(), u64>, path: &[NodeId]) -> Option<u64> {
+        let mut total = 0;
+        for pair in path.windows(2) {
+            total += graph.edges_from(pair[0]).filter(|e| e.target == pair[1]).map(|e| *e.data).min()?;
+        }
+        Some(total)
+    }
+
+    #[test]
+    fn test_matches_dijkstra() {
+        let mut rng = Rng::new(5);
+        for &directed in [true, false].iter() {
+            let edges: Vec<_> = (0..400).map(|_| (rng.gen_range(100) as usize, rng.gen_range(100) as usize, rng.gen_range(20))).collect();
+            let g: Graph<(), u64> = Graph::from_edges(directed, 100, edges);
+            for s in 0..10 {
+                let paths = dijkstra(&g, s).unwrap();
+                for t in 0..100 {
+                    let result = bidirectional_dijkstra(&g, s, t).unwrap();
+                    assert_eq!(paths.distance(t), result.as_ref().map(|r| r.0));
+                    if let Some((cost, path)) = result {
+                        assert_eq!((s, t), (path[0], path[path.len() - 1]));
+                        assert_eq!(Some(cost), path_cost(&g, &path));
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_same_node() {
+        let g: Graph<(), u64> = Graph::from_edges(true, 2, vec![(0, 1, 1)]);
+        assert_eq!(Ok(Some((0, vec![1]))), bidirectional_dijkstra(&g, 1, 1));
+    }
+
+    #[test]
+    fn test_unreachable() {
+        let g: Graph<(), u64> = Graph::from_edges(true, 3, vec![(0, 1, 1), (2, 1, 1)]);
+        assert_eq!(Ok(None), bidirectional_dijkstra(&g, 0, 2));
+        assert_eq!(Ok(Some((1, vec![2, 1]))), bidirectional_dijkstra(&g, 2, 1));
+    }
+
+    #[test]
+    fn test_negative_weight() {
+        // The searches meet at 1 and stop before finding the shorter path 0 -> 2 -> 3 -> 1
+        let g: Graph<(), i64> = Graph::from_edges(true, 4, vec![(0, 1, 2), (0, 2, 3), (2, 3, 3), (3, 1, -10)]);
+        assert_eq!(Err(NegativeWeightError { edge: 3 }), bidirectional_dijkstra(&g, 0, 1));
+    }
+}
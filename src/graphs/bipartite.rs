@@ -0,0 +1,303 @@
+use std::collections::VecDeque;
+use std::fmt;
+
+use crate::graph::{Graph, NodeId};
+
+/// Side of a bipartite graph a node is on
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Side {
+    Left,
+    Right,
+}
+
+/// Error for an edge between two nodes on the same side, which a bipartite graph can't have
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SameSideError {
+    pub u: NodeId,
+    pub v: NodeId,
+}
+
+impl fmt::Display for SameSideError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "edge ({}, {}) joins two nodes on the same side", self.u, self.v)
+    }
+}
+
+impl std::error::Error for SameSideError {}
+
+/// Error for a graph with an odd cycle, which can't be 2-colored
+///
+/// `cycle` lists the nodes of the cycle in order, with an edge between each node and the next and
+/// between the last node and the first, ignoring edge directions.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct OddCycleError {
+    pub cycle: Vec<NodeId>,
+}
+
+impl fmt::Display for OddCycleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "graph has an odd cycle through nodes {:?}", self.cycle)
+    }
+}
+
+impl std::error::Error for OddCycleError {}
+
+/// Colors the nodes with two colors so every edge joins different colors, if the graph is bipartite
+///
+/// Runs a breadth-first search from every uncolored node, giving each node the opposite color of
+/// the node it was reached from, in `O(V + E)` time. An edge between two nodes of the same color
+/// closes an odd cycle through their closest common ancestor in the search tree, which is returned
+/// as an error, since no 2-coloring exists. Edge directions are ignored, and removed nodes are
+/// colored `Side::Left`. The colors can be passed to `BipartiteGraph::from_graph`.
+///
+/// # Arguments
+///
+/// * `graph` - Graph to color
+pub fn two_color<N, E>(graph: &Graph<N, E>) -> Result<Vec<Side>, OddCycleError> {
+    let n = graph.node_bound();
+    let mut depth: Vec<Option<usize>> = vec![None; n];
+    let mut parent = vec![0; n];
+    let mut queue = VecDeque::new();
+
+    for root in graph.node_ids() {
+        if depth[root].is_some() {
+            continue;
+        }
+        depth[root] = Some(0);
+        queue.push_back(root);
+        while let Some(u) = queue.pop_front() {
+            let du = depth[u].unwrap();
+            for v in graph.neighbors(u).chain(graph.predecessors(u)) {
+                match depth[v] {
+                    None => {
+                        depth[v] = Some(du + 1);
+                        parent[v] = u;
+                        queue.push_back(v);
+                    }
+                    Some(dv) if dv % 2 == du % 2 => return Err(OddCycleError { cycle: odd_cycle(&depth, &parent, u, v) }),
+                    Some(_) => {}
+                }
+            }
+        }
+    }
+    Ok(depth.into_iter().map(|d| if d.unwrap_or(0) % 2 == 0 { Side::Left } else { Side::Right }).collect())
+}
+
+/// Internal function to close the cycle of an edge `(u, v)` between nodes at the same parity
+///
+/// Walks both nodes up the search tree to their closest common ancestor, the cycle runs from `u`
+/// up to it and down to `v`.
+fn odd_cycle(depth: &[Option<usize>], parent: &[NodeId], u: NodeId, v: NodeId) -> Vec<NodeId> {
+    let (mut a, mut b) = (u, v);
+    let mut up = vec![a];
+    let mut down = vec![b];
+    while a != b {
+        if depth[a] >= depth[b] {
+            a = parent[a];
+            up.push(a);
+        } else {
+            b = parent[b];
+            down.push(b);
+        }
+    }
+    // Both walks end at the common ancestor, which is kept once
+    down.pop();
+    up.extend(down.into_iter().rev());
+    up
+}
+
+/// An undirected graph whose nodes are split into a left and a right side, with every edge
+/// joining the two sides
+///
+/// Every node has a side, and only left nodes keep adjacency lists, which is all matching
+/// algorithms need. Adding an edge checks the sides, so the structure is always valid.
+#[derive(Clone, Debug)]
+pub struct BipartiteGraph {
+    sides: Vec<Side>,
+    adjacency: Vec<Vec<NodeId>>,
+    edge_count: usize,
+}
+
+impl BipartiteGraph {
+
+    /// Creates a new `BipartiteGraph` without edges, with left nodes `0..left` and right nodes
+    /// `left..left + right`
+    ///
+    /// # Arguments
+    ///
+    /// * `left` - Number of left nodes
+    /// * `right` - Number of right nodes
+    pub fn new(left: usize, right: usize) -> BipartiteGraph {
+        let mut sides = vec![Side::Left; left];
+        sides.resize(left + right, Side::Right);
+        BipartiteGraph { sides, adjacency: vec![Vec::new(); left + right], edge_count: 0 }
+    }
+
+    /// Builds a `BipartiteGraph` from edges, with a side for every node
+    ///
+    /// Returns a `SameSideError` for the first edge that doesn't join the two sides.
+    ///
+    /// # Arguments
+    ///
+    /// * `sides` - Side of every node
+    /// * `edges` - Edges as pairs of nodes, in either order
+    ///
+    /// # Panics
+    ///
+    /// If an edge's endpoint isn't a node
+    pub fn from_edges<I: IntoIterator<Item = (NodeId, NodeId)>>(sides: Vec<Side>, edges: I) -> Result<BipartiteGraph, SameSideError> {
+        let n = sides.len();
+        let mut graph = BipartiteGraph { sides, adjacency: vec![Vec::new(); n], edge_count: 0 };
+        for (u, v) in edges {
+            graph.try_add_edge(u, v)?;
+        }
+        Ok(graph)
+    }
+
+    /// Builds a `BipartiteGraph` from the edges of a `Graph`, with a side for every node
+    ///
+    /// Edge directions are ignored, and removed nodes become nodes without edges. Sides can come
+    /// from `two_color`.
+    ///
+    /// # Arguments
+    ///
+    /// * `graph` - Graph to copy the edges of
+    /// * `sides` - Side of every node, indexed up to `graph.node_bound()`
+    ///
+    /// # Panics
+    ///
+    /// If `sides` is shorter than `graph.node_bound()`
+    pub fn from_graph<N, E>(graph: &Graph<N, E>, sides: &[Side]) -> Result<BipartiteGraph, SameSideError> {
+        assert!(sides.len() >= graph.node_bound(), "every node needs a side");
+        BipartiteGraph::from_edges(sides[..graph.node_bound()].to_vec(), graph.edges().map(|e| (e.source, e.target)))
+    }
+
+    /// Number of nodes on both sides
+    pub fn node_count(&self) -> usize {
+        self.sides.len()
+    }
+
+    /// Number of edges
+    pub fn edge_count(&self) -> usize {
+        self.edge_count
+    }
+
+    /// Side of a node
+    ///
+    /// # Arguments
+    ///
+    /// * `u` - Node to look up
+    pub fn side(&self, u: NodeId) -> Side {
+        self.sides[u]
+    }
+
+    /// Nodes on the left side, in increasing order
+    pub fn left_nodes(&self) -> impl Iterator<Item = NodeId> + '_ {
+        (0..self.sides.len()).filter(move |&u| self.sides[u] == Side::Left)
+    }
+
+    /// Right nodes adjacent to a left node
+    ///
+    /// # Arguments
+    ///
+    /// * `u` - Left node to look up
+    pub fn neighbors(&self, u: NodeId) -> &[NodeId] {
+        &self.adjacency[u]
+    }
+
+    /// Adds a node on a side, and returns its index
+    ///
+    /// # Arguments
+    ///
+    /// * `side` - Side to add the node to
+    pub fn add_node(&mut self, side: Side) -> NodeId {
+        self.sides.push(side);
+        self.adjacency.push(Vec::new());
+        self.sides.len() - 1
+    }
+
+    /// Adds an edge between a left and a right node, given in either order
+    ///
+    /// # Arguments
+    ///
+    /// * `u` - One endpoint
+    /// * `v` - The other endpoint
+    ///
+    /// # Panics
+    ///
+    /// If either node doesn't exist, or both are on the same side
+    pub fn add_edge(&mut self, u: NodeId, v: NodeId) {
+        if let Err(e) = self.try_add_edge(u, v) {
+            panic!("{}", e);
+        }
+    }
+
+    /// Internal function to add an edge, or fail if it doesn't join the two sides
+    fn try_add_edge(&mut self, u: NodeId, v: NodeId) -> Result<(), SameSideError> {
+        assert!(u < self.sides.len() && v < self.sides.len(), "edge endpoints must be nodes in the graph");
+        match (self.sides[u], self.sides[v]) {
+            (Side::Left, Side::Right) => self.adjacency[u].push(v),
+            (Side::Right, Side::Left) => self.adjacency[v].push(u),
+            _ => return Err(SameSideError { u, v }),
+        }
+        self.edge_count += 1;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bipartite_graph() {
+        let mut graph = BipartiteGraph::new(2, 2);
+        graph.add_edge(0, 2);
+        graph.add_edge(3, 0);
+        let u = graph.add_node(Side::Left);
+        graph.add_edge(u, 3);
+        assert_eq!((5, 3), (graph.node_count(), graph.edge_count()));
+        assert_eq!(&[2, 3], graph.neighbors(0));
+        assert_eq!(vec![0, 1, 4], graph.left_nodes().collect::<Vec<_>>());
+        assert_eq!(Side::Right, graph.side(2));
+    }
+
+    #[test]
+    fn test_same_side() {
+        let sides = vec![Side::Left, Side::Right, Side::Left];
+        assert!(BipartiteGraph::from_edges(sides.clone(), vec![(0, 1), (1, 2)]).is_ok());
+        assert_eq!(SameSideError { u: 2, v: 0 }, BipartiteGraph::from_edges(sides.clone(), vec![(0, 1), (2, 0)]).unwrap_err());
+
+        let g: Graph<(), ()> = Graph::from_edges(true, 3, vec![(1, 0, ()), (1, 2, ())]);
+        assert_eq!(2, BipartiteGraph::from_graph(&g, &sides).unwrap().edge_count());
+    }
+
+    #[test]
+    fn test_two_color() {
+        let g: Graph<(), ()> = Graph::from_edges(false, 6, vec![(0, 1, ()), (1, 2, ()), (2, 3, ()), (3, 0, ()), (4, 5, ())]);
+        let sides = two_color(&g).unwrap();
+        assert_eq!(vec![Side::Left, Side::Right, Side::Left, Side::Right, Side::Left, Side::Right], sides);
+        assert!(BipartiteGraph::from_graph(&g, &sides).is_ok());
+    }
+
+    #[test]
+    fn test_odd_cycle() {
+        // Pentagon with a tail, found as a cycle of edges ignoring direction
+        let g: Graph<(), ()> = Graph::from_edges(true, 6, vec![(5, 0, ()), (0, 1, ()), (1, 2, ()), (3, 2, ()), (3, 4, ()), (0, 4, ())]);
+        let cycle = two_color(&g).unwrap_err().cycle;
+        assert_eq!(5, cycle.len());
+        for i in 0..cycle.len() {
+            let (u, v) = (cycle[i], cycle[(i + 1) % cycle.len()]);
+            assert!(g.find_edge(u, v).is_some() || g.find_edge(v, u).is_some());
+        }
+
+        let g: Graph<(), ()> = Graph::from_edges(false, 2, vec![(0, 1, ()), (1, 1, ())]);
+        assert_eq!(vec![1], two_color(&g).unwrap_err().cycle);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_add_edge_same_side() {
+        BipartiteGraph::new(2, 2).add_edge(2, 3);
+    }
+}
@@ -0,0 +1,100 @@
+use crate::disjoint_set::DisjointSet;
+use crate::graph::{EdgeId, Graph};
+use crate::shortest_paths::Weight;
+use crate::spanning_tree::SpanningForest;
+
+/// Borůvka's minimum spanning forest, with edge data as weights
+///
+/// Starts with every node as its own tree, and in each round finds the lightest edge leaving every
+/// tree and adds them all at once, merging trees in a `DisjointSet`. Every round at least halves the
+/// number of trees that can still grow, so there are at most `log(V)` rounds of `O(E)` work. Ties
+/// between equal weights are broken by edge index, so the edges chosen in a round never form a
+/// cycle. The rounds' searches are independent per edge, which makes it the natural MST algorithm
+/// to parallelize.
+///
+/// # Arguments
+///
+/// * `graph` - Graph to span
+///
+/// # Panics
+///
+/// If the graph is directed
+pub fn boruvka_mst<N, W: Weight>(graph: &Graph<N, W>) -> SpanningForest<W> {
+    assert!(!graph.is_directed(), "a minimum spanning tree requires an undirected graph");
+    let mut sets = DisjointSet::with_size(graph.node_bound());
+    let mut forest = SpanningForest { edges: Vec::new(), weight: W::zero() };
+
+    loop {
+        let mut lightest: Vec<Option<(W, EdgeId)>> = vec![None; graph.node_bound()];
+        for e in graph.edges() {
+            let (a, b) = (sets.find(e.source), sets.find(e.target));
+            if a == b {
+                continue;
+            }
+            let candidate = (*e.data, e.id);
+            for &root in [a, b].iter() {
+                if lightest[root].is_none_or(|best| candidate < best) {
+                    lightest[root] = Some(candidate);
+                }
+            }
+        }
+
+        let mut merged = false;
+        for (w, e) in lightest.into_iter().flatten() {
+            let (u, v) = graph.endpoints(e).unwrap();
+            // Two trees can pick the same edge, which is only added once
+            if sets.union(u, v) {
+                forest.edges.push(e);
+                forest.weight = forest.weight + w;
+                merged = true;
+            }
+        }
+        if !merged {
+            return forest;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::kruskal::kruskal_mst;
+    use crate::rng::Rng;
+
+    #[test]
+    fn test_boruvka() {
+        // CLRS Figure 23.1
+        let (a, b, c, d, e, f, g, h, i) = (0, 1, 2, 3, 4, 5, 6, 7, 8);
+        let edges = vec![
+            (a, b, 4), (a, h, 8), (b, c, 8), (b, h, 11), (c, d, 7), (c, f, 4), (c, i, 2),
+            (d, e, 9), (d, f, 14), (e, f, 10), (f, g, 2), (g, h, 1), (g, i, 6), (h, i, 7),
+        ];
+        let graph: Graph<(), u32> = Graph::from_edges(false, 9, edges);
+        let forest = boruvka_mst(&graph);
+        assert_eq!(37, forest.weight);
+        assert_eq!(8, forest.edges.len());
+    }
+
+    #[test]
+    fn test_equal_weights() {
+        // Every spanning tree of a cycle with equal weights is minimum, but picking edges
+        // per tree without a tie break could close the cycle
+        let g: Graph<(), u32> = Graph::from_edges(false, 4, vec![(0, 1, 1), (1, 2, 1), (2, 3, 1), (3, 0, 1)]);
+        let forest = boruvka_mst(&g);
+        assert_eq!(3, forest.edges.len());
+        assert_eq!(3, forest.weight);
+    }
+
+    #[test]
+    fn test_matches_kruskal() {
+        let mut rng = Rng::new(13);
+        let edges: Vec<_> = (0..400).map(|_| (rng.gen_range(120) as usize, rng.gen_range(120) as usize, rng.gen_range(50))).collect();
+        let g: Graph<(), u64> = Graph::from_edges(false, 120, edges);
+        let (a, b) = (boruvka_mst(&g), kruskal_mst(&g));
+        assert_eq!(b.weight, a.weight);
+        let (mut ea, mut eb) = (a.edges, b.edges);
+        ea.sort_unstable();
+        eb.sort_unstable();
+        assert_eq!(eb, ea);
+    }
+}
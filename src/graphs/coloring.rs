@@ -0,0 +1,161 @@
+use std::cmp::Reverse;
+use std::collections::HashSet;
+
+use crate::graph::{Graph, NodeId};
+use crate::max_heap::MaxHeap;
+
+/// Order in which `greedy_coloring` colors the nodes
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ColoringStrategy {
+    /// Nodes in index order
+    Sequential,
+    /// Nodes in decreasing order of degree, Welsh-Powell, since high degree nodes are the hardest
+    /// to fit in late
+    LargestFirst,
+    /// The node with the most distinct colors among its neighbours next, ties broken by degree,
+    /// Brélaz's DSATUR, which colors bipartite graphs, cycles and wheels optimally
+    Dsatur,
+}
+
+impl ColoringStrategy {
+
+    /// Every strategy, for comparing them
+    pub const ALL: [ColoringStrategy; 3] = [ColoringStrategy::Sequential, ColoringStrategy::LargestFirst, ColoringStrategy::Dsatur];
+}
+
+/// Colors of the nodes of a graph, with no edge between two nodes of the same color
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Coloring {
+    /// Color of each node, from `0` to `num_colors - 1`, indexed by `NodeId` with None for
+    /// removed nodes
+    pub colors: Vec<Option<usize>>,
+    /// Number of colors used
+    pub num_colors: usize,
+}
+
+/// Colors a graph greedily, giving each node the smallest color none of its neighbours have
+///
+/// Finding the fewest colors is NP-hard, so the order the nodes are colored in is a heuristic, and
+/// the result never uses more than one more color than the largest degree. `Sequential` and
+/// `LargestFirst` take `O(V + E)` time after sorting, and `Dsatur` `O((V + E) log(V))` with a
+/// `MaxHeap` of saturations. Edge directions are ignored, and self-loops can't be satisfied, so
+/// they're ignored too. Register allocation is coloring where nodes are variables, edges join
+/// variables live at the same time, and colors are registers.
+///
+/// # Arguments
+///
+/// * `graph` - Graph to color
+/// * `strategy` - Order to color the nodes in
+pub fn greedy_coloring<N, E>(graph: &Graph<N, E>, strategy: ColoringStrategy) -> Coloring {
+    let n = graph.node_bound();
+    let mut adjacency = vec![Vec::new(); n];
+    for e in graph.edges() {
+        if e.source != e.target {
+            adjacency[e.source].push(e.target);
+            adjacency[e.target].push(e.source);
+        }
+    }
+    let mut coloring = Coloring { colors: vec![None; n], num_colors: 0 };
+
+    match strategy {
+        ColoringStrategy::Sequential | ColoringStrategy::LargestFirst => {
+            let mut order: Vec<NodeId> = graph.node_ids().collect();
+            if strategy == ColoringStrategy::LargestFirst {
+                order.sort_by_key(|&u| Reverse(adjacency[u].len()));
+            }
+            for u in order {
+                assign(&adjacency, &mut coloring, u);
+            }
+        }
+        ColoringStrategy::Dsatur => {
+            // Distinct colors next to each node, with entries pushed as they grow and stale ones
+            // skipped when popped
+            let mut neighbor_colors: Vec<HashSet<usize>> = vec![HashSet::new(); n];
+            let mut heap = MaxHeap::new();
+            for u in graph.node_ids() {
+                heap.insert((0, adjacency[u].len(), Reverse(u)));
+            }
+            while let Some((saturation, _, Reverse(u))) = heap.pop() {
+                if coloring.colors[u].is_some() || saturation < neighbor_colors[u].len() {
+                    continue;
+                }
+                let c = assign(&adjacency, &mut coloring, u);
+                for &v in adjacency[u].iter() {
+                    if coloring.colors[v].is_none() && neighbor_colors[v].insert(c) {
+                        heap.insert((neighbor_colors[v].len(), adjacency[v].len(), Reverse(v)));
+                    }
+                }
+            }
+        }
+    }
+    coloring
+}
+
+/// Internal function to give a node the smallest color its neighbours don't have, and return it
+fn assign(adjacency: &[Vec<NodeId>], coloring: &mut Coloring, u: NodeId) -> usize {
+    // Some color up to the degree is always free
+    let mut used = vec![false; adjacency[u].len() + 1];
+    for &v in adjacency[u].iter() {
+        if let Some(c) = coloring.colors[v] {
+            if c < used.len() {
+                used[c] = true;
+            }
+        }
+    }
+    let c = used.iter().position(|&taken| !taken).unwrap();
+    coloring.colors[u] = Some(c);
+    coloring.num_colors = coloring.num_colors.max(c + 1);
+    c
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::Rng;
+
+    /// Internal function to check no edge joins two nodes of the same color
+    fn is_proper<N, E>(graph: &Graph<N, E>, coloring: &Coloring) -> bool {
+        graph.edges().all(|e| e.source == e.target || coloring.colors[e.source] != coloring.colors[e.target])
+    }
+
+    #[test]
+    fn test_greedy_coloring() {
+        let g: Graph<(), ()> = Graph::complete(5);
+        for &strategy in ColoringStrategy::ALL.iter() {
+            let coloring = greedy_coloring(&g, strategy);
+            assert_eq!(5, coloring.num_colors);
+            assert!(is_proper(&g, &coloring));
+        }
+    }
+
+    #[test]
+    fn test_crown() {
+        // A crown graph, u_i joined to every v_j with j != i, interleaved so index order
+        // alternates sides and needs a color per pair, while DSATUR finds the 2-coloring
+        let k = 6;
+        let edges = (0..k).flat_map(|i| (0..k).filter(move |&j| j != i).map(move |j| (2 * i, 2 * j + 1, ())));
+        let g: Graph<(), ()> = Graph::from_edges(false, 2 * k, edges.collect::<Vec<_>>());
+        assert_eq!(k, greedy_coloring(&g, ColoringStrategy::Sequential).num_colors);
+        let coloring = greedy_coloring(&g, ColoringStrategy::Dsatur);
+        assert_eq!(2, coloring.num_colors);
+        assert!(is_proper(&g, &coloring));
+    }
+
+    #[test]
+    fn test_random() {
+        let mut rng = Rng::new(43);
+        let edges: Vec<_> = (0..600).map(|_| (rng.gen_range(100) as usize, rng.gen_range(100) as usize, ())).collect();
+        let mut g: Graph<(), ()> = Graph::from_edges(true, 100, edges);
+        g.remove_node(50);
+        let max_degree = g.node_ids().map(|u| g.degree(u)).max().unwrap();
+        for &strategy in ColoringStrategy::ALL.iter() {
+            let coloring = greedy_coloring(&g, strategy);
+            assert!(is_proper(&g, &coloring));
+            assert!(coloring.num_colors <= max_degree + 1);
+            assert_eq!(None, coloring.colors[50]);
+        }
+        // Cycles of odd length need three colors, even ones two
+        assert_eq!(3, greedy_coloring(&Graph::<(), ()>::cycle(7), ColoringStrategy::Dsatur).num_colors);
+        assert_eq!(2, greedy_coloring(&Graph::<(), ()>::cycle(8), ColoringStrategy::Dsatur).num_colors);
+    }
+}
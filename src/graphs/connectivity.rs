@@ -0,0 +1,162 @@
+use crate::bit_set::BitSet;
+use crate::disjoint_set::DisjointSet;
+use crate::graph::{Graph, NodeId};
+
+/// Finds the connected components of an undirected graph
+///
+/// Returns the components ordered by their smallest node, each in breadth-first order from that
+/// node. Takes `O(V + E)` time.
+///
+/// # Arguments
+///
+/// * `graph` - Graph to search
+///
+/// # Panics
+///
+/// If the graph is directed
+pub fn connected_components<N, E>(graph: &Graph<N, E>) -> Vec<Vec<NodeId>> {
+    assert!(!graph.is_directed(), "connected components require an undirected graph");
+    let mut visited = BitSet::with_num_bits(graph.node_bound());
+    let mut components = Vec::new();
+
+    for root in graph.node_ids() {
+        if !visited.set(root) {
+            continue;
+        }
+        let mut component = vec![root];
+        let mut i = 0;
+        while i < component.len() {
+            let u = component[i];
+            i += 1;
+            for v in graph.neighbors(u) {
+                if visited.set(v) {
+                    component.push(v);
+                }
+            }
+        }
+        components.push(component);
+    }
+    components
+}
+
+/// Connectivity of an undirected graph as edges are added
+///
+/// Keeps the components in a `DisjointSet`, so adding an edge and checking if two nodes are
+/// connected take `O(α(n))` amortized time, and can be interleaved in any order. Edges can't be
+/// removed.
+#[derive(Clone, Debug)]
+pub struct IncrementalConnectivity {
+    sets: DisjointSet,
+    absent: usize,
+}
+
+impl IncrementalConnectivity {
+
+    /// Creates a new `IncrementalConnectivity` of `n` nodes without edges
+    ///
+    /// # Arguments
+    ///
+    /// * `n` - Number of nodes
+    pub fn with_size(n: usize) -> IncrementalConnectivity {
+        IncrementalConnectivity { sets: DisjointSet::with_size(n), absent: 0 }
+    }
+
+    /// Creates a new `IncrementalConnectivity` with the nodes and edges of a graph
+    ///
+    /// Node indexes are the same as in the graph. Edges are treated as undirected.
+    ///
+    /// # Arguments
+    ///
+    /// * `graph` - Graph to start from
+    pub fn from_graph<N, E>(graph: &Graph<N, E>) -> IncrementalConnectivity {
+        let mut connectivity = IncrementalConnectivity::with_size(graph.node_bound());
+        connectivity.absent = graph.node_bound() - graph.node_count();
+        for e in graph.edges() {
+            connectivity.add_edge(e.source, e.target);
+        }
+        connectivity
+    }
+
+    /// Adds a new node without edges, and returns its index
+    pub fn add_node(&mut self) -> NodeId {
+        self.sets.make_set()
+    }
+
+    /// Adds an edge between two nodes
+    ///
+    /// Returns `true` if it joined two components.
+    ///
+    /// # Arguments
+    ///
+    /// * `u` - First node
+    /// * `v` - Second node
+    pub fn add_edge(&mut self, u: NodeId, v: NodeId) -> bool {
+        self.sets.union(u, v)
+    }
+
+    /// Checks if there is a path between two nodes
+    ///
+    /// # Arguments
+    ///
+    /// * `u` - First node
+    /// * `v` - Second node
+    pub fn connected(&mut self, u: NodeId, v: NodeId) -> bool {
+        self.sets.connected(u, v)
+    }
+
+    /// Number of nodes in the component containing `u`
+    ///
+    /// # Arguments
+    ///
+    /// * `u` - Node of the component
+    pub fn component_size(&mut self, u: NodeId) -> usize {
+        self.sets.size_of(u)
+    }
+
+    /// Number of connected components
+    pub fn num_components(&self) -> usize {
+        self.sets.num_components() - self.absent
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_connected_components() {
+        let g: Graph<(), ()> = Graph::from_edges(false, 7, vec![(0, 1, ()), (5, 1, ()), (2, 4, ()), (3, 3, ()), (1, 0, ())]);
+        assert_eq!(vec![vec![0, 1, 5], vec![2, 4], vec![3], vec![6]], connected_components(&g));
+    }
+
+    #[test]
+    fn test_incremental() {
+        let mut c = IncrementalConnectivity::with_size(5);
+        assert_eq!(5, c.num_components());
+        assert!(c.add_edge(0, 1));
+        assert!(c.add_edge(3, 4));
+        assert!(!c.connected(1, 3));
+        assert!(c.add_edge(1, 4));
+        assert!(c.connected(0, 3));
+        assert!(!c.add_edge(0, 3));
+        assert_eq!(2, c.num_components());
+        assert_eq!(4, c.component_size(4));
+
+        let u = c.add_node();
+        assert_eq!(5, u);
+        assert!(!c.connected(u, 2));
+        assert_eq!(3, c.num_components());
+    }
+
+    #[test]
+    fn test_from_graph() {
+        let mut g: Graph<(), ()> = Graph::from_edges(false, 4, vec![(0, 1, ()), (2, 3, ())]);
+        g.remove_node(3);
+        let mut c = IncrementalConnectivity::from_graph(&g);
+        assert_eq!(2, c.num_components());
+        assert!(c.connected(0, 1));
+        c.add_edge(1, 2);
+        assert_eq!(1, c.num_components());
+        assert_eq!(connected_components(&g).len(), 2);
+    }
+}
@@ -0,0 +1,81 @@
+use crate::dfs::{dfs, EdgeKind};
+use crate::graph::{Graph, NodeId};
+
+/// Finds a cycle in a directed or undirected graph
+///
+/// Returns the nodes of one cycle in order, with an edge from each node to the next and from the
+/// last node back to the first, or None if the graph is acyclic. Runs a DFS, where any back edge
+/// closes a cycle with the tree path it points up. In an undirected graph the edge to a node's
+/// parent is tracked by its id, so it isn't mistaken for a cycle, but a parallel edge is one.
+///
+/// # Arguments
+///
+/// * `graph` - Graph to search
+pub fn find_cycle<N, E>(graph: &Graph<N, E>) -> Option<Vec<NodeId>> {
+    let result = dfs(graph);
+    let e = result.edges_of_kind(EdgeKind::Back).next()?;
+    let (u, v) = graph.endpoints(e).unwrap();
+
+    // An undirected edge is stored in either direction, so find which end is the ancestor
+    if result.is_ancestor(v, u) {
+        result.tree_path(v, u)
+    } else {
+        result.tree_path(u, v)
+    }
+}
+
+/// Checks if a graph has a cycle
+///
+/// # Arguments
+///
+/// * `graph` - Graph to check
+pub fn is_acyclic<N, E>(graph: &Graph<N, E>) -> bool {
+    find_cycle(graph).is_none()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Internal function to check that a cycle is made of edges in the graph
+    fn is_cycle<N, E>(graph: &Graph<N, E>, cycle: &[NodeId]) -> bool {
+        !cycle.is_empty() && (0..cycle.len()).all(|i| graph.find_edge(cycle[i], cycle[(i + 1) % cycle.len()]).is_some())
+    }
+
+    #[test]
+    fn test_directed() {
+        let mut g: Graph<(), ()> = Graph::from_edges(true, 4, vec![(0, 1, ()), (1, 2, ()), (0, 2, ()), (3, 2, ())]);
+        assert_eq!(None, find_cycle(&g));
+        assert!(is_acyclic(&g));
+
+        g.add_edge(2, 0, ());
+        let cycle = find_cycle(&g).unwrap();
+        assert!(is_cycle(&g, &cycle));
+        assert_eq!(vec![0, 1, 2], cycle);
+    }
+
+    #[test]
+    fn test_undirected() {
+        let mut g: Graph<(), ()> = Graph::from_edges(false, 5, vec![(0, 1, ()), (1, 2, ()), (3, 1, ()), (4, 3, ())]);
+        assert_eq!(None, find_cycle(&g));
+
+        g.add_edge(4, 1, ());
+        let cycle = find_cycle(&g).unwrap();
+        assert_eq!(3, cycle.len());
+        assert!(is_cycle(&g, &cycle));
+    }
+
+    #[test]
+    fn test_parallel_edges() {
+        let g: Graph<(), ()> = Graph::from_edges(false, 2, vec![(0, 1, ()), (1, 0, ())]);
+        assert_eq!(Some(vec![0, 1]), find_cycle(&g));
+    }
+
+    #[test]
+    fn test_self_loop() {
+        let g: Graph<(), ()> = Graph::from_edges(false, 2, vec![(0, 1, ()), (1, 1, ())]);
+        assert_eq!(Some(vec![1]), find_cycle(&g));
+        let g: Graph<(), ()> = Graph::from_edges(true, 2, vec![(0, 1, ()), (1, 1, ())]);
+        assert_eq!(Some(vec![1]), find_cycle(&g));
+    }
+}
@@ -0,0 +1,123 @@
+use crate::graph::{Graph, NodeId};
+use crate::shortest_paths::{ShortestPaths, Weight};
+use crate::topological_sort::{topological_sort, CycleError};
+
+/// Single source shortest paths in a directed acyclic graph, with edge data as weights
+///
+/// Relaxes the edges out of each node in topological order, so every node's distance is final
+/// before its edges are used. Takes `O(V + E)` time and allows negative weights. Returns a
+/// `CycleError` if the graph has a cycle.
+///
+/// # Arguments
+///
+/// * `graph` - Graph to search
+/// * `source` - Node to find paths from
+///
+/// # Panics
+///
+/// If the graph is undirected, or the source isn't a node in the graph
+pub fn dag_shortest_paths<N, W: Weight>(graph: &Graph<N, W>, source: NodeId) -> Result<ShortestPaths<W>, CycleError> {
+    assert!(graph.contains_node(source), "source must be a node in the graph");
+    let order = topological_sort(graph)?;
+    let mut paths = ShortestPaths::new(source, graph.node_bound(), W::zero());
+    for u in order {
+        let d = match paths.distance[u] {
+            Some(d) => d,
+            None => continue,
+        };
+        for e in graph.edges_from(u) {
+            let nd = d + *e.data;
+            if paths.distance[e.target].is_none_or(|dv| nd < dv) {
+                paths.distance[e.target] = Some(nd);
+                paths.predecessor[e.target] = Some(u);
+            }
+        }
+    }
+    Ok(paths)
+}
+
+/// Longest path in a directed acyclic graph, with edge data as weights
+///
+/// The critical path of a schedule, where nodes are tasks and an edge `(u, v)` with weight `w`
+/// means `v` can start `w` after `u` starts, is the longest path, and its cost is the shortest time
+/// to finish every task. Computes the longest path ending at each node in topological order, in
+/// `O(V + E)` time, and returns the cost and nodes of the longest one. A path may start at any node,
+/// so the result is never shorter than a single node with cost zero. Returns a `CycleError` if the
+/// graph has a cycle, since a positive cycle would have no longest path.
+///
+/// # Arguments
+///
+/// * `graph` - Graph to search
+///
+/// # Panics
+///
+/// If the graph is undirected or empty
+pub fn dag_longest_path<N, W: Weight>(graph: &Graph<N, W>) -> Result<(W, Vec<NodeId>), CycleError> {
+    let order = topological_sort(graph)?;
+    assert!(!order.is_empty(), "graph must have a node");
+    let mut longest = vec![W::zero(); graph.node_bound()];
+    let mut predecessor = vec![None; graph.node_bound()];
+    for &u in order.iter() {
+        for e in graph.edges_from(u) {
+            let nd = longest[u] + *e.data;
+            if nd > longest[e.target] {
+                longest[e.target] = nd;
+                predecessor[e.target] = Some(u);
+            }
+        }
+    }
+
+    let mut end = order[0];
+    for &u in order.iter() {
+        if longest[u] > longest[end] {
+            end = u;
+        }
+    }
+    let mut path = vec![end];
+    let mut u = end;
+    while let Some(p) = predecessor[u] {
+        path.push(p);
+        u = p;
+    }
+    path.reverse();
+    Ok((longest[end], path))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_shortest_paths() {
+        // CLRS Figure 24.5
+        let (r, s, t, x, y, z) = (0, 1, 2, 3, 4, 5);
+        let edges = vec![(r, s, 5), (r, t, 3), (s, t, 2), (s, x, 6), (t, x, 7), (t, y, 4), (t, z, 2), (x, y, -1), (x, z, 1), (y, z, -2)];
+        let g: Graph<(), i32> = Graph::from_edges(true, 6, edges);
+        let paths = dag_shortest_paths(&g, s).unwrap();
+        assert_eq!(vec![None, Some(0), Some(2), Some(6), Some(5), Some(3)], paths.distance);
+        assert_eq!(Some(vec![s, x, y, z]), paths.path_to(z));
+    }
+
+    #[test]
+    fn test_critical_path() {
+        // Task durations are the weights of their outgoing edges, with a finish node 5
+        let edges = vec![(0, 1, 3), (0, 2, 3), (1, 3, 2), (2, 3, 4), (2, 4, 4), (3, 5, 1), (4, 5, 6)];
+        let g: Graph<(), u32> = Graph::from_edges(true, 6, edges);
+        assert_eq!((13, vec![0, 2, 4, 5]), dag_longest_path(&g).unwrap());
+    }
+
+    #[test]
+    fn test_negative_weights() {
+        let g: Graph<(), i32> = Graph::from_edges(true, 3, vec![(0, 1, -5), (1, 2, 3)]);
+        assert_eq!((3, vec![1, 2]), dag_longest_path(&g).unwrap());
+        let g: Graph<(), i32> = Graph::from_edges(true, 2, vec![(0, 1, -5)]);
+        assert_eq!(0, dag_longest_path(&g).unwrap().0);
+    }
+
+    #[test]
+    fn test_cycle() {
+        let g: Graph<(), i32> = Graph::from_edges(true, 3, vec![(0, 1, 1), (1, 2, 1), (2, 1, 1)]);
+        assert!(dag_shortest_paths(&g, 0).is_err());
+        assert_eq!(2, dag_longest_path(&g).unwrap_err().cycle.len());
+    }
+}
@@ -0,0 +1,351 @@
+use crate::bit_set::BitSet;
+use crate::graph::{EdgeId, Graph, NodeId};
+
+/// Classification of an edge by a depth-first search
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EdgeKind {
+    /// Edge to a newly discovered node, part of the DFS forest
+    Tree,
+    /// Edge to an ancestor in the DFS forest, including self-loops
+    Back,
+    /// Edge to a descendant that was already discovered, only in directed graphs
+    Forward,
+    /// Edge to a node in another subtree or tree, only in directed graphs
+    Cross,
+}
+
+/// Result of a depth-first search
+///
+/// Vectors are indexed by `NodeId` or `EdgeId`, with None for nodes that were not visited or
+/// edges that were not explored.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DfsResult {
+    /// Time each node was discovered, starting at 1
+    pub discovery: Vec<Option<usize>>,
+    /// Time each node was finished, after every node reachable from it
+    pub finish: Vec<Option<usize>>,
+    /// Parent of each node in the DFS forest, None for roots
+    pub parent: Vec<Option<NodeId>>,
+    /// Roots of the trees in the DFS forest, in the order they were searched
+    pub roots: Vec<NodeId>,
+    /// Classification of each edge
+    pub edge_kinds: Vec<Option<EdgeKind>>,
+    /// Nodes in the order they finished
+    pub finish_order: Vec<NodeId>,
+}
+
+impl DfsResult {
+
+    /// Checks if `u` is an ancestor of `v` in the DFS forest, including `u == v`
+    ///
+    /// By the parenthesis theorem, this is when `u`'s discovery to finish interval contains `v`'s.
+    ///
+    /// # Arguments
+    ///
+    /// * `u` - Possible ancestor
+    /// * `v` - Possible descendant
+    pub fn is_ancestor(&self, u: NodeId, v: NodeId) -> bool {
+        match (self.discovery[u], self.finish[u], self.discovery[v], self.finish[v]) {
+            (Some(du), Some(fu), Some(dv), Some(fv)) => du <= dv && fv <= fu,
+            _ => false,
+        }
+    }
+
+    /// Path of nodes down the DFS forest from `ancestor` to `descendant`
+    ///
+    /// Returns None if `ancestor` is not an ancestor of `descendant`.
+    ///
+    /// # Arguments
+    ///
+    /// * `ancestor` - First node of the path
+    /// * `descendant` - Last node of the path
+    pub fn tree_path(&self, ancestor: NodeId, descendant: NodeId) -> Option<Vec<NodeId>> {
+        if !self.is_ancestor(ancestor, descendant) {
+            return None;
+        }
+        let mut path = vec![descendant];
+        let mut u = descendant;
+        while u != ancestor {
+            u = self.parent[u]?;
+            path.push(u);
+        }
+        path.reverse();
+        Some(path)
+    }
+
+    /// Returns an iterator over the edges classified as a kind
+    ///
+    /// # Arguments
+    ///
+    /// * `kind` - Kind of edge to return
+    pub fn edges_of_kind(&self, kind: EdgeKind) -> impl Iterator<Item = EdgeId> + '_ {
+        self.edge_kinds.iter().enumerate().filter(move |(_, k)| **k == Some(kind)).map(|(e, _)| e)
+    }
+}
+
+/// Depth-first search of a whole graph
+///
+/// Starts a new tree from every undiscovered node, in ascending order. Runs in `O(V + E)` time
+/// with an explicit stack, so deep graphs don't overflow the call stack.
+///
+/// # Arguments
+///
+/// * `graph` - Graph to search
+pub fn dfs<N, E>(graph: &Graph<N, E>) -> DfsResult {
+    dfs_from_roots(graph, graph.node_ids())
+}
+
+/// Depth-first search from a sequence of roots
+///
+/// Starts a new tree from each root that is still undiscovered, in order, and leaves nodes that
+/// aren't reachable from any root unvisited.
+///
+/// # Arguments
+///
+/// * `graph` - Graph to search
+/// * `roots` - Nodes to start trees from
+pub fn dfs_from_roots<N, E, I: IntoIterator<Item = NodeId>>(graph: &Graph<N, E>, roots: I) -> DfsResult {
+    let n = graph.node_bound();
+    let mut result = DfsResult {
+        discovery: vec![None; n],
+        finish: vec![None; n],
+        parent: vec![None; n],
+        roots: Vec::new(),
+        edge_kinds: vec![None; graph.edge_bound()],
+        finish_order: Vec::with_capacity(graph.node_count()),
+    };
+    let adjacency = adjacency(graph);
+    let mut time = 0;
+    let mut stack: Vec<(NodeId, usize)> = Vec::new();
+
+    for root in roots {
+        if result.discovery[root].is_some() {
+            continue;
+        }
+        result.roots.push(root);
+        time += 1;
+        result.discovery[root] = Some(time);
+        stack.push((root, 0));
+
+        while let Some(&mut (u, ref mut pos)) = stack.last_mut() {
+            if *pos == adjacency[u].len() {
+                stack.pop();
+                time += 1;
+                result.finish[u] = Some(time);
+                result.finish_order.push(u);
+                continue;
+            }
+            let (e, v) = adjacency[u][*pos];
+            *pos += 1;
+
+            // An undirected edge is classified from whichever end explores it first
+            if result.edge_kinds[e].is_some() {
+                continue;
+            }
+            let kind = match (result.discovery[v], result.finish[v]) {
+                (None, _) => EdgeKind::Tree,
+                (Some(_), None) => EdgeKind::Back,
+                (Some(dv), Some(_)) => {
+                    if result.discovery[u].unwrap() < dv {
+                        EdgeKind::Forward
+                    } else {
+                        EdgeKind::Cross
+                    }
+                }
+            };
+            result.edge_kinds[e] = Some(kind);
+            if kind == EdgeKind::Tree {
+                time += 1;
+                result.discovery[v] = Some(time);
+                result.parent[v] = Some(u);
+                stack.push((v, 0));
+            }
+        }
+    }
+    result
+}
+
+/// Lazy depth-first search, an iterator over the nodes reachable from a start node
+///
+/// Yields nodes in the order they're discovered, the same preorder as `dfs`, searching only as far
+/// as the iterator is advanced, so a search can stop as soon as it finds what it's looking for.
+/// Between steps, the parents found so far and the path from the start to the node being
+/// explored can be inspected. The neighbours of a node are collected when it's discovered, so the
+/// search takes `O(V + E)` time and space.
+#[derive(Clone, Debug)]
+pub struct Dfs<'a, N, E> {
+    graph: &'a Graph<N, E>,
+    start: Option<NodeId>,
+    stack: Vec<(NodeId, std::vec::IntoIter<NodeId>)>,
+    discovered: BitSet,
+    parent: Vec<Option<NodeId>>,
+}
+
+impl<'a, N, E> Dfs<'a, N, E> {
+
+    /// Starts a depth-first search, without visiting any nodes yet
+    ///
+    /// # Arguments
+    ///
+    /// * `graph` - Graph to search, edges are followed in both directions if undirected
+    /// * `start` - Node to search from
+    ///
+    /// # Panics
+    ///
+    /// If the start isn't a node in the graph
+    pub fn new(graph: &'a Graph<N, E>, start: NodeId) -> Dfs<'a, N, E> {
+        assert!(graph.contains_node(start), "start must be a node in the graph");
+        let n = graph.node_bound();
+        Dfs { graph, start: Some(start), stack: Vec::new(), discovered: BitSet::with_num_bits(n), parent: vec![None; n] }
+    }
+
+    /// Checks if a node has been discovered, and so yielded
+    ///
+    /// # Arguments
+    ///
+    /// * `u` - Node to check
+    pub fn is_discovered(&self, u: NodeId) -> bool {
+        self.discovered.test(u)
+    }
+
+    /// Node a node was discovered from, or None for the start and undiscovered nodes
+    ///
+    /// # Arguments
+    ///
+    /// * `u` - Node to get the parent of
+    pub fn parent(&self, u: NodeId) -> Option<NodeId> {
+        self.parent[u]
+    }
+
+    /// Returns an iterator over the nodes that haven't finished, from the start down to the last
+    /// node yielded
+    pub fn path(&self) -> impl Iterator<Item = NodeId> + '_ {
+        self.stack.iter().map(|(u, _)| *u)
+    }
+
+    /// Internal function to discover a node and push it on the stack
+    fn discover(&mut self, u: NodeId) -> NodeId {
+        self.discovered.set(u);
+        self.stack.push((u, self.graph.neighbors(u).collect::<Vec<_>>().into_iter()));
+        u
+    }
+}
+
+impl<'a, N, E> Iterator for Dfs<'a, N, E> {
+    type Item = NodeId;
+
+    fn next(&mut self) -> Option<NodeId> {
+        if let Some(start) = self.start.take() {
+            return Some(self.discover(start));
+        }
+        let discovered = &self.discovered;
+        while let Some((u, neighbors)) = self.stack.last_mut() {
+            let u = *u;
+            match neighbors.find(|&v| !discovered.test(v)) {
+                Some(v) => {
+                    self.parent[v] = Some(u);
+                    return Some(self.discover(v));
+                }
+                None => {
+                    self.stack.pop();
+                }
+            }
+        }
+        None
+    }
+}
+
+/// Internal function to collect the `(edge, target)` pairs leaving each node
+pub(crate) fn adjacency<N, E>(graph: &Graph<N, E>) -> Vec<Vec<(EdgeId, NodeId)>> {
+    let mut adjacency = vec![Vec::new(); graph.node_bound()];
+    for u in graph.node_ids() {
+        adjacency[u] = graph.edges_from(u).map(|e| (e.id, e.target)).collect();
+    }
+    adjacency
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_timestamps() {
+        // CLRS Figure 22.4
+        let (u, v, w, x, y, z) = (0, 1, 2, 3, 4, 5);
+        let edges = vec![(u, v, ()), (u, x, ()), (v, y, ()), (w, y, ()), (w, z, ()), (x, v, ()), (y, x, ()), (z, z, ())];
+        let g: Graph<(), ()> = Graph::from_edges(true, 6, edges);
+        let r = dfs(&g);
+
+        let times: Vec<_> = (0..6).map(|i| (r.discovery[i].unwrap(), r.finish[i].unwrap())).collect();
+        assert_eq!(vec![(1, 8), (2, 7), (9, 12), (4, 5), (3, 6), (10, 11)], times);
+        assert_eq!(vec![u, w], r.roots);
+        assert_eq!(vec![None, Some(u), None, Some(y), Some(v), Some(w)], r.parent);
+        assert_eq!(vec![x, y, v, u, z, w], r.finish_order);
+    }
+
+    #[test]
+    fn test_edge_kinds() {
+        let (u, v, w, x, y, z) = (0, 1, 2, 3, 4, 5);
+        let edges = vec![(u, v, ()), (u, x, ()), (v, y, ()), (w, y, ()), (w, z, ()), (x, v, ()), (y, x, ()), (z, z, ())];
+        let g: Graph<(), ()> = Graph::from_edges(true, 6, edges);
+        let r = dfs(&g);
+
+        use EdgeKind::*;
+        assert_eq!(
+            vec![Some(Tree), Some(Forward), Some(Tree), Some(Cross), Some(Tree), Some(Back), Some(Tree), Some(Back)],
+            r.edge_kinds
+        );
+        assert_eq!(vec![5, 7], r.edges_of_kind(Back).collect::<Vec<_>>());
+        assert!(r.is_ancestor(u, x));
+        assert!(!r.is_ancestor(x, u));
+        assert!(!r.is_ancestor(u, z));
+        assert_eq!(Some(vec![u, v, y, x]), r.tree_path(u, x));
+        assert_eq!(None, r.tree_path(x, u));
+    }
+
+    #[test]
+    fn test_undirected() {
+        let g: Graph<(), ()> = Graph::from_edges(false, 4, vec![(0, 1, ()), (1, 2, ()), (2, 0, ()), (3, 3, ())]);
+        let r = dfs(&g);
+
+        use EdgeKind::*;
+        assert_eq!(vec![Some(Tree), Some(Tree), Some(Back), Some(Back)], r.edge_kinds);
+        assert_eq!(0, r.edges_of_kind(Forward).chain(r.edges_of_kind(Cross)).count());
+    }
+
+    #[test]
+    fn test_from_roots() {
+        let g: Graph<(), ()> = Graph::from_edges(true, 4, vec![(0, 1, ()), (2, 3, ())]);
+        let r = dfs_from_roots(&g, vec![2, 3]);
+        assert_eq!(vec![2], r.roots);
+        assert_eq!(None, r.discovery[0]);
+        assert_eq!(None, r.edge_kinds[0]);
+        assert_eq!(vec![3, 2], r.finish_order);
+    }
+
+    #[test]
+    fn test_deep_path() {
+        let n = 100_000;
+        let g: Graph<(), ()> = Graph::from_edges(true, n, (1..n).map(|i| (i - 1, i, ())));
+        let r = dfs(&g);
+        assert_eq!(Some(2 * n), r.finish[0]);
+        assert_eq!(Some(n - 2), r.parent[n - 1]);
+        assert_eq!(n, Dfs::new(&g, 0).count());
+    }
+
+    #[test]
+    fn test_lazy_dfs() {
+        let (u, v, w, x, y, z) = (0, 1, 2, 3, 4, 5);
+        let edges = vec![(u, v, ()), (u, x, ()), (v, y, ()), (w, y, ()), (w, z, ()), (x, v, ()), (y, x, ()), (z, z, ())];
+        let g: Graph<(), ()> = Graph::from_edges(true, 6, edges);
+        assert_eq!(vec![u, v, y, x], Dfs::new(&g, u).collect::<Vec<_>>());
+
+        // Stop when x is found, with the path down to it still on the stack
+        let mut search = Dfs::new(&g, u);
+        assert_eq!(Some(x), search.by_ref().find(|&a| a == x));
+        assert_eq!(vec![u, v, y, x], search.path().collect::<Vec<_>>());
+        assert_eq!(Some(y), search.parent(x));
+        assert!(!search.is_discovered(w));
+        assert_eq!(None, search.next());
+    }
+}
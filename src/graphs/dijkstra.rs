@@ -0,0 +1,231 @@
+use std::cmp::{Ordering, Reverse};
+
+use crate::graph::{Graph, NodeId};
+use crate::indexed_min_heap::IndexedMinHeap;
+use crate::max_heap::MaxHeap;
+use crate::priority_queue::PriorityQueue;
+use crate::property_map::{EdgeData, EdgeMap};
+use crate::shortest_paths::{NegativeWeightError, ShortestPaths, Weight};
+
+/// Dijkstra's single source shortest paths, with edge data as weights
+///
+/// Keeps a `MaxHeap` of `Reverse` distances as a min-priority queue. Instead of decreasing a key,
+/// a shorter path to a node pushes a new entry, and stale entries are skipped when popped. Takes
+/// `O((V + E) log(E))` time. Returns an error if any edge has a negative weight, or a weight that
+/// doesn't compare, like `NaN`.
+///
+/// # Arguments
+///
+/// * `graph` - Graph to search, edges are followed in both directions if undirected
+/// * `source` - Node to find paths from
+///
+/// # Panics
+///
+/// If the source isn't a node in the graph
+pub fn dijkstra<N, W: Weight>(graph: &Graph<N, W>, source: NodeId) -> Result<ShortestPaths<W>, NegativeWeightError> {
+    dijkstra_with(graph, source, &EdgeData)
+}
+
+/// Dijkstra's single source shortest paths, with weights read from an edge map
+///
+/// The same as `dijkstra`, but weights can come from a vector indexed by `EdgeId` or a closure, so
+/// the same graph can be searched with different weights.
+///
+/// # Arguments
+///
+/// * `graph` - Graph to search, edges are followed in both directions if undirected
+/// * `source` - Node to find paths from
+/// * `weight` - Weight of each edge
+///
+/// # Panics
+///
+/// If the source isn't a node in the graph
+pub fn dijkstra_with<N, E, W, M>(graph: &Graph<N, E>, source: NodeId, weight: &M) -> Result<ShortestPaths<W>, NegativeWeightError>
+where
+    W: Weight,
+    M: EdgeMap<E, Value = W> + ?Sized,
+{
+    dijkstra_with_queue(graph, source, weight, MaxHeap::new())
+}
+
+/// Dijkstra's single source shortest paths, with weights read from an edge map and any priority
+/// queue
+///
+/// The same as `dijkstra_with`, but the `Reverse` distances go in the given queue instead of a
+/// `MaxHeap`, like the stdlib's `BinaryHeap` when the weights are `Ord`, so heaps can be compared
+/// on the same searches.
+///
+/// # Arguments
+///
+/// * `graph` - Graph to search, edges are followed in both directions if undirected
+/// * `source` - Node to find paths from
+/// * `weight` - Weight of each edge
+/// * `queue` - Empty queue to keep the tentative distances in
+///
+/// # Panics
+///
+/// If the source isn't a node in the graph
+pub fn dijkstra_with_queue<N, E, W, M, Q>(graph: &Graph<N, E>, source: NodeId, weight: &M, mut queue: Q) -> Result<ShortestPaths<W>, NegativeWeightError>
+where
+    W: Weight,
+    M: EdgeMap<E, Value = W> + ?Sized,
+    Q: PriorityQueue<(Reverse<W>, NodeId)>,
+{
+    assert!(graph.contains_node(source), "source must be a node in the graph");
+    check_weights(graph, weight)?;
+
+    let mut paths = ShortestPaths::new(source, graph.node_bound(), W::zero());
+    let mut settled = vec![false; graph.node_bound()];
+    queue.push((Reverse(W::zero()), source));
+
+    while let Some((Reverse(d), u)) = queue.pop() {
+        if settled[u] {
+            continue;
+        }
+        settled[u] = true;
+        for e in graph.edges_from(u) {
+            let v = e.target;
+            let nd = d + weight.edge_value(e);
+            if paths.distance[v].is_none_or(|dv| nd < dv) {
+                paths.distance[v] = Some(nd);
+                paths.predecessor[v] = Some(u);
+                queue.push((Reverse(nd), v));
+            }
+        }
+    }
+    Ok(paths)
+}
+
+/// Dijkstra's single source shortest paths, decreasing keys in an `IndexedMinHeap`
+///
+/// Finds the same distances as `dijkstra`, but keeps at most one heap entry per node and lowers its
+/// priority when a shorter path is found, so the heap never holds more than `V` entries. Takes
+/// `O((V + E) log(V))` time.
+///
+/// # Arguments
+///
+/// * `graph` - Graph to search, edges are followed in both directions if undirected
+/// * `source` - Node to find paths from
+///
+/// # Panics
+///
+/// If the source isn't a node in the graph
+pub fn dijkstra_decrease_key<N, W: Weight>(graph: &Graph<N, W>, source: NodeId) -> Result<ShortestPaths<W>, NegativeWeightError> {
+    assert!(graph.contains_node(source), "source must be a node in the graph");
+    check_weights(graph, &EdgeData)?;
+
+    let mut paths = ShortestPaths::new(source, graph.node_bound(), W::zero());
+    let mut heap = IndexedMinHeap::with_capacity(graph.node_bound());
+    heap.push(source, W::zero());
+
+    while let Some((u, d)) = heap.pop() {
+        for e in graph.edges_from(u) {
+            let v = e.target;
+            let nd = d + *e.data;
+            if heap.contains(v) {
+                if heap.decrease_key(v, nd) {
+                    paths.distance[v] = Some(nd);
+                    paths.predecessor[v] = Some(u);
+                }
+            } else if paths.distance[v].is_none() {
+                paths.distance[v] = Some(nd);
+                paths.predecessor[v] = Some(u);
+                heap.push(v, nd);
+            }
+        }
+    }
+    Ok(paths)
+}
+
+/// Internal function to find an edge with a negative or incomparable weight
+pub(crate) fn check_weights<N, E, W: Weight, M: EdgeMap<E, Value = W> + ?Sized>(graph: &Graph<N, E>, weight: &M) -> Result<(), NegativeWeightError> {
+    match graph.edges().find(|&e| matches!(weight.edge_value(e).partial_cmp(&W::zero()), Some(Ordering::Less) | None)) {
+        Some(e) => Err(NegativeWeightError { edge: e.id }),
+        None => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::EdgeRef;
+    use crate::rng::Rng;
+    use std::collections::BinaryHeap;
+
+    #[test]
+    fn test_dijkstra() {
+        // CLRS Figure 24.6
+        let (s, t, x, y, z) = (0, 1, 2, 3, 4);
+        let edges = vec![
+            (s, t, 10), (s, y, 5), (t, x, 1), (t, y, 2), (x, z, 4), (y, t, 3), (y, x, 9), (y, z, 2), (z, s, 7), (z, x, 6),
+        ];
+        let g: Graph<(), u32> = Graph::from_edges(true, 5, edges);
+        let paths = dijkstra(&g, s).unwrap();
+        assert_eq!(vec![Some(0), Some(8), Some(9), Some(5), Some(7)], paths.distance);
+        assert_eq!(Some(vec![s, y, t, x]), paths.path_to(x));
+        assert_eq!(Some(vec![s]), paths.path_to(s));
+    }
+
+    #[test]
+    fn test_decrease_key() {
+        let (s, t, x, y, z) = (0, 1, 2, 3, 4);
+        let edges = vec![
+            (s, t, 10), (s, y, 5), (t, x, 1), (t, y, 2), (x, z, 4), (y, t, 3), (y, x, 9), (y, z, 2), (z, s, 7), (z, x, 6),
+        ];
+        let g: Graph<(), u32> = Graph::from_edges(true, 5, edges);
+        assert_eq!(dijkstra(&g, s), dijkstra_decrease_key(&g, s));
+
+        let mut rng = Rng::new(7);
+        let edges: Vec<_> = (0..600).map(|_| (rng.gen_range(100) as usize, rng.gen_range(100) as usize, rng.gen_range(50))).collect();
+        let g: Graph<(), u64> = Graph::from_edges(true, 100, edges);
+        assert_eq!(dijkstra(&g, 0).unwrap().distance, dijkstra_decrease_key(&g, 0).unwrap().distance);
+    }
+
+    #[test]
+    fn test_unreachable() {
+        let g: Graph<(), f64> = Graph::from_edges(true, 3, vec![(0, 1, 1.5), (2, 0, 1.0)]);
+        let paths = dijkstra(&g, 0).unwrap();
+        assert_eq!(Some(1.5), paths.distance(1));
+        assert_eq!(None, paths.distance(2));
+        assert_eq!(None, paths.path_to(2));
+    }
+
+    #[test]
+    fn test_undirected() {
+        let g: Graph<(), u32> = Graph::from_edges(false, 4, vec![(1, 0, 4), (2, 1, 1), (0, 2, 2), (3, 2, 7)]);
+        let paths = dijkstra(&g, 0).unwrap();
+        assert_eq!(vec![Some(0), Some(3), Some(2), Some(9)], paths.distance);
+        assert_eq!(Some(vec![0, 2, 1]), paths.path_to(1));
+    }
+
+    #[test]
+    fn test_edge_maps() {
+        // The same graph searched with its own weights, then a vector and a closure instead
+        let g: Graph<&str, u32> = Graph::from_edges(true, 3, vec![(0, 1, 1), (1, 2, 1), (0, 2, 3)]);
+        assert_eq!(Some(2), dijkstra(&g, 0).unwrap().distance(2));
+        let paths = dijkstra_with(&g, 0, &vec![5u32, 5, 3]).unwrap();
+        assert_eq!(Some(vec![0, 2]), paths.path_to(2));
+        let paths = dijkstra_with(&g, 0, &|e: EdgeRef<'_, u32>| f64::from(*e.data) * 0.5).unwrap();
+        assert_eq!(Some(1.0), paths.distance(2));
+        assert_eq!(Err(NegativeWeightError { edge: 1 }), dijkstra_with(&g, 0, &[1i32, -1, 0][..]));
+    }
+
+    #[test]
+    fn test_queues() {
+        let mut rng = Rng::new(457);
+        let edges: Vec<_> = (0..600).map(|_| (rng.gen_range(100) as usize, rng.gen_range(100) as usize, rng.gen_range(50))).collect();
+        let g: Graph<(), u64> = Graph::from_edges(true, 100, edges);
+        let expected = dijkstra(&g, 0).unwrap().distance;
+        assert_eq!(expected, dijkstra_with_queue(&g, 0, &EdgeData, BinaryHeap::new()).unwrap().distance);
+        assert_eq!(expected, dijkstra_with_queue(&g, 0, &EdgeData, MaxHeap::new()).unwrap().distance);
+    }
+
+    #[test]
+    fn test_negative_weight() {
+        let g: Graph<(), i32> = Graph::from_edges(true, 3, vec![(0, 1, 1), (1, 2, -1)]);
+        assert_eq!(Err(NegativeWeightError { edge: 1 }), dijkstra(&g, 0));
+
+        let g: Graph<(), f64> = Graph::from_edges(true, 2, vec![(0, 1, f64::NAN)]);
+        assert!(dijkstra(&g, 0).is_err());
+    }
+}
@@ -0,0 +1,416 @@
+use std::collections::HashMap;
+use std::fmt;
+
+use crate::graph::{Graph, NodeId};
+
+/// Error for DOT text that couldn't be read
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DotError {
+    /// Line the error was found on, starting at 1
+    pub line: usize,
+    pub message: String,
+}
+
+impl fmt::Display for DotError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+impl std::error::Error for DotError {}
+
+/// Internal DOT token
+#[derive(Clone, Debug, PartialEq)]
+enum Token {
+    Id(String),
+    Edge(bool),
+    LeftBrace,
+    RightBrace,
+    LeftBracket,
+    RightBracket,
+    Equals,
+    Separator,
+}
+
+impl<N, E> Graph<N, E> {
+
+    /// Writes the graph in the DOT language of Graphviz, without labels
+    ///
+    /// Nodes are named by their index, so `from_dot` reads the graph back with the same indexes if
+    /// no nodes were removed.
+    pub fn to_dot(&self) -> String {
+        self.to_dot_with(|_| String::new(), |_| String::new())
+    }
+
+    /// Writes the graph in the DOT language of Graphviz, labelling nodes and edges with their data
+    ///
+    /// Empty labels are left out.
+    ///
+    /// # Arguments
+    ///
+    /// * `node_label` - Label of a node's data
+    /// * `edge_label` - Label of an edge's data
+    pub fn to_dot_with<F: Fn(&N) -> String, G: Fn(&E) -> String>(&self, node_label: F, edge_label: G) -> String {
+        let (kind, op) = if self.is_directed() { ("digraph", "->") } else { ("graph", "--") };
+        let mut dot = format!("{} {{\n", kind);
+        for (u, data) in self.nodes() {
+            dot.push_str(&format!("    {}{};\n", u, attributes(&node_label(data))));
+        }
+        for e in self.edges() {
+            dot.push_str(&format!("    {} {} {}{};\n", e.source, op, e.target, attributes(&edge_label(e.data))));
+        }
+        dot.push_str("}\n");
+        dot
+    }
+
+    /// Reads a graph written in a subset of the DOT language, building node and edge data from
+    /// their names and labels
+    ///
+    /// Supports `graph` and `digraph` with an optional name and `strict`, node statements, chains of
+    /// edges like `a -> b -> c`, attribute lists, and `//`, `#` and `/* */` comments. Names are
+    /// identifiers, numbers or quoted strings. Nodes are indexed in the order they first appear.
+    /// An edge's label is its `label` attribute, or its `weight` if it has no label, or empty. A
+    /// `strict` graph keeps one edge between each pair of nodes, so a repeated edge only replaces
+    /// the label of the first if it has one. Node and graph wide attribute statements are skipped,
+    /// and subgraphs aren't supported.
+    ///
+    /// # Arguments
+    ///
+    /// * `dot` - DOT text to read
+    /// * `node` - Builds a node's data from its name
+    /// * `edge` - Builds an edge's data from its label, or `None` if the label is invalid
+    pub fn from_dot_with<F, G>(dot: &str, mut node: F, mut edge: G) -> Result<Graph<N, E>, DotError>
+    where
+        F: FnMut(&str) -> N,
+        G: FnMut(&str) -> Option<E>,
+    {
+        let tokens = tokenize(dot)?;
+        let mut parser = Parser { tokens, i: 0 };
+
+        let strict = matches!(parser.peek(), Some(Token::Id(id)) if id.eq_ignore_ascii_case("strict"));
+        if strict {
+            parser.i += 1;
+        }
+        let directed = match parser.next() {
+            Some(Token::Id(id)) if id.eq_ignore_ascii_case("digraph") => true,
+            Some(Token::Id(id)) if id.eq_ignore_ascii_case("graph") => false,
+            _ => return Err(parser.error("expected graph or digraph")),
+        };
+        if let Some(Token::Id(_)) = parser.peek() {
+            parser.i += 1;
+        }
+        if parser.next() != Some(Token::LeftBrace) {
+            return Err(parser.error("expected {"));
+        }
+
+        let mut graph = Graph::new(directed);
+        let mut ids: HashMap<String, NodeId> = HashMap::new();
+        loop {
+            let name = match parser.next() {
+                Some(Token::RightBrace) => break,
+                Some(Token::Separator) => continue,
+                Some(Token::Id(name)) => name,
+                Some(Token::LeftBrace) => return Err(parser.error("subgraphs are not supported")),
+                _ => return Err(parser.error("expected a statement")),
+            };
+            // Attribute statements and graph attributes are skipped
+            if ["graph", "node", "edge"].iter().any(|k| name.eq_ignore_ascii_case(k)) {
+                parser.attributes()?;
+                continue;
+            }
+            if parser.peek() == Some(&Token::Equals) {
+                parser.i += 1;
+                parser.id()?;
+                continue;
+            }
+
+            let mut chain = vec![name];
+            while let Some(&Token::Edge(d)) = parser.peek() {
+                if d != directed {
+                    return Err(parser.error(if directed { "expected -> in a digraph" } else { "expected -- in a graph" }));
+                }
+                parser.i += 1;
+                chain.push(parser.id()?);
+            }
+            let attributes = parser.attributes()?;
+
+            let mut nodes = Vec::new();
+            for name in chain.iter() {
+                let u = match ids.get(name) {
+                    Some(&u) => u,
+                    None => {
+                        let u = graph.add_node(node(name));
+                        ids.insert(name.clone(), u);
+                        u
+                    }
+                };
+                nodes.push(u);
+            }
+            let label = attributes.get("label").or_else(|| attributes.get("weight")).map(|l| l.as_str());
+            for pair in nodes.windows(2) {
+                // A strict graph merges repeated edges, with a later label replacing an earlier one
+                let existing = if strict { graph.find_edge(pair[0], pair[1]) } else { None };
+                if existing.is_some() && label.is_none() {
+                    continue;
+                }
+                let data = match edge(label.unwrap_or("")) {
+                    Some(data) => data,
+                    None => return Err(parser.error(&format!("invalid edge label {:?}", label.unwrap_or("")))),
+                };
+                match existing {
+                    Some(e) => *graph.edge_mut(e).unwrap() = data,
+                    None => {
+                        graph.add_edge(pair[0], pair[1], data);
+                    }
+                }
+            }
+        }
+        if parser.peek().is_some() {
+            return Err(parser.error("unexpected text after the graph"));
+        }
+        Ok(graph)
+    }
+}
+
+impl Graph<String, String> {
+
+    /// Reads a graph written in a subset of the DOT language, with node names and edge labels as
+    /// data
+    ///
+    /// See `from_dot_with` for the subset supported.
+    ///
+    /// # Arguments
+    ///
+    /// * `dot` - DOT text to read
+    pub fn from_dot(dot: &str) -> Result<Graph<String, String>, DotError> {
+        Graph::from_dot_with(dot, |name| name.to_string(), |label| Some(label.to_string()))
+    }
+}
+
+/// Internal function to write a label attribute list, or nothing for an empty label
+fn attributes(label: &str) -> String {
+    if label.is_empty() {
+        String::new()
+    } else {
+        format!(" [label=\"{}\"]", label.replace('\\', "\\\\").replace('"', "\\\""))
+    }
+}
+
+/// Internal function to split DOT text into tokens, each with the line it's on
+fn tokenize(dot: &str) -> Result<Vec<(Token, usize)>, DotError> {
+    let chars: Vec<char> = dot.chars().collect();
+    let mut tokens = Vec::new();
+    let mut line = 1;
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        let next = chars.get(i + 1).copied();
+        match c {
+            '\n' => line += 1,
+            c if c.is_whitespace() => {}
+            '#' => {
+                while i + 1 < chars.len() && chars[i + 1] != '\n' {
+                    i += 1;
+                }
+            }
+            '/' if next == Some('/') => {
+                while i + 1 < chars.len() && chars[i + 1] != '\n' {
+                    i += 1;
+                }
+            }
+            '/' if next == Some('*') => {
+                i += 2;
+                while i < chars.len() && !(chars[i] == '*' && chars.get(i + 1) == Some(&'/')) {
+                    if chars[i] == '\n' {
+                        line += 1;
+                    }
+                    i += 1;
+                }
+                if i >= chars.len() {
+                    return Err(DotError { line, message: "unterminated comment".to_string() });
+                }
+                i += 1;
+            }
+            '-' if next == Some('>') || next == Some('-') => {
+                tokens.push((Token::Edge(next == Some('>')), line));
+                i += 1;
+            }
+            '{' => tokens.push((Token::LeftBrace, line)),
+            '}' => tokens.push((Token::RightBrace, line)),
+            '[' => tokens.push((Token::LeftBracket, line)),
+            ']' => tokens.push((Token::RightBracket, line)),
+            '=' => tokens.push((Token::Equals, line)),
+            ';' | ',' => tokens.push((Token::Separator, line)),
+            '"' => {
+                let start = line;
+                let mut id = String::new();
+                i += 1;
+                while i < chars.len() && chars[i] != '"' {
+                    if chars[i] == '\\' && matches!(chars.get(i + 1), Some('"') | Some('\\')) {
+                        i += 1;
+                    } else if chars[i] == '\n' {
+                        line += 1;
+                    }
+                    id.push(chars[i]);
+                    i += 1;
+                }
+                if i >= chars.len() {
+                    return Err(DotError { line: start, message: "unterminated string".to_string() });
+                }
+                tokens.push((Token::Id(id), start));
+            }
+            c if c.is_alphanumeric() || c == '_' || c == '.' || c == '-' => {
+                let mut id = String::new();
+                while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_' || chars[i] == '.' || (chars[i] == '-' && id.is_empty())) {
+                    id.push(chars[i]);
+                    i += 1;
+                }
+                tokens.push((Token::Id(id), line));
+                continue;
+            }
+            c => return Err(DotError { line, message: format!("unexpected character {:?}", c) }),
+        }
+        i += 1;
+    }
+    Ok(tokens)
+}
+
+/// Internal cursor over DOT tokens
+struct Parser {
+    tokens: Vec<(Token, usize)>,
+    i: usize,
+}
+
+impl Parser {
+
+    /// Internal function to look at the next token
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.i).map(|(t, _)| t)
+    }
+
+    /// Internal function to take the next token
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.i).map(|(t, _)| t.clone());
+        self.i += 1;
+        token
+    }
+
+    /// Internal function to take a name
+    fn id(&mut self) -> Result<String, DotError> {
+        match self.next() {
+            Some(Token::Id(id)) => Ok(id),
+            _ => Err(self.error("expected a name")),
+        }
+    }
+
+    /// Internal function to take any number of attribute lists, like `[a=1, b=2][c=3]`
+    fn attributes(&mut self) -> Result<HashMap<String, String>, DotError> {
+        let mut attributes = HashMap::new();
+        while self.peek() == Some(&Token::LeftBracket) {
+            self.i += 1;
+            loop {
+                match self.next() {
+                    Some(Token::RightBracket) => break,
+                    Some(Token::Separator) => continue,
+                    Some(Token::Id(key)) => {
+                        if self.next() != Some(Token::Equals) {
+                            return Err(self.error("expected = in an attribute"));
+                        }
+                        let value = self.id()?;
+                        attributes.insert(key, value);
+                    }
+                    _ => return Err(self.error("expected an attribute or ]")),
+                }
+            }
+        }
+        Ok(attributes)
+    }
+
+    /// Internal function to build an error at the last token taken
+    fn error(&self, message: &str) -> DotError {
+        let line = match self.tokens.get(self.i.saturating_sub(1)).or_else(|| self.tokens.last()) {
+            Some(&(_, line)) => line,
+            None => 1,
+        };
+        DotError { line, message: message.to_string() }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_dot() {
+        let g: Graph<&str, u32> = Graph::from_edges(true, 0, vec![]);
+        assert_eq!("digraph {\n}\n", g.to_dot());
+
+        let mut g: Graph<&str, u32> = Graph::new_undirected();
+        let a = g.add_node("a");
+        let b = g.add_node("say \"b\"");
+        g.add_edge(a, b, 7);
+        assert_eq!("graph {\n    0;\n    1;\n    0 -- 1;\n}\n", g.to_dot());
+        assert_eq!(
+            "graph {\n    0 [label=\"a\"];\n    1 [label=\"say \\\"b\\\"\"];\n    0 -- 1 [label=\"7\"];\n}\n",
+            g.to_dot_with(|n| n.to_string(), |e| e.to_string())
+        );
+    }
+
+    #[test]
+    fn test_from_dot() {
+        let dot = r#"
+            // A small weighted graph
+            strict digraph G {
+                rankdir = LR; node [shape=circle]
+                a -> b [label=4]; a -> "c d" [weight=2, color=red]
+                /* a chain of
+                   two edges */
+                b -> "c d" -> e [label="1"]
+                f # isolated
+            }
+        "#;
+        let g = Graph::from_dot(dot).unwrap();
+        assert!(g.is_directed());
+        let names: Vec<_> = g.nodes().map(|(_, n)| n.as_str()).collect();
+        assert_eq!(vec!["a", "b", "c d", "e", "f"], names);
+        let edges: Vec<_> = g.edges().map(|e| (e.source, e.target, e.data.as_str())).collect();
+        assert_eq!(vec![(0, 1, "4"), (0, 2, "2"), (1, 2, "1"), (2, 3, "1")], edges);
+    }
+
+    #[test]
+    fn test_strict() {
+        let dot = "strict digraph { a -> b; a -> b [label=2]; b -> a; a -> b }";
+        let g = Graph::from_dot(dot).unwrap();
+        let edges: Vec<_> = g.edges().map(|e| (e.source, e.target, e.data.as_str())).collect();
+        assert_eq!(vec![(0, 1, "2"), (1, 0, "")], edges);
+
+        let g = Graph::from_dot("strict graph { a -- b [label=1]; b -- a -- b }").unwrap();
+        assert_eq!(1, g.edge_count());
+        assert_eq!(2, Graph::from_dot("digraph { a -> b; a -> b }").unwrap().edge_count());
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let g: Graph<(), i64> = Graph::from_edges(false, 4, vec![(0, 1, -3), (1, 2, 5), (2, 2, 1), (3, 0, 2)]);
+        let dot = g.to_dot_with(|_| String::new(), |w| w.to_string());
+        let h: Graph<(), i64> = Graph::from_dot_with(&dot, |_| (), |l| l.parse().ok()).unwrap();
+        assert_eq!(dot, h.to_dot_with(|_| String::new(), |w| w.to_string()));
+
+        let labels = ["a\\b", "end\\", "say \"c\\\""];
+        let g: Graph<(), String> = Graph::from_edges(true, 2, labels.iter().map(|l| (0, 1, l.to_string())));
+        let dot = g.to_dot_with(|_| String::new(), |e| e.clone());
+        let h: Graph<(), String> = Graph::from_dot_with(&dot, |_| (), |l| Some(l.to_string())).unwrap();
+        assert_eq!(labels.to_vec(), h.edges().map(|e| e.data.as_str()).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_errors() {
+        assert_eq!(1, Graph::from_dot("tree { a }").unwrap_err().line);
+        assert_eq!(3, Graph::from_dot("graph {\n a -- b\n b -> c\n}").unwrap_err().line);
+        assert_eq!(2, Graph::from_dot("digraph {\n a -> b [label]\n}").unwrap_err().line);
+        assert!(Graph::from_dot("digraph { subgraph { a } }").is_err());
+        assert!(Graph::from_dot("digraph { a -> b").is_err());
+        let e = Graph::<(), u32>::from_dot_with("digraph { a -> b [label=x] }", |_| (), |l| l.parse().ok()).unwrap_err();
+        assert_eq!("line 1: invalid edge label \"x\"", e.to_string());
+    }
+}
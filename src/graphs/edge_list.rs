@@ -0,0 +1,709 @@
+use std::collections::HashMap;
+use std::fmt;
+
+use crate::graph::{Graph, NodeId};
+
+/// Error for JSON or CSV text that couldn't be read as a graph
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EdgeListError {
+    /// Line the error was found on, starting at 1
+    pub line: usize,
+    pub message: String,
+}
+
+impl fmt::Display for EdgeListError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+impl std::error::Error for EdgeListError {}
+
+/// Internal JSON value, with numbers kept as their text
+#[derive(Clone, Debug, PartialEq)]
+enum Json {
+    Null,
+    Bool(bool),
+    Number(String),
+    String(String),
+    Array(Vec<(Json, usize)>),
+    Object(Vec<(String, Json, usize)>),
+}
+
+impl Json {
+
+    /// Internal function to look up a key of an object
+    fn get(&self, key: &str) -> Option<&Json> {
+        match self {
+            Json::Object(fields) => fields.iter().find(|(k, _, _)| k == key).map(|(_, v, _)| v),
+            _ => None,
+        }
+    }
+
+    /// Internal function to get the text of a number or string, what closures are given
+    fn text(&self) -> Option<&str> {
+        match self {
+            Json::Number(s) | Json::String(s) => Some(s),
+            _ => None,
+        }
+    }
+}
+
+impl<N, E> Graph<N, E> {
+
+    /// Writes the graph as JSON, without data
+    ///
+    /// See `to_json_with` for the format.
+    pub fn to_json(&self) -> String {
+        self.to_json_with(|_| String::new(), |_| String::new())
+    }
+
+    /// Writes the graph as JSON, with a label for every node and a weight for every edge
+    ///
+    /// The format is an object with `directed`, a list of `nodes` with an `id` and optional
+    /// `label`, and a list of `edges` with a `source`, `target` and optional `weight`, the same
+    /// shape as the node-link format of Python's NetworkX. Empty labels and weights are left out,
+    /// and weights already written as JSON numbers, like `-2.5e3` but not `.5` or `+5`, are written
+    /// as numbers.
+    ///
+    /// # Arguments
+    ///
+    /// * `node_label` - Label of a node's data
+    /// * `edge_weight` - Weight of an edge's data
+    pub fn to_json_with<F: Fn(&N) -> String, G: Fn(&E) -> String>(&self, node_label: F, edge_weight: G) -> String {
+        let nodes: Vec<String> = self
+            .nodes()
+            .map(|(u, data)| match node_label(data) {
+                label if label.is_empty() => format!("    {{\"id\": {}}}", u),
+                label => format!("    {{\"id\": {}, \"label\": {}}}", u, quote(&label)),
+            })
+            .collect();
+        let edges: Vec<String> = self
+            .edges()
+            .map(|e| match edge_weight(e.data) {
+                weight if weight.is_empty() => format!("    {{\"source\": {}, \"target\": {}}}", e.source, e.target),
+                weight if is_number(&weight) => {
+                    format!("    {{\"source\": {}, \"target\": {}, \"weight\": {}}}", e.source, e.target, weight)
+                }
+                weight => format!("    {{\"source\": {}, \"target\": {}, \"weight\": {}}}", e.source, e.target, quote(&weight)),
+            })
+            .collect();
+        format!("{{\n  \"directed\": {},\n  \"nodes\": [\n{}\n  ],\n  \"edges\": [\n{}\n  ]\n}}\n", self.is_directed(), nodes.join(",\n"), edges.join(",\n"))
+    }
+
+    /// Reads a graph written as JSON, building node and edge data from their labels and weights
+    ///
+    /// Reads the format of `to_json_with`. Node ids can be numbers or strings, nodes are indexed in
+    /// the order they're listed, and edges may also be called `links` as NetworkX does. `directed`
+    /// defaults to `false`. A node's data is built from its `label`, or its `id` if it has no
+    /// label, and an edge's from its `weight`, or empty if it has none or it's `null`.
+    ///
+    /// # Arguments
+    ///
+    /// * `json` - JSON text to read
+    /// * `node` - Builds a node's data from its label
+    /// * `edge` - Builds an edge's data from its weight, or `None` if the weight is invalid
+    pub fn from_json_with<F, G>(json: &str, mut node: F, mut edge: G) -> Result<Graph<N, E>, EdgeListError>
+    where
+        F: FnMut(&str) -> N,
+        G: FnMut(&str) -> Option<E>,
+    {
+        let chars: Vec<char> = json.chars().collect();
+        let mut parser = JsonParser { chars: &chars, i: 0, line: 1 };
+        let root = parser.document()?;
+        let error = |line, message: &str| EdgeListError { line, message: message.to_string() };
+        if !matches!(root, Json::Object(_)) {
+            return Err(error(1, "expected an object"));
+        }
+
+        let directed = match root.get("directed") {
+            None | Some(Json::Null) => false,
+            Some(Json::Bool(b)) => *b,
+            Some(_) => return Err(error(1, "directed must be true or false")),
+        };
+        let mut graph = Graph::new(directed);
+        let mut ids: HashMap<String, NodeId> = HashMap::new();
+        let nodes = match root.get("nodes") {
+            Some(Json::Array(nodes)) => nodes.as_slice(),
+            None => &[],
+            Some(_) => return Err(error(1, "nodes must be a list")),
+        };
+        for (n, line) in nodes.iter() {
+            let id = n.get("id").and_then(Json::text).ok_or_else(|| error(*line, "node needs a number or string id"))?;
+            let label = n.get("label").and_then(Json::text).unwrap_or(id);
+            if ids.insert(id.to_string(), graph.add_node(node(label))).is_some() {
+                return Err(error(*line, &format!("duplicate node id {:?}", id)));
+            }
+        }
+        let edges = match root.get("edges").or_else(|| root.get("links")) {
+            Some(Json::Array(edges)) => edges.as_slice(),
+            None => &[],
+            Some(_) => return Err(error(1, "edges must be a list")),
+        };
+        for (e, line) in edges.iter() {
+            let endpoint = |key| {
+                let id = e.get(key).and_then(Json::text).ok_or_else(|| error(*line, &format!("edge needs a {}", key)))?;
+                ids.get(id).copied().ok_or_else(|| error(*line, &format!("unknown node id {:?}", id)))
+            };
+            let (u, v) = (endpoint("source")?, endpoint("target")?);
+            let weight = match e.get("weight") {
+                None | Some(Json::Null) => "",
+                Some(w) => w.text().ok_or_else(|| error(*line, "edge weight must be a number or string"))?,
+            };
+            let data = edge(weight).ok_or_else(|| error(*line, &format!("invalid edge weight {:?}", weight)))?;
+            graph.add_edge(u, v, data);
+        }
+        Ok(graph)
+    }
+
+    /// Reads a graph from CSV lines of `source,target` or `source,target,weight`
+    ///
+    /// Node names are any text, and nodes are indexed in the order they first appear. A first line
+    /// of exactly `source,target` or `source,target,weight` is a header and skipped, as are empty
+    /// lines, so a node named `source` can still be the source of the first edge. Fields are trimmed, and
+    /// may be quoted to contain commas.
+    ///
+    /// # Arguments
+    ///
+    /// * `csv` - CSV text to read
+    /// * `directed` - If the edges are directed
+    /// * `node` - Builds a node's data from its name
+    /// * `edge` - Builds an edge's data from its weight, empty if the line has none, or `None` if
+    ///   the weight is invalid
+    pub fn from_csv_with<F, G>(csv: &str, directed: bool, mut node: F, mut edge: G) -> Result<Graph<N, E>, EdgeListError>
+    where
+        F: FnMut(&str) -> N,
+        G: FnMut(&str) -> Option<E>,
+    {
+        let mut graph = Graph::new(directed);
+        let mut ids: HashMap<String, NodeId> = HashMap::new();
+        for (i, line) in csv.lines().enumerate() {
+            let fields = split_csv(line);
+            let error = |message: String| EdgeListError { line: i + 1, message };
+            if fields.iter().all(|f| f.is_empty()) || (i == 0 && is_header(&fields)) {
+                continue;
+            }
+            if fields.len() < 2 || fields.len() > 3 {
+                return Err(error(format!("expected 2 or 3 fields, found {}", fields.len())));
+            }
+            let mut endpoints = [0; 2];
+            for (endpoint, name) in endpoints.iter_mut().zip(fields.iter()) {
+                *endpoint = match ids.get(name) {
+                    Some(&u) => u,
+                    None => {
+                        let u = graph.add_node(node(name));
+                        ids.insert(name.clone(), u);
+                        u
+                    }
+                };
+            }
+            let weight = fields.get(2).map_or("", |w| w.as_str());
+            let data = edge(weight).ok_or_else(|| error(format!("invalid edge weight {:?}", weight)))?;
+            graph.add_edge(endpoints[0], endpoints[1], data);
+        }
+        Ok(graph)
+    }
+}
+
+impl Graph<String, String> {
+
+    /// Reads a graph written as JSON, with node labels and edge weights as data
+    ///
+    /// See `from_json_with` for the format.
+    ///
+    /// # Arguments
+    ///
+    /// * `json` - JSON text to read
+    pub fn from_json(json: &str) -> Result<Graph<String, String>, EdgeListError> {
+        Graph::from_json_with(json, |label| label.to_string(), |weight| Some(weight.to_string()))
+    }
+
+    /// Reads a graph from CSV lines, with node names and edge weights as data
+    ///
+    /// See `from_csv_with` for the format.
+    ///
+    /// # Arguments
+    ///
+    /// * `csv` - CSV text to read
+    /// * `directed` - If the edges are directed
+    pub fn from_csv(csv: &str, directed: bool) -> Result<Graph<String, String>, EdgeListError> {
+        Graph::from_csv_with(csv, directed, |name| name.to_string(), |weight| Some(weight.to_string()))
+    }
+}
+
+/// Internal function to write a JSON string
+fn quote(s: &str) -> String {
+    let mut quoted = String::from("\"");
+    for c in s.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\t' => quoted.push_str("\\t"),
+            c if (c as u32) < 0x20 => quoted.push_str(&format!("\\u{:04x}", c as u32)),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+/// Internal function to check if text is a number in JSON's grammar,
+/// `-?(0|[1-9][0-9]*)(\.[0-9]+)?([eE][+-]?[0-9]+)?`
+fn is_number(s: &str) -> bool {
+    let digits = |s: &str| s.bytes().take_while(u8::is_ascii_digit).count();
+    let s = s.strip_prefix('-').unwrap_or(s);
+    let n = digits(s);
+    if n == 0 || (n > 1 && s.starts_with('0')) {
+        return false;
+    }
+    let mut rest = &s[n..];
+    if let Some(fraction) = rest.strip_prefix('.') {
+        let n = digits(fraction);
+        if n == 0 {
+            return false;
+        }
+        rest = &fraction[n..];
+    }
+    if let Some(exponent) = rest.strip_prefix(['e', 'E']) {
+        let exponent = exponent.strip_prefix(['+', '-']).unwrap_or(exponent);
+        let n = digits(exponent);
+        if n == 0 {
+            return false;
+        }
+        rest = &exponent[n..];
+    }
+    rest.is_empty()
+}
+
+/// Internal function to check if CSV fields are the header row, `source,target` with an optional
+/// `weight`
+fn is_header(fields: &[String]) -> bool {
+    let header = ["source", "target", "weight"];
+    (2..=3).contains(&fields.len()) && fields.iter().zip(header.iter()).all(|(f, h)| f.eq_ignore_ascii_case(h))
+}
+
+/// Internal function to split a CSV line into trimmed fields, with double quotes around fields
+/// that contain commas and `""` for a quote inside them
+fn split_csv(line: &str) -> Vec<String> {
+    let mut fields = vec![String::new()];
+    let mut quoted = false;
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                chars.next();
+                fields.last_mut().unwrap().push('"');
+            }
+            '"' => quoted = !quoted,
+            ',' if !quoted => fields.push(String::new()),
+            c => fields.last_mut().unwrap().push(c),
+        }
+    }
+    fields.iter().map(|f| f.trim().to_string()).collect()
+}
+
+/// Internal recursive descent JSON parser, tracking the line for errors
+struct JsonParser<'a> {
+    chars: &'a [char],
+    i: usize,
+    line: usize,
+}
+
+impl<'a> JsonParser<'a> {
+
+    /// Internal function to parse a whole document, one value with nothing after it
+    fn document(&mut self) -> Result<Json, EdgeListError> {
+        let value = self.value()?;
+        self.whitespace();
+        if self.i < self.chars.len() {
+            return Err(self.error("unexpected text after the JSON value"));
+        }
+        Ok(value)
+    }
+
+    /// Internal function to skip whitespace, counting lines
+    fn whitespace(&mut self) {
+        while let Some(&c) = self.chars.get(self.i) {
+            if !c.is_whitespace() {
+                break;
+            }
+            if c == '\n' {
+                self.line += 1;
+            }
+            self.i += 1;
+        }
+    }
+
+    /// Internal function to take an expected character after any whitespace
+    fn expect(&mut self, c: char) -> Result<(), EdgeListError> {
+        self.whitespace();
+        if self.chars.get(self.i) != Some(&c) {
+            return Err(self.error(&format!("expected {:?}", c)));
+        }
+        self.i += 1;
+        Ok(())
+    }
+
+    /// Internal function to parse any value
+    fn value(&mut self) -> Result<Json, EdgeListError> {
+        self.whitespace();
+        match self.chars.get(self.i) {
+            Some('{') => self.object(),
+            Some('[') => self.array(),
+            Some('"') => Ok(Json::String(self.string()?)),
+            Some(c) if *c == '-' || c.is_ascii_digit() => {
+                let start = self.i;
+                while self.chars.get(self.i).is_some_and(|c| c.is_ascii_digit() || "+-.eE".contains(*c)) {
+                    self.i += 1;
+                }
+                let number: String = self.chars[start..self.i].iter().collect();
+                if is_number(&number) {
+                    Ok(Json::Number(number))
+                } else {
+                    Err(self.error(&format!("invalid number {}", number)))
+                }
+            }
+            _ => {
+                for (word, value) in [("true", Json::Bool(true)), ("false", Json::Bool(false)), ("null", Json::Null)] {
+                    if self.chars[self.i..].starts_with(&word.chars().collect::<Vec<_>>()) {
+                        self.i += word.len();
+                        return Ok(value);
+                    }
+                }
+                Err(self.error("expected a value"))
+            }
+        }
+    }
+
+    /// Internal function to parse an object, remembering the line of every field
+    fn object(&mut self) -> Result<Json, EdgeListError> {
+        self.expect('{')?;
+        let mut fields = Vec::new();
+        self.whitespace();
+        if self.chars.get(self.i) == Some(&'}') {
+            self.i += 1;
+            return Ok(Json::Object(fields));
+        }
+        loop {
+            self.whitespace();
+            let line = self.line;
+            let key = self.string()?;
+            self.expect(':')?;
+            let value = self.value()?;
+            fields.push((key, value, line));
+            self.whitespace();
+            match self.chars.get(self.i) {
+                Some(',') => self.i += 1,
+                Some('}') => {
+                    self.i += 1;
+                    return Ok(Json::Object(fields));
+                }
+                _ => return Err(self.error("expected ',' or '}'")),
+            }
+        }
+    }
+
+    /// Internal function to parse an array, remembering the line of every element
+    fn array(&mut self) -> Result<Json, EdgeListError> {
+        self.expect('[')?;
+        let mut elements = Vec::new();
+        self.whitespace();
+        if self.chars.get(self.i) == Some(&']') {
+            self.i += 1;
+            return Ok(Json::Array(elements));
+        }
+        loop {
+            self.whitespace();
+            let line = self.line;
+            elements.push((self.value()?, line));
+            self.whitespace();
+            match self.chars.get(self.i) {
+                Some(',') => self.i += 1,
+                Some(']') => {
+                    self.i += 1;
+                    return Ok(Json::Array(elements));
+                }
+                _ => return Err(self.error("expected ',' or ']'")),
+            }
+        }
+    }
+
+    /// Internal function to parse a string with its escapes
+    fn string(&mut self) -> Result<String, EdgeListError> {
+        self.expect('"')?;
+        let mut s = String::new();
+        loop {
+            let c = match self.chars.get(self.i) {
+                Some(&c) => c,
+                None => return Err(self.error("unterminated string")),
+            };
+            self.i += 1;
+            match c {
+                '"' => return Ok(s),
+                '\\' => {
+                    let escape = self.chars.get(self.i).copied();
+                    self.i += 1;
+                    match escape {
+                        Some('n') => s.push('\n'),
+                        Some('t') => s.push('\t'),
+                        Some('r') => s.push('\r'),
+                        Some('b') => s.push('\u{8}'),
+                        Some('f') => s.push('\u{c}'),
+                        Some('u') => s.push(self.unicode_escape()?),
+                        Some(c) if c == '"' || c == '\\' || c == '/' => s.push(c),
+                        _ => return Err(self.error("invalid escape")),
+                    }
+                }
+                '\n' => return Err(self.error("unterminated string")),
+                c => s.push(c),
+            }
+        }
+    }
+
+    /// Internal function to decode a `\uXXXX` escape after its `\u`, joining a UTF-16 surrogate
+    /// pair like `\ud83d\ude00` into one character
+    fn unicode_escape(&mut self) -> Result<char, EdgeListError> {
+        let high = self.hex()?;
+        let code = match high {
+            0xd800..=0xdbff => {
+                if self.chars.get(self.i..self.i + 2) != Some(&['\\', 'u']) {
+                    return Err(self.error(&format!("unpaired surrogate \\u{:04x}", high)));
+                }
+                self.i += 2;
+                let low = self.hex()?;
+                if !(0xdc00..=0xdfff).contains(&low) {
+                    return Err(self.error(&format!("unpaired surrogate \\u{:04x}", high)));
+                }
+                0x10000 + ((high - 0xd800) << 10) + (low - 0xdc00)
+            }
+            0xdc00..=0xdfff => return Err(self.error(&format!("unpaired surrogate \\u{:04x}", high))),
+            code => code,
+        };
+        // Every code outside the surrogates is a character
+        Ok(char::from_u32(code).unwrap())
+    }
+
+    /// Internal function to read the 4 hex digits of a `\u` escape
+    fn hex(&mut self) -> Result<u32, EdgeListError> {
+        let hex: String = self.chars.iter().skip(self.i).take(4).collect();
+        self.i += 4;
+        match u32::from_str_radix(&hex, 16) {
+            Ok(code) if hex.len() == 4 && hex.chars().all(|c| c.is_ascii_hexdigit()) => Ok(code),
+            _ => Err(self.error(&format!("invalid escape \\u{}", hex))),
+        }
+    }
+
+    /// Internal function to build an error at the current line
+    fn error(&self, message: &str) -> EdgeListError {
+        EdgeListError { line: self.line, message: message.to_string() }
+    }
+}
+
+/// `Serialize` and `Deserialize` for `Graph` under the `serde` feature, in the same node-link
+/// shape as `to_json_with`, with each node's data as its `label` and each edge's as its `weight`
+///
+/// Nodes are indexed in the order they're listed when deserializing, like `from_json_with`, so
+/// the ids of a graph with removed nodes don't have to be contiguous.
+#[cfg(feature = "serde")]
+mod serde_graph {
+    use std::collections::HashMap;
+
+    use serde::de::Error;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    use crate::graph::{Graph, NodeId};
+
+    /// Internal node of the serialized graph
+    #[derive(Serialize, Deserialize)]
+    struct JsonNode<N> {
+        id: NodeId,
+        label: N,
+    }
+
+    /// Internal edge of the serialized graph
+    #[derive(Serialize, Deserialize)]
+    struct JsonEdge<E> {
+        source: NodeId,
+        target: NodeId,
+        weight: E,
+    }
+
+    /// Internal serialized graph
+    #[derive(Serialize, Deserialize)]
+    struct JsonGraph<N, E> {
+        #[serde(default)]
+        directed: bool,
+        nodes: Vec<JsonNode<N>>,
+        #[serde(alias = "links")]
+        edges: Vec<JsonEdge<E>>,
+    }
+
+    impl<N: Serialize, E: Serialize> Serialize for Graph<N, E> {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            JsonGraph {
+                directed: self.is_directed(),
+                nodes: self.nodes().map(|(id, label)| JsonNode { id, label }).collect(),
+                edges: self.edges().map(|e| JsonEdge { source: e.source, target: e.target, weight: e.data }).collect(),
+            }
+            .serialize(serializer)
+        }
+    }
+
+    impl<'de, N: Deserialize<'de>, E: Deserialize<'de>> Deserialize<'de> for Graph<N, E> {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            let json = JsonGraph::<N, E>::deserialize(deserializer)?;
+            let mut graph = Graph::new(json.directed);
+            let mut ids: HashMap<NodeId, NodeId> = HashMap::new();
+            for node in json.nodes {
+                if ids.insert(node.id, graph.add_node(node.label)).is_some() {
+                    return Err(D::Error::custom(format!("duplicate node id {}", node.id)));
+                }
+            }
+            for edge in json.edges {
+                let endpoint = |id| ids.get(&id).copied().ok_or_else(|| D::Error::custom(format!("unknown node id {}", id)));
+                let (u, v) = (endpoint(edge.source)?, endpoint(edge.target)?);
+                graph.add_edge(u, v, edge.weight);
+            }
+            Ok(graph)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_json() {
+        let mut g: Graph<&str, &str> = Graph::new_directed();
+        let a = g.add_node("a");
+        let b = g.add_node("");
+        g.add_edge(a, b, "2.5");
+        g.add_edge(b, a, "far \"away\"");
+        g.add_edge(a, a, "");
+        let expected = r#"{
+  "directed": true,
+  "nodes": [
+    {"id": 0, "label": "a"},
+    {"id": 1}
+  ],
+  "edges": [
+    {"source": 0, "target": 1, "weight": 2.5},
+    {"source": 1, "target": 0, "weight": "far \"away\""},
+    {"source": 0, "target": 0}
+  ]
+}
+"#;
+        assert_eq!(expected, g.to_json_with(|n| n.to_string(), |e| e.to_string()));
+    }
+
+    #[test]
+    fn test_from_json() {
+        // NetworkX node-link output, with string ids and links
+        let json = r#"{"directed": false, "multigraph": false, "graph": {},
+            "nodes": [{"id": "x"}, {"id": "y", "label": "Y!"}, {"id": 7}],
+            "links": [{"source": "x", "target": "y", "weight": -1.5e1}, {"source": 7, "target": "x"}]}"#;
+        let g = Graph::from_json(json).unwrap();
+        assert!(!g.is_directed());
+        assert_eq!(vec!["x", "Y!", "7"], g.nodes().map(|(_, n)| n.as_str()).collect::<Vec<_>>());
+        let edges: Vec<_> = g.edges().map(|e| (e.source, e.target, e.data.as_str())).collect();
+        assert_eq!(vec![(0, 1, "-1.5e1"), (2, 0, "")], edges);
+
+        let g: Graph<(), i64> = Graph::from_edges(true, 3, vec![(0, 1, 4), (1, 2, -2), (2, 0, 9)]);
+        let json = g.to_json_with(|_| String::new(), |w| w.to_string());
+        let h: Graph<(), i64> = Graph::from_json_with(&json, |_| (), |w| w.parse().ok()).unwrap();
+        assert_eq!(json, h.to_json_with(|_| String::new(), |w| w.to_string()));
+
+        // Text that Rust parses as a number but JSON doesn't is written as a string
+        let weights = [".5", "+5", "5.", "1.e3", "-0.25E+2", "007", "0"];
+        let g: Graph<(), String> = Graph::from_edges(true, 1, weights.iter().map(|w| (0, 0, w.to_string())));
+        let json = g.to_json_with(|_| String::new(), |w| w.clone());
+        assert!(json.contains("\"weight\": \".5\"") && json.contains("\"weight\": -0.25E+2"), "{}", json);
+        let h = Graph::from_json(&json).unwrap();
+        assert_eq!(weights.to_vec(), h.edges().map(|e| e.data.as_str()).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_json_errors() {
+        assert_eq!(3, Graph::from_json("{\"nodes\": [\n{\"id\": 0},\n{\"id\": 0}]}").unwrap_err().line);
+        assert_eq!(2, Graph::from_json("{\"nodes\": [],\n \"edges\": [{\"source\": 0, \"target\": 1}]}").unwrap_err().line);
+        assert!(Graph::from_json("{\"nodes\": [}").is_err());
+        assert!(Graph::from_json("{} {}").is_err());
+        for root in ["[1, 2, 3]", "42", "\"graph\"", "null"] {
+            assert_eq!("line 1: expected an object", Graph::from_json(root).unwrap_err().to_string());
+        }
+        for number in ["01", "+1", "-.5", "1.", "1e", "0x10"] {
+            let json = format!("{{\"nodes\": [{{\"id\": {}}}]}}", number);
+            assert!(Graph::from_json(&json).is_err(), "{} isn't a JSON number", number);
+        }
+        let e = Graph::<(), u32>::from_json_with("{\"nodes\": [{\"id\": 1}], \"edges\": [{\"source\": 1, \"target\": 1, \"weight\": -1}]}", |_| (), |w| w.parse().ok());
+        assert_eq!("line 1: invalid edge weight \"-1\"", e.unwrap_err().to_string());
+        assert_eq!("line 1: nodes must be a list", Graph::from_json("{\"nodes\": 5}").unwrap_err().to_string());
+        for weight in ["{}", "[]", "true"] {
+            let json = format!("{{\"nodes\": [{{\"id\": 0}}],\n\"edges\": [{{\"source\": 0, \"target\": 0, \"weight\": {}}}]}}", weight);
+            assert_eq!("line 2: edge weight must be a number or string", Graph::from_json(&json).unwrap_err().to_string());
+        }
+        let json = "{\"nodes\": [{\"id\": 0}], \"edges\": [{\"source\": 0, \"target\": 0, \"weight\": null}]}";
+        assert_eq!(vec![""], Graph::from_json(json).unwrap().edges().map(|e| e.data.as_str()).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_unicode_escapes() {
+        // Python's json.dumps escapes every non-ASCII character, with surrogate pairs above U+FFFF
+        let json = r#"{"nodes": [{"id": "caf\u00e9"}, {"id": "\ud83d\ude00"}, {"id": "\u4e2d"}]}"#;
+        let g = Graph::from_json(json).unwrap();
+        assert_eq!(vec!["café", "😀", "中"], g.nodes().map(|(_, n)| n.as_str()).collect::<Vec<_>>());
+        for id in ["\\ud83d", "\\ud83dx", "\\ud83d\\u0041", "\\ude00", "\\u12"] {
+            let json = format!("{{\"nodes\": [{{\"id\": \"{}\"}}]}}", id);
+            assert!(Graph::from_json(&json).is_err(), "{} should be rejected", id);
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn test_serde() {
+        let mut g: Graph<String, f64> = Graph::new_directed();
+        let (a, b, c) = (g.add_node("a".to_string()), g.add_node("b".to_string()), g.add_node("c".to_string()));
+        g.add_edge(a, c, 2.5);
+        g.add_edge(c, a, -1.0);
+        g.remove_node(b);
+        let json = serde_json::to_string(&g).unwrap();
+        let expected = r#"{"directed":true,"nodes":[{"id":0,"label":"a"},{"id":2,"label":"c"}],"edges":[{"source":0,"target":2,"weight":2.5},{"source":2,"target":0,"weight":-1.0}]}"#;
+        assert_eq!(expected, json);
+
+        // Read back with the nodes indexed in order, by serde and by the hand-written reader
+        let h: Graph<String, f64> = serde_json::from_str(&json).unwrap();
+        assert_eq!(vec!["a", "c"], h.nodes().map(|(_, n)| n.as_str()).collect::<Vec<_>>());
+        assert_eq!(vec![(0, 1, 2.5), (1, 0, -1.0)], h.edges().map(|e| (e.source, e.target, *e.data)).collect::<Vec<_>>());
+        let h = Graph::from_json(&json).unwrap();
+        assert_eq!(vec!["2.5", "-1.0"], h.edges().map(|e| e.data.as_str()).collect::<Vec<_>>());
+
+        let links = r#"{"nodes": [{"id": 4, "label": [1, 2]}], "links": [{"source": 4, "target": 4, "weight": null}]}"#;
+        let h: Graph<Vec<u8>, Option<u8>> = serde_json::from_str(links).unwrap();
+        assert!(!h.is_directed());
+        assert_eq!((Some(&vec![1, 2]), 1), (h.node(0), h.edge_count()));
+        let duplicate = r#"{"nodes": [{"id": 0, "label": 1}, {"id": 0, "label": 2}], "edges": []}"#;
+        assert!(serde_json::from_str::<Graph<u8, u8>>(duplicate).unwrap_err().to_string().contains("duplicate node id 0"));
+        let unknown = r#"{"nodes": [], "edges": [{"source": 0, "target": 1, "weight": 1}]}"#;
+        assert!(serde_json::from_str::<Graph<u8, u8>>(unknown).unwrap_err().to_string().contains("unknown node id 0"));
+    }
+
+    #[test]
+    fn test_from_csv() {
+        let csv = "source,target,weight\nToronto, Montreal ,5\n\n\"Ottawa, ON\",Toronto,3\nMontreal,\"Ottawa, ON\"\n";
+        let g = Graph::from_csv(csv, true).unwrap();
+        assert_eq!(vec!["Toronto", "Montreal", "Ottawa, ON"], g.nodes().map(|(_, n)| n.as_str()).collect::<Vec<_>>());
+        let edges: Vec<_> = g.edges().map(|e| (e.source, e.target, e.data.as_str())).collect();
+        assert_eq!(vec![(0, 1, "5"), (2, 0, "3"), (1, 2, "")], edges);
+
+        let e = Graph::<(), f64>::from_csv_with("a,b,1\nb,c,x", false, |_| (), |w| w.parse().ok()).unwrap_err();
+        assert_eq!(2, e.line);
+        assert_eq!(1, Graph::from_csv("a,b,c,d", false).unwrap_err().line);
+
+        // Only a whole header row is skipped, not a first edge from a node named source
+        let g = Graph::from_csv("Source,Target\nsource,sink\n", true).unwrap();
+        assert_eq!(vec!["source", "sink"], g.nodes().map(|(_, n)| n.as_str()).collect::<Vec<_>>());
+        assert_eq!(1, Graph::from_csv("source,sink,2\n", true).unwrap().edge_count());
+    }
+}
@@ -0,0 +1,134 @@
+use std::collections::VecDeque;
+
+use crate::flow_network::{FlowNetwork, MaxFlow};
+use crate::graph::NodeId;
+use crate::shortest_paths::Weight;
+
+/// Edmonds-Karp maximum flow from `s` to `t`
+///
+/// Ford-Fulkerson with a breadth-first search for each augmenting path, so every path is a
+/// shortest one in the residual graph, which bounds the number of augmentations by `O(V * E)` and
+/// the running time by `O(V * E^2)`. Leaves the maximum flow in the network. Starting from a
+/// network that already has flow, like one solved before, only adds to it, and the result's `value`
+/// counts just the flow added.
+///
+/// # Arguments
+///
+/// * `network` - Network to push flow through
+/// * `s` - Source node
+/// * `t` - Sink node
+///
+/// # Panics
+///
+/// If `s` and `t` are the same node
+pub fn edmonds_karp<C: Weight>(network: &mut FlowNetwork<C>, s: NodeId, t: NodeId) -> MaxFlow<C> {
+    assert!(s != t, "source and sink must be different nodes");
+    let mut value = C::zero();
+    let mut parent_arc = vec![usize::MAX; network.node_count()];
+
+    loop {
+        for p in parent_arc.iter_mut() {
+            *p = usize::MAX;
+        }
+        let mut queue = VecDeque::new();
+        queue.push_back(s);
+        while let Some(u) = queue.pop_front() {
+            if u == t {
+                break;
+            }
+            for &a in network.arcs(u) {
+                let v = network.head(a);
+                if v != s && parent_arc[v] == usize::MAX && network.residual(a) > C::zero() {
+                    parent_arc[v] = a;
+                    queue.push_back(v);
+                }
+            }
+        }
+        if parent_arc[t] == usize::MAX {
+            break;
+        }
+
+        // Bottleneck of the path, found by walking back from the sink
+        let mut bottleneck = network.residual(parent_arc[t]);
+        let mut v = network.head(parent_arc[t] ^ 1);
+        while v != s {
+            let a = parent_arc[v];
+            if network.residual(a) < bottleneck {
+                bottleneck = network.residual(a);
+            }
+            v = network.head(a ^ 1);
+        }
+        let mut v = t;
+        while v != s {
+            let a = parent_arc[v];
+            network.push(a, bottleneck);
+            v = network.head(a ^ 1);
+        }
+        value = value + bottleneck;
+    }
+    network.max_flow(s, value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// CLRS Figure 26.1
+    fn clrs() -> FlowNetwork<u32> {
+        let (s, v1, v2, v3, v4, t) = (0, 1, 2, 3, 4, 5);
+        let mut network = FlowNetwork::new(6);
+        for &(u, v, c) in [(s, v1, 16), (s, v2, 13), (v1, v3, 12), (v2, v1, 4), (v2, v4, 14), (v3, v2, 9), (v3, t, 20), (v4, v3, 7), (v4, t, 4)].iter() {
+            network.add_edge(u, v, c);
+        }
+        network
+    }
+
+    #[test]
+    fn test_edmonds_karp() {
+        let mut network = clrs();
+        let flow = edmonds_karp(&mut network, 0, 5);
+        assert_eq!(23, flow.value);
+
+        // Flow is conserved at every inner node, and within capacity on every edge
+        let mut net = vec![0i64; 6];
+        for e in 0..network.edge_count() {
+            let (u, v) = network.endpoints(e);
+            assert!(flow.flows[e] <= network.capacity(e));
+            net[u] -= flow.flows[e] as i64;
+            net[v] += flow.flows[e] as i64;
+        }
+        assert_eq!(vec![-23, 0, 0, 0, 0, 23], net);
+    }
+
+    #[test]
+    fn test_min_cut() {
+        let mut network = clrs();
+        let flow = edmonds_karp(&mut network, 0, 5);
+        assert_eq!(vec![0, 1, 2, 4], flow.source_side);
+        let cut: u32 = flow.cut_edges.iter().map(|&e| network.capacity(e)).sum();
+        assert_eq!(flow.value, cut);
+    }
+
+    #[test]
+    fn test_disconnected() {
+        let mut network = FlowNetwork::new(3);
+        network.add_edge(0, 1, 2.5);
+        let flow = edmonds_karp(&mut network, 0, 2);
+        assert_eq!(0.0, flow.value);
+        assert_eq!(vec![0, 1], flow.source_side);
+        assert!(flow.cut_edges.is_empty());
+    }
+
+    #[test]
+    fn test_antiparallel() {
+        let mut network = FlowNetwork::new(4);
+        network.add_edge(0, 1, 3);
+        network.add_edge(1, 0, 2);
+        network.add_edge(1, 2, 5);
+        network.add_edge(0, 2, 1);
+        network.add_edge(2, 3, 10);
+        assert_eq!(4, edmonds_karp(&mut network, 0, 3).value);
+        // Solving again finds no more flow
+        assert_eq!(0, edmonds_karp(&mut network, 0, 3).value);
+    }
+}
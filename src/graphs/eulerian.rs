@@ -0,0 +1,187 @@
+use crate::dfs::adjacency;
+use crate::graph::{EdgeId, Graph, NodeId};
+
+/// A walk that uses every edge of a graph exactly once
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EulerianTrail {
+    /// Nodes in the order they're visited, one more than the edges, the first and last being the
+    /// same for a circuit
+    pub nodes: Vec<NodeId>,
+    /// Edges in the order they're used, from `nodes[i]` to `nodes[i + 1]`
+    pub edges: Vec<EdgeId>,
+}
+
+/// Finds an Eulerian circuit, a closed walk using every edge exactly once
+///
+/// One exists when every node has as many edges in as out, or an even degree if the graph is
+/// undirected, and all edges are connected. The degrees are checked first, then Hierholzer's
+/// algorithm builds the circuit in `O(V + E)` time. Returns `None` if there's no circuit. A graph
+/// without edges has an empty circuit at its first node, or no nodes at all if it's empty.
+///
+/// # Arguments
+///
+/// * `graph` - Graph to walk
+pub fn eulerian_circuit<N, E>(graph: &Graph<N, E>) -> Option<EulerianTrail> {
+    let balanced = graph.node_ids().all(|u| {
+        if graph.is_directed() {
+            graph.in_degree(u) == graph.out_degree(u)
+        } else {
+            graph.degree(u) & 1 == 0
+        }
+    });
+    if !balanced {
+        return None;
+    }
+    let start = graph.node_ids().find(|&u| graph.degree(u) > 0).or_else(|| graph.node_ids().next());
+    hierholzer(graph, start)
+}
+
+/// Finds an Eulerian path, a walk using every edge exactly once that may end somewhere else
+///
+/// In a directed graph one exists when every node has as many edges in as out, except at most a
+/// start with one more out and an end with one more in. In an undirected graph at most two nodes
+/// may have an odd degree, and the path runs between them. All edges must be connected either way.
+/// Starts at the node with the extra edge out, or the first node with an odd degree, so a graph
+/// with an Eulerian circuit gives that circuit. Takes `O(V + E)` time, and returns `None` if
+/// there's no path.
+///
+/// # Arguments
+///
+/// * `graph` - Graph to walk
+pub fn eulerian_path<N, E>(graph: &Graph<N, E>) -> Option<EulerianTrail> {
+    let mut start = None;
+    let mut ends = 0;
+    for u in graph.node_ids() {
+        if graph.is_directed() {
+            let (out, into) = (graph.out_degree(u), graph.in_degree(u));
+            if out == into + 1 && start.is_none() {
+                start = Some(u);
+            } else if into == out + 1 {
+                ends += 1;
+            } else if out != into {
+                return None;
+            }
+        } else if graph.degree(u) & 1 == 1 {
+            start = start.or(Some(u));
+            ends += 1;
+        }
+    }
+    let feasible = if graph.is_directed() { ends == usize::from(start.is_some()) } else { ends == 0 || ends == 2 };
+    if !feasible {
+        return None;
+    }
+    match start {
+        Some(u) => hierholzer(graph, Some(u)),
+        None => eulerian_circuit(graph),
+    }
+}
+
+/// Internal function for Hierholzer's algorithm from a node whose degrees allow a trail
+///
+/// Follows unused edges until stuck, which can only happen where the trail ends, and backtracks
+/// along the walk, adding each node to the trail as it's left behind and splicing in the detours
+/// found on the way back. The trail comes out in reverse. If edges are left unused, they aren't
+/// connected to the start and there's no trail.
+fn hierholzer<N, E>(graph: &Graph<N, E>, start: Option<NodeId>) -> Option<EulerianTrail> {
+    let start = match start {
+        Some(u) => u,
+        None => return Some(EulerianTrail { nodes: Vec::new(), edges: Vec::new() }),
+    };
+    let adjacency = adjacency(graph);
+    let mut used = vec![false; graph.edge_bound()];
+    let mut next = vec![0; graph.node_bound()];
+    let mut trail = EulerianTrail { nodes: Vec::new(), edges: Vec::new() };
+    let mut stack: Vec<(NodeId, Option<EdgeId>)> = vec![(start, None)];
+
+    while let Some(&(u, e)) = stack.last() {
+        while next[u] < adjacency[u].len() && used[adjacency[u][next[u]].0] {
+            next[u] += 1;
+        }
+        match adjacency[u].get(next[u]) {
+            Some(&(f, v)) => {
+                used[f] = true;
+                stack.push((v, Some(f)));
+            }
+            None => {
+                stack.pop();
+                trail.nodes.push(u);
+                trail.edges.extend(e);
+            }
+        }
+    }
+
+    if trail.edges.len() < graph.edge_count() {
+        return None;
+    }
+    trail.nodes.reverse();
+    trail.edges.reverse();
+    Some(trail)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Internal function to check a trail walks every edge of the graph once
+    fn check<E>(graph: &Graph<(), E>, trail: &EulerianTrail) {
+        assert_eq!(graph.edge_count(), trail.edges.len());
+        assert_eq!(trail.edges.len() + 1, trail.nodes.len());
+        let mut edges = trail.edges.clone();
+        edges.sort_unstable();
+        edges.dedup();
+        assert_eq!(graph.edge_count(), edges.len());
+        for (i, &e) in trail.edges.iter().enumerate() {
+            let (u, v) = graph.endpoints(e).unwrap();
+            let (a, b) = (trail.nodes[i], trail.nodes[i + 1]);
+            assert!((u, v) == (a, b) || (!graph.is_directed() && (v, u) == (a, b)));
+        }
+    }
+
+    #[test]
+    fn test_directed_circuit() {
+        // Two cycles sharing node 0, so the first walk around one needs the other spliced in
+        let edges = vec![(0, 1, ()), (1, 2, ()), (2, 0, ()), (0, 3, ()), (3, 4, ()), (4, 0, ()), (2, 2, ())];
+        let g: Graph<(), ()> = Graph::from_edges(true, 5, edges);
+        let trail = eulerian_circuit(&g).unwrap();
+        check(&g, &trail);
+        assert_eq!((Some(&0), Some(&0)), (trail.nodes.first(), trail.nodes.last()));
+        assert_eq!(trail, eulerian_path(&g).unwrap());
+    }
+
+    #[test]
+    fn test_undirected_path() {
+        // The house of Santa Claus, drawn without lifting the pen from a bottom corner
+        let edges = vec![(0, 1, ()), (1, 2, ()), (2, 3, ()), (3, 0, ()), (0, 2, ()), (1, 3, ()), (2, 4, ()), (3, 4, ())];
+        let g: Graph<(), ()> = Graph::from_edges(false, 5, edges);
+        assert!(eulerian_circuit(&g).is_none());
+        let trail = eulerian_path(&g).unwrap();
+        check(&g, &trail);
+        assert_eq!((Some(&0), Some(&1)), (trail.nodes.first(), trail.nodes.last()));
+    }
+
+    #[test]
+    fn test_directed_path() {
+        let g: Graph<(), ()> = Graph::from_edges(true, 4, vec![(1, 0, ()), (0, 2, ()), (2, 1, ()), (1, 3, ())]);
+        let trail = eulerian_path(&g).unwrap();
+        check(&g, &trail);
+        assert_eq!(vec![1, 0, 2, 1, 3], trail.nodes);
+
+        // Two starts
+        let g: Graph<(), ()> = Graph::from_edges(true, 3, vec![(0, 2, ()), (1, 2, ())]);
+        assert!(eulerian_path(&g).is_none());
+    }
+
+    #[test]
+    fn test_disconnected() {
+        // Degrees are all even, but the edges form two separate cycles
+        let g: Graph<(), ()> = Graph::from_edges(false, 6, vec![(0, 1, ()), (1, 2, ()), (2, 0, ()), (3, 4, ()), (4, 5, ()), (5, 3, ())]);
+        assert!(eulerian_circuit(&g).is_none());
+        assert!(eulerian_path(&g).is_none());
+
+        // Isolated nodes don't matter
+        let g: Graph<(), ()> = Graph::from_edges(false, 4, vec![(1, 2, ()), (2, 1, ())]);
+        assert_eq!(vec![1, 2, 1], eulerian_circuit(&g).unwrap().nodes);
+        let g: Graph<(), ()> = Graph::from_edges(false, 2, vec![]);
+        assert_eq!(vec![0], eulerian_circuit(&g).unwrap().nodes);
+    }
+}
@@ -0,0 +1,224 @@
+use crate::graph::{EdgeId, NodeId};
+use crate::shortest_paths::Weight;
+
+/// A directed graph with a capacity on every edge, for flow algorithms
+///
+/// Each edge is stored as a pair of arcs in the residual graph: arc `2e` from `u` to `v` with the
+/// capacity left, and arc `2e + 1` from `v` to `u` with the flow that can be pushed back. The flow
+/// on an edge is the residual capacity of its reverse arc, so flows never go negative and unsigned
+/// capacities work. Edges also carry a cost per unit of flow, which only minimum cost flows use.
+#[derive(Clone, Debug)]
+pub struct FlowNetwork<C> {
+    arcs: Vec<Vec<usize>>,
+    head: Vec<NodeId>,
+    capacity: Vec<C>,
+    cost: Vec<C>,
+    residual: Vec<C>,
+}
+
+/// Result of a maximum flow algorithm
+#[derive(Clone, Debug, PartialEq)]
+pub struct MaxFlow<C> {
+    /// Total flow from the source to the sink
+    pub value: C,
+    /// Flow on each edge, indexed by `EdgeId`
+    pub flows: Vec<C>,
+    /// Nodes on the source side of a minimum cut, those reachable from the source in the residual graph
+    pub source_side: Vec<NodeId>,
+    /// Edges crossing the minimum cut, whose capacities add up to the flow value
+    pub cut_edges: Vec<EdgeId>,
+}
+
+impl<C: Weight> FlowNetwork<C> {
+
+    /// Creates a new `FlowNetwork` of `n` nodes without edges
+    ///
+    /// # Arguments
+    ///
+    /// * `n` - Number of nodes
+    pub fn new(n: usize) -> FlowNetwork<C> {
+        FlowNetwork { arcs: vec![Vec::new(); n], head: Vec::new(), capacity: Vec::new(), cost: Vec::new(), residual: Vec::new() }
+    }
+
+    /// Number of nodes
+    pub fn node_count(&self) -> usize {
+        self.arcs.len()
+    }
+
+    /// Number of edges
+    pub fn edge_count(&self) -> usize {
+        self.capacity.len()
+    }
+
+    /// Adds a node, and returns its index
+    pub fn add_node(&mut self) -> NodeId {
+        self.arcs.push(Vec::new());
+        self.arcs.len() - 1
+    }
+
+    /// Adds an edge without flow or cost, and returns its index
+    ///
+    /// # Arguments
+    ///
+    /// * `u` - Source node
+    /// * `v` - Target node
+    /// * `capacity` - Most flow the edge can carry
+    ///
+    /// # Panics
+    ///
+    /// If either node doesn't exist, or the capacity is negative
+    pub fn add_edge(&mut self, u: NodeId, v: NodeId, capacity: C) -> EdgeId {
+        self.add_edge_with_cost(u, v, capacity, C::zero())
+    }
+
+    /// Adds an edge without flow, and returns its index
+    ///
+    /// # Arguments
+    ///
+    /// * `u` - Source node
+    /// * `v` - Target node
+    /// * `capacity` - Most flow the edge can carry
+    /// * `cost` - Cost of each unit of flow on the edge, which can be negative
+    ///
+    /// # Panics
+    ///
+    /// If either node doesn't exist, or the capacity is negative
+    pub fn add_edge_with_cost(&mut self, u: NodeId, v: NodeId, capacity: C, cost: C) -> EdgeId {
+        assert!(u < self.arcs.len() && v < self.arcs.len(), "edge endpoints must be nodes in the network");
+        assert!(capacity >= C::zero(), "capacity must not be negative");
+        let e = self.capacity.len();
+        self.arcs[u].push(2 * e);
+        self.arcs[v].push(2 * e + 1);
+        self.head.push(v);
+        self.head.push(u);
+        self.residual.push(capacity);
+        self.residual.push(C::zero());
+        self.capacity.push(capacity);
+        self.cost.push(cost);
+        e
+    }
+
+    /// Source and target nodes of an edge
+    ///
+    /// # Arguments
+    ///
+    /// * `e` - Edge to look up
+    pub fn endpoints(&self, e: EdgeId) -> (NodeId, NodeId) {
+        (self.head[2 * e + 1], self.head[2 * e])
+    }
+
+    /// Capacity of an edge
+    ///
+    /// # Arguments
+    ///
+    /// * `e` - Edge to look up
+    pub fn capacity(&self, e: EdgeId) -> C {
+        self.capacity[e]
+    }
+
+    /// Cost per unit of flow on an edge
+    ///
+    /// # Arguments
+    ///
+    /// * `e` - Edge to look up
+    pub fn cost(&self, e: EdgeId) -> C {
+        self.cost[e]
+    }
+
+    /// Flow on an edge
+    ///
+    /// # Arguments
+    ///
+    /// * `e` - Edge to look up
+    pub fn flow(&self, e: EdgeId) -> C {
+        self.residual[2 * e + 1]
+    }
+
+    /// Removes all flow, so the network can be solved again
+    pub fn reset_flow(&mut self) {
+        for (e, &c) in self.capacity.iter().enumerate() {
+            self.residual[2 * e] = c;
+            self.residual[2 * e + 1] = C::zero();
+        }
+    }
+
+    /// Arcs leaving a node in the residual graph
+    pub(crate) fn arcs(&self, u: NodeId) -> &[usize] {
+        &self.arcs[u]
+    }
+
+    /// Node an arc points to
+    pub(crate) fn head(&self, a: usize) -> NodeId {
+        self.head[a]
+    }
+
+    /// Capacity left on an arc
+    pub(crate) fn residual(&self, a: usize) -> C {
+        self.residual[a]
+    }
+
+    /// Pushes flow along an arc, freeing the same amount on its reverse
+    pub(crate) fn push(&mut self, a: usize, amount: C) {
+        self.residual[a] = self.residual[a] - amount;
+        self.residual[a ^ 1] = self.residual[a ^ 1] + amount;
+    }
+
+    /// Nodes reachable from `s` through arcs with capacity left
+    pub(crate) fn reachable(&self, s: NodeId) -> Vec<bool> {
+        let mut seen = vec![false; self.node_count()];
+        seen[s] = true;
+        let mut stack = vec![s];
+        while let Some(u) = stack.pop() {
+            for &a in self.arcs[u].iter() {
+                let v = self.head[a];
+                if !seen[v] && self.residual[a] > C::zero() {
+                    seen[v] = true;
+                    stack.push(v);
+                }
+            }
+        }
+        seen
+    }
+
+    /// Collects the flows and minimum cut of a maximum flow from `s`
+    pub(crate) fn max_flow(&self, s: NodeId, value: C) -> MaxFlow<C> {
+        let seen = self.reachable(s);
+        let cut_edges = (0..self.edge_count())
+            .filter(|&e| {
+                let (u, v) = self.endpoints(e);
+                seen[u] && !seen[v]
+            })
+            .collect();
+        MaxFlow {
+            value,
+            flows: (0..self.edge_count()).map(|e| self.flow(e)).collect(),
+            source_side: (0..self.node_count()).filter(|&u| seen[u]).collect(),
+            cut_edges,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_edges() {
+        let mut network = FlowNetwork::new(2);
+        let c = network.add_node();
+        let e = network.add_edge(0, c, 5u32);
+        network.add_edge(c, 1, 3);
+        assert_eq!((3, 2), (network.node_count(), network.edge_count()));
+        assert_eq!((0, 2), network.endpoints(e));
+        assert_eq!(5, network.capacity(e));
+
+        network.push(2 * e, 3);
+        network.push(2, 3);
+        assert_eq!(3, network.flow(e));
+        assert_eq!(2, network.residual(2 * e));
+        assert_eq!(3, network.residual(2 * e + 1));
+        assert_eq!(vec![true, false, true], network.reachable(0));
+        network.reset_flow();
+        assert_eq!(0, network.flow(e));
+    }
+}
@@ -0,0 +1,113 @@
+use crate::graph::Graph;
+use crate::rng::Rng;
+
+impl<N: Default, E: Default> Graph<N, E> {
+
+    /// Builds an undirected `w x h` grid, with node `(x, y)` at index `y * w + x` joined to its
+    /// right and lower neighbours
+    ///
+    /// # Arguments
+    ///
+    /// * `w` - Number of columns
+    /// * `h` - Number of rows
+    pub fn grid(w: usize, h: usize) -> Graph<N, E> {
+        let mut edges = Vec::new();
+        for y in 0..h {
+            for x in 0..w {
+                if x + 1 < w {
+                    edges.push((y * w + x, y * w + x + 1, E::default()));
+                }
+                if y + 1 < h {
+                    edges.push((y * w + x, (y + 1) * w + x, E::default()));
+                }
+            }
+        }
+        Graph::from_edges(false, w * h, edges)
+    }
+
+    /// Builds an undirected complete graph, with an edge between every pair of the `n` nodes
+    ///
+    /// # Arguments
+    ///
+    /// * `n` - Number of nodes
+    pub fn complete(n: usize) -> Graph<N, E> {
+        Graph::from_edges(false, n, (0..n).flat_map(|u| (u + 1..n).map(move |v| (u, v, E::default()))))
+    }
+
+    /// Builds an undirected path `0 - 1 - ... - (n - 1)`
+    ///
+    /// # Arguments
+    ///
+    /// * `n` - Number of nodes
+    pub fn path(n: usize) -> Graph<N, E> {
+        Graph::from_edges(false, n, (1..n).map(|v| (v - 1, v, E::default())))
+    }
+
+    /// Builds an undirected cycle `0 - 1 - ... - (n - 1) - 0`
+    ///
+    /// A cycle of one node is a self-loop, and of two nodes a pair of parallel edges.
+    ///
+    /// # Arguments
+    ///
+    /// * `n` - Number of nodes
+    pub fn cycle(n: usize) -> Graph<N, E> {
+        let mut graph = Graph::path(n);
+        if n > 0 {
+            graph.add_edge(n - 1, 0, E::default());
+        }
+        graph
+    }
+
+    /// Builds a random undirected tree, where each node after the first is joined to a uniformly
+    /// random earlier node
+    ///
+    /// The same seed always builds the same tree. Trees built this way have an expected depth of
+    /// `O(log(n))`.
+    ///
+    /// # Arguments
+    ///
+    /// * `n` - Number of nodes
+    /// * `seed` - Seed for the random choices
+    pub fn random_tree(n: usize, seed: u64) -> Graph<N, E> {
+        let mut rng = Rng::new(seed);
+        Graph::from_edges(false, n, (1..n).map(|v| (rng.gen_range(v as u64) as usize, v, E::default())))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::connectivity::connected_components;
+
+    #[test]
+    fn test_grid() {
+        let g: Graph<(), ()> = Graph::grid(4, 3);
+        assert_eq!((12, 3 * 3 + 4 * 2), (g.node_count(), g.edge_count()));
+        assert_eq!(vec![1, 4], g.neighbors(0).collect::<Vec<_>>());
+        assert_eq!(4, g.degree(5));
+        assert_eq!(0, Graph::<(), ()>::grid(0, 5).node_count());
+    }
+
+    #[test]
+    fn test_complete_path_cycle() {
+        let g: Graph<(), u32> = Graph::complete(5);
+        assert_eq!(10, g.edge_count());
+        assert!(g.node_ids().all(|u| g.degree(u) == 4));
+
+        let g: Graph<(), ()> = Graph::path(4);
+        assert_eq!(vec![(0, 1), (1, 2), (2, 3)], g.edges().map(|e| (e.source, e.target)).collect::<Vec<_>>());
+        let g: Graph<(), ()> = Graph::cycle(4);
+        assert!(g.node_ids().all(|u| g.degree(u) == 2));
+        assert_eq!(0, Graph::<(), ()>::cycle(0).edge_count());
+    }
+
+    #[test]
+    fn test_random_tree() {
+        let g: Graph<(), ()> = Graph::random_tree(200, 7);
+        assert_eq!(199, g.edge_count());
+        assert_eq!(1, connected_components(&g).len());
+        let h: Graph<(), ()> = Graph::random_tree(200, 7);
+        assert_eq!(g.to_dot(), h.to_dot());
+        assert_ne!(g.to_dot(), Graph::<(), ()>::random_tree(200, 8).to_dot());
+    }
+}
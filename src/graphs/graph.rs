@@ -0,0 +1,559 @@
+/// Index of a node in a `Graph`
+///
+/// Node indexes are stable, removing a node never changes the index of another node.
+pub type NodeId = usize;
+
+/// Index of an edge in a `Graph`
+///
+/// Edge indexes are stable, removing an edge never changes the index of another edge.
+pub type EdgeId = usize;
+
+/// Internal node storage, the node's data and the edges at the node
+#[derive(Clone, Debug)]
+struct Node<N> {
+    data: N,
+    outgoing: Vec<EdgeId>,
+    incoming: Vec<EdgeId>,
+}
+
+/// Internal edge storage, the edge's endpoints and data
+#[derive(Clone, Debug)]
+struct Edge<E> {
+    source: NodeId,
+    target: NodeId,
+    data: E,
+}
+
+/// A reference to an edge and its endpoints
+///
+/// Edges returned for a node are oriented away from it, so `source` is the node the edges were
+/// asked for, even for an undirected edge that was added the other way around.
+#[derive(Debug, PartialEq)]
+pub struct EdgeRef<'a, E> {
+    pub id: EdgeId,
+    pub source: NodeId,
+    pub target: NodeId,
+    pub data: &'a E,
+}
+
+impl<'a, E> Clone for EdgeRef<'a, E> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'a, E> Copy for EdgeRef<'a, E> {}
+
+/// A directed or undirected Graph using adjacency lists
+///
+/// Nodes carry data of type `N` and edges data of type `E`, e.g. a weight. Each node keeps the
+/// list of its edges, so neighbours can be iterated in `O(deg(v))` time and the graph uses
+/// `O(V + E)` space. Parallel edges and self-loops are allowed.
+///
+/// Removed nodes and edges leave an empty slot behind so the indexes of the others are stable,
+/// and algorithms can size per-node arrays with `node_bound`.
+#[derive(Clone, Debug)]
+pub struct Graph<N, E> {
+    nodes: Vec<Option<Node<N>>>,
+    edges: Vec<Option<Edge<E>>>,
+    node_count: usize,
+    edge_count: usize,
+    directed: bool,
+}
+
+impl<N, E> Graph<N, E> {
+
+    /// Creates a new empty directed `Graph`
+    pub fn new_directed() -> Graph<N, E> {
+        Graph::new(true)
+    }
+
+    /// Creates a new empty undirected `Graph`
+    pub fn new_undirected() -> Graph<N, E> {
+        Graph::new(false)
+    }
+
+    /// Creates a new empty `Graph`
+    ///
+    /// # Arguments
+    ///
+    /// * `directed` - If the edges of the graph are directed
+    pub fn new(directed: bool) -> Graph<N, E> {
+        Graph { nodes: Vec::new(), edges: Vec::new(), node_count: 0, edge_count: 0, directed }
+    }
+
+    /// Creates a new `Graph` with nodes `0..n` and a list of edges
+    ///
+    /// # Arguments
+    ///
+    /// * `directed` - If the edges of the graph are directed
+    /// * `n` - Number of nodes, each with the default data
+    /// * `edges` - Edges as `(source, target, data)`
+    pub fn from_edges<I: IntoIterator<Item = (NodeId, NodeId, E)>>(directed: bool, n: usize, edges: I) -> Graph<N, E>
+    where
+        N: Default,
+    {
+        let mut graph = Graph::new(directed);
+        for _ in 0..n {
+            graph.add_node(N::default());
+        }
+        for (u, v, e) in edges {
+            graph.add_edge(u, v, e);
+        }
+        graph
+    }
+
+    /// Checks if the edges of the graph are directed
+    pub fn is_directed(&self) -> bool {
+        self.directed
+    }
+
+    /// Number of nodes
+    pub fn node_count(&self) -> usize {
+        self.node_count
+    }
+
+    /// Number of edges
+    pub fn edge_count(&self) -> usize {
+        self.edge_count
+    }
+
+    /// Upper bound on the node indexes, every node index is less than it
+    pub fn node_bound(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Upper bound on the edge indexes, every edge index is less than it
+    pub fn edge_bound(&self) -> usize {
+        self.edges.len()
+    }
+
+    /// Adds a node, and returns its index
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - Data of the node
+    pub fn add_node(&mut self, data: N) -> NodeId {
+        self.nodes.push(Some(Node { data, outgoing: Vec::new(), incoming: Vec::new() }));
+        self.node_count += 1;
+        self.nodes.len() - 1
+    }
+
+    /// Removes a node and every edge at it, and returns the node's data
+    ///
+    /// Returns None if the node doesn't exist.
+    ///
+    /// # Arguments
+    ///
+    /// * `u` - Node to remove
+    pub fn remove_node(&mut self, u: NodeId) -> Option<N> {
+        let node = self.nodes.get(u)?.as_ref()?;
+        let mut incident: Vec<EdgeId> = node.outgoing.iter().chain(node.incoming.iter()).copied().collect();
+        incident.sort_unstable();
+        incident.dedup();
+        for e in incident {
+            self.remove_edge(e);
+        }
+        self.node_count -= 1;
+        self.nodes[u].take().map(|n| n.data)
+    }
+
+    /// Adds an edge, and returns its index
+    ///
+    /// # Arguments
+    ///
+    /// * `u` - Source node
+    /// * `v` - Target node
+    /// * `data` - Data of the edge
+    ///
+    /// # Panics
+    ///
+    /// If either node doesn't exist
+    pub fn add_edge(&mut self, u: NodeId, v: NodeId, data: E) -> EdgeId {
+        assert!(self.contains_node(u) && self.contains_node(v), "edge endpoints must be nodes in the graph");
+        let e = self.edges.len();
+        self.edges.push(Some(Edge { source: u, target: v, data }));
+        self.edge_count += 1;
+
+        self.node_entry(u).outgoing.push(e);
+        if self.directed {
+            self.node_entry(v).incoming.push(e);
+        } else {
+            // An undirected self-loop is listed twice, so it adds 2 to the node's degree
+            self.node_entry(v).outgoing.push(e);
+        }
+        e
+    }
+
+    /// Removes an edge, and returns its data
+    ///
+    /// Returns None if the edge doesn't exist.
+    ///
+    /// # Arguments
+    ///
+    /// * `e` - Edge to remove
+    pub fn remove_edge(&mut self, e: EdgeId) -> Option<E> {
+        let edge = self.edges.get_mut(e)?.take()?;
+        self.edge_count -= 1;
+        self.node_entry(edge.source).outgoing.retain(|&x| x != e);
+        if self.directed {
+            self.node_entry(edge.target).incoming.retain(|&x| x != e);
+        } else {
+            self.node_entry(edge.target).outgoing.retain(|&x| x != e);
+        }
+        Some(edge.data)
+    }
+
+    /// Checks if a node exists
+    ///
+    /// # Arguments
+    ///
+    /// * `u` - Node to look for
+    pub fn contains_node(&self, u: NodeId) -> bool {
+        matches!(self.nodes.get(u), Some(Some(_)))
+    }
+
+    /// Checks if an edge exists
+    ///
+    /// # Arguments
+    ///
+    /// * `e` - Edge to look for
+    pub fn contains_edge(&self, e: EdgeId) -> bool {
+        matches!(self.edges.get(e), Some(Some(_)))
+    }
+
+    /// Gets the data of a node
+    ///
+    /// # Arguments
+    ///
+    /// * `u` - Node to look up
+    pub fn node(&self, u: NodeId) -> Option<&N> {
+        self.nodes.get(u)?.as_ref().map(|n| &n.data)
+    }
+
+    /// Gets the mutable data of a node
+    ///
+    /// # Arguments
+    ///
+    /// * `u` - Node to look up
+    pub fn node_mut(&mut self, u: NodeId) -> Option<&mut N> {
+        self.nodes.get_mut(u)?.as_mut().map(|n| &mut n.data)
+    }
+
+    /// Gets an edge, as it was added
+    ///
+    /// # Arguments
+    ///
+    /// * `e` - Edge to look up
+    pub fn edge(&self, e: EdgeId) -> Option<EdgeRef<'_, E>> {
+        self.edges.get(e)?.as_ref().map(|edge| EdgeRef { id: e, source: edge.source, target: edge.target, data: &edge.data })
+    }
+
+    /// Gets the mutable data of an edge
+    ///
+    /// # Arguments
+    ///
+    /// * `e` - Edge to look up
+    pub fn edge_mut(&mut self, e: EdgeId) -> Option<&mut E> {
+        self.edges.get_mut(e)?.as_mut().map(|edge| &mut edge.data)
+    }
+
+    /// Gets the `(source, target)` endpoints of an edge
+    ///
+    /// # Arguments
+    ///
+    /// * `e` - Edge to look up
+    pub fn endpoints(&self, e: EdgeId) -> Option<(NodeId, NodeId)> {
+        self.edges.get(e)?.as_ref().map(|edge| (edge.source, edge.target))
+    }
+
+    /// Finds an edge from `u` to `v`, in `O(deg(u))` time
+    ///
+    /// In an undirected graph, the edge can have been added in either direction.
+    ///
+    /// # Arguments
+    ///
+    /// * `u` - Source node
+    /// * `v` - Target node
+    pub fn find_edge(&self, u: NodeId, v: NodeId) -> Option<EdgeId> {
+        if !self.contains_node(u) {
+            return None;
+        }
+        self.edges_from(u).find(|e| e.target == v).map(|e| e.id)
+    }
+
+    /// Returns an iterator over the node indexes, in ascending order
+    pub fn node_ids(&self) -> impl Iterator<Item = NodeId> + '_ {
+        self.nodes.iter().enumerate().filter(|(_, n)| n.is_some()).map(|(u, _)| u)
+    }
+
+    /// Returns an iterator over the nodes and their data, in ascending order
+    pub fn nodes(&self) -> impl Iterator<Item = (NodeId, &N)> {
+        self.nodes.iter().enumerate().filter_map(|(u, n)| n.as_ref().map(|n| (u, &n.data)))
+    }
+
+    /// Returns an iterator over every edge once, as it was added, in ascending order
+    pub fn edges(&self) -> impl Iterator<Item = EdgeRef<'_, E>> {
+        self.edges.iter().enumerate().filter_map(|(e, edge)| {
+            edge.as_ref().map(|edge| EdgeRef { id: e, source: edge.source, target: edge.target, data: &edge.data })
+        })
+    }
+
+    /// Returns an iterator over the edges leaving `u`, oriented away from `u`
+    ///
+    /// In an undirected graph, these are all the edges at `u`.
+    ///
+    /// # Arguments
+    ///
+    /// * `u` - Node to get the edges of
+    ///
+    /// # Panics
+    ///
+    /// If the node doesn't exist
+    pub fn edges_from(&self, u: NodeId) -> impl Iterator<Item = EdgeRef<'_, E>> {
+        self.incident(u, &self.node_ref(u).outgoing)
+    }
+
+    /// Returns an iterator over the edges entering `u`, oriented toward `u`
+    ///
+    /// In an undirected graph, these are all the edges at `u`.
+    ///
+    /// # Arguments
+    ///
+    /// * `u` - Node to get the edges of
+    ///
+    /// # Panics
+    ///
+    /// If the node doesn't exist
+    pub fn edges_to(&self, u: NodeId) -> impl Iterator<Item = EdgeRef<'_, E>> {
+        let list = if self.directed { &self.node_ref(u).incoming } else { &self.node_ref(u).outgoing };
+        self.incident(u, list).map(|e| EdgeRef { id: e.id, source: e.target, target: e.source, data: e.data })
+    }
+
+    /// Returns an iterator over the nodes `u` has an edge to
+    ///
+    /// A node appears once per edge, so parallel edges repeat it.
+    ///
+    /// # Arguments
+    ///
+    /// * `u` - Node to get the neighbours of
+    ///
+    /// # Panics
+    ///
+    /// If the node doesn't exist
+    pub fn neighbors(&self, u: NodeId) -> impl Iterator<Item = NodeId> + '_ {
+        self.edges_from(u).map(|e| e.target)
+    }
+
+    /// Returns an iterator over the nodes that have an edge to `u`
+    ///
+    /// # Arguments
+    ///
+    /// * `u` - Node to get the predecessors of
+    ///
+    /// # Panics
+    ///
+    /// If the node doesn't exist
+    pub fn predecessors(&self, u: NodeId) -> impl Iterator<Item = NodeId> + '_ {
+        self.edges_to(u).map(|e| e.source)
+    }
+
+    /// Number of edges leaving `u`
+    ///
+    /// # Arguments
+    ///
+    /// * `u` - Node to get the degree of
+    pub fn out_degree(&self, u: NodeId) -> usize {
+        self.node_ref(u).outgoing.len()
+    }
+
+    /// Number of edges entering `u`
+    ///
+    /// # Arguments
+    ///
+    /// * `u` - Node to get the degree of
+    pub fn in_degree(&self, u: NodeId) -> usize {
+        if self.directed {
+            self.node_ref(u).incoming.len()
+        } else {
+            self.node_ref(u).outgoing.len()
+        }
+    }
+
+    /// Number of edges at `u`
+    ///
+    /// For a directed graph this is the in-degree plus the out-degree. Self-loops count twice.
+    ///
+    /// # Arguments
+    ///
+    /// * `u` - Node to get the degree of
+    pub fn degree(&self, u: NodeId) -> usize {
+        let node = self.node_ref(u);
+        node.outgoing.len() + node.incoming.len()
+    }
+
+    /// Returns the transpose of the graph, with every edge reversed
+    ///
+    /// Nodes and edges keep their indexes, so no remapping is needed. An undirected graph is its
+    /// own transpose.
+    pub fn reverse(&self) -> Graph<N, E>
+    where
+        N: Clone,
+        E: Clone,
+    {
+        let mut graph = self.clone();
+        if graph.directed {
+            for edge in graph.edges.iter_mut().flatten() {
+                std::mem::swap(&mut edge.source, &mut edge.target);
+            }
+            for node in graph.nodes.iter_mut().flatten() {
+                std::mem::swap(&mut node.outgoing, &mut node.incoming);
+            }
+        }
+        graph
+    }
+
+    /// Internal function to get a node, panicking if it doesn't exist
+    fn node_ref(&self, u: NodeId) -> &Node<N> {
+        match self.nodes.get(u) {
+            Some(Some(node)) => node,
+            _ => panic!("node {} is not in the graph", u),
+        }
+    }
+
+    /// Internal function to get a mutable node, panicking if it doesn't exist
+    fn node_entry(&mut self, u: NodeId) -> &mut Node<N> {
+        match self.nodes.get_mut(u) {
+            Some(Some(node)) => node,
+            _ => panic!("node {} is not in the graph", u),
+        }
+    }
+
+    /// Internal function to orient a list of edges away from `u`
+    fn incident<'a>(&'a self, u: NodeId, list: &'a [EdgeId]) -> impl Iterator<Item = EdgeRef<'a, E>> {
+        list.iter().map(move |&e| {
+            let edge = self.edges[e].as_ref().unwrap();
+            let target = if edge.source == u { edge.target } else { edge.source };
+            EdgeRef { id: e, source: u, target, data: &edge.data }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_directed() {
+        let mut g = Graph::new_directed();
+        let a = g.add_node("a");
+        let b = g.add_node("b");
+        let c = g.add_node("c");
+        g.add_edge(a, b, 1);
+        g.add_edge(a, c, 2);
+        g.add_edge(c, b, 3);
+
+        assert!(g.is_directed());
+        assert_eq!(3, g.node_count());
+        assert_eq!(3, g.edge_count());
+        assert_eq!(vec![b, c], g.neighbors(a).collect::<Vec<_>>());
+        assert_eq!(0, g.neighbors(b).count());
+        assert_eq!(vec![a, c], g.predecessors(b).collect::<Vec<_>>());
+        assert_eq!(2, g.out_degree(a));
+        assert_eq!(2, g.in_degree(b));
+        assert_eq!(2, g.degree(c));
+        assert_eq!(Some(&"c"), g.node(c));
+        assert_eq!(Some(1), g.find_edge(a, c));
+        assert_eq!(None, g.find_edge(c, a));
+    }
+
+    #[test]
+    fn test_undirected() {
+        let mut g: Graph<(), u32> = Graph::from_edges(false, 3, vec![(0, 1, 5), (2, 1, 7)]);
+        assert!(!g.is_directed());
+        assert_eq!(vec![0, 2], g.neighbors(1).collect::<Vec<_>>());
+        assert_eq!(vec![1], g.neighbors(2).collect::<Vec<_>>());
+        assert_eq!(2, g.degree(1));
+        assert_eq!(2, g.in_degree(1));
+        assert_eq!(Some(1), g.find_edge(1, 2));
+
+        let e = g.edges_from(2).next().unwrap();
+        assert_eq!((2, 1, &7), (e.source, e.target, e.data));
+        let e = g.edges_to(2).next().unwrap();
+        assert_eq!((1, 2), (e.source, e.target));
+
+        *g.edge_mut(0).unwrap() = 6;
+        assert_eq!(Some(&6), g.edge(0).map(|e| e.data));
+    }
+
+    #[test]
+    fn test_self_loop() {
+        let mut g: Graph<(), ()> = Graph::from_edges(false, 1, vec![(0, 0, ())]);
+        assert_eq!(2, g.degree(0));
+        assert_eq!(vec![0, 0], g.neighbors(0).collect::<Vec<_>>());
+        g.remove_edge(0);
+        assert_eq!(0, g.degree(0));
+
+        let g: Graph<(), ()> = Graph::from_edges(true, 1, vec![(0, 0, ())]);
+        assert_eq!(1, g.out_degree(0));
+        assert_eq!(1, g.in_degree(0));
+        assert_eq!(2, g.degree(0));
+    }
+
+    #[test]
+    fn test_remove_edge() {
+        let mut g: Graph<(), char> = Graph::from_edges(true, 2, vec![(0, 1, 'x'), (0, 1, 'y')]);
+        assert_eq!(Some('x'), g.remove_edge(0));
+        assert_eq!(None, g.remove_edge(0));
+        assert!(!g.contains_edge(0));
+        assert_eq!(1, g.edge_count());
+        assert_eq!(2, g.edge_bound());
+        assert_eq!(vec![1], g.edges().map(|e| e.id).collect::<Vec<_>>());
+        assert_eq!(1, g.in_degree(1));
+    }
+
+    #[test]
+    fn test_remove_node() {
+        let mut g: Graph<u32, ()> = Graph::new_undirected();
+        for i in 0..4 {
+            g.add_node(i * 10);
+        }
+        g.add_edge(0, 1, ());
+        g.add_edge(1, 2, ());
+        g.add_edge(2, 3, ());
+        g.add_edge(1, 1, ());
+
+        assert_eq!(Some(10), g.remove_node(1));
+        assert_eq!(None, g.remove_node(1));
+        assert!(!g.contains_node(1));
+        assert_eq!(3, g.node_count());
+        assert_eq!(1, g.edge_count());
+        assert_eq!(0, g.degree(0));
+        assert_eq!(vec![3], g.neighbors(2).collect::<Vec<_>>());
+
+        // Indexes of the other nodes are stable
+        assert_eq!(vec![0, 2, 3], g.node_ids().collect::<Vec<_>>());
+        assert_eq!(4, g.node_bound());
+        assert_eq!(4, g.add_node(40));
+    }
+
+    #[test]
+    fn test_reverse() {
+        let mut g: Graph<(), char> = Graph::from_edges(true, 3, vec![(0, 1, 'x'), (1, 2, 'y'), (0, 2, 'z')]);
+        g.remove_edge(0);
+        let r = g.reverse();
+        assert_eq!(vec![(2, 1, 'y'), (2, 0, 'z')], r.edges().map(|e| (e.source, e.target, *e.data)).collect::<Vec<_>>());
+        assert_eq!(vec![1, 0], r.neighbors(2).collect::<Vec<_>>());
+        assert_eq!(0, r.out_degree(0));
+        assert_eq!(3, r.edge_bound());
+        assert_eq!(None, r.find_edge(0, 2));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_add_edge_missing_node() {
+        let mut g: Graph<(), ()> = Graph::new_directed();
+        g.add_node(());
+        g.add_edge(0, 1, ());
+    }
+}
@@ -0,0 +1,254 @@
+use std::collections::VecDeque;
+
+use crate::bipartite::{BipartiteGraph, Side};
+use crate::graph::NodeId;
+
+const UNREACHED: usize = usize::MAX;
+
+/// A maximum matching of a bipartite graph, with a minimum vertex cover of the same size
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BipartiteMatching {
+    /// Matched edges as pairs of a left and a right node, in order of the left node
+    pub pairs: Vec<(NodeId, NodeId)>,
+    /// Node each node is matched to, if any
+    pub mate: Vec<Option<NodeId>>,
+    /// Nodes that touch every edge, in increasing order
+    pub vertex_cover: Vec<NodeId>,
+}
+
+/// Hopcroft-Karp maximum bipartite matching
+///
+/// Works in phases: a breadth-first search from every unmatched left node finds the length of the
+/// shortest augmenting paths, alternating between unmatched and matched edges, then a depth-first
+/// search augments along as many node disjoint paths of that length as it can. There are
+/// `O(sqrt(V))` phases, for `O(E * sqrt(V))` time in total.
+///
+/// The minimum vertex cover comes from König's theorem: with `Z` the nodes reachable from unmatched
+/// left nodes by alternating paths, the left nodes outside `Z` and the right nodes in `Z` cover
+/// every edge, one node per matched edge.
+///
+/// # Arguments
+///
+/// * `graph` - Graph to match
+pub fn hopcroft_karp(graph: &BipartiteGraph) -> BipartiteMatching {
+    let n = graph.node_count();
+    let mut mate = vec![None; n];
+    let mut level = vec![UNREACHED; n];
+    let mut next = vec![0; n];
+    let left: Vec<NodeId> = graph.left_nodes().collect();
+
+    while let Some(limit) = layers(graph, &left, &mate, &mut level) {
+        for i in next.iter_mut() {
+            *i = 0;
+        }
+        for &root in left.iter() {
+            if mate[root].is_none() {
+                augment(graph, root, limit, &mut mate, &mut level, &mut next);
+            }
+        }
+    }
+
+    let pairs = left.iter().filter_map(|&u| mate[u].map(|v| (u, v))).collect();
+    let vertex_cover = konig(graph, &left, &mate);
+    BipartiteMatching { pairs, mate, vertex_cover }
+}
+
+/// Internal function to label left nodes with their alternating distance from an unmatched one
+///
+/// Stops at the level of the first left node next to an unmatched right node, and returns that
+/// level, or None if no augmenting path exists. Left nodes past it are left unreached, so the
+/// phase only augments along shortest paths.
+fn layers(graph: &BipartiteGraph, left: &[NodeId], mate: &[Option<NodeId>], level: &mut [usize]) -> Option<usize> {
+    let mut queue = VecDeque::new();
+    for &u in left.iter() {
+        if mate[u].is_none() {
+            level[u] = 0;
+            queue.push_back(u);
+        } else {
+            level[u] = UNREACHED;
+        }
+    }
+    let mut limit = None;
+    while let Some(u) = queue.pop_front() {
+        // The queue is in order of level, so the rest are too far
+        if limit.is_some_and(|l| level[u] > l) {
+            break;
+        }
+        for &v in graph.neighbors(u) {
+            match mate[v] {
+                None => limit = Some(level[u]),
+                Some(w) if level[w] == UNREACHED => {
+                    level[w] = level[u] + 1;
+                    queue.push_back(w);
+                }
+                Some(_) => {}
+            }
+        }
+    }
+    if let Some(l) = limit {
+        for &u in left.iter() {
+            if level[u] != UNREACHED && level[u] > l {
+                level[u] = UNREACHED;
+            }
+        }
+    }
+    limit
+}
+
+/// Internal function to search the layers for an augmenting path from `root`, and flip it
+///
+/// Iterative, with the left nodes of the path on a stack and the right nodes between them beside
+/// it. Only left nodes at level `limit` end a path at an unmatched right node, so every path is a
+/// shortest one. Left nodes that lead nowhere are taken out of the layers for the rest of the
+/// phase.
+fn augment(graph: &BipartiteGraph, root: NodeId, limit: usize, mate: &mut [Option<NodeId>], level: &mut [usize], next: &mut [usize]) {
+    let mut stack = vec![root];
+    let mut via = Vec::new();
+    while let Some(&u) = stack.last() {
+        if next[u] == graph.neighbors(u).len() {
+            level[u] = UNREACHED;
+            stack.pop();
+            via.pop();
+            continue;
+        }
+        let v = graph.neighbors(u)[next[u]];
+        next[u] += 1;
+        match mate[v] {
+            None if level[u] == limit => {
+                via.push(v);
+                for (&l, &r) in stack.iter().zip(via.iter()) {
+                    mate[l] = Some(r);
+                    mate[r] = Some(l);
+                }
+                return;
+            }
+            Some(w) if level[w] == level[u] + 1 => {
+                stack.push(w);
+                via.push(v);
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Internal function to build a minimum vertex cover from a maximum matching by König's theorem
+fn konig(graph: &BipartiteGraph, left: &[NodeId], mate: &[Option<NodeId>]) -> Vec<NodeId> {
+    let mut reached = vec![false; graph.node_count()];
+    let mut stack: Vec<NodeId> = left.iter().copied().filter(|&u| mate[u].is_none()).collect();
+    for &u in stack.iter() {
+        reached[u] = true;
+    }
+    while let Some(u) = stack.pop() {
+        for &v in graph.neighbors(u) {
+            if !reached[v] {
+                reached[v] = true;
+                // A reachable right node is matched, or the matching wasn't maximum
+                let w = mate[v].unwrap();
+                if !reached[w] {
+                    reached[w] = true;
+                    stack.push(w);
+                }
+            }
+        }
+    }
+    (0..graph.node_count())
+        .filter(|&u| match graph.side(u) {
+            Side::Left => !reached[u],
+            Side::Right => reached[u],
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dinic::dinic;
+    use crate::flow_network::FlowNetwork;
+    use crate::rng::Rng;
+
+    /// Internal function to check a matching and cover against the graph
+    fn check(graph: &BipartiteGraph, matching: &BipartiteMatching) {
+        assert_eq!(matching.pairs.len(), matching.vertex_cover.len());
+        let mut in_cover = vec![false; graph.node_count()];
+        for &u in matching.vertex_cover.iter() {
+            in_cover[u] = true;
+        }
+        for u in graph.left_nodes() {
+            for &v in graph.neighbors(u) {
+                assert!(in_cover[u] || in_cover[v]);
+            }
+        }
+        for &(u, v) in matching.pairs.iter() {
+            assert!(graph.neighbors(u).contains(&v));
+            assert_eq!((Some(v), Some(u)), (matching.mate[u], matching.mate[v]));
+        }
+    }
+
+    #[test]
+    fn test_hopcroft_karp() {
+        // Greedily matching 0 to 3 first needs an augmenting path to fix
+        let graph = BipartiteGraph::from_edges(
+            vec![Side::Left, Side::Left, Side::Left, Side::Right, Side::Right, Side::Right],
+            vec![(0, 3), (0, 4), (1, 3), (2, 4), (2, 5)],
+        )
+        .unwrap();
+        let matching = hopcroft_karp(&graph);
+        assert_eq!(vec![(0, 4), (1, 3), (2, 5)], matching.pairs);
+        check(&graph, &matching);
+    }
+
+    #[test]
+    fn test_layers() {
+        // 0 is next to the unmatched 4, so the layers stop at 0 and don't go on through 3 to 1
+        // and 5 to 2, which would let a phase augment along the longer path 0 - 3 - 1 - 5 - 2 - 6
+        let mut graph = BipartiteGraph::new(3, 4);
+        for (u, v) in [(0, 3), (0, 4), (1, 3), (1, 5), (2, 5), (2, 6)] {
+            graph.add_edge(u, v);
+        }
+        let mut mate = vec![None; 7];
+        for (u, v) in [(1, 3), (2, 5)] {
+            mate[u] = Some(v);
+            mate[v] = Some(u);
+        }
+        let mut level = vec![UNREACHED; 7];
+        assert_eq!(Some(0), layers(&graph, &[0, 1, 2], &mate, &mut level));
+        assert_eq!(vec![0, UNREACHED, UNREACHED], level[..3].to_vec());
+    }
+
+    #[test]
+    fn test_vertex_cover() {
+        // A star needs only its centre in the cover
+        let mut graph = BipartiteGraph::new(1, 4);
+        for v in 1..5 {
+            graph.add_edge(0, v);
+        }
+        let matching = hopcroft_karp(&graph);
+        assert_eq!(1, matching.pairs.len());
+        assert_eq!(vec![0], matching.vertex_cover);
+        check(&graph, &matching);
+    }
+
+    #[test]
+    fn test_matches_max_flow() {
+        let mut rng = Rng::new(31);
+        for _ in 0..20 {
+            let (l, r) = (1 + rng.gen_range(40) as usize, 1 + rng.gen_range(40) as usize);
+            let mut graph = BipartiteGraph::new(l, r);
+            let mut network = FlowNetwork::new(l + r + 2);
+            for u in 0..l {
+                network.add_edge(l + r, u, 1);
+            }
+            for v in l..l + r {
+                network.add_edge(v, l + r + 1, 1);
+            }
+            for _ in 0..rng.gen_range(120) {
+                let (u, v) = (rng.gen_range(l as u64) as usize, l + rng.gen_range(r as u64) as usize);
+                graph.add_edge(u, v);
+                network.add_edge(u, v, 1);
+            }
+            let matching = hopcroft_karp(&graph);
+            assert_eq!(dinic(&mut network, l + r, l + r + 1).value, matching.pairs.len() as u32);
+            check(&graph, &matching);
+        }
+    }
+}
@@ -0,0 +1,110 @@
+use crate::bellman_ford::{bellman_ford, NegativeCycleError};
+use crate::dijkstra::dijkstra;
+use crate::graph::Graph;
+use crate::shortest_paths::{AllPairsShortestPaths, Weight};
+
+/// Johnson's all pairs shortest paths, with edge data as weights
+///
+/// Runs Bellman-Ford once from a new node with a zero weight edge to every node, and uses the
+/// distances `h` to reweight each edge `(u, v)` to `w + h(u) - h(v)`, which is never negative and
+/// keeps the same shortest paths. Then runs Dijkstra from every node and undoes the reweighting.
+/// Takes `O(V * E * log(E))` time, faster than Floyd-Warshall's `O(V^3)` on sparse graphs. Returns
+/// a `NegativeCycleError` if the graph has a negative cycle anywhere.
+///
+/// Reweighted floating point weights that round below zero are clamped to zero.
+///
+/// # Arguments
+///
+/// * `graph` - Graph to search, edges are followed in both directions if undirected
+pub fn johnson<N, W: Weight>(graph: &Graph<N, W>) -> Result<AllPairsShortestPaths<W>, NegativeCycleError> {
+    let n = graph.node_bound();
+    let mut edges = Vec::with_capacity(graph.edge_count() + graph.node_count());
+    for u in graph.node_ids() {
+        edges.extend(graph.edges_from(u).map(|e| (u, e.target, *e.data)));
+    }
+
+    let mut augmented: Graph<(), W> = Graph::from_edges(true, n + 1, edges.iter().copied());
+    for u in graph.node_ids() {
+        augmented.add_edge(n, u, W::zero());
+    }
+    let h = bellman_ford(&augmented, n)?.distance;
+
+    let reweighted: Graph<(), W> = Graph::from_edges(
+        true,
+        n,
+        edges.into_iter().map(|(u, v, w)| {
+            let w = w + h[u].unwrap() - h[v].unwrap();
+            (u, v, if w < W::zero() { W::zero() } else { w })
+        }),
+    );
+
+    let mut sources = vec![None; n];
+    for u in graph.node_ids() {
+        let mut paths = dijkstra(&reweighted, u).expect("reweighted edges are never negative");
+        for (v, d) in paths.distance.iter_mut().enumerate() {
+            if let Some(d) = d.as_mut() {
+                *d = *d + h[v].unwrap() - h[u].unwrap();
+            }
+        }
+        sources[u] = Some(paths);
+    }
+    Ok(AllPairsShortestPaths { sources })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::Rng;
+
+    #[test]
+    fn test_johnson() {
+        // CLRS Figure 25.6
+        let edges = vec![(0, 1, 3), (0, 2, 8), (0, 4, -4), (1, 3, 1), (1, 4, 7), (2, 1, 4), (3, 0, 2), (3, 2, -5), (4, 3, 6)];
+        let g: Graph<(), i32> = Graph::from_edges(true, 5, edges);
+        let all = johnson(&g).unwrap();
+        let expected = [
+            [0, 1, -3, 2, -4],
+            [3, 0, -4, 1, -1],
+            [7, 4, 0, 5, 3],
+            [2, -1, -5, 0, -2],
+            [8, 5, 1, 6, 0],
+        ];
+        for (u, row) in expected.iter().enumerate() {
+            for (v, &d) in row.iter().enumerate() {
+                assert_eq!(Some(d), all.distance(u, v));
+            }
+        }
+        assert_eq!(Some(vec![2, 1, 3, 0, 4]), all.path(2, 4));
+    }
+
+    #[test]
+    fn test_matches_bellman_ford() {
+        let mut rng = Rng::new(3);
+        let edges: Vec<_> = (0..200)
+            .map(|_| {
+                let u = rng.gen_range(40) as usize;
+                let v = rng.gen_range(40) as usize;
+                // Edges only go forward, so negative weights can't form a cycle
+                (u.min(v), u.max(v), rng.gen_range(30) as i64 - 10)
+            })
+            .filter(|&(u, v, _)| u != v)
+            .collect();
+        let g: Graph<(), i64> = Graph::from_edges(true, 40, edges);
+        let all = johnson(&g).unwrap();
+        for u in 0..40 {
+            let expected = bellman_ford(&g, u).unwrap();
+            for v in 0..40 {
+                assert_eq!(expected.distance(v), all.distance(u, v));
+            }
+        }
+    }
+
+    #[test]
+    fn test_negative_cycle() {
+        let g: Graph<(), i32> = Graph::from_edges(true, 4, vec![(0, 1, 1), (2, 3, -2), (3, 2, 1)]);
+        let err = johnson(&g).unwrap_err();
+        let mut cycle = err.cycle;
+        cycle.sort_unstable();
+        assert_eq!(vec![2, 3], cycle);
+    }
+}
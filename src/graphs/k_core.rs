@@ -0,0 +1,171 @@
+use crate::graph::{Graph, NodeId};
+
+/// Core numbers of the nodes of a graph
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CoreDecomposition {
+    /// Largest `k` for which each node is in the k-core, indexed by `NodeId` with None for removed
+    /// nodes
+    pub cores: Vec<Option<usize>>,
+    /// Nodes in the order they were peeled, each with at most `degeneracy` neighbours after it
+    pub order: Vec<NodeId>,
+    /// Largest core number, the smallest `d` such that every subgraph has a node of degree `d` or
+    /// less
+    pub degeneracy: usize,
+}
+
+impl CoreDecomposition {
+
+    /// Nodes of the k-core, in increasing order
+    ///
+    /// # Arguments
+    ///
+    /// * `k` - Minimum degree of the core
+    pub fn core_nodes(&self, k: usize) -> Vec<NodeId> {
+        (0..self.cores.len()).filter(|&u| self.cores[u].is_some_and(|c| c >= k)).collect()
+    }
+
+    /// Subgraph of the k-core, the graph with every node outside it removed so the rest keep their
+    /// ids
+    ///
+    /// # Arguments
+    ///
+    /// * `graph` - Graph the decomposition was computed from
+    /// * `k` - Minimum degree of the core
+    pub fn k_core<N: Clone, E: Clone>(&self, graph: &Graph<N, E>, k: usize) -> Graph<N, E> {
+        let mut core = graph.clone();
+        for u in graph.node_ids() {
+            if self.cores[u].is_none_or(|c| c < k) {
+                core.remove_node(u);
+            }
+        }
+        core
+    }
+}
+
+/// Finds the core number of every node, by repeatedly removing a node of smallest degree
+///
+/// The k-core is the largest subgraph where every node has degree at least `k`, found by removing
+/// nodes of degree less than `k` until none are left, and a node's core number is the largest `k`
+/// whose core contains it. Batagelj and Zaversnik's algorithm peels every core at once: nodes are
+/// bucket sorted by degree, and when the smallest is removed its neighbours each move down one
+/// bucket, so it takes `O(V + E)` time. Edge directions are ignored, parallel edges each count
+/// towards the degree, and self-loops are ignored.
+///
+/// # Arguments
+///
+/// * `graph` - Graph to decompose
+pub fn k_cores<N, E>(graph: &Graph<N, E>) -> CoreDecomposition {
+    let n = graph.node_bound();
+    let mut adjacency = vec![Vec::new(); n];
+    for e in graph.edges() {
+        if e.source != e.target {
+            adjacency[e.source].push(e.target);
+            adjacency[e.target].push(e.source);
+        }
+    }
+    let mut degree: Vec<usize> = adjacency.iter().map(|a| a.len()).collect();
+    let max_degree = degree.iter().copied().max().unwrap_or(0);
+
+    // Nodes sorted by degree in `order`, where `start[d]` is the first with degree `d` and
+    // `position[u]` is where `u` is
+    let mut start = vec![0; max_degree + 2];
+    for u in graph.node_ids() {
+        start[degree[u] + 1] += 1;
+    }
+    for d in 1..start.len() {
+        start[d] += start[d - 1];
+    }
+    let mut order = vec![0; graph.node_count()];
+    let mut position = vec![0; n];
+    let mut next = start.clone();
+    for u in graph.node_ids() {
+        position[u] = next[degree[u]];
+        order[position[u]] = u;
+        next[degree[u]] += 1;
+    }
+
+    let mut cores = vec![None; n];
+    let mut degeneracy = 0;
+    for i in 0..order.len() {
+        let u = order[i];
+        cores[u] = Some(degree[u]);
+        degeneracy = degeneracy.max(degree[u]);
+        for &v in adjacency[u].iter() {
+            if degree[v] > degree[u] {
+                // Swap v with the first node of its bucket, then shrink the bucket past it
+                let d = degree[v];
+                let w = order[start[d]];
+                order.swap(position[v], start[d]);
+                position[w] = position[v];
+                position[v] = start[d];
+                start[d] += 1;
+                degree[v] -= 1;
+            }
+        }
+    }
+    CoreDecomposition { cores, order, degeneracy }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::Rng;
+
+    #[test]
+    fn test_k_cores() {
+        // A 4-clique 0..4 with a triangle 3, 4, 5 hanging off it and a pendant node 6
+        let edges = vec![(0, 1, ()), (0, 2, ()), (0, 3, ()), (1, 2, ()), (1, 3, ()), (2, 3, ()), (3, 4, ()), (4, 5, ()), (5, 3, ()), (5, 6, ())];
+        let g: Graph<(), ()> = Graph::from_edges(false, 7, edges);
+        let d = k_cores(&g);
+        assert_eq!(vec![Some(3), Some(3), Some(3), Some(3), Some(2), Some(2), Some(1)], d.cores);
+        assert_eq!(3, d.degeneracy);
+        assert_eq!(vec![0, 1, 2, 3, 4, 5], d.core_nodes(2));
+
+        let core = d.k_core(&g, 3);
+        assert_eq!(4, core.node_count());
+        assert_eq!(6, core.edge_count());
+        assert!(core.node_ids().all(|u| core.degree(u) >= 3));
+    }
+
+    #[test]
+    fn test_empty_and_isolated() {
+        let g: Graph<(), ()> = Graph::new(false);
+        let d = k_cores(&g);
+        assert!(d.cores.is_empty());
+        assert_eq!(0, d.degeneracy);
+
+        let mut g: Graph<(), ()> = Graph::from_edges(true, 4, vec![(0, 1, ()), (2, 2, ())]);
+        g.remove_node(3);
+        assert_eq!(vec![Some(1), Some(1), Some(0), None], k_cores(&g).cores);
+    }
+
+    #[test]
+    fn test_matches_peeling() {
+        let mut rng = Rng::new(59);
+        let edges: Vec<_> = (0..300).map(|_| (rng.gen_range(60) as usize, rng.gen_range(60) as usize, ())).collect();
+        let g: Graph<(), ()> = Graph::from_edges(false, 60, edges);
+        let d = k_cores(&g);
+
+        for k in 0..=d.degeneracy + 1 {
+            // Remove nodes of degree less than k until none are left
+            let mut core = g.clone();
+            loop {
+                let low = core.node_ids().find(|&u| core.neighbors(u).filter(|&v| v != u).count() < k);
+                match low {
+                    Some(u) => core.remove_node(u),
+                    None => break,
+                };
+            }
+            assert_eq!(core.node_ids().collect::<Vec<_>>(), d.core_nodes(k));
+        }
+
+        // Every node has at most `degeneracy` neighbours peeled after it
+        let mut rank = vec![0; 60];
+        for (i, &u) in d.order.iter().enumerate() {
+            rank[u] = i;
+        }
+        for u in 0..60 {
+            assert!(g.neighbors(u).filter(|&v| rank[v] > rank[u]).count() <= d.degeneracy);
+        }
+    }
+}
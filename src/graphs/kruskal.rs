@@ -0,0 +1,74 @@
+use crate::disjoint_set::DisjointSet;
+use crate::graph::Graph;
+use crate::max_heap::MaxHeap;
+use crate::shortest_paths::Weight;
+use crate::spanning_tree::SpanningForest;
+
+/// Kruskal's minimum spanning forest, with edge data as weights
+///
+/// Sorts the edges by weight with `MaxHeap::heapsort`, then adds each edge in order unless its
+/// endpoints are already connected, tracking the trees in a `DisjointSet`. Takes `O(E log(E))`
+/// time, and the forest's edges are in ascending order of weight.
+///
+/// # Arguments
+///
+/// * `graph` - Graph to span
+///
+/// # Panics
+///
+/// If the graph is directed
+pub fn kruskal_mst<N, W: Weight>(graph: &Graph<N, W>) -> SpanningForest<W> {
+    assert!(!graph.is_directed(), "a minimum spanning tree requires an undirected graph");
+    let edges = MaxHeap::heapsort(graph.edges().map(|e| (*e.data, e.id)).collect());
+    let mut sets = DisjointSet::with_size(graph.node_bound());
+    let mut forest = SpanningForest { edges: Vec::new(), weight: W::zero() };
+
+    for (w, e) in edges {
+        let (u, v) = graph.endpoints(e).unwrap();
+        if sets.union(u, v) {
+            forest.edges.push(e);
+            forest.weight = forest.weight + w;
+        }
+    }
+    forest
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prim::prim_mst;
+    use crate::rng::Rng;
+
+    #[test]
+    fn test_kruskal() {
+        // CLRS Figure 23.1
+        let (a, b, c, d, e, f, g, h, i) = (0, 1, 2, 3, 4, 5, 6, 7, 8);
+        let edges = vec![
+            (a, b, 4), (a, h, 8), (b, c, 8), (b, h, 11), (c, d, 7), (c, f, 4), (c, i, 2),
+            (d, e, 9), (d, f, 14), (e, f, 10), (f, g, 2), (g, h, 1), (g, i, 6), (h, i, 7),
+        ];
+        let graph: Graph<(), u32> = Graph::from_edges(false, 9, edges);
+        let forest = kruskal_mst(&graph);
+        assert_eq!(37, forest.weight);
+        let weights: Vec<_> = forest.edges.iter().map(|&e| *graph.edge(e).unwrap().data).collect();
+        assert_eq!(vec![1, 2, 2, 4, 4, 7, 8, 9], weights);
+    }
+
+    #[test]
+    fn test_matches_prim() {
+        let mut rng = Rng::new(11);
+        let edges: Vec<_> = (0..300).map(|_| (rng.gen_range(80) as usize, rng.gen_range(80) as usize, rng.gen_range(1000))).collect();
+        let g: Graph<(), u64> = Graph::from_edges(false, 80, edges);
+        let (a, b) = (kruskal_mst(&g), prim_mst(&g));
+        assert_eq!(b.weight, a.weight);
+        assert_eq!(b.edges.len(), a.edges.len());
+    }
+
+    #[test]
+    fn test_forest() {
+        let g: Graph<(), i32> = Graph::from_edges(false, 5, vec![(0, 1, -2), (1, 2, 5), (2, 0, 3), (3, 4, 1), (3, 4, 0)]);
+        let forest = kruskal_mst(&g);
+        assert_eq!(vec![0, 4, 2], forest.edges);
+        assert_eq!(1, forest.weight);
+    }
+}
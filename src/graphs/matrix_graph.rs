@@ -0,0 +1,317 @@
+use std::convert::TryFrom;
+use std::fmt;
+
+use crate::graph::{Graph, NodeId};
+
+/// Error for a `Graph` with parallel edges, which an adjacency matrix can't represent
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ParallelEdgeError {
+    pub source: NodeId,
+    pub target: NodeId,
+}
+
+impl fmt::Display for ParallelEdgeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "graph has parallel edges from {} to {}", self.source, self.target)
+    }
+}
+
+impl std::error::Error for ParallelEdgeError {}
+
+/// A directed or undirected Graph using an adjacency matrix
+///
+/// Stores a `V x V` matrix with the data of the edge from `u` to `v` at entry `(u, v)`, so edges
+/// are found, added, and removed in `O(1)` time, at the cost of `O(V^2)` space and `O(V)` time
+/// to list the neighbours of a node. An undirected graph only stores the entry with `u <= v`.
+///
+/// Nodes are numbered `0..n` and can't be removed, and there is at most one edge per pair of nodes.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MatrixGraph<N, E> {
+    nodes: Vec<N>,
+    matrix: Vec<Option<E>>,
+    edge_count: usize,
+    directed: bool,
+}
+
+impl<N, E> MatrixGraph<N, E> {
+
+    /// Creates a new empty `MatrixGraph`
+    ///
+    /// # Arguments
+    ///
+    /// * `directed` - If the edges of the graph are directed
+    pub fn new(directed: bool) -> MatrixGraph<N, E> {
+        MatrixGraph { nodes: Vec::new(), matrix: Vec::new(), edge_count: 0, directed }
+    }
+
+    /// Checks if the edges of the graph are directed
+    pub fn is_directed(&self) -> bool {
+        self.directed
+    }
+
+    /// Number of nodes
+    pub fn node_count(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Number of edges
+    pub fn edge_count(&self) -> usize {
+        self.edge_count
+    }
+
+    /// Adds a node, and returns its index
+    ///
+    /// Grows the matrix, which takes `O(V^2)` time.
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - Data of the node
+    pub fn add_node(&mut self, data: N) -> NodeId {
+        let n = self.nodes.len();
+        let mut matrix = Vec::with_capacity((n + 1) * (n + 1));
+        let mut old = self.matrix.drain(..);
+        for _ in 0..n {
+            matrix.extend(old.by_ref().take(n));
+            matrix.push(None);
+        }
+        drop(old);
+        matrix.extend((0..=n).map(|_| None));
+        self.matrix = matrix;
+        self.nodes.push(data);
+        n
+    }
+
+    /// Gets the data of a node
+    ///
+    /// # Arguments
+    ///
+    /// * `u` - Node to look up
+    pub fn node(&self, u: NodeId) -> Option<&N> {
+        self.nodes.get(u)
+    }
+
+    /// Adds an edge, or replaces the edge between the nodes, and returns the replaced edge's data
+    ///
+    /// # Arguments
+    ///
+    /// * `u` - Source node
+    /// * `v` - Target node
+    /// * `data` - Data of the edge
+    ///
+    /// # Panics
+    ///
+    /// If either node doesn't exist
+    pub fn add_edge(&mut self, u: NodeId, v: NodeId, data: E) -> Option<E> {
+        let i = self.index(u, v);
+        let old = self.matrix[i].replace(data);
+        if old.is_none() {
+            self.edge_count += 1;
+        }
+        old
+    }
+
+    /// Removes the edge from `u` to `v`, and returns its data
+    ///
+    /// # Arguments
+    ///
+    /// * `u` - Source node
+    /// * `v` - Target node
+    pub fn remove_edge(&mut self, u: NodeId, v: NodeId) -> Option<E> {
+        let i = self.index(u, v);
+        let old = self.matrix[i].take();
+        if old.is_some() {
+            self.edge_count -= 1;
+        }
+        old
+    }
+
+    /// Gets the data of the edge from `u` to `v`, in `O(1)` time
+    ///
+    /// # Arguments
+    ///
+    /// * `u` - Source node
+    /// * `v` - Target node
+    pub fn edge(&self, u: NodeId, v: NodeId) -> Option<&E> {
+        if u >= self.nodes.len() || v >= self.nodes.len() {
+            return None;
+        }
+        self.matrix[self.index(u, v)].as_ref()
+    }
+
+    /// Checks if there is an edge from `u` to `v`, in `O(1)` time
+    ///
+    /// # Arguments
+    ///
+    /// * `u` - Source node
+    /// * `v` - Target node
+    pub fn has_edge(&self, u: NodeId, v: NodeId) -> bool {
+        self.edge(u, v).is_some()
+    }
+
+    /// Returns an iterator over the nodes `u` has an edge to, in `O(V)` time
+    ///
+    /// # Arguments
+    ///
+    /// * `u` - Node to get the neighbours of
+    pub fn neighbors(&self, u: NodeId) -> impl Iterator<Item = NodeId> + '_ {
+        (0..self.nodes.len()).filter(move |&v| self.has_edge(u, v))
+    }
+
+    /// Returns an iterator over every edge once as `(source, target, data)`, in row-major order
+    ///
+    /// An undirected edge is returned with `source <= target`.
+    pub fn edges(&self) -> impl Iterator<Item = (NodeId, NodeId, &E)> {
+        let n = self.nodes.len();
+        self.matrix.iter().enumerate().filter_map(move |(i, e)| e.as_ref().map(|e| (i / n, i % n, e)))
+    }
+
+    /// Internal function for the matrix index of the entry `(u, v)`
+    fn index(&self, u: NodeId, v: NodeId) -> usize {
+        let n = self.nodes.len();
+        assert!(u < n && v < n, "edge endpoints must be nodes in the graph");
+        if !self.directed && u > v {
+            v * n + u
+        } else {
+            u * n + v
+        }
+    }
+}
+
+impl<N: Clone, E: Clone> MatrixGraph<N, E> {
+
+    /// Creates a `MatrixGraph` from an adjacency list `Graph`
+    ///
+    /// Nodes are renumbered `0..n` in ascending order of their index in the `Graph`, which keeps
+    /// every index the same if no nodes were removed. Returns an error if the graph has parallel
+    /// edges, since only one edge fits in a matrix entry.
+    ///
+    /// # Arguments
+    ///
+    /// * `graph` - Graph to convert
+    pub fn from_graph(graph: &Graph<N, E>) -> Result<MatrixGraph<N, E>, ParallelEdgeError> {
+        let mut id = vec![usize::MAX; graph.node_bound()];
+        let mut matrix = MatrixGraph::new(graph.is_directed());
+        for (u, data) in graph.nodes() {
+            id[u] = matrix.nodes.len();
+            matrix.nodes.push(data.clone());
+        }
+        let n = matrix.nodes.len();
+        matrix.matrix = vec![None; n * n];
+
+        for e in graph.edges() {
+            if matrix.add_edge(id[e.source], id[e.target], e.data.clone()).is_some() {
+                return Err(ParallelEdgeError { source: e.source, target: e.target });
+            }
+        }
+        Ok(matrix)
+    }
+
+    /// Creates an adjacency list `Graph` from the `MatrixGraph`
+    ///
+    /// Node indexes are the same, and edges are added in row-major order.
+    pub fn to_graph(&self) -> Graph<N, E> {
+        let mut graph = Graph::new(self.directed);
+        for data in self.nodes.iter() {
+            graph.add_node(data.clone());
+        }
+        for (u, v, e) in self.edges() {
+            graph.add_edge(u, v, e.clone());
+        }
+        graph
+    }
+}
+
+impl<N: Clone, E: Clone> TryFrom<&Graph<N, E>> for MatrixGraph<N, E> {
+    type Error = ParallelEdgeError;
+
+    fn try_from(graph: &Graph<N, E>) -> Result<Self, Self::Error> {
+        MatrixGraph::from_graph(graph)
+    }
+}
+
+impl<N: Clone, E: Clone> From<&MatrixGraph<N, E>> for Graph<N, E> {
+    fn from(matrix: &MatrixGraph<N, E>) -> Self {
+        matrix.to_graph()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_directed() {
+        let mut g = MatrixGraph::new(true);
+        for i in 0..3 {
+            assert_eq!(i, g.add_node(i));
+        }
+        assert_eq!(None, g.add_edge(0, 1, 'a'));
+        assert_eq!(None, g.add_edge(2, 0, 'b'));
+        assert_eq!(Some('a'), g.add_edge(0, 1, 'c'));
+        assert_eq!(2, g.edge_count());
+        assert_eq!(Some(&'c'), g.edge(0, 1));
+        assert!(!g.has_edge(1, 0));
+        assert!(!g.has_edge(0, 5));
+        assert_eq!(vec![0], g.neighbors(2).collect::<Vec<_>>());
+
+        // Growing the matrix keeps the existing edges in place
+        g.add_node(3);
+        g.add_edge(3, 2, 'd');
+        assert_eq!(vec![(0, 1, &'c'), (2, 0, &'b'), (3, 2, &'d')], g.edges().collect::<Vec<_>>());
+
+        assert_eq!(Some('b'), g.remove_edge(2, 0));
+        assert_eq!(None, g.remove_edge(2, 0));
+        assert_eq!(2, g.edge_count());
+    }
+
+    #[test]
+    fn test_undirected() {
+        let mut g = MatrixGraph::new(false);
+        g.add_node(());
+        g.add_node(());
+        g.add_edge(1, 0, 5);
+        assert!(g.has_edge(0, 1));
+        assert!(g.has_edge(1, 0));
+        assert_eq!(vec![(0, 1, &5)], g.edges().collect::<Vec<_>>());
+        assert_eq!(vec![1], g.neighbors(0).collect::<Vec<_>>());
+        assert_eq!(vec![0], g.neighbors(1).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let graph: Graph<(), u32> = Graph::from_edges(true, 4, vec![(0, 1, 1), (1, 2, 2), (3, 0, 3), (2, 2, 4)]);
+        let matrix = MatrixGraph::try_from(&graph).unwrap();
+        assert_eq!(4, matrix.edge_count());
+        assert_eq!(Some(&3), matrix.edge(3, 0));
+
+        let back = Graph::from(&matrix);
+        assert_eq!(graph.node_count(), back.node_count());
+        let mut a: Vec<_> = graph.edges().map(|e| (e.source, e.target, *e.data)).collect();
+        let mut b: Vec<_> = back.edges().map(|e| (e.source, e.target, *e.data)).collect();
+        a.sort();
+        b.sort();
+        assert_eq!(a, b);
+        assert_eq!(matrix, MatrixGraph::from_graph(&back).unwrap());
+    }
+
+    #[test]
+    fn test_from_graph_renumbers() {
+        let mut graph: Graph<char, ()> = Graph::new_undirected();
+        graph.add_node('a');
+        graph.add_node('b');
+        graph.add_node('c');
+        graph.add_edge(2, 0, ());
+        graph.remove_node(1);
+
+        let matrix = MatrixGraph::from_graph(&graph).unwrap();
+        assert_eq!(Some(&'c'), matrix.node(1));
+        assert!(matrix.has_edge(0, 1));
+    }
+
+    #[test]
+    fn test_parallel_edges() {
+        let graph: Graph<(), ()> = Graph::from_edges(false, 2, vec![(0, 1, ()), (1, 0, ())]);
+        let err = MatrixGraph::from_graph(&graph).unwrap_err();
+        assert_eq!(ParallelEdgeError { source: 1, target: 0 }, err);
+    }
+}
@@ -0,0 +1,289 @@
+use std::ops::Mul;
+
+use crate::bellman_ford::NegativeCycleError;
+use crate::flow_network::{FlowNetwork, MaxFlow};
+use crate::graph::NodeId;
+use crate::indexed_min_heap::IndexedMinHeap;
+use crate::shortest_paths::Weight;
+
+/// Result of a minimum cost maximum flow algorithm
+#[derive(Clone, Debug, PartialEq)]
+pub struct MinCostFlow<C> {
+    /// The maximum flow, with its flows and minimum cut
+    pub flow: MaxFlow<C>,
+    /// Total cost of the flow, the sum of flow times cost over every edge
+    pub cost: C,
+}
+
+/// Minimum cost maximum flow from `s` to `t` by successive shortest paths
+///
+/// Sends flow along a cheapest path in the residual graph until `t` can't be reached, where pushing
+/// flow back along an edge refunds its cost. Node potentials from the previous search turn every
+/// residual arc's cost non-negative, so each path is found with Dijkstra's algorithm on an
+/// `IndexedMinHeap` in `O(E * log(V))` time, and every path's reversed arcs stay non-negative too.
+/// With negative costs, the first potentials come from a Bellman-Ford pass in `O(V * E)` time.
+/// There are at most as many paths as units of flow with integer capacities. Solving an assignment
+/// problem is a network from a source to every worker, worker to job edges with the costs, and
+/// every job to a sink, all with capacity one.
+///
+/// Clears any flow in the network first, and leaves the minimum cost maximum flow in it.
+///
+/// # Arguments
+///
+/// * `network` - Network to push flow through, with costs set by `add_edge_with_cost`
+/// * `s` - Source node
+/// * `t` - Sink node
+///
+/// # Panics
+///
+/// If `s` and `t` are the same node
+pub fn min_cost_max_flow<C: Weight + Mul<Output = C>>(network: &mut FlowNetwork<C>, s: NodeId, t: NodeId) -> Result<MinCostFlow<C>, NegativeCycleError> {
+    assert!(s != t, "source and sink must be different nodes");
+    network.reset_flow();
+    let n = network.node_count();
+    let mut potential = initial_potentials(network, s)?;
+    let mut distance = vec![None; n];
+    let mut parent_arc = vec![usize::MAX; n];
+    let mut value = C::zero();
+
+    while cheapest_path(network, s, t, &potential, &mut distance, &mut parent_arc) {
+        // Nodes that weren't reached never will be, so only reached potentials matter
+        for (p, d) in potential.iter_mut().zip(distance.iter()) {
+            if let Some(d) = *d {
+                *p = *p + d;
+            }
+        }
+
+        let mut bottleneck = network.residual(parent_arc[t]);
+        let mut v = network.head(parent_arc[t] ^ 1);
+        while v != s {
+            let a = parent_arc[v];
+            if network.residual(a) < bottleneck {
+                bottleneck = network.residual(a);
+            }
+            v = network.head(a ^ 1);
+        }
+        let mut v = t;
+        while v != s {
+            let a = parent_arc[v];
+            network.push(a, bottleneck);
+            v = network.head(a ^ 1);
+        }
+        value = value + bottleneck;
+    }
+
+    let cost = (0..network.edge_count()).fold(C::zero(), |total, e| total + network.flow(e) * network.cost(e));
+    Ok(MinCostFlow { flow: network.max_flow(s, value), cost })
+}
+
+/// Internal function to find potentials that make every edge's reduced cost non-negative
+///
+/// All zero without negative costs, otherwise Bellman-Ford distances from `s` over the edges with
+/// capacity, which fails on a negative cycle reachable from `s`.
+fn initial_potentials<C: Weight>(network: &FlowNetwork<C>, s: NodeId) -> Result<Vec<C>, NegativeCycleError> {
+    let n = network.node_count();
+    if (0..network.edge_count()).all(|e| network.cost(e) >= C::zero()) {
+        return Ok(vec![C::zero(); n]);
+    }
+
+    let mut distance: Vec<Option<C>> = vec![None; n];
+    let mut parent = vec![s; n];
+    distance[s] = Some(C::zero());
+    for round in 0..n {
+        let mut changed = None;
+        for e in 0..network.edge_count() {
+            let (u, v) = network.endpoints(e);
+            if network.capacity(e) <= C::zero() {
+                continue;
+            }
+            if let Some(d) = distance[u] {
+                let nd = d + network.cost(e);
+                if distance[v].is_none_or(|dv| nd < dv) {
+                    distance[v] = Some(nd);
+                    parent[v] = u;
+                    changed = Some(v);
+                }
+            }
+        }
+        match changed {
+            None => break,
+            // Relaxed in the V-th round, so V steps back along the parents lands on the cycle
+            Some(v) if round == n - 1 => {
+                let mut u = v;
+                for _ in 0..n {
+                    u = parent[u];
+                }
+                let mut cycle = vec![u];
+                let mut w = parent[u];
+                while w != u {
+                    cycle.push(w);
+                    w = parent[w];
+                }
+                cycle.reverse();
+                return Err(NegativeCycleError { cycle });
+            }
+            Some(_) => {}
+        }
+    }
+    Ok(distance.into_iter().map(|d| d.unwrap_or_else(C::zero)).collect())
+}
+
+/// Internal function for Dijkstra's algorithm over the residual graph with reduced costs
+///
+/// Fills in the reduced distance and the arc into every node reached from `s`, and returns whether
+/// `t` was reached.
+fn cheapest_path<C: Weight>(network: &FlowNetwork<C>, s: NodeId, t: NodeId, potential: &[C], distance: &mut [Option<C>], parent_arc: &mut [usize]) -> bool {
+    for d in distance.iter_mut() {
+        *d = None;
+    }
+    distance[s] = Some(C::zero());
+    let mut heap = IndexedMinHeap::with_capacity(network.node_count());
+    heap.push(s, C::zero());
+
+    while let Some((u, d)) = heap.pop() {
+        for &a in network.arcs(u) {
+            if network.residual(a) <= C::zero() {
+                continue;
+            }
+            let v = network.head(a);
+            let nd = d + reduced_cost(network, a, potential);
+            if heap.contains(v) {
+                if heap.decrease_key(v, nd) {
+                    distance[v] = Some(nd);
+                    parent_arc[v] = a;
+                }
+            } else if distance[v].is_none() {
+                distance[v] = Some(nd);
+                parent_arc[v] = a;
+                heap.push(v, nd);
+            }
+        }
+    }
+    distance[t].is_some()
+}
+
+/// Internal function for an arc's cost adjusted by the potentials of its ends
+///
+/// A reverse arc costs the negative of its edge, so the sums are arranged to never go below zero
+/// with unsigned costs. Rounding with floats can leave tiny negative values, which are clamped.
+fn reduced_cost<C: Weight>(network: &FlowNetwork<C>, a: usize, potential: &[C]) -> C {
+    let (u, v) = (network.head(a ^ 1), network.head(a));
+    let cost = network.cost(a / 2);
+    let (x, y) = if a & 1 == 0 { (cost + potential[u], potential[v]) } else { (potential[u], cost + potential[v]) };
+    if x < y {
+        C::zero()
+    } else {
+        x - y
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dinic::dinic;
+    use crate::rng::Rng;
+
+    /// Builds an assignment network for a square cost matrix, with the source and sink last
+    fn assignment(costs: &[Vec<u32>]) -> FlowNetwork<u32> {
+        let k = costs.len();
+        let mut network = FlowNetwork::new(2 * k + 2);
+        for (i, row) in costs.iter().enumerate() {
+            network.add_edge(2 * k, i, 1);
+            network.add_edge(k + i, 2 * k + 1, 1);
+            for (j, &c) in row.iter().enumerate() {
+                network.add_edge_with_cost(i, k + j, 1, c);
+            }
+        }
+        network
+    }
+
+    /// Internal function to find the cheapest assignment by trying every permutation
+    fn brute_force(costs: &[Vec<u32>], row: usize, used: &mut [bool]) -> u32 {
+        if row == costs.len() {
+            return 0;
+        }
+        let mut best = u32::MAX;
+        for j in 0..costs.len() {
+            if !used[j] {
+                used[j] = true;
+                best = best.min(costs[row][j] + brute_force(costs, row + 1, used));
+                used[j] = false;
+            }
+        }
+        best
+    }
+
+    #[test]
+    fn test_min_cost_max_flow() {
+        let mut network = FlowNetwork::new(3);
+        let cheap = network.add_edge_with_cost(0, 1, 2, 1);
+        network.add_edge_with_cost(1, 2, 2, 1);
+        let direct = network.add_edge_with_cost(0, 2, 1, 5);
+        let result = min_cost_max_flow(&mut network, 0, 2).unwrap();
+        assert_eq!(3, result.flow.value);
+        assert_eq!(9, result.cost);
+        assert_eq!((2, 1), (network.flow(cheap), network.flow(direct)));
+    }
+
+    #[test]
+    fn test_assignment() {
+        let mut rng = Rng::new(23);
+        for k in 1..7 {
+            let costs: Vec<Vec<u32>> = (0..k).map(|_| (0..k).map(|_| rng.gen_range(100) as u32).collect()).collect();
+            let mut network = assignment(&costs);
+            let result = min_cost_max_flow(&mut network, 2 * k, 2 * k + 1).unwrap();
+            assert_eq!(k as u32, result.flow.value);
+            assert_eq!(brute_force(&costs, 0, &mut vec![false; k]), result.cost);
+        }
+    }
+
+    #[test]
+    fn test_optimal() {
+        // A flow is cheapest exactly when its residual graph has no negative cycle
+        let mut rng = Rng::new(29);
+        for _ in 0..10 {
+            let mut network = FlowNetwork::new(20);
+            for _ in 0..80 {
+                let (u, v) = (rng.gen_range(20) as usize, rng.gen_range(20) as usize);
+                network.add_edge_with_cost(u, v, rng.gen_range(10) as i64, rng.gen_range(50) as i64 - 2);
+            }
+            let mut other = network.clone();
+            let result = match min_cost_max_flow(&mut network, 0, 19) {
+                Ok(result) => result,
+                Err(_) => continue,
+            };
+            assert_eq!(dinic(&mut other, 0, 19).value, result.flow.value);
+
+            let mut distance = [0i64; 20];
+            let mut changed = true;
+            for _ in 0..20 {
+                changed = false;
+                for u in 0..20 {
+                    for &a in network.arcs(u).iter().filter(|&&a| network.residual(a) > 0) {
+                        let cost = if a & 1 == 0 { network.cost(a / 2) } else { -network.cost(a / 2) };
+                        if distance[u] + cost < distance[network.head(a)] {
+                            distance[network.head(a)] = distance[u] + cost;
+                            changed = true;
+                        }
+                    }
+                }
+            }
+            assert!(!changed);
+        }
+    }
+
+    #[test]
+    fn test_negative_costs() {
+        let mut network = FlowNetwork::new(3);
+        network.add_edge_with_cost(0, 1, 1, -3i32);
+        network.add_edge_with_cost(1, 2, 1, 1);
+        network.add_edge_with_cost(0, 2, 1, 0);
+        let result = min_cost_max_flow(&mut network, 0, 2).unwrap();
+        assert_eq!((2, -2), (result.flow.value, result.cost));
+
+        network.add_edge_with_cost(2, 1, 1, 1);
+        network.add_edge_with_cost(1, 2, 1, -2);
+        let cycle = min_cost_max_flow(&mut network, 0, 2).unwrap_err().cycle;
+        assert_eq!(2, cycle.len());
+    }
+}
@@ -0,0 +1,129 @@
+use crate::graph::Graph;
+
+/// Ranks found by `pagerank`, with how the iteration ended
+#[derive(Clone, Debug, PartialEq)]
+pub struct PageRank {
+    /// Rank of each node, summing to one, indexed by `NodeId` with `0.0` for removed nodes
+    pub ranks: Vec<f64>,
+    /// Number of iterations run
+    pub iterations: usize,
+    /// Sum of the absolute changes in rank over the last iteration
+    pub delta: f64,
+    /// If `delta` fell below the tolerance before running out of iterations
+    pub converged: bool,
+}
+
+/// Ranks the nodes of a graph by the PageRank of a random surfer
+///
+/// The surfer follows a random edge out of its node with probability `damping`, and otherwise
+/// jumps to a node chosen uniformly at random. A node's rank is the fraction of time the surfer
+/// spends there in the long run, so nodes linked from highly ranked nodes rank highly. From a
+/// node with no edges out, a dangling node, the surfer always jumps, so its rank is spread evenly
+/// over every node instead of leaking away.
+///
+/// The ranks are the stationary distribution of this Markov chain, found by power iteration:
+/// starting from the uniform distribution, every node passes its rank along its edges until the
+/// total change is less than `tol`. Each iteration takes `O(V + E)` time, and the change shrinks by
+/// a factor of `damping` each time. Parallel edges are each followed, and an undirected edge is
+/// followed both ways.
+///
+/// # Arguments
+///
+/// * `graph` - Graph to rank
+/// * `damping` - Probability of following an edge rather than jumping, commonly `0.85`
+/// * `tol` - Change in rank, summed over the nodes, to stop below
+/// * `max_iters` - Most iterations to run
+///
+/// # Panics
+///
+/// If `damping` isn't between zero and one
+pub fn pagerank<N, E>(graph: &Graph<N, E>, damping: f64, tol: f64, max_iters: usize) -> PageRank {
+    assert!((0.0..=1.0).contains(&damping), "damping must be between zero and one, not {}", damping);
+    let n = graph.node_count() as f64;
+    let mut result = PageRank { ranks: vec![0.0; graph.node_bound()], iterations: 0, delta: 0.0, converged: true };
+    if graph.node_count() == 0 {
+        return result;
+    }
+    for u in graph.node_ids() {
+        result.ranks[u] = 1.0 / n;
+    }
+
+    let mut next = vec![0.0; graph.node_bound()];
+    result.converged = false;
+    while result.iterations < max_iters {
+        let dangling: f64 = graph.node_ids().filter(|&u| graph.out_degree(u) == 0).map(|u| result.ranks[u]).sum();
+        let jump = (1.0 - damping) / n + damping * dangling / n;
+        for u in graph.node_ids() {
+            next[u] = jump;
+        }
+        for u in graph.node_ids().filter(|&u| graph.out_degree(u) > 0) {
+            let share = damping * result.ranks[u] / graph.out_degree(u) as f64;
+            for v in graph.neighbors(u) {
+                next[v] += share;
+            }
+        }
+
+        result.delta = graph.node_ids().map(|u| (next[u] - result.ranks[u]).abs()).sum();
+        std::mem::swap(&mut result.ranks, &mut next);
+        result.iterations += 1;
+        if result.delta < tol {
+            result.converged = true;
+            break;
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::Rng;
+
+    #[test]
+    fn test_pagerank() {
+        // Node 1 is dangling, so r0 = 0.075 + 0.425 * r1 and r1 = 1 - r0
+        let g: Graph<(), ()> = Graph::from_edges(true, 2, vec![(0, 1, ())]);
+        let r = pagerank(&g, 0.85, 1e-12, 100);
+        assert!(r.converged);
+        assert!((r.ranks[0] - 0.5 / 1.425).abs() < 1e-9);
+        assert!((r.ranks[1] - 0.925 / 1.425).abs() < 1e-9);
+
+        let g: Graph<(), ()> = Graph::cycle(5);
+        let r = pagerank(&g, 0.85, 1e-12, 100);
+        assert!(r.ranks.iter().all(|&x| (x - 0.2).abs() < 1e-12));
+    }
+
+    #[test]
+    fn test_not_converged() {
+        let g: Graph<(), ()> = Graph::from_edges(true, 3, vec![(0, 1, ()), (1, 2, ()), (2, 0, ()), (0, 2, ())]);
+        let r = pagerank(&g, 0.85, 1e-12, 2);
+        assert!(!r.converged);
+        assert_eq!(2, r.iterations);
+        assert!(r.delta > 1e-12);
+
+        let g: Graph<(), ()> = Graph::new(true);
+        assert!(pagerank(&g, 0.85, 1e-6, 10).converged);
+    }
+
+    #[test]
+    fn test_random() {
+        let mut rng = Rng::new(61);
+        let edges: Vec<_> = (0..300).map(|_| (rng.gen_range(50) as usize, rng.gen_range(50) as usize, ())).collect();
+        let mut g: Graph<(), ()> = Graph::from_edges(true, 50, edges);
+        g.remove_node(7);
+        let r = pagerank(&g, 0.85, 1e-10, 1000);
+        assert!(r.converged);
+        assert!((r.ranks.iter().sum::<f64>() - 1.0).abs() < 1e-9);
+        assert_eq!(0.0, r.ranks[7]);
+
+        // The ranks are a fixed point of one more step
+        let again = pagerank(&g, 0.85, 0.0, r.iterations + 1);
+        for u in g.node_ids() {
+            assert!((again.ranks[u] - r.ranks[u]).abs() < 1e-9);
+        }
+
+        // With no damping the surfer only jumps
+        let r = pagerank(&g, 0.0, 1e-10, 10);
+        assert!(g.node_ids().all(|u| (r.ranks[u] - 1.0 / 49.0).abs() < 1e-12));
+    }
+}
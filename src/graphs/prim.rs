@@ -0,0 +1,178 @@
+use std::cmp::Reverse;
+
+use crate::graph::{EdgeId, Graph, NodeId};
+use crate::indexed_min_heap::IndexedMinHeap;
+use crate::max_heap::MaxHeap;
+use crate::priority_queue::PriorityQueue;
+use crate::shortest_paths::Weight;
+use crate::spanning_tree::SpanningForest;
+
+/// Prim's minimum spanning forest, with edge data as weights
+///
+/// Grows a tree from a node by repeatedly adding the lightest edge from the tree to a node outside
+/// it, and starts a new tree from the next node outside every tree once a component is spanned.
+/// Keeps the candidate edges in a `MaxHeap` of `Reverse` weights, skipping edges to nodes already
+/// in a tree as they're popped, in `O(E log(E))` time.
+///
+/// # Arguments
+///
+/// * `graph` - Graph to span
+///
+/// # Panics
+///
+/// If the graph is directed
+pub fn prim_mst<N, W: Weight>(graph: &Graph<N, W>) -> SpanningForest<W> {
+    prim_mst_with_queue(graph, MaxHeap::new())
+}
+
+/// Prim's minimum spanning forest, with any priority queue
+///
+/// The same as `prim_mst`, but the candidate edges go in the given queue instead of a `MaxHeap`,
+/// like the stdlib's `BinaryHeap` when the weights are `Ord`.
+///
+/// # Arguments
+///
+/// * `graph` - Graph to span
+/// * `queue` - Empty queue to keep the candidate edges in
+///
+/// # Panics
+///
+/// If the graph is directed
+pub fn prim_mst_with_queue<N, W, Q>(graph: &Graph<N, W>, mut queue: Q) -> SpanningForest<W>
+where
+    W: Weight,
+    Q: PriorityQueue<(Reverse<W>, EdgeId, NodeId)>,
+{
+    assert!(!graph.is_directed(), "a minimum spanning tree requires an undirected graph");
+    let mut in_tree = vec![false; graph.node_bound()];
+    let mut forest = SpanningForest { edges: Vec::new(), weight: W::zero() };
+
+    for root in graph.node_ids() {
+        if in_tree[root] {
+            continue;
+        }
+        in_tree[root] = true;
+        for e in graph.edges_from(root) {
+            queue.push((Reverse(*e.data), e.id, e.target));
+        }
+        while let Some((Reverse(w), e, v)) = queue.pop() {
+            if in_tree[v] {
+                continue;
+            }
+            in_tree[v] = true;
+            forest.edges.push(e);
+            forest.weight = forest.weight + w;
+            for e in graph.edges_from(v) {
+                if !in_tree[e.target] {
+                    queue.push((Reverse(*e.data), e.id, e.target));
+                }
+            }
+        }
+    }
+    forest
+}
+
+/// Prim's minimum spanning forest, decreasing keys in an `IndexedMinHeap`
+///
+/// Finds a forest of the same weight as `prim_mst`, but keeps one heap entry per node outside the
+/// tree, keyed by its lightest edge to the tree, in `O(E log(V))` time.
+///
+/// # Arguments
+///
+/// * `graph` - Graph to span
+///
+/// # Panics
+///
+/// If the graph is directed
+pub fn prim_mst_decrease_key<N, W: Weight>(graph: &Graph<N, W>) -> SpanningForest<W> {
+    assert!(!graph.is_directed(), "a minimum spanning tree requires an undirected graph");
+    let n = graph.node_bound();
+    let mut in_tree = vec![false; n];
+    let mut lightest: Vec<Option<EdgeId>> = vec![None; n];
+    let mut forest = SpanningForest { edges: Vec::new(), weight: W::zero() };
+    let mut heap = IndexedMinHeap::with_capacity(n);
+
+    for root in graph.node_ids() {
+        if in_tree[root] {
+            continue;
+        }
+        heap.push(root, W::zero());
+        while let Some((u, w)) = heap.pop() {
+            in_tree[u] = true;
+            if let Some(e) = lightest[u] {
+                forest.edges.push(e);
+                forest.weight = forest.weight + w;
+            }
+            for e in graph.edges_from(u) {
+                let v = e.target;
+                if in_tree[v] {
+                    continue;
+                }
+                if heap.contains(v) {
+                    if heap.decrease_key(v, *e.data) {
+                        lightest[v] = Some(e.id);
+                    }
+                } else {
+                    heap.push(v, *e.data);
+                    lightest[v] = Some(e.id);
+                }
+            }
+        }
+    }
+    forest
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::Rng;
+    use std::collections::BinaryHeap;
+
+    /// CLRS Figure 23.1
+    fn clrs() -> Graph<(), u32> {
+        let (a, b, c, d, e, f, g, h, i) = (0, 1, 2, 3, 4, 5, 6, 7, 8);
+        let edges = vec![
+            (a, b, 4), (a, h, 8), (b, c, 8), (b, h, 11), (c, d, 7), (c, f, 4), (c, i, 2),
+            (d, e, 9), (d, f, 14), (e, f, 10), (f, g, 2), (g, h, 1), (g, i, 6), (h, i, 7),
+        ];
+        Graph::from_edges(false, 9, edges)
+    }
+
+    #[test]
+    fn test_prim() {
+        let g = clrs();
+        let forest = prim_mst(&g);
+        assert_eq!(37, forest.weight);
+        assert_eq!(8, forest.edges.len());
+        assert_eq!(37, forest.edges.iter().map(|&e| *g.edge(e).unwrap().data).sum::<u32>());
+        let other = prim_mst_with_queue(&g, BinaryHeap::new());
+        assert_eq!(37, other.weight);
+        assert_eq!(8, other.edges.len());
+    }
+
+    #[test]
+    fn test_decrease_key() {
+        let forest = prim_mst_decrease_key(&clrs());
+        assert_eq!(37, forest.weight);
+        assert_eq!(8, forest.edges.len());
+
+        let mut rng = Rng::new(9);
+        let edges: Vec<_> = (0..300).map(|_| (rng.gen_range(60) as usize, rng.gen_range(60) as usize, rng.gen_range(100))).collect();
+        let g: Graph<(), u64> = Graph::from_edges(false, 60, edges);
+        let (a, b) = (prim_mst(&g), prim_mst_decrease_key(&g));
+        assert_eq!(a.weight, b.weight);
+        assert_eq!(a.edges.len(), b.edges.len());
+    }
+
+    #[test]
+    fn test_forest() {
+        let mut g: Graph<(), f64> = Graph::from_edges(false, 6, vec![(0, 1, 1.5), (1, 2, 0.5), (0, 2, 1.0), (3, 4, 2.0), (4, 4, 0.1)]);
+        g.remove_node(5);
+        for forest in [prim_mst(&g), prim_mst_decrease_key(&g)] {
+            assert_eq!(3.5, forest.weight);
+            let mut edges = forest.edges;
+            edges.sort_unstable();
+            assert_eq!(vec![1, 2, 3], edges);
+        }
+    }
+}
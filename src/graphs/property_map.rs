@@ -0,0 +1,114 @@
+use crate::graph::{EdgeRef, NodeId};
+
+/// A value for every edge of a graph, like a weight or capacity
+///
+/// Lets algorithms read edge values without caring where they're stored: in the graph's edge
+/// data with `EdgeData`, in a vector or slice indexed by `EdgeId`, or computed by a closure from
+/// the edge. Changing the weights between runs then only needs a new vector, not a new graph.
+pub trait EdgeMap<E> {
+    type Value;
+
+    /// Value of an edge
+    ///
+    /// # Arguments
+    ///
+    /// * `edge` - Edge to look up
+    fn edge_value(&self, edge: EdgeRef<'_, E>) -> Self::Value;
+}
+
+/// A value for every node of a graph, like a label or a heuristic
+///
+/// Implemented for vectors and slices indexed by `NodeId`, and closures of the node.
+pub trait NodeMap {
+    type Value;
+
+    /// Value of a node
+    ///
+    /// # Arguments
+    ///
+    /// * `u` - Node to look up
+    fn node_value(&self, u: NodeId) -> Self::Value;
+}
+
+/// Edge map reading the data stored in each edge
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct EdgeData;
+
+impl<E: Copy> EdgeMap<E> for EdgeData {
+    type Value = E;
+
+    fn edge_value(&self, edge: EdgeRef<'_, E>) -> E {
+        *edge.data
+    }
+}
+
+impl<E, V: Copy> EdgeMap<E> for [V] {
+    type Value = V;
+
+    fn edge_value(&self, edge: EdgeRef<'_, E>) -> V {
+        self[edge.id]
+    }
+}
+
+impl<E, V: Copy> EdgeMap<E> for Vec<V> {
+    type Value = V;
+
+    fn edge_value(&self, edge: EdgeRef<'_, E>) -> V {
+        self[edge.id]
+    }
+}
+
+impl<E, V, F: Fn(EdgeRef<'_, E>) -> V> EdgeMap<E> for F {
+    type Value = V;
+
+    fn edge_value(&self, edge: EdgeRef<'_, E>) -> V {
+        self(edge)
+    }
+}
+
+impl<V: Copy> NodeMap for [V] {
+    type Value = V;
+
+    fn node_value(&self, u: NodeId) -> V {
+        self[u]
+    }
+}
+
+impl<V: Copy> NodeMap for Vec<V> {
+    type Value = V;
+
+    fn node_value(&self, u: NodeId) -> V {
+        self[u]
+    }
+}
+
+impl<V, F: Fn(NodeId) -> V> NodeMap for F {
+    type Value = V;
+
+    fn node_value(&self, u: NodeId) -> V {
+        self(u)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::Graph;
+
+    #[test]
+    fn test_edge_maps() {
+        let g: Graph<(), u32> = Graph::from_edges(true, 3, vec![(0, 1, 5), (1, 2, 7)]);
+        let e = g.edge(1).unwrap();
+        assert_eq!(7, EdgeData.edge_value(e));
+        assert_eq!(-2, vec![1i64, -2].edge_value(e));
+        assert_eq!(2.5, [0.5, 2.5][..].edge_value(e));
+        assert_eq!(14, (|e: EdgeRef<'_, u32>| 2 * *e.data).edge_value(e));
+    }
+
+    #[test]
+    fn test_node_maps() {
+        assert_eq!('b', vec!['a', 'b'].node_value(1));
+        assert_eq!(1, [3, 1][..].node_value(1));
+        assert_eq!(9, (|u: NodeId| u * u).node_value(3));
+    }
+}
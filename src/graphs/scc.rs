@@ -0,0 +1,253 @@
+use std::collections::HashMap;
+
+use crate::dfs::{adjacency, dfs};
+use crate::graph::{Graph, NodeId};
+
+const UNVISITED: usize = usize::MAX;
+
+/// Finds the strongly connected components of a graph with Tarjan's algorithm
+///
+/// Returns the components in reverse topological order of the condensation, so no component has
+/// an edge to a later one. Runs one DFS in `O(V + E)` time with an explicit stack, so large graphs
+/// don't overflow the call stack.
+///
+/// # Arguments
+///
+/// * `graph` - Graph to search
+pub fn tarjan_scc<N, E>(graph: &Graph<N, E>) -> Vec<Vec<NodeId>> {
+    let adjacency = adjacency(graph);
+    let n = graph.node_bound();
+    let mut index = vec![UNVISITED; n];
+    let mut low = vec![0; n];
+    let mut on_stack = vec![false; n];
+    let mut stack = Vec::new();
+    let mut calls: Vec<(NodeId, usize)> = Vec::new();
+    let mut next = 0;
+    let mut components = Vec::new();
+
+    for root in graph.node_ids() {
+        if index[root] != UNVISITED {
+            continue;
+        }
+        calls.push((root, 0));
+        index[root] = next;
+        low[root] = next;
+        next += 1;
+        stack.push(root);
+        on_stack[root] = true;
+
+        while let Some(&mut (u, ref mut pos)) = calls.last_mut() {
+            if *pos < adjacency[u].len() {
+                let (_, v) = adjacency[u][*pos];
+                *pos += 1;
+                if index[v] == UNVISITED {
+                    index[v] = next;
+                    low[v] = next;
+                    next += 1;
+                    stack.push(v);
+                    on_stack[v] = true;
+                    calls.push((v, 0));
+                } else if on_stack[v] {
+                    low[u] = low[u].min(index[v]);
+                }
+                continue;
+            }
+
+            calls.pop();
+            if let Some(&(parent, _)) = calls.last() {
+                low[parent] = low[parent].min(low[u]);
+            }
+            // u is the first node of its component found, so the component is everything above it
+            if low[u] == index[u] {
+                let mut component = Vec::new();
+                loop {
+                    let w = stack.pop().unwrap();
+                    on_stack[w] = false;
+                    component.push(w);
+                    if w == u {
+                        break;
+                    }
+                }
+                components.push(component);
+            }
+        }
+    }
+    components
+}
+
+/// Finds the strongly connected components of a graph with Kosaraju's algorithm
+///
+/// Returns the components in topological order of the condensation, so no component has an edge
+/// to an earlier one. Runs a DFS of the graph, then searches the transposed graph from each node in
+/// decreasing finish time, and each search finds one component. Takes `O(V + E)` time.
+///
+/// # Arguments
+///
+/// * `graph` - Graph to search
+pub fn kosaraju_scc<N, E>(graph: &Graph<N, E>) -> Vec<Vec<NodeId>> {
+    let finish_order = dfs(graph).finish_order;
+    let mut transpose = vec![Vec::new(); graph.node_bound()];
+    for u in graph.node_ids() {
+        for v in graph.neighbors(u) {
+            transpose[v].push(u);
+        }
+    }
+
+    let mut visited = vec![false; graph.node_bound()];
+    let mut components = Vec::new();
+    for &root in finish_order.iter().rev() {
+        if visited[root] {
+            continue;
+        }
+        visited[root] = true;
+        let mut component = Vec::new();
+        let mut stack = vec![root];
+        while let Some(u) = stack.pop() {
+            component.push(u);
+            for &v in transpose[u].iter() {
+                if !visited[v] {
+                    visited[v] = true;
+                    stack.push(v);
+                }
+            }
+        }
+        components.push(component);
+    }
+    components
+}
+
+/// Component graph of a graph's strongly connected components
+#[derive(Clone, Debug)]
+pub struct Condensation {
+    /// DAG with a node per component holding its nodes, and an edge between components holding
+    /// the number of edges between them in the original graph
+    pub graph: Graph<Vec<NodeId>, usize>,
+    /// Component of each node in the original graph, None for removed nodes
+    pub component: Vec<Option<NodeId>>,
+}
+
+/// Contracts each strongly connected component of a directed graph to a single node
+///
+/// The result is a DAG, with components numbered in topological order. Parallel edges between two
+/// components are merged into one edge labelled with how many there were, and edges inside a
+/// component are dropped.
+///
+/// # Arguments
+///
+/// * `graph` - Graph to contract
+///
+/// # Panics
+///
+/// If the graph is undirected
+pub fn condensation<N, E>(graph: &Graph<N, E>) -> Condensation {
+    assert!(graph.is_directed(), "condensation requires a directed graph");
+    let components = kosaraju_scc(graph);
+    let mut component = vec![None; graph.node_bound()];
+    for (i, c) in components.iter().enumerate() {
+        for &u in c {
+            component[u] = Some(i);
+        }
+    }
+
+    let mut multiplicity: HashMap<(NodeId, NodeId), usize> = HashMap::new();
+    for e in graph.edges() {
+        let (a, b) = (component[e.source].unwrap(), component[e.target].unwrap());
+        if a != b {
+            *multiplicity.entry((a, b)).or_insert(0) += 1;
+        }
+    }
+    let mut edges: Vec<_> = multiplicity.into_iter().collect();
+    edges.sort_unstable();
+
+    let mut dag = Graph::new_directed();
+    for c in components {
+        dag.add_node(c);
+    }
+    for ((a, b), count) in edges {
+        dag.add_edge(a, b, count);
+    }
+    Condensation { graph: dag, component }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Internal function to sort each component and the components, for comparison
+    fn normalize(mut components: Vec<Vec<NodeId>>) -> Vec<Vec<NodeId>> {
+        for c in components.iter_mut() {
+            c.sort_unstable();
+        }
+        components.sort();
+        components
+    }
+
+    /// CLRS Figure 22.9
+    fn clrs() -> Graph<(), ()> {
+        let edges = vec![
+            (0, 1, ()), (1, 2, ()), (1, 4, ()), (1, 5, ()), (2, 3, ()), (2, 6, ()), (3, 2, ()), (3, 7, ()),
+            (4, 0, ()), (4, 5, ()), (5, 6, ()), (6, 5, ()), (6, 7, ()), (7, 7, ()),
+        ];
+        Graph::from_edges(true, 8, edges)
+    }
+
+    #[test]
+    fn test_tarjan() {
+        let components = tarjan_scc(&clrs());
+        assert_eq!(vec![vec![0, 1, 4], vec![2, 3], vec![5, 6], vec![7]], normalize(components));
+    }
+
+    #[test]
+    fn test_kosaraju() {
+        let g = clrs();
+        assert_eq!(normalize(tarjan_scc(&g)), normalize(kosaraju_scc(&g)));
+        assert_eq!(vec![0, 1, 4], normalize(vec![kosaraju_scc(&g)[0].clone()])[0]);
+    }
+
+    #[test]
+    fn test_condensation() {
+        let mut g = clrs();
+        g.add_edge(4, 5, ());
+        let c = condensation(&g);
+        assert_eq!(4, c.graph.node_count());
+        assert_eq!(c.component[0], c.component[4]);
+        assert_ne!(c.component[0], c.component[2]);
+
+        // Edges go forward in the topological numbering, with parallel edges counted
+        let edges: Vec<_> = c.graph.edges().map(|e| (e.source, e.target, *e.data)).collect();
+        assert!(edges.iter().all(|&(a, b, _)| a < b));
+        let (a, b) = (c.component[4].unwrap(), c.component[5].unwrap());
+        assert_eq!(Some(&3), c.graph.find_edge(a, b).and_then(|e| c.graph.edge(e)).map(|e| e.data));
+        assert_eq!(5, edges.len());
+    }
+
+    #[test]
+    fn test_reverse_topological_order() {
+        let g = clrs();
+        let components = tarjan_scc(&g);
+        let mut component = vec![0; g.node_bound()];
+        for (i, c) in components.iter().enumerate() {
+            for &u in c {
+                component[u] = i;
+            }
+        }
+        for e in g.edges() {
+            assert!(component[e.source] >= component[e.target]);
+        }
+    }
+
+    #[test]
+    fn test_dag() {
+        let g: Graph<(), ()> = Graph::from_edges(true, 3, vec![(0, 1, ()), (1, 2, ())]);
+        assert_eq!(vec![vec![2], vec![1], vec![0]], tarjan_scc(&g));
+    }
+
+    #[test]
+    fn test_deep_cycle() {
+        let n = 100_000;
+        let g: Graph<(), ()> = Graph::from_edges(true, n, (0..n).map(|i| (i, (i + 1) % n, ())));
+        let components = tarjan_scc(&g);
+        assert_eq!(1, components.len());
+        assert_eq!(n, components[0].len());
+    }
+}
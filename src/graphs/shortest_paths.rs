@@ -0,0 +1,161 @@
+use std::fmt;
+use std::ops::{Add, Sub};
+
+use crate::graph::{EdgeId, NodeId};
+
+/// Edge weight for shortest path algorithms
+///
+/// Implemented for the primitive integer and floating point types.
+pub trait Weight: Copy + PartialOrd + Add<Output = Self> + Sub<Output = Self> {
+
+    /// Weight of an empty path
+    fn zero() -> Self;
+}
+
+macro_rules! impl_weight {
+    ($zero:expr, $($t:ty),*) => {
+        $(
+            impl Weight for $t {
+                fn zero() -> Self {
+                    $zero
+                }
+            }
+        )*
+    };
+}
+
+impl_weight!(0, i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+impl_weight!(0.0, f32, f64);
+
+/// Error for an edge with a negative weight, which the algorithm doesn't support
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct NegativeWeightError {
+    pub edge: EdgeId,
+}
+
+impl fmt::Display for NegativeWeightError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "edge {} has a negative weight", self.edge)
+    }
+}
+
+impl std::error::Error for NegativeWeightError {}
+
+/// Shortest paths from a single source
+///
+/// Vectors are indexed by `NodeId`, with None for nodes that aren't reachable from the source.
+/// Every single source algorithm returns this type, `bfs`, `dijkstra`, `bellman_ford`,
+/// `dag_shortest_paths` and `astar_paths`, so code using the paths doesn't depend on which
+/// algorithm found them.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ShortestPaths<W> {
+    /// Source node of the paths
+    pub source: NodeId,
+    /// Length of the shortest path to each node
+    pub distance: Vec<Option<W>>,
+    /// Node before each node on its shortest path, None for the source
+    pub predecessor: Vec<Option<NodeId>>,
+}
+
+impl<W: Copy> ShortestPaths<W> {
+
+    /// Creates `ShortestPaths` where only the source is reached
+    ///
+    /// # Arguments
+    ///
+    /// * `source` - Source node of the paths
+    /// * `n` - Node bound of the graph
+    /// * `zero` - Distance to the source
+    pub(crate) fn new(source: NodeId, n: usize, zero: W) -> ShortestPaths<W> {
+        let mut distance = vec![None; n];
+        distance[source] = Some(zero);
+        ShortestPaths { source, distance, predecessor: vec![None; n] }
+    }
+
+    /// Length of the shortest path to a node
+    ///
+    /// # Arguments
+    ///
+    /// * `v` - Target node
+    pub fn distance(&self, v: NodeId) -> Option<W> {
+        self.distance.get(v).copied().flatten()
+    }
+
+    /// Nodes on the shortest path from the source to a node, including both ends
+    ///
+    /// Returns None if the node isn't reachable.
+    ///
+    /// # Arguments
+    ///
+    /// * `v` - Target node
+    pub fn path_to(&self, v: NodeId) -> Option<Vec<NodeId>> {
+        self.distance(v)?;
+        let mut path = vec![v];
+        let mut u = v;
+        while let Some(p) = self.predecessor[u] {
+            path.push(p);
+            u = p;
+        }
+        path.reverse();
+        Some(path)
+    }
+
+    /// Edges of the shortest path tree as `(predecessor, node)` pairs, in order of node
+    ///
+    /// Every reached node except the source has one, so following them backwards from a node
+    /// gives its path.
+    pub fn tree(&self) -> impl Iterator<Item = (NodeId, NodeId)> + '_ {
+        self.predecessor.iter().enumerate().filter_map(|(v, p)| p.map(|u| (u, v)))
+    }
+}
+
+/// Shortest paths between every pair of nodes
+///
+/// Holds the single source shortest paths from each node, indexed by `NodeId`, with None for
+/// removed nodes.
+#[derive(Clone, Debug, PartialEq)]
+pub struct AllPairsShortestPaths<W> {
+    pub sources: Vec<Option<ShortestPaths<W>>>,
+}
+
+impl<W: Copy> AllPairsShortestPaths<W> {
+
+    /// Length of the shortest path from `u` to `v`
+    ///
+    /// # Arguments
+    ///
+    /// * `u` - Source node
+    /// * `v` - Target node
+    pub fn distance(&self, u: NodeId, v: NodeId) -> Option<W> {
+        self.sources.get(u)?.as_ref()?.distance(v)
+    }
+
+    /// Nodes on the shortest path from `u` to `v`, including both ends
+    ///
+    /// # Arguments
+    ///
+    /// * `u` - Source node
+    /// * `v` - Target node
+    pub fn path(&self, u: NodeId, v: NodeId) -> Option<Vec<NodeId>> {
+        self.sources.get(u)?.as_ref()?.path_to(v)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_shortest_paths() {
+        let mut paths = ShortestPaths::new(1, 5, 0);
+        for &(u, v, d) in [(1, 0, 2), (0, 3, 5), (1, 4, 1)].iter() {
+            paths.distance[v] = Some(d);
+            paths.predecessor[v] = Some(u);
+        }
+        assert_eq!((Some(5), None), (paths.distance(3), paths.distance(2)));
+        assert_eq!(Some(vec![1, 0, 3]), paths.path_to(3));
+        assert_eq!(Some(vec![1]), paths.path_to(1));
+        assert_eq!(None, paths.path_to(2));
+        assert_eq!(vec![(1, 0), (0, 3), (1, 4)], paths.tree().collect::<Vec<_>>());
+    }
+}
@@ -0,0 +1,77 @@
+use crate::boruvka::boruvka_mst;
+use crate::graph::{EdgeId, Graph};
+use crate::kruskal::kruskal_mst;
+use crate::prim::prim_mst;
+use crate::shortest_paths::Weight;
+
+/// Minimum spanning forest of an undirected graph
+///
+/// Holds one minimum spanning tree per connected component, so a connected graph gives a single
+/// tree of `V - 1` edges.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SpanningForest<W> {
+    /// Edges of the forest
+    pub edges: Vec<EdgeId>,
+    /// Total weight of the edges
+    pub weight: W,
+}
+
+/// Algorithm used to compute a minimum spanning forest
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MstAlgorithm {
+    /// Grows one tree at a time from a heap of edges, see `prim_mst`
+    Prim,
+    /// Adds edges in sorted order with a Union-Find, see `kruskal_mst`
+    Kruskal,
+    /// Merges every tree with its lightest edge each round, see `boruvka_mst`
+    Boruvka,
+}
+
+impl MstAlgorithm {
+
+    /// Every algorithm, for comparing them
+    pub const ALL: [MstAlgorithm; 3] = [MstAlgorithm::Prim, MstAlgorithm::Kruskal, MstAlgorithm::Boruvka];
+
+    /// Function implementing the algorithm, all with the same signature
+    pub fn function<N, W: Weight>(self) -> fn(&Graph<N, W>) -> SpanningForest<W> {
+        match self {
+            MstAlgorithm::Prim => prim_mst,
+            MstAlgorithm::Kruskal => kruskal_mst,
+            MstAlgorithm::Boruvka => boruvka_mst,
+        }
+    }
+}
+
+/// Minimum spanning forest of an undirected graph with a chosen algorithm
+///
+/// Every algorithm finds a forest of the same weight, but can pick different edges of equal weight.
+///
+/// # Arguments
+///
+/// * `graph` - Graph to span
+/// * `algorithm` - Algorithm to use
+///
+/// # Panics
+///
+/// If the graph is directed
+pub fn minimum_spanning_forest<N, W: Weight>(graph: &Graph<N, W>, algorithm: MstAlgorithm) -> SpanningForest<W> {
+    algorithm.function()(graph)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::Rng;
+
+    #[test]
+    fn test_algorithms_agree() {
+        let mut rng = Rng::new(17);
+        let edges: Vec<_> = (0..500).map(|_| (rng.gen_range(150) as usize, rng.gen_range(150) as usize, rng.gen_range(1000) as i64 - 500)).collect();
+        let g: Graph<(), i64> = Graph::from_edges(false, 150, edges);
+        let forests: Vec<_> = MstAlgorithm::ALL.iter().map(|&a| minimum_spanning_forest(&g, a)).collect();
+        for forest in forests.iter() {
+            assert_eq!(forests[0].weight, forest.weight);
+            assert_eq!(forests[0].edges.len(), forest.edges.len());
+        }
+    }
+}
@@ -0,0 +1,185 @@
+use std::collections::VecDeque;
+use std::fmt;
+
+use crate::dfs::{dfs, EdgeKind};
+use crate::graph::{Graph, NodeId};
+
+/// Error for a graph with a cycle, which has no topological order
+///
+/// `cycle` lists the nodes of one cycle in order, with an edge from each node to the next and
+/// from the last node back to the first.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CycleError {
+    pub cycle: Vec<NodeId>,
+}
+
+impl fmt::Display for CycleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "graph has a cycle through nodes {:?}", self.cycle)
+    }
+}
+
+impl std::error::Error for CycleError {}
+
+/// Algorithm used to compute a topological order
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TopologicalSortAlgorithm {
+    /// Repeatedly removes a node with no incoming edges, using a queue
+    Kahn,
+    /// Orders nodes by decreasing DFS finish time
+    Dfs,
+}
+
+/// Orders the nodes of a directed acyclic graph so every edge goes from an earlier to a later node
+///
+/// Uses Kahn's algorithm, in `O(V + E)` time. Returns a `CycleError` if the graph has a cycle.
+///
+/// # Arguments
+///
+/// * `graph` - Graph to sort
+///
+/// # Panics
+///
+/// If the graph is undirected
+pub fn topological_sort<N, E>(graph: &Graph<N, E>) -> Result<Vec<NodeId>, CycleError> {
+    topological_sort_with(graph, TopologicalSortAlgorithm::Kahn)
+}
+
+/// Orders the nodes of a directed acyclic graph with a chosen algorithm
+///
+/// Both algorithms take `O(V + E)` time, but can return different orders.
+///
+/// # Arguments
+///
+/// * `graph` - Graph to sort
+/// * `algorithm` - Algorithm to use
+///
+/// # Panics
+///
+/// If the graph is undirected
+pub fn topological_sort_with<N, E>(graph: &Graph<N, E>, algorithm: TopologicalSortAlgorithm) -> Result<Vec<NodeId>, CycleError> {
+    assert!(graph.is_directed(), "topological sort requires a directed graph");
+    match algorithm {
+        TopologicalSortAlgorithm::Kahn => kahn(graph),
+        TopologicalSortAlgorithm::Dfs => dfs_order(graph),
+    }
+}
+
+/// Internal function for Kahn's algorithm
+fn kahn<N, E>(graph: &Graph<N, E>) -> Result<Vec<NodeId>, CycleError> {
+    let mut in_degree = vec![0; graph.node_bound()];
+    for e in graph.edges() {
+        in_degree[e.target] += 1;
+    }
+    let mut queue: VecDeque<NodeId> = graph.node_ids().filter(|&u| in_degree[u] == 0).collect();
+    let mut order = Vec::with_capacity(graph.node_count());
+
+    while let Some(u) = queue.pop_front() {
+        order.push(u);
+        for v in graph.neighbors(u) {
+            in_degree[v] -= 1;
+            if in_degree[v] == 0 {
+                queue.push_back(v);
+            }
+        }
+    }
+
+    if order.len() == graph.node_count() {
+        return Ok(order);
+    }
+
+    // Every node left has an incoming edge from another node left, so walking those edges
+    // backwards must revisit a node
+    let start = graph.node_ids().find(|&u| in_degree[u] > 0).unwrap();
+    let mut seen = vec![false; graph.node_bound()];
+    let mut walk = vec![start];
+    seen[start] = true;
+    loop {
+        let u = *walk.last().unwrap();
+        let p = graph.predecessors(u).find(|&p| in_degree[p] > 0).unwrap();
+        if seen[p] {
+            let i = walk.iter().position(|&w| w == p).unwrap();
+            let mut cycle = walk.split_off(i);
+            cycle.reverse();
+            return Err(CycleError { cycle });
+        }
+        seen[p] = true;
+        walk.push(p);
+    }
+}
+
+/// Internal function for the DFS finish time algorithm
+fn dfs_order<N, E>(graph: &Graph<N, E>) -> Result<Vec<NodeId>, CycleError> {
+    let result = dfs(graph);
+    if let Some(e) = result.edges_of_kind(EdgeKind::Back).next() {
+        let (u, v) = graph.endpoints(e).unwrap();
+        let cycle = result.tree_path(v, u).unwrap();
+        return Err(CycleError { cycle });
+    }
+    let mut order = result.finish_order;
+    order.reverse();
+    Ok(order)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Internal function to check that every edge goes forward in an order
+    fn is_topological<N, E>(graph: &Graph<N, E>, order: &[NodeId]) -> bool {
+        let mut position = vec![usize::MAX; graph.node_bound()];
+        for (i, &u) in order.iter().enumerate() {
+            position[u] = i;
+        }
+        order.len() == graph.node_count() && graph.edges().all(|e| position[e.source] < position[e.target])
+    }
+
+    /// Internal function to check that a cycle is made of edges in the graph
+    fn is_cycle<N, E>(graph: &Graph<N, E>, cycle: &[NodeId]) -> bool {
+        !cycle.is_empty() && (0..cycle.len()).all(|i| graph.find_edge(cycle[i], cycle[(i + 1) % cycle.len()]).is_some())
+    }
+
+    #[test]
+    fn test_kahn() {
+        // CLRS Figure 22.7, getting dressed
+        let edges = vec![(0, 1, ()), (0, 3, ()), (1, 2, ()), (1, 3, ()), (4, 2, ()), (5, 6, ()), (5, 1, ()), (6, 1, ())];
+        let g: Graph<(), ()> = Graph::from_edges(true, 8, edges);
+        let order = topological_sort(&g).unwrap();
+        assert_eq!(vec![0, 4, 5, 7, 6, 1, 2, 3], order);
+        assert!(is_topological(&g, &order));
+    }
+
+    #[test]
+    fn test_dfs() {
+        let edges = vec![(0, 1, ()), (0, 3, ()), (1, 2, ()), (1, 3, ()), (4, 2, ()), (5, 6, ()), (5, 1, ()), (6, 1, ())];
+        let g: Graph<(), ()> = Graph::from_edges(true, 8, edges);
+        let order = topological_sort_with(&g, TopologicalSortAlgorithm::Dfs).unwrap();
+        assert!(is_topological(&g, &order));
+    }
+
+    #[test]
+    fn test_cycle() {
+        let edges = vec![(0, 1, ()), (1, 2, ()), (2, 3, ()), (3, 1, ()), (4, 0, ())];
+        let g: Graph<(), ()> = Graph::from_edges(true, 5, edges);
+        for &algorithm in [TopologicalSortAlgorithm::Kahn, TopologicalSortAlgorithm::Dfs].iter() {
+            let err = topological_sort_with(&g, algorithm).unwrap_err();
+            assert_eq!(3, err.cycle.len());
+            assert!(is_cycle(&g, &err.cycle));
+        }
+    }
+
+    #[test]
+    fn test_self_loop() {
+        let g: Graph<(), ()> = Graph::from_edges(true, 2, vec![(0, 1, ()), (1, 1, ())]);
+        assert_eq!(vec![1], topological_sort(&g).unwrap_err().cycle);
+        assert_eq!(vec![1], topological_sort_with(&g, TopologicalSortAlgorithm::Dfs).unwrap_err().cycle);
+    }
+
+    #[test]
+    fn test_removed_nodes() {
+        let mut g: Graph<(), ()> = Graph::from_edges(true, 3, vec![(2, 0, ()), (0, 1, ())]);
+        g.remove_node(1);
+        assert_eq!(vec![2, 0], topological_sort(&g).unwrap());
+        assert_eq!(vec![2, 0], topological_sort_with(&g, TopologicalSortAlgorithm::Dfs).unwrap());
+    }
+}
@@ -0,0 +1,185 @@
+use crate::graph::{EdgeId, EdgeRef, Graph, NodeId};
+
+/// Where the nodes and edges of a graph ended up in a graph built from it
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Remapping {
+    /// New index of each old node, indexed by the old `NodeId` with None for nodes left out
+    pub nodes: Vec<Option<NodeId>>,
+    /// New index of each old edge, indexed by the old `EdgeId` with None for edges left out
+    pub edges: Vec<Option<EdgeId>>,
+}
+
+impl Remapping {
+
+    /// Internal function to create a remapping where nothing has been copied yet
+    fn new<N, E>(graph: &Graph<N, E>) -> Remapping {
+        Remapping { nodes: vec![None; graph.node_bound()], edges: vec![None; graph.edge_bound()] }
+    }
+
+    /// Old index of each new node, the inverse of `nodes`
+    pub fn inverse_nodes(&self) -> Vec<NodeId> {
+        invert(&self.nodes)
+    }
+
+    /// Old index of each new edge, the inverse of `edges`
+    pub fn inverse_edges(&self) -> Vec<EdgeId> {
+        invert(&self.edges)
+    }
+}
+
+/// Internal function to invert a partial map of indexes into one with no gaps
+fn invert(map: &[Option<usize>]) -> Vec<usize> {
+    let mut inverse = vec![0; map.iter().flatten().count()];
+    for (old, new) in map.iter().enumerate() {
+        if let Some(new) = new {
+            inverse[*new] = old;
+        }
+    }
+    inverse
+}
+
+impl<N: Clone, E: Clone> Graph<N, E> {
+
+    /// Builds the subgraph on a set of nodes and every edge between them
+    ///
+    /// The new graph has no removed slots: the nodes are numbered in the order given, with
+    /// duplicates skipped, and the edges keep their order. The remapping gives the new index of
+    /// each old node and edge.
+    ///
+    /// # Arguments
+    ///
+    /// * `nodes` - Nodes to keep
+    ///
+    /// # Panics
+    ///
+    /// If a node doesn't exist
+    pub fn induced_subgraph(&self, nodes: &[NodeId]) -> (Graph<N, E>, Remapping) {
+        let mut graph = Graph::new(self.is_directed());
+        let mut map = Remapping::new(self);
+        for &u in nodes {
+            let data = self.node(u).unwrap_or_else(|| panic!("node {} is not in the graph", u));
+            if map.nodes[u].is_none() {
+                map.nodes[u] = Some(graph.add_node(data.clone()));
+            }
+        }
+        self.copy_edges(&mut graph, &mut map, |_| true);
+        (graph, map)
+    }
+
+    /// Builds the subgraph of every node and the edges `keep` returns true for
+    ///
+    /// The new graph has no removed slots, and the nodes and edges keep their order. The remapping
+    /// gives the new index of each old node and edge.
+    ///
+    /// # Arguments
+    ///
+    /// * `keep` - Checks if an edge should be kept
+    pub fn filter_edges<F: FnMut(EdgeRef<'_, E>) -> bool>(&self, keep: F) -> (Graph<N, E>, Remapping) {
+        let mut graph = Graph::new(self.is_directed());
+        let mut map = Remapping::new(self);
+        self.copy_nodes(&mut graph, &mut map);
+        self.copy_edges(&mut graph, &mut map, keep);
+        (graph, map)
+    }
+
+    /// Builds the disjoint union of two graphs, with the nodes and edges of `other` after those of
+    /// `self`
+    ///
+    /// The new graph has no removed slots. The remappings give the new index of each node and edge
+    /// of `self` and `other`.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - Graph to add
+    ///
+    /// # Panics
+    ///
+    /// If one graph is directed and the other isn't
+    pub fn union(&self, other: &Graph<N, E>) -> (Graph<N, E>, Remapping, Remapping) {
+        assert_eq!(self.is_directed(), other.is_directed(), "both graphs must be directed or undirected");
+        let mut graph = Graph::new(self.is_directed());
+        let mut first = Remapping::new(self);
+        let mut second = Remapping::new(other);
+        self.copy_nodes(&mut graph, &mut first);
+        other.copy_nodes(&mut graph, &mut second);
+        self.copy_edges(&mut graph, &mut first, |_| true);
+        other.copy_edges(&mut graph, &mut second, |_| true);
+        (graph, first, second)
+    }
+
+    /// Internal function to copy every node into another graph
+    fn copy_nodes(&self, graph: &mut Graph<N, E>, map: &mut Remapping) {
+        for (u, data) in self.nodes() {
+            map.nodes[u] = Some(graph.add_node(data.clone()));
+        }
+    }
+
+    /// Internal function to copy the kept edges between copied nodes into another graph
+    fn copy_edges<F: FnMut(EdgeRef<'_, E>) -> bool>(&self, graph: &mut Graph<N, E>, map: &mut Remapping, mut keep: F) {
+        for e in self.edges() {
+            if let (Some(u), Some(v)) = (map.nodes[e.source], map.nodes[e.target]) {
+                if keep(e) {
+                    map.edges[e.id] = Some(graph.add_edge(u, v, e.data.clone()));
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_induced_subgraph() {
+        let mut g: Graph<char, u32> = Graph::new_directed();
+        for c in ['a', 'b', 'c', 'd'] {
+            g.add_node(c);
+        }
+        g.add_edge(0, 1, 1);
+        g.add_edge(1, 2, 2);
+        g.add_edge(2, 3, 3);
+        g.add_edge(3, 1, 4);
+
+        let (h, map) = g.induced_subgraph(&[3, 1, 3, 2]);
+        assert_eq!(vec![(0, &'d'), (1, &'b'), (2, &'c')], h.nodes().collect::<Vec<_>>());
+        assert_eq!(vec![None, Some(1), Some(2), Some(0)], map.nodes);
+        assert_eq!(vec![None, Some(0), Some(1), Some(2)], map.edges);
+        assert_eq!(vec![(1, 2, 2), (2, 0, 3), (0, 1, 4)], h.edges().map(|e| (e.source, e.target, *e.data)).collect::<Vec<_>>());
+        assert_eq!(vec![3, 1, 2], map.inverse_nodes());
+        assert_eq!(vec![1, 2, 3], map.inverse_edges());
+    }
+
+    #[test]
+    fn test_filter_edges() {
+        let mut g: Graph<(), u32> = Graph::from_edges(false, 4, vec![(0, 1, 5), (1, 2, 1), (2, 3, 7)]);
+        g.remove_node(0);
+        let (h, map) = g.filter_edges(|e| *e.data > 2);
+        assert_eq!(3, h.node_count());
+        assert_eq!(vec![None, Some(0), Some(1), Some(2)], map.nodes);
+        assert_eq!(vec![None, None, Some(0)], map.edges);
+        assert_eq!(Some(0), h.find_edge(2, 1));
+    }
+
+    #[test]
+    fn test_union() {
+        let a: Graph<(), ()> = Graph::path(3);
+        let b: Graph<(), ()> = Graph::cycle(3);
+        let (g, first, second) = a.union(&b);
+        assert_eq!(6, g.node_count());
+        assert_eq!(5, g.edge_count());
+        assert_eq!(vec![Some(0), Some(1), Some(2)], first.nodes);
+        assert_eq!(vec![Some(3), Some(4), Some(5)], second.nodes);
+        assert_eq!(vec![Some(2), Some(3), Some(4)], second.edges);
+        assert_eq!(Some(4), g.find_edge(5, 3));
+        assert_eq!(None, g.find_edge(2, 3));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_union_mixed() {
+        let a: Graph<(), ()> = Graph::new_directed();
+        let b: Graph<(), ()> = Graph::new_undirected();
+        a.union(&b);
+    }
+}
@@ -0,0 +1,89 @@
+use crate::bit_set::BitSet;
+use crate::graph::{Graph, NodeId};
+
+/// Finds every node reachable from every node, by Warshall's algorithm on rows of bits
+///
+/// Row `u` of the result has bit `v` set if there's a path of at least one edge from `u` to `v`, so
+/// `u` is in its own row only if it's on a cycle. In an undirected graph an edge can be walked
+/// back, so every node with an edge reaches itself. Each row starts as the node's neighbours, then
+/// for every node `k` in turn, each row that reaches `k` takes the union of `k`'s row, since a path
+/// through `k` reaches everything `k` does. The union combines 64 bits at a time, so it takes
+/// `O(V^3 / 64)` time and `O(V^2 / 64)` space.
+///
+/// # Arguments
+///
+/// * `graph` - Graph to search, edges are followed in both directions if undirected
+pub fn transitive_closure<N, E>(graph: &Graph<N, E>) -> Vec<BitSet> {
+    let n = graph.node_bound();
+    let mut rows: Vec<BitSet> = (0..n).map(|_| BitSet::with_num_bits(n)).collect();
+    for u in graph.node_ids() {
+        rows[u].extend(graph.neighbors(u));
+    }
+    for k in graph.node_ids() {
+        let through = rows[k].clone();
+        for row in rows.iter_mut() {
+            if row.test(k) {
+                row.union_with(&through);
+            }
+        }
+    }
+    rows
+}
+
+/// Checks if there's a path of at least one edge from one node to another, from a transitive
+/// closure
+///
+/// # Arguments
+///
+/// * `closure` - Result of `transitive_closure`
+/// * `u` - Node the path starts at
+/// * `v` - Node the path ends at
+pub fn reaches(closure: &[BitSet], u: NodeId, v: NodeId) -> bool {
+    closure.get(u).is_some_and(|row| row.test(v))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bfs::Bfs;
+    use crate::rng::Rng;
+
+    #[test]
+    fn test_dag() {
+        let g: Graph<(), ()> = Graph::from_edges(true, 5, vec![(0, 1, ()), (1, 2, ()), (0, 3, ()), (4, 3, ())]);
+        let closure = transitive_closure(&g);
+        assert_eq!(vec![1, 2, 3], closure[0].iter().collect::<Vec<_>>());
+        assert_eq!(vec![3], closure[4].iter().collect::<Vec<_>>());
+        assert!(closure[3].is_empty());
+        assert!(reaches(&closure, 0, 2));
+        assert!(!reaches(&closure, 2, 0));
+        assert!(!reaches(&closure, 0, 0));
+    }
+
+    #[test]
+    fn test_cycle() {
+        let g: Graph<(), ()> = Graph::from_edges(true, 4, vec![(0, 1, ()), (1, 2, ()), (2, 0, ()), (2, 3, ())]);
+        let closure = transitive_closure(&g);
+        for row in &closure[..3] {
+            assert_eq!(vec![0, 1, 2, 3], row.iter().collect::<Vec<_>>());
+        }
+        assert!(closure[3].is_empty());
+    }
+
+    #[test]
+    fn test_matches_bfs() {
+        let mut rng = Rng::new(149);
+        let n = 60;
+        let edges: Vec<_> = (0..90).map(|_| (rng.gen_range(n) as usize, rng.gen_range(n) as usize, ())).collect();
+        let g: Graph<(), ()> = Graph::from_edges(true, n as usize, edges);
+        let closure = transitive_closure(&g);
+        for u in g.node_ids() {
+            // Nodes reachable by at least one edge are those found from u's neighbours
+            let mut expected = BitSet::with_num_bits(n as usize);
+            for v in g.neighbors(u) {
+                expected.extend(Bfs::new(&g, v));
+            }
+            assert_eq!(expected, closure[u]);
+        }
+    }
+}
@@ -0,0 +1,302 @@
+use std::f64::consts::LN_2;
+use std::hash::Hash;
+use std::marker::PhantomData;
+
+use crate::rng::Rng;
+use crate::universal_hasher::{MultiplyShift, UniversalHasher};
+
+/// Computes the optimal number of cells for a Bloom filter
+///
+/// `m = -n * ln(p) / ln(2)^2`, rounded up and at least 1.
+///
+/// # Arguments
+///
+/// * `expected_items` - Number of items expected to be inserted
+/// * `false_positive_rate` - Target false positive rate, between 0 and 1 exclusive
+pub fn optimal_num_cells(expected_items: usize, false_positive_rate: f64) -> usize {
+    assert!(false_positive_rate > 0.0 && false_positive_rate < 1.0, "false positive rate must be in (0, 1)");
+    let n = expected_items.max(1) as f64;
+    let m = -n * false_positive_rate.ln() / (LN_2 * LN_2);
+    (m.ceil() as usize).max(1)
+}
+
+/// Computes the optimal number of hash functions for a Bloom filter
+///
+/// `k = (m / n) * ln(2)`, rounded and at least 1.
+///
+/// # Arguments
+///
+/// * `num_cells` - Number of cells in the filter
+/// * `expected_items` - Number of items expected to be inserted
+pub fn optimal_num_hashes(num_cells: usize, expected_items: usize) -> u32 {
+    let k = (num_cells as f64 / expected_items.max(1) as f64) * LN_2;
+    (k.round() as u32).max(1)
+}
+
+/// The pair of hash functions used to derive a Bloom filter's cell indexes
+///
+/// Uses the double hashing scheme `g_i(x) = h1(x) + i * h2(x) mod m` from Kirsch and Mitzenmacher,
+/// which keeps the false positive rate of `k` independent functions while only hashing twice.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DoubleHasher {
+    h1: MultiplyShift,
+    h2: MultiplyShift,
+}
+
+impl DoubleHasher {
+
+    /// Draws a new pair of hash functions
+    ///
+    /// # Arguments
+    ///
+    /// * `rng` - Source of the random parameters
+    pub fn new(rng: &mut Rng) -> DoubleHasher {
+        DoubleHasher { h1: MultiplyShift::new(64, rng), h2: MultiplyShift::new(64, rng) }
+    }
+
+    /// Returns an iterator over the `k` cell indexes of an item
+    ///
+    /// # Arguments
+    ///
+    /// * `item` - Item to hash
+    /// * `k` - Number of indexes
+    /// * `m` - Number of cells
+    pub fn indexes<T: Hash + ?Sized>(&self, item: &T, k: u32, m: usize) -> impl Iterator<Item = usize> {
+        let a = self.h1.hash(item);
+        let b = self.h2.hash(item) | 1;
+        let m = m as u64;
+        (0..k as u64).map(move |i| (a.wrapping_add(i.wrapping_mul(b)) % m) as usize)
+    }
+}
+
+/// A Bloom filter, a space efficient probabilistic set
+///
+/// `contains` never has false negatives, but may report an item that was never inserted with a
+/// probability close to the false positive rate given at construction.
+#[derive(Debug)]
+pub struct BloomFilter<T: Hash + ?Sized> {
+    bits: Vec<u64>,
+    num_bits: usize,
+    num_hashes: u32,
+    hasher: DoubleHasher,
+    marker: PhantomData<fn(&T)>,
+}
+
+impl<T: Hash + ?Sized> BloomFilter<T> {
+
+    /// Creates a new empty `BloomFilter` sized for a number of items and false positive rate
+    ///
+    /// # Arguments
+    ///
+    /// * `expected_items` - Number of items expected to be inserted
+    /// * `false_positive_rate` - Target false positive rate once `expected_items` are inserted
+    pub fn new(expected_items: usize, false_positive_rate: f64) -> BloomFilter<T> {
+        BloomFilter::with_rng(expected_items, false_positive_rate, &mut Rng::from_entropy())
+    }
+
+    /// Creates a new empty `BloomFilter` with seeded hash functions
+    ///
+    /// Filters with the same size, rate, and seed are compatible for `union` and `intersection`.
+    ///
+    /// # Arguments
+    ///
+    /// * `expected_items` - Number of items expected to be inserted
+    /// * `false_positive_rate` - Target false positive rate once `expected_items` are inserted
+    /// * `seed` - Seed for the hash functions
+    pub fn with_seed(expected_items: usize, false_positive_rate: f64, seed: u64) -> BloomFilter<T> {
+        BloomFilter::with_rng(expected_items, false_positive_rate, &mut Rng::new(seed))
+    }
+
+    /// Internal function to create a `BloomFilter` with hash functions drawn from `rng`
+    fn with_rng(expected_items: usize, false_positive_rate: f64, rng: &mut Rng) -> BloomFilter<T> {
+        let num_bits = optimal_num_cells(expected_items, false_positive_rate);
+        BloomFilter {
+            bits: vec![0; num_bits.div_ceil(64)],
+            num_bits,
+            num_hashes: optimal_num_hashes(num_bits, expected_items),
+            hasher: DoubleHasher::new(rng),
+            marker: PhantomData,
+        }
+    }
+
+    /// Number of bits in the filter
+    pub fn num_bits(&self) -> usize {
+        self.num_bits
+    }
+
+    /// Number of hash functions per item
+    pub fn num_hashes(&self) -> u32 {
+        self.num_hashes
+    }
+
+    /// Inserts an item into the `BloomFilter`
+    ///
+    /// # Arguments
+    ///
+    /// * `item` - Item to insert
+    pub fn insert(&mut self, item: &T) {
+        for i in self.hasher.indexes(item, self.num_hashes, self.num_bits) {
+            self.bits[i / 64] |= 1 << (i % 64);
+        }
+    }
+
+    /// Checks if an item may be in the `BloomFilter`
+    ///
+    /// Returns `false` only if the item was never inserted.
+    ///
+    /// # Arguments
+    ///
+    /// * `item` - Item to look for
+    pub fn contains(&self, item: &T) -> bool {
+        self.hasher
+            .indexes(item, self.num_hashes, self.num_bits)
+            .all(|i| self.bits[i / 64] & (1 << (i % 64)) != 0)
+    }
+
+    /// Estimates the number of distinct items inserted
+    ///
+    /// Uses the Swamidass–Baldi estimate `n = -(m / k) * ln(1 - X / m)` where `X` is the number
+    /// of set bits. Returns infinity if every bit is set.
+    pub fn estimated_len(&self) -> f64 {
+        let m = self.num_bits as f64;
+        let x = self.bits.iter().map(|w| w.count_ones() as f64).sum::<f64>();
+        -(m / self.num_hashes as f64) * (1.0 - x / m).ln()
+    }
+
+    /// Checks if another filter has the same size and hash functions
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - Filter to compare against
+    pub fn is_compatible(&self, other: &BloomFilter<T>) -> bool {
+        self.num_bits == other.num_bits && self.num_hashes == other.num_hashes && self.hasher == other.hasher
+    }
+
+    /// Creates the union of two compatible filters
+    ///
+    /// The result is exactly the filter that would have been built by inserting the items of both.
+    /// Returns None if the filters are not compatible.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - Filter to union with
+    pub fn union(&self, other: &BloomFilter<T>) -> Option<BloomFilter<T>> {
+        self.combine(other, |a, b| a | b)
+    }
+
+    /// Creates the intersection of two compatible filters
+    ///
+    /// Contains every item in both filters, but may have a higher false positive rate than a
+    /// filter built from only the common items. Returns None if the filters are not compatible.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - Filter to intersect with
+    pub fn intersection(&self, other: &BloomFilter<T>) -> Option<BloomFilter<T>> {
+        self.combine(other, |a, b| a & b)
+    }
+
+    /// Internal function to combine the bits of two compatible filters word by word
+    fn combine(&self, other: &BloomFilter<T>, f: impl Fn(u64, u64) -> u64) -> Option<BloomFilter<T>> {
+        if !self.is_compatible(other) {
+            return None;
+        }
+        let mut result = self.clone();
+        for (a, b) in result.bits.iter_mut().zip(other.bits.iter()) {
+            *a = f(*a, *b);
+        }
+        Some(result)
+    }
+}
+
+impl<T: Hash + ?Sized> Clone for BloomFilter<T> {
+    fn clone(&self) -> Self {
+        BloomFilter {
+            bits: self.bits.clone(),
+            num_bits: self.num_bits,
+            num_hashes: self.num_hashes,
+            hasher: self.hasher,
+            marker: PhantomData,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sizing() {
+        // 1% false positive rate needs about 9.6 bits per item and 7 hash functions
+        let m = optimal_num_cells(1000, 0.01);
+        assert_eq!(9586, m);
+        assert_eq!(7, optimal_num_hashes(m, 1000));
+
+        let filter: BloomFilter<u32> = BloomFilter::new(1000, 0.01);
+        assert_eq!(9586, filter.num_bits());
+        assert_eq!(7, filter.num_hashes());
+    }
+
+    #[test]
+    fn test_insert_contains() {
+        let mut filter = BloomFilter::with_seed(100, 0.01, 1);
+        for i in 0..100 {
+            filter.insert(&i);
+        }
+        for i in 0..100 {
+            assert!(filter.contains(&i));
+        }
+
+        let mut filter: BloomFilter<str> = BloomFilter::new(10, 0.01);
+        filter.insert("apple");
+        assert!(filter.contains("apple"));
+    }
+
+    #[test]
+    fn test_false_positive_rate() {
+        let mut filter = BloomFilter::with_seed(1000, 0.01, 2);
+        for i in 0..1000 {
+            filter.insert(&i);
+        }
+        let false_positives = (1000..101000).filter(|i| filter.contains(i)).count();
+        assert!(false_positives < 2000);
+    }
+
+    #[test]
+    fn test_estimated_len() {
+        let mut filter = BloomFilter::with_seed(1000, 0.01, 3);
+        assert_eq!(0.0, filter.estimated_len());
+        for i in 0..500 {
+            filter.insert(&i);
+        }
+        let n = filter.estimated_len();
+        assert!(n > 475.0 && n < 525.0);
+    }
+
+    #[test]
+    fn test_union_intersection() {
+        let mut a = BloomFilter::with_seed(100, 0.01, 4);
+        let mut b = BloomFilter::with_seed(100, 0.01, 4);
+        for i in 0..50 {
+            a.insert(&i);
+        }
+        for i in 25..75 {
+            b.insert(&i);
+        }
+
+        let u = a.union(&b).unwrap();
+        for i in 0..75 {
+            assert!(u.contains(&i));
+        }
+
+        let n = a.intersection(&b).unwrap();
+        for i in 25..50 {
+            assert!(n.contains(&i));
+        }
+
+        let c = BloomFilter::with_seed(100, 0.01, 5);
+        assert!(a.union(&c).is_none());
+        let d = BloomFilter::with_seed(200, 0.01, 4);
+        assert!(a.intersection(&d).is_none());
+    }
+}
@@ -0,0 +1,245 @@
+use std::collections::BTreeMap;
+use std::hash::Hash;
+
+use crate::universal_hasher::{MultiplyShift, UniversalHasher};
+
+/// A consistent hashing ring, assigns keys to a changing set of nodes
+///
+/// Every node is hashed to several points on a ring of `2^64` positions (its virtual nodes), and
+/// a key is routed to the node owning the first point clockwise from the key's hash. Adding or
+/// removing a node only moves the keys between it and its neighbouring points, about `1/n` of the
+/// keys, instead of nearly all of them like `hash(key) mod n`[1].
+///
+/// [1] Karger, David, et al. <i>Consistent hashing and random trees<i>.
+#[derive(Clone, Debug)]
+pub struct ConsistentHashRing<N: Hash + Eq> {
+    ring: BTreeMap<u64, usize>,
+    nodes: Vec<Option<N>>,
+    virtual_nodes: usize,
+    hasher: MultiplyShift,
+}
+
+impl<N: Hash + Eq> ConsistentHashRing<N> {
+
+    /// Creates a new empty `ConsistentHashRing`
+    ///
+    /// More virtual nodes spread each node's share of the ring more evenly, at the cost of memory.
+    ///
+    /// # Arguments
+    ///
+    /// * `virtual_nodes` - Number of points on the ring per node
+    /// * `seed` - Seed for the hash function, rings with the same seed route identically
+    pub fn new(virtual_nodes: usize, seed: u64) -> ConsistentHashRing<N> {
+        assert!(virtual_nodes > 0, "a node needs at least one virtual node");
+        ConsistentHashRing {
+            ring: BTreeMap::new(),
+            nodes: Vec::new(),
+            virtual_nodes,
+            hasher: MultiplyShift::from_seed(64, seed),
+        }
+    }
+
+    /// Number of nodes on the ring
+    pub fn len(&self) -> usize {
+        self.nodes.iter().filter(|n| n.is_some()).count()
+    }
+
+    /// Checks if the ring has no nodes
+    pub fn is_empty(&self) -> bool {
+        self.ring.is_empty()
+    }
+
+    /// Returns an iterator over the nodes on the ring
+    pub fn nodes(&self) -> impl Iterator<Item = &N> {
+        self.nodes.iter().filter_map(|n| n.as_ref())
+    }
+
+    /// Adds a node to the ring
+    ///
+    /// Returns `false` if the node is already on the ring.
+    ///
+    /// # Arguments
+    ///
+    /// * `node` - Node to add
+    pub fn add_node(&mut self, node: N) -> bool {
+        if self.position(&node).is_some() {
+            return false;
+        }
+        let slot = match self.nodes.iter().position(|n| n.is_none()) {
+            Some(slot) => slot,
+            None => {
+                self.nodes.push(None);
+                self.nodes.len() - 1
+            }
+        };
+        for v in 0..self.virtual_nodes {
+            // On the rare collision, the point stays with the node that was there first
+            self.ring.entry(self.hasher.hash(&(&node, v))).or_insert(slot);
+        }
+        self.nodes[slot] = Some(node);
+        true
+    }
+
+    /// Removes a node from the ring
+    ///
+    /// Returns the node, or None if it was not on the ring.
+    ///
+    /// # Arguments
+    ///
+    /// * `node` - Node to remove
+    pub fn remove_node(&mut self, node: &N) -> Option<N> {
+        let slot = self.position(node)?;
+        for v in 0..self.virtual_nodes {
+            let point = self.hasher.hash(&(node, v));
+            if self.ring.get(&point) == Some(&slot) {
+                self.ring.remove(&point);
+            }
+        }
+        self.nodes[slot].take()
+    }
+
+    /// Routes a key to the node responsible for it
+    ///
+    /// Returns None if the ring is empty.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - Key to route
+    pub fn route<K: Hash + ?Sized>(&self, key: &K) -> Option<&N> {
+        let h = self.hasher.hash(key);
+        let (_, &slot) = self.ring.range(h..).next().or_else(|| self.ring.iter().next())?;
+        self.nodes[slot].as_ref()
+    }
+
+    /// Computes the fraction of the ring each node owns
+    ///
+    /// A node owns the arc ending at each of its points, so with uniformly hashed keys this is the
+    /// expected fraction of keys routed to it.
+    pub fn ownership(&self) -> Vec<(&N, f64)> {
+        let mut owned = vec![0u64; self.nodes.len()];
+        let mut prev = match self.ring.iter().next_back() {
+            Some((&p, _)) => p,
+            None => return Vec::new(),
+        };
+        for (&p, &slot) in self.ring.iter() {
+            owned[slot] = owned[slot].wrapping_add(p.wrapping_sub(prev));
+            prev = p;
+        }
+        // A single point owns the whole ring, which wraps to 0
+        if self.ring.len() == 1 {
+            owned[*self.ring.values().next().unwrap()] = u64::MAX;
+        }
+        self.nodes
+            .iter()
+            .zip(owned)
+            .filter_map(|(n, o)| n.as_ref().map(|n| (n, o as f64 / u64::MAX as f64)))
+            .collect()
+    }
+
+    /// Computes the fraction of keys routed to a different node by another ring
+    ///
+    /// Compare a ring before and after a membership change to measure how many keys moved.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - Ring to compare against, should use the same seed
+    /// * `keys` - Sample of keys to route on both rings
+    pub fn moved_fraction<'a, K: Hash + 'a, I: IntoIterator<Item = &'a K>>(&self, other: &ConsistentHashRing<N>, keys: I) -> f64 {
+        let mut total = 0;
+        let mut moved = 0;
+        for key in keys {
+            total += 1;
+            if self.route(key) != other.route(key) {
+                moved += 1;
+            }
+        }
+        if total == 0 {
+            0.0
+        } else {
+            moved as f64 / total as f64
+        }
+    }
+
+    /// Internal function to find the slot of a node
+    fn position(&self, node: &N) -> Option<usize> {
+        self.nodes.iter().position(|n| n.as_ref() == Some(node))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_route() {
+        let mut ring = ConsistentHashRing::new(50, 1);
+        assert_eq!(None, ring.route("key"));
+
+        ring.add_node("a");
+        assert_eq!(Some(&"a"), ring.route("key"));
+
+        ring.add_node("b");
+        ring.add_node("c");
+        assert!(!ring.add_node("c"));
+        assert_eq!(3, ring.len());
+
+        let node = ring.route("key").cloned();
+        assert_eq!(node.as_ref(), ring.route("key"));
+    }
+
+    #[test]
+    fn test_remove_node() {
+        let mut ring = ConsistentHashRing::new(50, 2);
+        ring.add_node(1);
+        ring.add_node(2);
+        assert_eq!(Some(1), ring.remove_node(&1));
+        assert_eq!(None, ring.remove_node(&1));
+        for k in 0..100 {
+            assert_eq!(Some(&2), ring.route(&k));
+        }
+        ring.remove_node(&2);
+        assert!(ring.is_empty());
+    }
+
+    #[test]
+    fn test_ownership() {
+        let mut ring = ConsistentHashRing::new(200, 3);
+        for n in 0..4 {
+            ring.add_node(n);
+        }
+        let owned = ring.ownership();
+        let total: f64 = owned.iter().map(|(_, o)| o).sum();
+        assert!((total - 1.0).abs() < 1e-6);
+        for (_, o) in owned {
+            assert!(o > 0.15 && o < 0.35);
+        }
+
+        let mut ring = ConsistentHashRing::new(1, 3);
+        ring.add_node(0);
+        assert_eq!(vec![(&0, 1.0)], ring.ownership());
+    }
+
+    #[test]
+    fn test_moved_fraction() {
+        let keys: Vec<u32> = (0..10000).collect();
+        let mut before = ConsistentHashRing::new(100, 4);
+        for n in 0..9 {
+            before.add_node(n);
+        }
+
+        // Adding a 10th node should only move about 1/10 of the keys, to the new node
+        let mut after = before.clone();
+        after.add_node(9);
+        let moved = before.moved_fraction(&after, &keys);
+        assert!(moved > 0.05 && moved < 0.15);
+        for k in keys.iter() {
+            if before.route(k) != after.route(k) {
+                assert_eq!(Some(&9), after.route(k));
+            }
+        }
+
+        // Removing it moves the same keys back
+        after.remove_node(&9);
+        assert_eq!(0.0, before.moved_fraction(&after, &keys));
+    }
+}
@@ -0,0 +1,187 @@
+use std::f64::consts::E;
+use std::hash::Hash;
+use std::marker::PhantomData;
+
+use crate::rng::Rng;
+use crate::universal_hasher::{CarterWegman, UniversalHasher};
+
+/// A Count-Min sketch, a probabilistic frequency table for streams
+///
+/// Keeps a `depth x width` table of counters with one pairwise independent hash function per row.
+/// Adding an item increments one counter in each row, and an item's estimate is the smallest of
+/// its counters. Estimates never undercount, and with probability at least `1 - δ` overcount by
+/// at most `ε * N`, where `N` is the total of all counts added[1].
+///
+/// [1] Cormode, Graham, and S. Muthukrishnan. <i>An improved data stream summary: the count-min sketch and its applications<i>.
+#[derive(Debug)]
+pub struct CountMinSketch<T: Hash + ?Sized> {
+    table: Vec<u64>,
+    width: usize,
+    hashers: Vec<CarterWegman>,
+    total: u64,
+    marker: PhantomData<fn(&T)>,
+}
+
+impl<T: Hash + ?Sized> CountMinSketch<T> {
+
+    /// Creates a new empty `CountMinSketch` with a given table size
+    ///
+    /// # Arguments
+    ///
+    /// * `width` - Number of counters per row
+    /// * `depth` - Number of rows, one hash function each
+    /// * `seed` - Seed for the hash functions, sketches must share a seed to be merged
+    pub fn new(width: usize, depth: usize, seed: u64) -> CountMinSketch<T> {
+        assert!(width > 0 && depth > 0, "width and depth must be greater than 0");
+        let mut rng = Rng::new(seed);
+        CountMinSketch {
+            table: vec![0; width * depth],
+            width,
+            hashers: (0..depth).map(|_| CarterWegman::new(width as u64, &mut rng)).collect(),
+            total: 0,
+            marker: PhantomData,
+        }
+    }
+
+    /// Creates a new empty `CountMinSketch` from error bounds
+    ///
+    /// Uses `width = ceil(e / ε)` and `depth = ceil(ln(1 / δ))`.
+    ///
+    /// # Arguments
+    ///
+    /// * `epsilon` - Estimates overcount by at most `epsilon` times the total count
+    /// * `delta` - Probability that an estimate exceeds the bound
+    /// * `seed` - Seed for the hash functions, sketches must share a seed to be merged
+    pub fn with_error(epsilon: f64, delta: f64, seed: u64) -> CountMinSketch<T> {
+        assert!(epsilon > 0.0 && delta > 0.0 && delta < 1.0, "epsilon must be positive and delta in (0, 1)");
+        let width = (E / epsilon).ceil() as usize;
+        let depth = (1.0 / delta).ln().ceil().max(1.0) as usize;
+        CountMinSketch::new(width, depth, seed)
+    }
+
+    /// Number of counters per row
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Number of rows
+    pub fn depth(&self) -> usize {
+        self.hashers.len()
+    }
+
+    /// Total of all counts added
+    pub fn total(&self) -> u64 {
+        self.total
+    }
+
+    /// Adds a count for an item
+    ///
+    /// # Arguments
+    ///
+    /// * `item` - Item to count
+    /// * `count` - Amount to add to the item's count
+    pub fn add(&mut self, item: &T, count: u64) {
+        for (row, h) in self.hashers.iter().enumerate() {
+            let i = row * self.width + h.hash(item) as usize;
+            self.table[i] = self.table[i].saturating_add(count);
+        }
+        self.total = self.total.saturating_add(count);
+    }
+
+    /// Estimates the count of an item
+    ///
+    /// Never less than the true count.
+    ///
+    /// # Arguments
+    ///
+    /// * `item` - Item to look up
+    pub fn estimate(&self, item: &T) -> u64 {
+        self.hashers
+            .iter()
+            .enumerate()
+            .map(|(row, h)| self.table[row * self.width + h.hash(item) as usize])
+            .min()
+            .unwrap_or(0)
+    }
+
+    /// Adds the counts of another sketch into this one
+    ///
+    /// The result is the sketch of both streams combined. Returns `false` and leaves this sketch
+    /// unchanged if the sketches have different sizes or hash functions.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - Sketch to merge in
+    pub fn merge(&mut self, other: &CountMinSketch<T>) -> bool {
+        if self.width != other.width || self.hashers != other.hashers {
+            return false;
+        }
+        for (a, b) in self.table.iter_mut().zip(other.table.iter()) {
+            *a = a.saturating_add(*b);
+        }
+        self.total = self.total.saturating_add(other.total);
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_with_error() {
+        let sketch: CountMinSketch<u32> = CountMinSketch::with_error(0.01, 0.01, 1);
+        assert_eq!(272, sketch.width());
+        assert_eq!(5, sketch.depth());
+    }
+
+    #[test]
+    fn test_estimate() {
+        let mut sketch = CountMinSketch::new(100, 4, 1);
+        sketch.add("a", 10);
+        sketch.add("b", 3);
+        sketch.add("a", 5);
+        assert_eq!(15, sketch.estimate("a"));
+        assert_eq!(3, sketch.estimate("b"));
+        assert_eq!(0, sketch.estimate("c"));
+        assert_eq!(18, sketch.total());
+    }
+
+    #[test]
+    fn test_error_bound() {
+        let epsilon = 0.001;
+        let mut sketch = CountMinSketch::with_error(epsilon, 0.01, 2);
+        // Item i appears i % 100 times
+        for i in 0..10000u64 {
+            sketch.add(&i, i % 100);
+        }
+        let bound = (epsilon * sketch.total() as f64) as u64;
+        let mut over = 0;
+        for i in 0..10000u64 {
+            let e = sketch.estimate(&i);
+            assert!(e >= i % 100);
+            if e > i % 100 + bound {
+                over += 1;
+            }
+        }
+        assert!(over < 100);
+    }
+
+    #[test]
+    fn test_merge() {
+        let mut a = CountMinSketch::new(50, 3, 3);
+        let mut b = CountMinSketch::new(50, 3, 3);
+        a.add("x", 2);
+        b.add("x", 3);
+        b.add("y", 1);
+        assert!(a.merge(&b));
+        assert_eq!(5, a.estimate("x"));
+        assert_eq!(1, a.estimate("y"));
+        assert_eq!(6, a.total());
+
+        let c = CountMinSketch::new(50, 3, 4);
+        assert!(!a.merge(&c));
+        let d = CountMinSketch::new(60, 3, 3);
+        assert!(!a.merge(&d));
+    }
+}
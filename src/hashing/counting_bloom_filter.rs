@@ -0,0 +1,267 @@
+use std::hash::Hash;
+use std::marker::PhantomData;
+
+use crate::bloom_filter::{optimal_num_cells, optimal_num_hashes, DoubleHasher};
+use crate::rng::Rng;
+
+/// A counting Bloom filter, a Bloom filter that supports removal
+///
+/// Each cell is a small counter instead of a bit. Inserting increments the `k` counters of an
+/// item and removing decrements them. A counter that reaches its maximum value saturates: it is
+/// never incremented or decremented again, which trades a permanently set cell for never causing
+/// a false negative.
+#[derive(Debug)]
+pub struct CountingBloomFilter<T: Hash + ?Sized> {
+    counters: Vec<u64>,
+    num_cells: usize,
+    num_hashes: u32,
+    counter_bits: u32,
+    saturated: usize,
+    hasher: DoubleHasher,
+    marker: PhantomData<fn(&T)>,
+}
+
+impl<T: Hash + ?Sized> CountingBloomFilter<T> {
+
+    /// Creates a new empty `CountingBloomFilter` with 4 bit counters
+    ///
+    /// 4 bits is enough that the probability of any counter overflowing is negligible at the
+    /// optimal number of hash functions[1].
+    ///
+    /// # Arguments
+    ///
+    /// * `expected_items` - Number of items expected to be inserted
+    /// * `false_positive_rate` - Target false positive rate once `expected_items` are inserted
+    ///
+    /// [1] Fan, Li, et al. <i>Summary cache: a scalable wide-area web cache sharing protocol<i>.
+    pub fn new(expected_items: usize, false_positive_rate: f64) -> CountingBloomFilter<T> {
+        CountingBloomFilter::with_rng(expected_items, false_positive_rate, 4, &mut Rng::from_entropy())
+    }
+
+    /// Creates a new empty `CountingBloomFilter` with 4 bit counters and seeded hash functions
+    ///
+    /// # Arguments
+    ///
+    /// * `expected_items` - Number of items expected to be inserted
+    /// * `false_positive_rate` - Target false positive rate once `expected_items` are inserted
+    /// * `seed` - Seed for the hash functions
+    pub fn with_seed(expected_items: usize, false_positive_rate: f64, seed: u64) -> CountingBloomFilter<T> {
+        CountingBloomFilter::with_rng(expected_items, false_positive_rate, 4, &mut Rng::new(seed))
+    }
+
+    /// Creates a new empty `CountingBloomFilter` with a chosen counter width and seeded hash functions
+    ///
+    /// # Arguments
+    ///
+    /// * `expected_items` - Number of items expected to be inserted
+    /// * `false_positive_rate` - Target false positive rate once `expected_items` are inserted
+    /// * `counter_bits` - Width of each counter, one of 2, 4, 8, 16, or 32
+    /// * `seed` - Seed for the hash functions
+    pub fn with_counter_bits(expected_items: usize, false_positive_rate: f64, counter_bits: u32, seed: u64) -> CountingBloomFilter<T> {
+        CountingBloomFilter::with_rng(expected_items, false_positive_rate, counter_bits, &mut Rng::new(seed))
+    }
+
+    /// Internal function to create a `CountingBloomFilter` with hash functions drawn from `rng`
+    fn with_rng(expected_items: usize, false_positive_rate: f64, counter_bits: u32, rng: &mut Rng) -> CountingBloomFilter<T> {
+        assert!(
+            [2, 4, 8, 16, 32].contains(&counter_bits),
+            "counter width must be one of 2, 4, 8, 16, or 32 bits"
+        );
+        let num_cells = optimal_num_cells(expected_items, false_positive_rate);
+        let per_word = (64 / counter_bits) as usize;
+        CountingBloomFilter {
+            counters: vec![0; num_cells.div_ceil(per_word)],
+            num_cells,
+            num_hashes: optimal_num_hashes(num_cells, expected_items),
+            counter_bits,
+            saturated: 0,
+            hasher: DoubleHasher::new(rng),
+            marker: PhantomData,
+        }
+    }
+
+    /// Number of counters in the filter
+    pub fn num_cells(&self) -> usize {
+        self.num_cells
+    }
+
+    /// Number of hash functions per item
+    pub fn num_hashes(&self) -> u32 {
+        self.num_hashes
+    }
+
+    /// Width of each counter in bits
+    pub fn counter_bits(&self) -> u32 {
+        self.counter_bits
+    }
+
+    /// Number of counters that have overflowed and are stuck at their maximum value
+    pub fn saturated(&self) -> usize {
+        self.saturated
+    }
+
+    /// Inserts an item into the `CountingBloomFilter`
+    ///
+    /// # Arguments
+    ///
+    /// * `item` - Item to insert
+    pub fn insert(&mut self, item: &T) {
+        let max = self.max();
+        for i in self.hasher.indexes(item, self.num_hashes, self.num_cells) {
+            let c = self.get(i);
+            if c < max {
+                self.set(i, c + 1);
+                if c + 1 == max {
+                    self.saturated += 1;
+                }
+            }
+        }
+    }
+
+    /// Removes one copy of an item from the `CountingBloomFilter`
+    ///
+    /// Returns `false` and leaves the filter unchanged if the item is definitely not in the filter.
+    /// Removing an item that was never inserted, but is a false positive, corrupts the filter.
+    ///
+    /// # Arguments
+    ///
+    /// * `item` - Item to remove
+    pub fn remove(&mut self, item: &T) -> bool {
+        if !self.contains(item) {
+            return false;
+        }
+        let max = self.max();
+        for i in self.hasher.indexes(item, self.num_hashes, self.num_cells) {
+            let c = self.get(i);
+            if c < max {
+                self.set(i, c - 1);
+            }
+        }
+        true
+    }
+
+    /// Checks if an item may be in the `CountingBloomFilter`
+    ///
+    /// Returns `false` only if the item is not in the filter.
+    ///
+    /// # Arguments
+    ///
+    /// * `item` - Item to look for
+    pub fn contains(&self, item: &T) -> bool {
+        self.hasher
+            .indexes(item, self.num_hashes, self.num_cells)
+            .all(|i| self.get(i) != 0)
+    }
+
+    /// Estimates how many times an item was inserted
+    ///
+    /// The smallest of the item's counters is an upper bound on its count, unless that counter
+    /// has saturated.
+    ///
+    /// # Arguments
+    ///
+    /// * `item` - Item to look for
+    pub fn count(&self, item: &T) -> u64 {
+        self.hasher
+            .indexes(item, self.num_hashes, self.num_cells)
+            .map(|i| self.get(i))
+            .min()
+            .unwrap_or(0)
+    }
+
+    /// Internal function for the maximum value of a counter
+    fn max(&self) -> u64 {
+        (1 << self.counter_bits) - 1
+    }
+
+    /// Internal function to read counter `i`
+    fn get(&self, i: usize) -> u64 {
+        let per_word = (64 / self.counter_bits) as usize;
+        let shift = (i % per_word) as u32 * self.counter_bits;
+        (self.counters[i / per_word] >> shift) & self.max()
+    }
+
+    /// Internal function to write counter `i`
+    fn set(&mut self, i: usize, value: u64) {
+        let per_word = (64 / self.counter_bits) as usize;
+        let shift = (i % per_word) as u32 * self.counter_bits;
+        let mask = self.max() << shift;
+        let word = &mut self.counters[i / per_word];
+        *word = (*word & !mask) | (value << shift);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_shared_sizing() {
+        let filter: CountingBloomFilter<u32> = CountingBloomFilter::new(1000, 0.01);
+        assert_eq!(optimal_num_cells(1000, 0.01), filter.num_cells());
+        assert_eq!(7, filter.num_hashes());
+        assert_eq!(4, filter.counter_bits());
+    }
+
+    #[test]
+    fn test_insert_remove() {
+        let mut filter = CountingBloomFilter::with_seed(100, 0.01, 1);
+        for i in 0..100 {
+            filter.insert(&i);
+        }
+        for i in 0..100 {
+            assert!(filter.contains(&i));
+        }
+        for i in 0..50 {
+            assert!(filter.remove(&i));
+        }
+        for i in 50..100 {
+            assert!(filter.contains(&i));
+        }
+        let remaining = (0..50).filter(|i| filter.contains(i)).count();
+        assert!(remaining < 5);
+        assert!(!filter.remove(&1000));
+    }
+
+    #[test]
+    fn test_count() {
+        let mut filter = CountingBloomFilter::with_seed(100, 0.01, 2);
+        filter.insert("a");
+        filter.insert("a");
+        filter.insert("b");
+        assert_eq!(2, filter.count("a"));
+        assert_eq!(1, filter.count("b"));
+        filter.remove("a");
+        assert_eq!(1, filter.count("a"));
+    }
+
+    #[test]
+    fn test_saturation() {
+        let mut filter = CountingBloomFilter::with_counter_bits(10, 0.01, 2, 3);
+        for _ in 0..5 {
+            filter.insert(&7);
+        }
+        assert_eq!(3, filter.count(&7));
+        assert_eq!(filter.num_hashes() as usize, filter.saturated());
+
+        // Saturated counters are never decremented, so there are no false negatives
+        for _ in 0..5 {
+            filter.remove(&7);
+        }
+        assert!(filter.contains(&7));
+    }
+
+    #[test]
+    fn test_counter_widths() {
+        for &bits in [2, 4, 8, 16, 32].iter() {
+            let mut filter = CountingBloomFilter::with_counter_bits(50, 0.05, bits, 4);
+            for i in 0..50 {
+                filter.insert(&i);
+                filter.insert(&i);
+            }
+            for i in 0..50 {
+                assert!(filter.count(&i) >= 2);
+            }
+        }
+    }
+}
@@ -0,0 +1,305 @@
+use std::hash::Hash;
+use std::marker::PhantomData;
+
+use crate::rng::Rng;
+use crate::universal_hasher::{MultiplyShift, UniversalHasher};
+
+/// Number of fingerprints stored per bucket
+const BUCKET_SIZE: usize = 4;
+
+/// Number of evictions to try before an insert gives up
+const MAX_KICKS: usize = 500;
+
+/// A cuckoo filter, a probabilistic set that supports removal
+///
+/// Stores a small fingerprint of each item in one of two candidate buckets. The second bucket is
+/// computed from the first bucket and the fingerprint alone (partial-key cuckoo hashing), so
+/// fingerprints can be moved between their buckets without knowing the original item[1].
+///
+/// [1] Fan, Bin, et al. <i>Cuckoo filter: Practically better than Bloom<i>.
+#[derive(Debug)]
+pub struct CuckooFilter<T: Hash + ?Sized> {
+    buckets: Vec<[u16; BUCKET_SIZE]>,
+    fingerprint_bits: u32,
+    len: usize,
+    victim: Option<(usize, u16)>,
+    index_hasher: MultiplyShift,
+    fingerprint_hasher: MultiplyShift,
+    rng: Rng,
+    marker: PhantomData<fn(&T)>,
+}
+
+impl<T: Hash + ?Sized> CuckooFilter<T> {
+
+    /// Creates a new empty `CuckooFilter` sized for a capacity and false positive rate
+    ///
+    /// The fingerprint size is `ceil(log2(2b / p))` bits for buckets of `b = 4` fingerprints,
+    /// capped at 16 bits, and the number of buckets is rounded up to a power of two.
+    ///
+    /// # Arguments
+    ///
+    /// * `capacity` - Number of items the filter should hold
+    /// * `false_positive_rate` - Target false positive rate
+    pub fn new(capacity: usize, false_positive_rate: f64) -> CuckooFilter<T> {
+        CuckooFilter::with_rng(capacity, false_positive_rate, Rng::from_entropy())
+    }
+
+    /// Creates a new empty `CuckooFilter` with seeded hash functions
+    ///
+    /// # Arguments
+    ///
+    /// * `capacity` - Number of items the filter should hold
+    /// * `false_positive_rate` - Target false positive rate
+    /// * `seed` - Seed for the hash functions and evictions
+    pub fn with_seed(capacity: usize, false_positive_rate: f64, seed: u64) -> CuckooFilter<T> {
+        CuckooFilter::with_rng(capacity, false_positive_rate, Rng::new(seed))
+    }
+
+    /// Internal function to create a `CuckooFilter` with hash functions drawn from `rng`
+    fn with_rng(capacity: usize, false_positive_rate: f64, mut rng: Rng) -> CuckooFilter<T> {
+        assert!(false_positive_rate > 0.0 && false_positive_rate < 1.0, "false positive rate must be in (0, 1)");
+        let f = (2.0 * BUCKET_SIZE as f64 / false_positive_rate).log2().ceil() as u32;
+        // Buckets are about 95% full at capacity
+        let num_buckets = ((capacity.max(1) as f64 / (BUCKET_SIZE as f64 * 0.95)).ceil() as usize).next_power_of_two();
+        CuckooFilter {
+            buckets: vec![[0; BUCKET_SIZE]; num_buckets],
+            fingerprint_bits: f.clamp(1, 16),
+            len: 0,
+            victim: None,
+            index_hasher: MultiplyShift::new(64, &mut rng),
+            fingerprint_hasher: MultiplyShift::new(64, &mut rng),
+            rng,
+            marker: PhantomData,
+        }
+    }
+
+    /// Number of items in the filter
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Checks if the filter is empty
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Number of buckets in the filter
+    pub fn num_buckets(&self) -> usize {
+        self.buckets.len()
+    }
+
+    /// Number of bits per fingerprint
+    pub fn fingerprint_bits(&self) -> u32 {
+        self.fingerprint_bits
+    }
+
+    /// Number of bits of fingerprint storage, `num_buckets * 4 * fingerprint_bits`
+    pub fn num_bits(&self) -> usize {
+        self.buckets.len() * BUCKET_SIZE * self.fingerprint_bits as usize
+    }
+
+    /// Fraction of fingerprint slots in use
+    pub fn load_factor(&self) -> f64 {
+        self.len as f64 / (self.buckets.len() * BUCKET_SIZE) as f64
+    }
+
+    /// Inserts an item into the `CuckooFilter`
+    ///
+    /// Returns `false` if the filter is too full to insert the item. Inserting the same item more
+    /// than `2 * 4` times will always fail, since it only has two candidate buckets.
+    ///
+    /// # Arguments
+    ///
+    /// * `item` - Item to insert
+    pub fn insert(&mut self, item: &T) -> bool {
+        if self.victim.is_some() {
+            return false;
+        }
+        let (fp, i1, i2) = self.locate(item);
+        if self.insert_into(i1, fp) || self.insert_into(i2, fp) {
+            self.len += 1;
+            return true;
+        }
+
+        // Both buckets are full, so evict random fingerprints along a cuckoo path
+        let mut i = if self.rng.gen_bool(0.5) { i1 } else { i2 };
+        let mut fp = fp;
+        for _ in 0..MAX_KICKS {
+            let slot = self.rng.gen_range(BUCKET_SIZE as u64) as usize;
+            std::mem::swap(&mut fp, &mut self.buckets[i][slot]);
+            i = self.alt_index(i, fp);
+            if self.insert_into(i, fp) {
+                self.len += 1;
+                return true;
+            }
+        }
+
+        // Keep the last evicted fingerprint so no inserted item is lost
+        self.victim = Some((i, fp));
+        self.len += 1;
+        true
+    }
+
+    /// Checks if an item may be in the `CuckooFilter`
+    ///
+    /// Returns `false` only if the item is not in the filter.
+    ///
+    /// # Arguments
+    ///
+    /// * `item` - Item to look for
+    pub fn contains(&self, item: &T) -> bool {
+        let (fp, i1, i2) = self.locate(item);
+        if let Some((i, v)) = self.victim {
+            if v == fp && (i == i1 || i == i2) {
+                return true;
+            }
+        }
+        self.buckets[i1].contains(&fp) || self.buckets[i2].contains(&fp)
+    }
+
+    /// Removes one copy of an item from the `CuckooFilter`
+    ///
+    /// Returns `false` if the item is not in the filter. Removing an item that was never inserted,
+    /// but is a false positive, removes another item.
+    ///
+    /// # Arguments
+    ///
+    /// * `item` - Item to remove
+    pub fn remove(&mut self, item: &T) -> bool {
+        let (fp, i1, i2) = self.locate(item);
+        if let Some((i, v)) = self.victim {
+            if v == fp && (i == i1 || i == i2) {
+                self.victim = None;
+                self.len -= 1;
+                return true;
+            }
+        }
+        for &i in [i1, i2].iter() {
+            if let Some(slot) = self.buckets[i].iter().position(|&f| f == fp) {
+                self.buckets[i][slot] = 0;
+                self.len -= 1;
+                // A free slot may fit the victim again
+                if let Some((vi, vfp)) = self.victim.take() {
+                    self.len -= 1;
+                    self.reinsert(vi, vfp);
+                }
+                return true;
+            }
+        }
+        false
+    }
+
+    /// Internal function to compute an item's fingerprint and two buckets
+    fn locate(&self, item: &T) -> (u16, usize, usize) {
+        let mask = (1u64 << self.fingerprint_bits) - 1;
+        // 0 marks an empty slot, so fingerprints are in [1, 2^f)
+        let fp = (self.fingerprint_hasher.hash(item) % mask + 1) as u16;
+        let i1 = (self.index_hasher.hash(item) as usize) & (self.buckets.len() - 1);
+        (fp, i1, self.alt_index(i1, fp))
+    }
+
+    /// Internal function for the other bucket of a fingerprint, `i xor hash(fp)`
+    fn alt_index(&self, i: usize, fp: u16) -> usize {
+        (i ^ self.index_hasher.hash_u64(fp as u64) as usize) & (self.buckets.len() - 1)
+    }
+
+    /// Internal function to place a fingerprint in a free slot of bucket `i`
+    fn insert_into(&mut self, i: usize, fp: u16) -> bool {
+        match self.buckets[i].iter().position(|&f| f == 0) {
+            Some(slot) => {
+                self.buckets[i][slot] = fp;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Internal function to put a victim fingerprint back into one of its buckets
+    fn reinsert(&mut self, i: usize, fp: u16) {
+        let j = self.alt_index(i, fp);
+        if self.insert_into(i, fp) || self.insert_into(j, fp) {
+            self.len += 1;
+        } else {
+            self.victim = Some((i, fp));
+            self.len += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sizing() {
+        let filter: CuckooFilter<u32> = CuckooFilter::new(1000, 0.01);
+        assert_eq!(10, filter.fingerprint_bits());
+        assert_eq!(512, filter.num_buckets());
+    }
+
+    #[test]
+    fn test_insert_contains_remove() {
+        let mut filter = CuckooFilter::with_seed(1000, 0.01, 1);
+        for i in 0..1000 {
+            assert!(filter.insert(&i));
+        }
+        assert_eq!(1000, filter.len());
+        for i in 0..1000 {
+            assert!(filter.contains(&i));
+        }
+        for i in 0..500 {
+            assert!(filter.remove(&i));
+        }
+        assert_eq!(500, filter.len());
+        for i in 500..1000 {
+            assert!(filter.contains(&i));
+        }
+        let remaining = (0..500).filter(|i| filter.contains(i)).count();
+        assert!(remaining < 10);
+    }
+
+    #[test]
+    fn test_false_positive_rate() {
+        let mut filter = CuckooFilter::with_seed(1000, 0.01, 2);
+        for i in 0..1000 {
+            filter.insert(&i);
+        }
+        let false_positives = (1000..101000).filter(|i| filter.contains(i)).count();
+        assert!(false_positives < 1000);
+    }
+
+    #[test]
+    fn test_full() {
+        let mut filter = CuckooFilter::with_seed(100, 0.01, 3);
+        let mut inserted = 0;
+        while filter.insert(&inserted) {
+            inserted += 1;
+        }
+        // Every item that was accepted is still found, including the last victim
+        for i in 0..inserted {
+            assert!(filter.contains(&i));
+        }
+        assert!(filter.load_factor() > 0.9);
+    }
+
+    #[test]
+    fn test_duplicates() {
+        let mut filter = CuckooFilter::with_seed(100, 0.01, 4);
+        filter.insert("a");
+        filter.insert("a");
+        assert!(filter.remove("a"));
+        assert!(filter.contains("a"));
+        assert!(filter.remove("a"));
+        assert!(!filter.contains("a"));
+        assert!(filter.is_empty());
+    }
+
+    #[test]
+    fn test_space_efficiency() {
+        // At low false positive rates, a cuckoo filter uses fewer bits per item than a Bloom filter
+        let bloom = crate::bloom_filter::optimal_num_cells(1_000_000, 0.0001);
+        let cuckoo: CuckooFilter<u32> = CuckooFilter::new(1_000_000, 0.0001);
+        let per_item = cuckoo.fingerprint_bits() as f64 / 0.95;
+        assert!(per_item < bloom as f64 / 1_000_000.0);
+    }
+}
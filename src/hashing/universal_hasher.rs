@@ -0,0 +1,295 @@
+use std::hash::{BuildHasher, Hash, Hasher};
+
+use crate::rng::Rng;
+
+/// The Mersenne prime `2^61 - 1`, used as the field size of `CarterWegman`
+pub const MERSENNE_61: u64 = (1 << 61) - 1;
+
+/// A hash function drawn at random from a universal family
+///
+/// A family `H` of functions `h: U -> [0, m)` is universal if for any two distinct keys `x != y`,
+/// `Pr[h(x) == h(y)] <= 1/m` when `h` is chosen uniformly from `H`. The guarantee is over the
+/// random choice of `h`, not over the keys, so no fixed input can be bad for every function.
+pub trait UniversalHasher: Copy {
+
+    /// Hashes a 64 bit key
+    ///
+    /// # Arguments
+    ///
+    /// * `x` - Key to hash
+    fn hash_u64(&self, x: u64) -> u64;
+
+    /// The number of possible outputs, every hash is in `[0, range)`
+    ///
+    /// A range of 0 means the full `u64` range.
+    fn range(&self) -> u64;
+
+    /// Hashes any key implementing `Hash`
+    ///
+    /// The key's bytes are folded into a single 64 bit word, mixed, and then hashed with
+    /// `hash_u64`, so the universal guarantee holds for keys whose folded words differ.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - Key to hash
+    fn hash<K: Hash + ?Sized>(&self, key: &K) -> u64 {
+        let mut state = UniversalState::new(*self);
+        key.hash(&mut state);
+        state.finish()
+    }
+}
+
+/// The multiply-add-shift family
+///
+/// `h(x) = ((a * x + b) mod 2^(64 + l)) >> 64` where `a` and `b` are random integers below
+/// `2^(64 + l)`. Maps 64 bit keys to `l` bit hashes, and is 2-independent[1] while only needing
+/// a multiplication and a shift.
+///
+/// [1] Dietzfelbinger, Martin. <i>Universal hashing and k-wise independent random variables via integer arithmetic without primes<i>.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MultiplyShift {
+    a: u128,
+    b: u128,
+    bits: u32,
+}
+
+impl MultiplyShift {
+
+    /// Draws a new function from the family
+    ///
+    /// # Arguments
+    ///
+    /// * `bits` - Number of output bits, between 1 and 64
+    /// * `rng` - Source of the random parameters
+    pub fn new(bits: u32, rng: &mut Rng) -> MultiplyShift {
+        assert!((1..=64).contains(&bits), "MultiplyShift needs between 1 and 64 output bits");
+        let mask = MultiplyShift::mask(bits);
+        let a = (((rng.next_u64() as u128) << 64) | rng.next_u64() as u128) & mask;
+        let b = (((rng.next_u64() as u128) << 64) | rng.next_u64() as u128) & mask;
+        MultiplyShift { a, b, bits }
+    }
+
+    /// Draws a new function from the family using a seed
+    ///
+    /// # Arguments
+    ///
+    /// * `bits` - Number of output bits, between 1 and 64
+    /// * `seed` - Seed for the random parameters
+    pub fn from_seed(bits: u32, seed: u64) -> MultiplyShift {
+        MultiplyShift::new(bits, &mut Rng::new(seed))
+    }
+
+    /// Number of output bits
+    pub fn bits(&self) -> u32 {
+        self.bits
+    }
+
+    /// Internal function to get the `2^(64 + bits) - 1` mask
+    fn mask(bits: u32) -> u128 {
+        if bits == 64 {
+            u128::MAX
+        } else {
+            (1u128 << (64 + bits)) - 1
+        }
+    }
+}
+
+impl UniversalHasher for MultiplyShift {
+    fn hash_u64(&self, x: u64) -> u64 {
+        let v = self.a.wrapping_mul(x as u128).wrapping_add(self.b) & MultiplyShift::mask(self.bits);
+        (v >> 64) as u64
+    }
+
+    fn range(&self) -> u64 {
+        if self.bits == 64 {
+            0
+        } else {
+            1 << self.bits
+        }
+    }
+}
+
+/// The Carter–Wegman family
+///
+/// `h(x) = ((a * x + b) mod p) mod m` where `p = 2^61 - 1` is prime, `a` is random in `[1, p)`
+/// and `b` is random in `[0, p)`[1]. Keys are reduced modulo `p` first, so the guarantee holds
+/// for keys that are distinct modulo `p`.
+///
+/// [1] Carter, J. Lawrence, and Mark N. Wegman. <i>Universal classes of hash functions<i>.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CarterWegman {
+    a: u64,
+    b: u64,
+    m: u64,
+}
+
+impl CarterWegman {
+
+    /// Draws a new function from the family
+    ///
+    /// # Arguments
+    ///
+    /// * `m` - Number of buckets, hashes are in `[0, m)`. Must be between 1 and `2^61 - 1`
+    /// * `rng` - Source of the random parameters
+    pub fn new(m: u64, rng: &mut Rng) -> CarterWegman {
+        assert!((1..=MERSENNE_61).contains(&m), "CarterWegman needs between 1 and 2^61 - 1 buckets");
+        let a = rng.gen_range(MERSENNE_61 - 1) + 1;
+        let b = rng.gen_range(MERSENNE_61);
+        CarterWegman { a, b, m }
+    }
+
+    /// Draws a new function from the family using a seed
+    ///
+    /// # Arguments
+    ///
+    /// * `m` - Number of buckets, hashes are in `[0, m)`
+    /// * `seed` - Seed for the random parameters
+    pub fn from_seed(m: u64, seed: u64) -> CarterWegman {
+        CarterWegman::new(m, &mut Rng::new(seed))
+    }
+}
+
+impl UniversalHasher for CarterWegman {
+    fn hash_u64(&self, x: u64) -> u64 {
+        let p = MERSENNE_61 as u128;
+        let v = (self.a as u128 * (x as u128 % p) + self.b as u128) % p;
+        v as u64 % self.m
+    }
+
+    fn range(&self) -> u64 {
+        self.m
+    }
+}
+
+/// A `Hasher` that folds written bytes into a word and finishes with a universal hash
+///
+/// Built by the `BuildHasher` implementations of the families, so they can parameterize any
+/// hash table that accepts a `BuildHasher`, e.g. `HashMap::with_hasher(MultiplyShift::from_seed(64, 1))`.
+#[derive(Clone, Debug)]
+pub struct UniversalState<H: UniversalHasher> {
+    hasher: H,
+    acc: u64,
+}
+
+impl<H: UniversalHasher> UniversalState<H> {
+
+    /// Creates a new empty state for a hash function
+    ///
+    /// # Arguments
+    ///
+    /// * `hasher` - Hash function applied in `finish`
+    pub fn new(hasher: H) -> UniversalState<H> {
+        UniversalState { hasher, acc: 0 }
+    }
+}
+
+impl<H: UniversalHasher> Hasher for UniversalState<H> {
+    fn write(&mut self, bytes: &[u8]) {
+        for chunk in bytes.chunks(8) {
+            let mut word = [0; 8];
+            word[..chunk.len()].copy_from_slice(chunk);
+            self.acc = (self.acc.rotate_left(5) ^ u64::from_le_bytes(word)).wrapping_mul(0x517c_c1b7_2722_0a95);
+        }
+    }
+
+    fn finish(&self) -> u64 {
+        // Structured keys fold into structured words, e.g. `(node, 0), (node, 1), ...` into an
+        // arithmetic progression, which multiply-shift maps to evenly spaced hashes. Mixing with
+        // a bijection first keeps distinct words distinct, so the universal guarantee still holds.
+        let mut z = self.acc;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        self.hasher.hash_u64(z ^ (z >> 31))
+    }
+}
+
+impl BuildHasher for MultiplyShift {
+    type Hasher = UniversalState<MultiplyShift>;
+
+    fn build_hasher(&self) -> Self::Hasher {
+        UniversalState::new(*self)
+    }
+}
+
+impl BuildHasher for CarterWegman {
+    type Hasher = UniversalState<CarterWegman>;
+
+    fn build_hasher(&self) -> Self::Hasher {
+        UniversalState::new(*self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_multiply_shift_range() {
+        let h = MultiplyShift::from_seed(10, 1);
+        assert_eq!(1024, h.range());
+        for x in 0..10000 {
+            assert!(h.hash_u64(x) < 1024);
+        }
+
+        let h = MultiplyShift::from_seed(64, 1);
+        assert_eq!(0, h.range());
+    }
+
+    #[test]
+    fn test_carter_wegman_range() {
+        let h = CarterWegman::from_seed(17, 1);
+        assert_eq!(17, h.range());
+        for x in 0..10000 {
+            assert!(h.hash_u64(x) < 17);
+        }
+    }
+
+    #[test]
+    fn test_seeded() {
+        let a = MultiplyShift::from_seed(32, 5);
+        let b = MultiplyShift::from_seed(32, 5);
+        assert_eq!(a.hash_u64(12345), b.hash_u64(12345));
+        assert_eq!(a.hash("key"), b.hash("key"));
+
+        let a = CarterWegman::from_seed(1000, 5);
+        let b = CarterWegman::from_seed(1000, 5);
+        assert_eq!(a.hash_u64(12345), b.hash_u64(12345));
+        assert_eq!(a.hash("key"), b.hash("key"));
+    }
+
+    #[test]
+    fn test_collision_probability() {
+        // Over random choices of h, two fixed keys should collide with probability about 1/m
+        let mut rng = Rng::new(0);
+        let trials = 20000;
+        let mut ms = 0;
+        let mut cw = 0;
+        for _ in 0..trials {
+            let h = MultiplyShift::new(4, &mut rng);
+            if h.hash_u64(1) == h.hash_u64(2) {
+                ms += 1;
+            }
+            let h = CarterWegman::new(16, &mut rng);
+            if h.hash_u64(1) == h.hash_u64(2) {
+                cw += 1;
+            }
+        }
+        assert!(ms < 2 * trials / 16);
+        assert!(cw < 2 * trials / 16);
+    }
+
+    #[test]
+    fn test_build_hasher() {
+        let mut map = HashMap::with_hasher(MultiplyShift::from_seed(64, 3));
+        for i in 0..100 {
+            map.insert(i, i * i);
+        }
+        assert_eq!(Some(&81), map.get(&9));
+
+        let mut map = HashMap::with_hasher(CarterWegman::from_seed(MERSENNE_61, 3));
+        map.insert("a", 1);
+        map.insert("b", 2);
+        assert_eq!(Some(&2), map.get("b"));
+    }
+}
@@ -0,0 +1,211 @@
+const ABSENT: usize = usize::MAX;
+
+/// A Vector based Min Heap of the keys `0..n`, each with a priority
+///
+/// Keeps the position of every key in the heap, so the priority of a key already in the heap can
+/// be lowered in `O(log(n))` time with `decrease_key`, which Dijkstra's and Prim's algorithms use.
+#[derive(Clone, Debug)]
+pub struct IndexedMinHeap<P: PartialOrd> {
+    heap: Vec<usize>,
+    position: Vec<usize>,
+    priority: Vec<Option<P>>,
+}
+
+impl<P: PartialOrd> IndexedMinHeap<P> {
+
+    /// Creates a new empty `IndexedMinHeap` for the keys `0..n`
+    ///
+    /// # Arguments
+    ///
+    /// * `n` - Number of keys
+    pub fn with_capacity(n: usize) -> IndexedMinHeap<P> {
+        IndexedMinHeap {
+            heap: Vec::with_capacity(n),
+            position: vec![ABSENT; n],
+            priority: (0..n).map(|_| None).collect(),
+        }
+    }
+
+    /// Number of keys in the heap
+    pub fn len(&self) -> usize {
+        self.heap.len()
+    }
+
+    /// Checks if the heap has no keys
+    pub fn is_empty(&self) -> bool {
+        self.heap.is_empty()
+    }
+
+    /// Checks if a key is in the heap
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - Key to look up
+    pub fn contains(&self, key: usize) -> bool {
+        self.position.get(key).is_some_and(|&p| p != ABSENT)
+    }
+
+    /// Gets the priority of a key in the heap
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - Key to look up
+    pub fn priority(&self, key: usize) -> Option<&P> {
+        if self.contains(key) {
+            self.priority[key].as_ref()
+        } else {
+            None
+        }
+    }
+
+    /// Gets the key with the smallest priority
+    pub fn peek(&self) -> Option<(usize, &P)> {
+        let &key = self.heap.first()?;
+        self.priority[key].as_ref().map(|p| (key, p))
+    }
+
+    /// Inserts a key with a priority
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - Key to insert
+    /// * `priority` - Priority of the key
+    ///
+    /// # Panics
+    ///
+    /// If the key is out of range or already in the heap
+    pub fn push(&mut self, key: usize, priority: P) {
+        assert!(key < self.position.len(), "key is out of range");
+        assert!(!self.contains(key), "key is already in the heap");
+        self.priority[key] = Some(priority);
+        self.position[key] = self.heap.len();
+        self.heap.push(key);
+        self.sift_up(self.heap.len() - 1);
+    }
+
+    /// Lowers the priority of a key in the heap
+    ///
+    /// Returns `false` and leaves the heap unchanged if the new priority isn't smaller.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - Key to update
+    /// * `priority` - New priority of the key
+    ///
+    /// # Panics
+    ///
+    /// If the key isn't in the heap
+    pub fn decrease_key(&mut self, key: usize, priority: P) -> bool {
+        assert!(self.contains(key), "key is not in the heap");
+        let lower = self.priority[key].as_ref().is_none_or(|p| priority < *p);
+        if !lower {
+            return false;
+        }
+        self.priority[key] = Some(priority);
+        self.sift_up(self.position[key]);
+        true
+    }
+
+    /// Removes and returns the key with the smallest priority, and its priority
+    pub fn pop(&mut self) -> Option<(usize, P)> {
+        if self.heap.is_empty() {
+            return None;
+        }
+        let last = self.heap.len() - 1;
+        self.swap(0, last);
+        let key = self.heap.pop().unwrap();
+        self.position[key] = ABSENT;
+        self.sift_down(0);
+        self.priority[key].take().map(|p| (key, p))
+    }
+
+    /// Internal function to check if the key at heap index `i` has a smaller priority than at `j`
+    fn less(&self, i: usize, j: usize) -> bool {
+        self.priority[self.heap[i]] < self.priority[self.heap[j]]
+    }
+
+    /// Internal function to swap two heap indexes and update the keys' positions
+    fn swap(&mut self, i: usize, j: usize) {
+        self.heap.swap(i, j);
+        self.position[self.heap[i]] = i;
+        self.position[self.heap[j]] = j;
+    }
+
+    /// Internal function to move the key at heap index `i` up until its parent is smaller
+    fn sift_up(&mut self, mut i: usize) {
+        while i > 0 && self.less(i, (i - 1) / 2) {
+            self.swap(i, (i - 1) / 2);
+            i = (i - 1) / 2;
+        }
+    }
+
+    /// Internal function to move the key at heap index `i` down until its children are larger
+    fn sift_down(&mut self, mut i: usize) {
+        loop {
+            let l = 2 * i + 1;
+            let r = 2 * i + 2;
+            let mut smallest = i;
+            if l < self.heap.len() && self.less(l, smallest) {
+                smallest = l;
+            }
+            if r < self.heap.len() && self.less(r, smallest) {
+                smallest = r;
+            }
+            if smallest == i {
+                return;
+            }
+            self.swap(i, smallest);
+            i = smallest;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_push_pop() {
+        let mut heap = IndexedMinHeap::with_capacity(6);
+        for &(key, p) in [(0, 5), (3, 1), (5, 9), (1, 4), (2, 7)].iter() {
+            heap.push(key, p);
+        }
+        assert_eq!(5, heap.len());
+        assert_eq!(Some((3, &1)), heap.peek());
+        assert!(heap.contains(5));
+        assert!(!heap.contains(4));
+
+        let mut popped = Vec::new();
+        while let Some(e) = heap.pop() {
+            popped.push(e);
+        }
+        assert_eq!(vec![(3, 1), (1, 4), (0, 5), (2, 7), (5, 9)], popped);
+        assert!(heap.is_empty());
+        assert!(!heap.contains(3));
+    }
+
+    #[test]
+    fn test_decrease_key() {
+        let mut heap = IndexedMinHeap::with_capacity(4);
+        heap.push(0, 10.0);
+        heap.push(1, 20.0);
+        heap.push(2, 30.0);
+        assert!(heap.decrease_key(2, 5.0));
+        assert!(!heap.decrease_key(1, 25.0));
+        assert_eq!(Some(&20.0), heap.priority(1));
+        assert_eq!(Some((2, 5.0)), heap.pop());
+
+        // A popped key can be pushed again
+        heap.push(2, 1.0);
+        assert_eq!(Some((2, 1.0)), heap.pop());
+        assert_eq!(Some((0, 10.0)), heap.pop());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_push_twice() {
+        let mut heap = IndexedMinHeap::with_capacity(2);
+        heap.push(1, 0);
+        heap.push(1, 0);
+    }
+}
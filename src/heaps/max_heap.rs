@@ -1,16 +1,27 @@
+use std::cmp::Ordering;
+
+use crate::comparator::{Comparator, Natural};
+#[cfg(feature = "rayon")]
+use crate::parallel_sort::par_heapify_by;
+use crate::priority_queue::PriorityQueue;
+
 
 /// A Vector based Max Heap implementation
 /// 
 /// Should satisfy the MaxHeap Property, that is, `MaxHeap.parent(i) >= MaxHeap.get(i)`.
-pub struct MaxHeap<T: PartialOrd> {
+/// 
+/// Elements are compared in their natural order, or by a `Comparator`, so the largest by the
+/// comparator is on top.
+pub struct MaxHeap<T, C = Natural> {
     data: Vec<T>,
+    comparator: C,
 }
 
 impl<T: PartialOrd> MaxHeap<T> {
 
     /// Creates a new empty `MaxHeap`
     pub fn new() -> MaxHeap<T> {
-        MaxHeap { data: Vec::new() }
+        MaxHeap::with_comparator(Natural)
     }
 
     /// Creates a new `MaxHeap` from an existing vector
@@ -19,37 +30,7 @@ impl<T: PartialOrd> MaxHeap<T> {
     /// 
     /// * `vec` - Vector to create a max heap from
     pub fn from_vec(vec: Vec<T>) -> MaxHeap<T> {
-        MaxHeap { data: MaxHeap::create_max_heap(vec) }
-    }
-
-    /// Internal function to create a new `MaxHeap` from a vector
-    /// 
-    /// # Arguments
-    /// 
-    /// * `vec` - The vector to modify to satisfy the Max Heap Property
-    fn create_max_heap(mut vec: Vec<T>) -> Vec<T> {
-        for j in (0..((vec.len() as f32 / 2.0).floor() as usize)).rev() {
-            let mut i = j;
-            let mut largest = i;
-            while { // Hacky Do-While loop
-                let l = (2 * i) + 1;
-                let r = (2 * i) + 2;
-                if l < vec.len() && vec[l] > vec[i] {
-                    largest = l;
-                }
-                if r < vec.len() && vec[r] > vec[largest] {
-                    largest = r;
-                }
-                if i != largest {
-                    vec.swap(i, largest);
-                }
-
-                i != largest
-            } {
-                i = largest;
-            }
-        }
-        vec
+        MaxHeap::from_vec_with_comparator(vec, Natural)
     }
 
     /// Uses the heapsort algorithm to sort a vector
@@ -60,22 +41,82 @@ impl<T: PartialOrd> MaxHeap<T> {
     /// 
     /// * `vec` - Vector to sort
     /// 
-    pub fn heapsort(vec: Vec<T>) -> Vec<T> {
-        let mut vec = MaxHeap::create_max_heap(vec);
+    pub fn heapsort(mut vec: Vec<T>) -> Vec<T> {
+        heapsort_by(&mut vec, |a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+        vec
+    }
+}
+
+#[cfg(feature = "rayon")]
+impl<T: PartialOrd + Send> MaxHeap<T> {
+
+    /// Creates a new `MaxHeap` from an existing vector, heapifying subtrees on rayon's threads
+    ///
+    /// # Arguments
+    ///
+    /// * `vec` - Vector to create a max heap from
+    pub fn par_from_vec(vec: Vec<T>) -> MaxHeap<T> {
+        MaxHeap::par_from_vec_with_comparator(vec, Natural)
+    }
+}
 
-        let mut c = 1;
-        for j in (1..vec.len()).rev() {
-            vec.swap(0, j);
+#[cfg(feature = "rayon")]
+impl<T: Send, C: Comparator<T> + Sync> MaxHeap<T, C> {
 
-            let mut i = 0;
+    /// Creates a new `MaxHeap` ordered by a comparator from an existing vector, heapifying
+    /// subtrees on rayon's threads
+    ///
+    /// Moves the subtrees below the top few levels into vectors of their own, heapifies them in
+    /// parallel, and sifts down the top levels once they're moved back, see
+    /// `parallel_sort::par_heapify_by`.
+    ///
+    /// # Arguments
+    ///
+    /// * `vec` - Vector to create a max heap from
+    /// * `comparator` - Order of the elements, the largest by it is on top
+    pub fn par_from_vec_with_comparator(vec: Vec<T>, comparator: C) -> MaxHeap<T, C> {
+        MaxHeap { data: par_heapify_by(vec, |a: &T, b: &T| comparator.compare(a, b)), comparator }
+    }
+}
+
+impl<T, C: Comparator<T>> MaxHeap<T, C> {
+
+    /// Creates a new empty `MaxHeap` ordered by a comparator
+    ///
+    /// # Arguments
+    ///
+    /// * `comparator` - Order of the elements, the largest by it is on top
+    pub fn with_comparator(comparator: C) -> MaxHeap<T, C> {
+        MaxHeap { data: Vec::new(), comparator }
+    }
+
+    /// Creates a new `MaxHeap` ordered by a comparator from an existing vector
+    ///
+    /// # Arguments
+    ///
+    /// * `vec` - Vector to create a max heap from
+    /// * `comparator` - Order of the elements, the largest by it is on top
+    pub fn from_vec_with_comparator(vec: Vec<T>, comparator: C) -> MaxHeap<T, C> {
+        MaxHeap { data: MaxHeap::create_max_heap(vec, &comparator), comparator }
+    }
+
+    /// Internal function to create a new `MaxHeap` from a vector
+    /// 
+    /// # Arguments
+    /// 
+    /// * `vec` - The vector to modify to satisfy the Max Heap Property
+    /// * `comparator` - Order of the elements
+    fn create_max_heap(mut vec: Vec<T>, comparator: &C) -> Vec<T> {
+        for j in (0..((vec.len() as f32 / 2.0).floor() as usize)).rev() {
+            let mut i = j;
             let mut largest = i;
             while { // Hacky Do-While loop
                 let l = (2 * i) + 1;
                 let r = (2 * i) + 2;
-                if l < (vec.len() - c) && vec[l] > vec[largest] {
+                if l < vec.len() && comparator.less(&vec[i], &vec[l]) {
                     largest = l;
                 }
-                if r < (vec.len() - c) && vec[r] > vec[largest] {
+                if r < vec.len() && comparator.less(&vec[largest], &vec[r]) {
                     largest = r;
                 }
                 if i != largest {
@@ -85,7 +126,6 @@ impl<T: PartialOrd> MaxHeap<T> {
                 i != largest
             } {
                 i = largest;
-                c += 1;
             }
         }
         vec
@@ -108,7 +148,7 @@ impl<T: PartialOrd> MaxHeap<T> {
     /// 
     /// If i is 0 or greater than the last index of the max heap, then the result will be None
     pub fn parent(&self, i: usize) -> Option<&T> {
-        if i <= 0 || i >= self.data.len() {
+        if i == 0 || i >= self.data.len() {
             return None
         }
         let pos = (i as f32 / 2.0).ceil() as usize;
@@ -139,14 +179,34 @@ impl<T: PartialOrd> MaxHeap<T> {
 
     /// Inserts a new element into the `MaxHeap`
     /// 
-    /// `MaxHeap` will automatically rebalance after insert, to satisfy the Max Heap Property.
+    /// `MaxHeap` will automatically rebalance after insert, to satisfy the Max Heap Property, by
+    /// moving the new element up from the end of the heap in `O(log(n))` time.
     /// 
     /// # Arguments
     /// 
     /// * `d` - New data to insert
     pub fn insert(&mut self, d: T) {
-        self.data.insert(0, d);
-        self.max_heapify(0);
+        self.data.push(d);
+        let mut i = self.data.len() - 1;
+        while i > 0 && self.comparator.less(&self.data[(i - 1) / 2], &self.data[i]) {
+            self.data.swap(i, (i - 1) / 2);
+            i = (i - 1) / 2;
+        }
+    }
+
+    /// Gets the largest value in the `MaxHeap`
+    pub fn peek(&self) -> Option<&T> {
+        self.data.first()
+    }
+
+    /// Number of elements in the `MaxHeap`
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    /// Checks if the `MaxHeap` has no elements
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
     }
 
     /// Rebalances the `MaxHeap` to satisfy the Max Heap Property
@@ -164,10 +224,10 @@ impl<T: PartialOrd> MaxHeap<T> {
         while { // Hacky Do-While loop
             let l = (2 * i) + 1;
             let r = (2 * i) + 2;
-            if l < self.data.len() && self.data[l] > self.data[largest] {
+            if l < self.data.len() && self.comparator.less(&self.data[largest], &self.data[l]) {
                 largest = l;
             }
-            if r < self.data.len() && self.data[r] > self.data[largest] {
+            if r < self.data.len() && self.comparator.less(&self.data[largest], &self.data[r]) {
                 largest = r;
             }
             if i != largest {
@@ -183,6 +243,9 @@ impl<T: PartialOrd> MaxHeap<T> {
     /// Removes and returns the largest value in the `MaxHeap`, then rebalances the `MaxHeap` 
     /// to satisfy the Max Heap Property.
     pub fn pop(&mut self) -> Option<T> {
+        if self.data.is_empty() {
+            return None;
+        }
         let i = self.data.len() - 1;
         self.data.swap(0, i);
         let e = self.data.pop();
@@ -192,9 +255,72 @@ impl<T: PartialOrd> MaxHeap<T> {
     }
 }
 
+impl<T: PartialOrd> Default for MaxHeap<T> {
+    fn default() -> Self {
+        MaxHeap::new()
+    }
+}
+
+impl<T, C: Comparator<T>> PriorityQueue<T> for MaxHeap<T, C> {
+    fn push(&mut self, item: T) {
+        MaxHeap::insert(self, item);
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        MaxHeap::pop(self)
+    }
+
+    fn peek(&self) -> Option<&T> {
+        MaxHeap::peek(self)
+    }
+
+    fn len(&self) -> usize {
+        MaxHeap::len(self)
+    }
+}
+
+/// Sorts a slice by a comparison function with heapsort
+///
+/// Arranges the slice into a max heap in `O(n)` time, then swaps the largest item to the end and
+/// moves the new root down to restore the heap, `n - 1` times, in `O(n*log(n))` time in every case
+/// and `O(1)` extra space. The sort isn't stable.
+///
+/// # Arguments
+///
+/// * `slice` - Slice to sort
+/// * `compare` - Function ordering two items
+pub fn heapsort_by<T, F: FnMut(&T, &T) -> Ordering>(slice: &mut [T], mut compare: F) {
+    for i in (0..slice.len() / 2).rev() {
+        sift_down_by(slice, i, &mut compare);
+    }
+    for end in (1..slice.len()).rev() {
+        slice.swap(0, end);
+        sift_down_by(&mut slice[..end], 0, &mut compare);
+    }
+}
+
+/// Internal function to move the item at `i` down a max heap until it's no smaller than its
+/// children
+pub(crate) fn sift_down_by<T, F: FnMut(&T, &T) -> Ordering>(heap: &mut [T], mut i: usize, compare: &mut F) {
+    loop {
+        let mut largest = i;
+        for child in [2 * i + 1, 2 * i + 2] {
+            if child < heap.len() && compare(&heap[child], &heap[largest]) == Ordering::Greater {
+                largest = child;
+            }
+        }
+        if largest == i {
+            return;
+        }
+        heap.swap(i, largest);
+        i = largest;
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::comparator::{by_key, Reverse};
 
     #[test]
     fn test_parent() {
@@ -242,18 +368,21 @@ mod tests {
     fn test_max_heapify() {
         let mut heap = MaxHeap {
             data: vec![1, 2, 0],
+            comparator: Natural,
         };
         heap.max_heapify(0);
         assert_eq!(heap.data, vec![2, 1, 0]);
 
         let mut heap = MaxHeap {
             data: vec![1, 0, 2],
+            comparator: Natural,
         };
         heap.max_heapify(0);
         assert_eq!(heap.data, vec![2, 0, 1]);
 
         let mut heap = MaxHeap {
             data: vec![1, 2, 0, 4],
+            comparator: Natural,
         };
         heap.max_heapify(0);
         assert_eq!(heap.data, vec![2, 4, 0, 1]);
@@ -270,27 +399,86 @@ mod tests {
         assert_eq!(heap.data, vec![1, 0]);
 
         heap.insert(-5);
-        assert_eq!(heap.data, vec![1, -5, 0]);
+        assert_eq!(heap.data, vec![1, 0, -5]);
 
         heap.insert(-1);
         assert_eq!(heap.data, vec![1, 0, -5, -1]);
+
+        // Inserting at the front used to shift every element and break the heap below the root
+        let mut heap = MaxHeap::from_vec(vec![10, 1, 9, 0, 0, 8, 8]);
+        heap.insert(5);
+        let mut sorted = Vec::new();
+        while let Some(e) = heap.pop() {
+            sorted.push(e);
+        }
+        assert_eq!(vec![10, 9, 8, 8, 5, 1, 0, 0], sorted);
+    }
+
+    #[test]
+    fn test_peek() {
+        let mut heap = MaxHeap::new();
+        assert_eq!(None, heap.peek());
+        assert_eq!(None, heap.pop());
+        assert!(heap.is_empty());
+
+        heap.insert(2);
+        heap.insert(7);
+        assert_eq!(Some(&7), heap.peek());
+        assert_eq!(2, heap.len());
+    }
+
+    #[test]
+    fn test_comparator() {
+        // A min heap without wrapping the elements in `std::cmp::Reverse`
+        let mut heap = MaxHeap::from_vec_with_comparator(vec![4.5, -1.0, 3.0, 0.5], Reverse);
+        heap.insert(-2.0);
+        assert_eq!(Some(&-2.0), heap.peek());
+        let mut sorted = Vec::new();
+        while let Some(e) = heap.pop() {
+            sorted.push(e);
+        }
+        assert_eq!(vec![-2.0, -1.0, 0.5, 3.0, 4.5], sorted);
+
+        let mut heap = MaxHeap::with_comparator(by_key(|s: &&str| s.len()));
+        for s in ["fig", "banana", "kiwi"] {
+            heap.insert(s);
+        }
+        assert_eq!(Some("banana"), heap.pop());
+        assert_eq!(Some("kiwi"), heap.pop());
     }
 
     #[test]
     fn test_build_max_heap() {
         let v = vec![0, 1, 2, 3];
-        let v = MaxHeap::create_max_heap(v);
+        let v = MaxHeap::create_max_heap(v, &Natural);
         assert_eq!(vec![3, 1, 2, 0], v);
 
         let v = vec![8, 2, 9, 4, 7];
-        let v = MaxHeap::create_max_heap(v);
+        let v = MaxHeap::create_max_heap(v, &Natural);
         assert_eq!(vec![9, 7, 8, 4, 2], v);
 
         let v = vec![9, 8, 7, 6, 5, 4, 3, 2, 1];
-        let v = MaxHeap::create_max_heap(v);
+        let v = MaxHeap::create_max_heap(v, &Natural);
         assert_eq!(vec![9, 8, 7, 6, 5, 4, 3, 2, 1], v);
     }
 
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn test_par_from_vec() {
+        let mut rng = crate::rng::Rng::new(577);
+        let v: Vec<u64> = (0..200_000).map(|_| rng.gen_range(1000)).collect();
+        let mut heap = MaxHeap::par_from_vec(v.clone());
+        let mut expected = v.clone();
+        expected.sort();
+        for &x in expected.iter().rev() {
+            assert_eq!(Some(x), heap.pop());
+        }
+        assert_eq!(None, heap.pop());
+
+        let heap = MaxHeap::par_from_vec_with_comparator(v, Reverse);
+        assert_eq!(Some(&expected[0]), heap.get(0));
+    }
+
     #[test]
     fn test_pop() {
         let mut heap = MaxHeap::from_vec(vec![3, 2, 1]);
@@ -313,5 +501,10 @@ mod tests {
         let v = vec![5, 2, 1, 3];
         let v = MaxHeap::heapsort(v);
         assert_eq!(vec![1, 2, 3, 5], v);
+
+        // Only the unsorted prefix `0..j` is a heap, the sorted suffix must not be sifted into
+        let v = vec![8, 3, 9, 1, 7, 2, 6, 5, 4, 0, 11, 10];
+        let v = MaxHeap::heapsort(v);
+        assert_eq!((0..12).collect::<Vec<_>>(), v);
     }
 }
@@ -0,0 +1,97 @@
+use std::collections::BinaryHeap;
+
+/// A max-priority queue, which pops its largest item first
+///
+/// Implemented by `MaxHeap` and the stdlib's `BinaryHeap`, so code like Dijkstra's and Prim's
+/// algorithms can be run on either. Wrapping items in `std::cmp::Reverse` pops the smallest first
+/// instead.
+pub trait PriorityQueue<T> {
+
+    /// Inserts an item into the queue
+    fn push(&mut self, item: T);
+
+    /// Removes and returns the largest item in the queue
+    fn pop(&mut self) -> Option<T>;
+
+    /// Gets the largest item in the queue
+    fn peek(&self) -> Option<&T>;
+
+    /// Number of items in the queue
+    fn len(&self) -> usize;
+
+    /// Checks if the queue is empty
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl<T: Ord> PriorityQueue<T> for BinaryHeap<T> {
+    fn push(&mut self, item: T) {
+        BinaryHeap::push(self, item);
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        BinaryHeap::pop(self)
+    }
+
+    fn peek(&self) -> Option<&T> {
+        BinaryHeap::peek(self)
+    }
+
+    fn len(&self) -> usize {
+        BinaryHeap::len(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::max_heap::MaxHeap;
+    use crate::rng::Rng;
+    use std::cmp::Reverse;
+
+    /// Internal function to push items into a queue and pop them all, largest first
+    fn drain<T, Q: PriorityQueue<T>>(queue: &mut Q, items: Vec<T>) -> Vec<T> {
+        for item in items {
+            queue.push(item);
+        }
+        let mut popped = Vec::with_capacity(queue.len());
+        while let Some(item) = queue.pop() {
+            popped.push(item);
+        }
+        popped
+    }
+
+    #[test]
+    fn test_queues() {
+        let mut rng = Rng::new(449);
+        let items: Vec<u64> = (0..1000).map(|_| rng.gen_range(100)).collect();
+        let mut expected = items.clone();
+        expected.sort_unstable_by(|a, b| b.cmp(a));
+        assert_eq!(expected, drain(&mut MaxHeap::new(), items.clone()));
+        assert_eq!(expected, drain(&mut BinaryHeap::new(), items.clone()));
+
+        let reversed: Vec<_> = items.into_iter().map(Reverse).collect();
+        expected.reverse();
+        let popped = drain(&mut MaxHeap::new(), reversed);
+        assert_eq!(expected, popped.into_iter().map(|Reverse(x)| x).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_peek() {
+        let queues: Vec<Box<dyn PriorityQueue<i32>>> = vec![Box::new(MaxHeap::new()), Box::new(BinaryHeap::new())];
+        for mut queue in queues {
+            assert!(queue.is_empty());
+            assert_eq!(None, queue.peek());
+            assert_eq!(None, queue.pop());
+            queue.push(3);
+            queue.push(9);
+            queue.push(-1);
+            assert_eq!(Some(&9), queue.peek());
+            assert_eq!(3, queue.len());
+            assert_eq!(Some(9), queue.pop());
+            assert_eq!(Some(&3), queue.peek());
+            assert!(!queue.is_empty());
+        }
+    }
+}
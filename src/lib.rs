@@ -1,2 +1,306 @@
 #[path = "heaps/max_heap.rs"]
-pub mod max_heap;
\ No newline at end of file
+pub mod max_heap;
+
+#[path = "hashing/universal_hasher.rs"]
+pub mod universal_hasher;
+
+#[path = "random/rng.rs"]
+pub mod rng;
+
+#[path = "hashing/bloom_filter.rs"]
+pub mod bloom_filter;
+
+#[path = "hashing/counting_bloom_filter.rs"]
+pub mod counting_bloom_filter;
+
+#[path = "hashing/cuckoo_filter.rs"]
+pub mod cuckoo_filter;
+
+#[path = "hashing/count_min_sketch.rs"]
+pub mod count_min_sketch;
+
+#[path = "hashing/consistent_hash_ring.rs"]
+pub mod consistent_hash_ring;
+
+#[path = "sets/hash_set.rs"]
+pub mod hash_set;
+
+#[path = "sets/tree_set.rs"]
+pub mod tree_set;
+
+#[path = "sets/multiset.rs"]
+pub mod multiset;
+
+#[path = "sets/sparse_set.rs"]
+pub mod sparse_set;
+
+#[path = "sets/bit_set.rs"]
+pub mod bit_set;
+
+#[path = "sets/rank_select.rs"]
+pub mod rank_select;
+
+#[path = "maps/multimap.rs"]
+pub mod multimap;
+
+#[path = "maps/skip_list.rs"]
+pub mod skip_list;
+
+#[path = "maps/indexable_skip_list.rs"]
+pub mod indexable_skip_list;
+
+#[path = "caches/cache.rs"]
+pub mod cache;
+
+#[path = "caches/lru_cache.rs"]
+pub mod lru_cache;
+
+#[path = "caches/lfu_cache.rs"]
+pub mod lfu_cache;
+
+#[path = "caches/clock_cache.rs"]
+pub mod clock_cache;
+
+#[path = "union_find/disjoint_set.rs"]
+pub mod disjoint_set;
+
+#[path = "union_find/disjoint_set_map.rs"]
+pub mod disjoint_set_map;
+
+#[path = "union_find/rollback_disjoint_set.rs"]
+pub mod rollback_disjoint_set;
+
+#[path = "union_find/weighted_disjoint_set.rs"]
+pub mod weighted_disjoint_set;
+
+#[path = "graphs/graph.rs"]
+pub mod graph;
+
+#[path = "graphs/matrix_graph.rs"]
+pub mod matrix_graph;
+
+#[path = "graphs/dfs.rs"]
+pub mod dfs;
+
+#[path = "graphs/topological_sort.rs"]
+pub mod topological_sort;
+
+#[path = "graphs/cycle.rs"]
+pub mod cycle;
+
+#[path = "graphs/scc.rs"]
+pub mod scc;
+
+#[path = "graphs/connectivity.rs"]
+pub mod connectivity;
+
+#[path = "graphs/transitive_closure.rs"]
+pub mod transitive_closure;
+
+#[path = "graphs/shortest_paths.rs"]
+pub mod shortest_paths;
+
+#[path = "graphs/dijkstra.rs"]
+pub mod dijkstra;
+
+#[path = "heaps/indexed_min_heap.rs"]
+pub mod indexed_min_heap;
+
+#[path = "graphs/bellman_ford.rs"]
+pub mod bellman_ford;
+
+#[path = "graphs/johnson.rs"]
+pub mod johnson;
+
+#[path = "graphs/astar.rs"]
+pub mod astar;
+
+#[path = "graphs/bidirectional_dijkstra.rs"]
+pub mod bidirectional_dijkstra;
+
+#[path = "graphs/dag_paths.rs"]
+pub mod dag_paths;
+
+#[path = "graphs/spanning_tree.rs"]
+pub mod spanning_tree;
+
+#[path = "graphs/prim.rs"]
+pub mod prim;
+
+#[path = "graphs/kruskal.rs"]
+pub mod kruskal;
+
+#[path = "graphs/boruvka.rs"]
+pub mod boruvka;
+
+#[path = "graphs/flow_network.rs"]
+pub mod flow_network;
+
+#[path = "graphs/edmonds_karp.rs"]
+pub mod edmonds_karp;
+
+#[path = "graphs/dinic.rs"]
+pub mod dinic;
+
+#[path = "graphs/min_cost_flow.rs"]
+pub mod min_cost_flow;
+
+#[path = "graphs/bipartite.rs"]
+pub mod bipartite;
+
+#[path = "graphs/hopcroft_karp.rs"]
+pub mod hopcroft_karp;
+
+#[path = "graphs/biconnectivity.rs"]
+pub mod biconnectivity;
+
+#[path = "graphs/eulerian.rs"]
+pub mod eulerian;
+
+#[path = "graphs/dot.rs"]
+pub mod dot;
+
+#[path = "graphs/generators.rs"]
+pub mod generators;
+
+#[path = "graphs/edge_list.rs"]
+pub mod edge_list;
+
+#[path = "graphs/bfs.rs"]
+pub mod bfs;
+
+#[path = "graphs/property_map.rs"]
+pub mod property_map;
+
+#[path = "graphs/coloring.rs"]
+pub mod coloring;
+
+#[path = "graphs/approximation.rs"]
+pub mod approximation;
+
+#[path = "graphs/k_core.rs"]
+pub mod k_core;
+
+#[path = "graphs/pagerank.rs"]
+pub mod pagerank;
+
+#[path = "graphs/betweenness.rs"]
+pub mod betweenness;
+
+#[path = "graphs/transform.rs"]
+pub mod transform;
+
+#[path = "linear/stack.rs"]
+pub mod stack;
+
+#[path = "linear/queue.rs"]
+pub mod queue;
+
+#[path = "linear/deque.rs"]
+pub mod deque;
+
+#[path = "linear/circular_buffer.rs"]
+pub mod circular_buffer;
+
+#[path = "linear/two_stack_queue.rs"]
+pub mod two_stack_queue;
+
+#[path = "linear/min_stack.rs"]
+pub mod min_stack;
+
+#[path = "linear/sliding_window.rs"]
+pub mod sliding_window;
+
+#[path = "linear/unrolled_list.rs"]
+pub mod unrolled_list;
+
+#[path = "linear/instrumented_vec.rs"]
+pub mod instrumented_vec;
+
+#[path = "text/gap_buffer.rs"]
+pub mod gap_buffer;
+
+#[path = "text/piece_table.rs"]
+pub mod piece_table;
+
+#[path = "sorting/merge_sort.rs"]
+pub mod merge_sort;
+
+#[path = "sorting/quick_sort.rs"]
+pub mod quick_sort;
+
+#[path = "sorting/elementary_sorts.rs"]
+pub mod elementary_sorts;
+
+#[path = "sorting/counting_sort.rs"]
+pub mod counting_sort;
+
+#[path = "sorting/radix_sort.rs"]
+pub mod radix_sort;
+
+#[path = "sorting/bucket_sort.rs"]
+pub mod bucket_sort;
+
+#[path = "sorting/intro_sort.rs"]
+pub mod intro_sort;
+
+#[path = "sorting/natural_merge_sort.rs"]
+pub mod natural_merge_sort;
+
+#[cfg(feature = "rayon")]
+#[path = "sorting/parallel_sort.rs"]
+pub mod parallel_sort;
+
+#[path = "sorting/quickselect.rs"]
+pub mod quickselect;
+
+#[path = "sorting/median_of_medians.rs"]
+pub mod median_of_medians;
+
+#[path = "sorting/partial_sort.rs"]
+pub mod partial_sort;
+
+#[path = "searching/binary_search.rs"]
+pub mod binary_search;
+
+#[path = "sorting/inversions.rs"]
+pub mod inversions;
+
+#[path = "random/sampling.rs"]
+pub mod sampling;
+
+#[path = "sorting/sorted_merge.rs"]
+pub mod sorted_merge;
+
+#[path = "strings/kmp.rs"]
+pub mod kmp;
+
+#[path = "strings/rabin_karp.rs"]
+pub mod rabin_karp;
+
+#[path = "strings/z_algorithm.rs"]
+pub mod z_algorithm;
+
+#[path = "strings/horspool.rs"]
+pub mod horspool;
+
+#[path = "strings/manacher.rs"]
+pub mod manacher;
+
+#[path = "strings/edit_distance.rs"]
+pub mod edit_distance;
+
+#[path = "strings/lcs.rs"]
+pub mod lcs;
+
+#[path = "heaps/priority_queue.rs"]
+pub mod priority_queue;
+
+#[path = "maps/dictionary.rs"]
+pub mod dictionary;
+
+#[path = "sets/set.rs"]
+pub mod set;
+
+#[path = "sorting/comparator.rs"]
+pub mod comparator;
\ No newline at end of file
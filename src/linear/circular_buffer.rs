@@ -0,0 +1,203 @@
+/// What a `CircularBuffer` does with a new item when it's full
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OverflowPolicy {
+    /// Refuse the new item and keep the old ones
+    Reject,
+    /// Drop the oldest item to make room, keeping the latest `N` items
+    Overwrite,
+}
+
+/// A circular buffer of at most `N` items that never allocates
+///
+/// Items live in an array that wraps around, from `head` for `len` slots, so the whole buffer
+/// can live on the stack or in a static, and pushing and popping take `O(1)` time. When it's full,
+/// a push either fails or overwrites the oldest item, depending on the `OverflowPolicy`, which
+/// suits logs that keep the latest entries and statistics over a sliding window.
+#[derive(Clone, Debug)]
+pub struct CircularBuffer<T, const N: usize> {
+    buffer: [Option<T>; N],
+    head: usize,
+    len: usize,
+    policy: OverflowPolicy,
+}
+
+impl<T, const N: usize> CircularBuffer<T, N> {
+
+    /// Creates a new empty `CircularBuffer`
+    ///
+    /// A `const fn`, so a buffer can be created in a `static`.
+    ///
+    /// # Arguments
+    ///
+    /// * `policy` - What to do with a new item when the buffer is full
+    ///
+    /// # Panics
+    ///
+    /// If `N` is zero
+    pub const fn new(policy: OverflowPolicy) -> CircularBuffer<T, N> {
+        assert!(N > 0, "a circular buffer needs room for at least one item");
+        CircularBuffer { buffer: [const { None }; N], head: 0, len: 0, policy }
+    }
+
+    /// Number of items in the buffer
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Checks if the buffer is empty
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Checks if the buffer holds `N` items
+    pub fn is_full(&self) -> bool {
+        self.len == N
+    }
+
+    /// Most items the buffer can hold, `N`
+    pub fn capacity(&self) -> usize {
+        N
+    }
+
+    /// What the buffer does with a new item when it's full
+    pub fn policy(&self) -> OverflowPolicy {
+        self.policy
+    }
+
+    /// Adds an item as the newest
+    ///
+    /// Returns the oldest item if it was overwritten to make room, or gives the new item back as
+    /// an error if the buffer is full and rejects new items.
+    ///
+    /// # Arguments
+    ///
+    /// * `item` - Item to add
+    pub fn push(&mut self, item: T) -> Result<Option<T>, T> {
+        if self.len < N {
+            let i = self.slot(self.len);
+            self.buffer[i] = Some(item);
+            self.len += 1;
+            return Ok(None);
+        }
+        match self.policy {
+            OverflowPolicy::Reject => Err(item),
+            OverflowPolicy::Overwrite => {
+                let oldest = self.buffer[self.head].replace(item);
+                self.head = self.slot(1);
+                Ok(oldest)
+            }
+        }
+    }
+
+    /// Removes and returns the oldest item, or None if the buffer is empty
+    pub fn pop(&mut self) -> Option<T> {
+        if self.len == 0 {
+            return None;
+        }
+        let item = self.buffer[self.head].take();
+        self.head = self.slot(1);
+        self.len -= 1;
+        item
+    }
+
+    /// Gets the oldest item, or None if the buffer is empty
+    pub fn oldest(&self) -> Option<&T> {
+        self.get(0)
+    }
+
+    /// Gets the newest item, or None if the buffer is empty
+    pub fn newest(&self) -> Option<&T> {
+        self.get(self.len.wrapping_sub(1))
+    }
+
+    /// Gets the item `i` places after the oldest, or None if there are `i` items or fewer
+    ///
+    /// # Arguments
+    ///
+    /// * `i` - Index of the item
+    pub fn get(&self, i: usize) -> Option<&T> {
+        if i >= self.len {
+            return None;
+        }
+        self.buffer[self.slot(i)].as_ref()
+    }
+
+    /// Removes every item
+    pub fn clear(&mut self) {
+        while self.pop().is_some() {}
+        self.head = 0;
+    }
+
+    /// Returns an iterator over the items from the oldest to the newest
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = &T> + ExactSizeIterator + '_ {
+        (0..self.len).map(move |i| self.buffer[self.slot(i)].as_ref().unwrap())
+    }
+
+    /// Internal function to get the array slot `i` places after the head
+    fn slot(&self, i: usize) -> usize {
+        (self.head + i) % N
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    #[test]
+    fn test_reject() {
+        let mut buffer: CircularBuffer<u32, 3> = CircularBuffer::new(OverflowPolicy::Reject);
+        assert_eq!(Ok(None), buffer.push(1));
+        assert_eq!(Ok(None), buffer.push(2));
+        assert_eq!(Ok(None), buffer.push(3));
+        assert!(buffer.is_full());
+        assert_eq!(Err(4), buffer.push(4));
+        assert_eq!(Some(1), buffer.pop());
+        assert_eq!(Ok(None), buffer.push(4));
+        assert_eq!(vec![&2, &3, &4], buffer.iter().collect::<Vec<_>>());
+        assert_eq!((Some(&2), Some(&4)), (buffer.oldest(), buffer.newest()));
+    }
+
+    #[test]
+    fn test_overwrite() {
+        let mut buffer: CircularBuffer<u32, 3> = CircularBuffer::new(OverflowPolicy::Overwrite);
+        for i in 0..3 {
+            assert_eq!(Ok(None), buffer.push(i));
+        }
+        for i in 3..10 {
+            assert_eq!(Ok(Some(i - 3)), buffer.push(i));
+        }
+        assert_eq!(vec![&7, &8, &9], buffer.iter().collect::<Vec<_>>());
+        assert_eq!(vec![&9, &8, &7], buffer.iter().rev().collect::<Vec<_>>());
+        assert_eq!(Some(&8), buffer.get(1));
+        assert_eq!(None, buffer.get(3));
+        buffer.clear();
+        assert!(buffer.is_empty());
+        assert_eq!(None, buffer.newest());
+    }
+
+    #[test]
+    fn test_static() {
+        static LOG: Mutex<CircularBuffer<&str, 2>> = Mutex::new(CircularBuffer::new(OverflowPolicy::Overwrite));
+        for message in ["started", "connected", "stopped"] {
+            LOG.lock().unwrap().push(message).unwrap();
+        }
+        assert_eq!(vec![&"connected", &"stopped"], LOG.lock().unwrap().iter().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_moving_average() {
+        // Average of the last 4 readings, without storing the rest
+        let mut window: CircularBuffer<f64, 4> = CircularBuffer::new(OverflowPolicy::Overwrite);
+        let mut sum = 0.0;
+        let mut averages = Vec::new();
+        for x in [1.0, 2.0, 3.0, 4.0, 5.0, 6.0] {
+            sum += x;
+            if let Ok(Some(old)) = window.push(x) {
+                sum -= old;
+            }
+            averages.push(sum / window.len() as f64);
+        }
+        assert_eq!(vec![1.0, 1.5, 2.0, 2.5, 3.5, 4.5], averages);
+    }
+}
@@ -0,0 +1,401 @@
+use std::fmt;
+use std::iter::FromIterator;
+use std::ops::{Index, IndexMut};
+
+/// A double-ended queue on a growable circular buffer
+///
+/// Items live in a buffer that wraps around, from `head` for `len` slots, so pushing or popping at
+/// either end only moves `head` or `len`, in amortized `O(1)` time, and the `i`th item is found
+/// in `O(1)` time. When the buffer is full it doubles, copying the items to the front of the new
+/// buffer.
+///
+/// Empty slots hold `None`, like `CircularBuffer` and `GapBuffer`, so the deque needs no unsafe
+/// code. Every slot from `head` for `len` places holds an item, and no other slot does.
+pub struct Deque<T> {
+    buffer: Vec<Option<T>>,
+    head: usize,
+    len: usize,
+}
+
+impl<T> Deque<T> {
+
+    /// Creates a new empty `Deque`
+    pub fn new() -> Deque<T> {
+        Deque::with_capacity(0)
+    }
+
+    /// Creates a new empty `Deque` with room for `capacity` items before growing
+    ///
+    /// # Arguments
+    ///
+    /// * `capacity` - Number of items to make room for
+    pub fn with_capacity(capacity: usize) -> Deque<T> {
+        Deque { buffer: empty(capacity), head: 0, len: 0 }
+    }
+
+    /// Number of items in the deque
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Checks if the deque is empty
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Number of items the deque can hold before growing
+    pub fn capacity(&self) -> usize {
+        self.buffer.len()
+    }
+
+    /// Adds an item to the back of the deque
+    ///
+    /// # Arguments
+    ///
+    /// * `item` - Item to add
+    pub fn push_back(&mut self, item: T) {
+        if self.len == self.buffer.len() {
+            self.grow();
+        }
+        let i = self.slot(self.len);
+        self.buffer[i] = Some(item);
+        self.len += 1;
+    }
+
+    /// Adds an item to the front of the deque
+    ///
+    /// # Arguments
+    ///
+    /// * `item` - Item to add
+    pub fn push_front(&mut self, item: T) {
+        if self.len == self.buffer.len() {
+            self.grow();
+        }
+        self.head = if self.head == 0 { self.buffer.len() - 1 } else { self.head - 1 };
+        self.buffer[self.head] = Some(item);
+        self.len += 1;
+    }
+
+    /// Removes and returns the item at the back of the deque, or None if the deque is empty
+    pub fn pop_back(&mut self) -> Option<T> {
+        if self.len == 0 {
+            return None;
+        }
+        self.len -= 1;
+        let i = self.slot(self.len);
+        Some(self.take(i))
+    }
+
+    /// Removes and returns the item at the front of the deque, or None if the deque is empty
+    pub fn pop_front(&mut self) -> Option<T> {
+        if self.len == 0 {
+            return None;
+        }
+        let item = self.take(self.head);
+        self.head = self.slot(1);
+        self.len -= 1;
+        Some(item)
+    }
+
+    /// Gets the item at the front of the deque, or None if the deque is empty
+    pub fn front(&self) -> Option<&T> {
+        self.get(0)
+    }
+
+    /// Gets the item at the back of the deque, or None if the deque is empty
+    pub fn back(&self) -> Option<&T> {
+        self.get(self.len.wrapping_sub(1))
+    }
+
+    /// Gets the item `i` places from the front, or None if there are `i` items or fewer
+    ///
+    /// # Arguments
+    ///
+    /// * `i` - Index of the item
+    pub fn get(&self, i: usize) -> Option<&T> {
+        if i >= self.len {
+            return None;
+        }
+        self.buffer[self.slot(i)].as_ref()
+    }
+
+    /// Gets the item `i` places from the front mutably, or None if there are `i` items or fewer
+    ///
+    /// # Arguments
+    ///
+    /// * `i` - Index of the item
+    pub fn get_mut(&mut self, i: usize) -> Option<&mut T> {
+        if i >= self.len {
+            return None;
+        }
+        let j = self.slot(i);
+        self.buffer[j].as_mut()
+    }
+
+    /// Removes every item, keeping the buffer
+    pub fn clear(&mut self) {
+        for slot in self.buffer.iter_mut() {
+            *slot = None;
+        }
+        self.head = 0;
+        self.len = 0;
+    }
+
+    /// Returns an iterator over the items from the front of the deque to the back
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter { deque: self, front: 0, back: self.len }
+    }
+
+    /// Rotates the buffer in place so the items start at its first slot, and returns an iterator
+    /// over them mutably, from front to back
+    ///
+    /// Takes `O(n)` time if the items wrap around the end of the buffer, and `O(1)` otherwise.
+    /// The slots hold `Option`s, so the items can't be borrowed as a `&mut [T]`, but after this
+    /// they're in one run of slots that the iterator walks without wrapping.
+    pub fn make_contiguous(&mut self) -> impl DoubleEndedIterator<Item = &mut T> + ExactSizeIterator {
+        if self.head + self.len > self.buffer.len() {
+            self.buffer.rotate_left(self.head);
+            self.head = 0;
+        }
+        // Every slot of the run holds an item
+        self.buffer[self.head..self.head + self.len].iter_mut().map(|slot| slot.as_mut().unwrap())
+    }
+
+    /// Internal function to get the buffer slot `i` places after the head
+    fn slot(&self, i: usize) -> usize {
+        let i = self.head + i;
+        if i >= self.buffer.len() {
+            i - self.buffer.len()
+        } else {
+            i
+        }
+    }
+
+    /// Internal function to move the item out of a slot, leaving it empty
+    ///
+    /// The caller must stop counting the slot as one of the items.
+    fn take(&mut self, slot: usize) -> T {
+        // Callers only take slots holding items
+        self.buffer[slot].take().unwrap()
+    }
+
+    /// Internal function to double the buffer, unwrapping the items to its front
+    fn grow(&mut self) {
+        let capacity = (2 * self.buffer.len()).max(4);
+        let mut buffer = empty(capacity);
+        for (i, slot) in buffer.iter_mut().enumerate().take(self.len) {
+            let j = self.slot(i);
+            *slot = self.buffer[j].take();
+        }
+        self.buffer = buffer;
+        self.head = 0;
+    }
+}
+
+/// Internal function to allocate a buffer of empty slots
+fn empty<T>(capacity: usize) -> Vec<Option<T>> {
+    (0..capacity).map(|_| None).collect()
+}
+
+/// Iterator over the items of a `Deque`, from front to back
+#[derive(Clone, Debug)]
+pub struct Iter<'a, T> {
+    deque: &'a Deque<T>,
+    front: usize,
+    back: usize,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        if self.front == self.back {
+            return None;
+        }
+        self.front += 1;
+        self.deque.get(self.front - 1)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let n = self.back - self.front;
+        (n, Some(n))
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for Iter<'a, T> {
+    fn next_back(&mut self) -> Option<&'a T> {
+        if self.front == self.back {
+            return None;
+        }
+        self.back -= 1;
+        self.deque.get(self.back)
+    }
+}
+
+impl<'a, T> ExactSizeIterator for Iter<'a, T> {}
+
+impl<T: Clone> Clone for Deque<T> {
+    fn clone(&self) -> Self {
+        let mut deque = Deque::with_capacity(self.len);
+        deque.extend(self.iter().cloned());
+        deque
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for Deque<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<T> Default for Deque<T> {
+    fn default() -> Self {
+        Deque::new()
+    }
+}
+
+impl<T: PartialEq> PartialEq for Deque<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.len == other.len && self.iter().eq(other.iter())
+    }
+}
+
+impl<T: Eq> Eq for Deque<T> {}
+
+impl<T> Index<usize> for Deque<T> {
+    type Output = T;
+
+    fn index(&self, i: usize) -> &T {
+        match self.get(i) {
+            Some(item) => item,
+            None => panic!("index {} out of range for deque of length {}", i, self.len),
+        }
+    }
+}
+
+impl<T> IndexMut<usize> for Deque<T> {
+    fn index_mut(&mut self, i: usize) -> &mut T {
+        let len = self.len;
+        match self.get_mut(i) {
+            Some(item) => item,
+            None => panic!("index {} out of range for deque of length {}", i, len),
+        }
+    }
+}
+
+impl<T> Extend<T> for Deque<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for item in iter {
+            self.push_back(item);
+        }
+    }
+}
+
+impl<T> FromIterator<T> for Deque<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut deque = Deque::new();
+        deque.extend(iter);
+        deque
+    }
+}
+
+impl<'a, T> IntoIterator for &'a Deque<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::Rng;
+    use std::collections::VecDeque;
+    use std::rc::Rc;
+
+    #[test]
+    fn test_both_ends() {
+        let mut deque = Deque::new();
+        deque.push_back(2);
+        deque.push_front(1);
+        deque.push_back(3);
+        deque.push_front(0);
+        assert_eq!(4, deque.len());
+        assert_eq!((Some(&0), Some(&3)), (deque.front(), deque.back()));
+        assert_eq!(vec![&0, &1, &2, &3], deque.iter().collect::<Vec<_>>());
+        assert_eq!(vec![&3, &2, &1, &0], deque.iter().rev().collect::<Vec<_>>());
+        assert_eq!(2, deque[2]);
+        deque[2] = 20;
+        assert_eq!(Some(&20), deque.get(2));
+        assert_eq!(None, deque.get(4));
+
+        assert_eq!(Some(0), deque.pop_front());
+        assert_eq!(Some(3), deque.pop_back());
+        assert_eq!(Some(20), deque.pop_back());
+        assert_eq!(Some(1), deque.pop_back());
+        assert_eq!(None, deque.pop_front());
+        assert_eq!(None, deque.back());
+    }
+
+    #[test]
+    fn test_make_contiguous() {
+        let mut deque = Deque::with_capacity(8);
+        deque.extend(4..8);
+        for i in (0..4).rev() {
+            deque.push_front(i);
+        }
+        // The items wrap around, 4..8 at the start of the buffer and 0..4 at the end
+        assert_eq!(8, deque.capacity());
+        assert_eq!(4, deque.head);
+        let items: Vec<&mut i32> = deque.make_contiguous().collect();
+        assert_eq!(vec![&0, &1, &2, &3, &4, &5, &6, &7], items);
+        for item in deque.make_contiguous().rev().take(3) {
+            *item *= 10;
+        }
+        assert_eq!(0, deque.head);
+        assert!(deque.buffer.iter().all(Option::is_some));
+        assert_eq!(vec![0, 1, 2, 3, 4, 50, 60, 70], deque.iter().copied().collect::<Vec<_>>());
+        assert_eq!(deque.clone(), deque);
+    }
+
+    #[test]
+    fn test_drops_items() {
+        let item = Rc::new(());
+        let mut deque = Deque::new();
+        for _ in 0..10 {
+            deque.push_front(Rc::clone(&item));
+            deque.push_back(Rc::clone(&item));
+        }
+        deque.pop_back();
+        assert_eq!(20, Rc::strong_count(&item));
+        drop(deque);
+        assert_eq!(1, Rc::strong_count(&item));
+    }
+
+    #[test]
+    fn test_matches_vec_deque() {
+        let mut rng = Rng::new(73);
+        let mut deque = Deque::new();
+        let mut expected = VecDeque::new();
+        for i in 0..2000 {
+            match rng.gen_range(5) {
+                0 => assert_eq!(expected.pop_front(), deque.pop_front()),
+                1 => assert_eq!(expected.pop_back(), deque.pop_back()),
+                2 => {
+                    deque.push_front(i);
+                    expected.push_front(i);
+                }
+                _ => {
+                    deque.push_back(i);
+                    expected.push_back(i);
+                }
+            }
+            assert_eq!(expected.len(), deque.len());
+            assert_eq!(expected.back(), deque.back());
+        }
+        assert!(expected.iter().eq(deque.iter()));
+        assert!(expected.make_contiguous().iter_mut().eq(deque.make_contiguous()));
+    }
+}
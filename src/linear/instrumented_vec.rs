@@ -0,0 +1,236 @@
+use std::ops::Index;
+
+/// How an `InstrumentedVec` picks its new capacity when it's full
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Growth {
+    /// Multiply the capacity by a factor greater than 1, like 1.5 or 2, rounding up
+    Factor(f64),
+    /// Add a fixed number of slots
+    Additive(usize),
+}
+
+impl Growth {
+
+    /// Internal function to compute the capacity to grow to from a full one
+    fn next_capacity(self, capacity: usize) -> usize {
+        match self {
+            Growth::Factor(factor) => ((capacity as f64 * factor).ceil() as usize).max(capacity + 1),
+            Growth::Additive(k) => capacity + k,
+        }
+    }
+}
+
+/// Totals of the costs an `InstrumentedVec` has recorded
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CostReport {
+    /// Number of operations
+    pub operations: usize,
+    /// Total cost of every operation, one per item written plus one per item copied
+    pub total: usize,
+    /// Cost of the most expensive operation
+    pub max: usize,
+    /// Number of times the array grew
+    pub reallocations: usize,
+    /// Total cost divided by the number of operations
+    pub amortized: f64,
+}
+
+/// A dynamic array that records what each operation costs, to check amortized analysis
+///
+/// Pushing onto a full array allocates a bigger one and copies every item over, an `O(n)` push,
+/// but how often that happens depends on how the capacity grows. Growing by a constant factor
+/// makes the total cost of `n` pushes `O(n)`, so each push takes amortized `O(1)` time: with
+/// doubling, the copies at capacities `1, 2, 4, ..., n` add up to less than `2n`. Growing by a
+/// constant `k` copies `n^2 / 2k` items in total, amortized `O(n)` per push.
+///
+/// Each operation's cost counts one for the item it writes or removes plus one for every item it
+/// copies, and the costs are kept so their running total can be compared with a bound.
+#[derive(Clone, Debug)]
+pub struct InstrumentedVec<T> {
+    items: Vec<T>,
+    capacity: usize,
+    growth: Growth,
+    costs: Vec<usize>,
+    reallocations: usize,
+}
+
+impl<T> InstrumentedVec<T> {
+
+    /// Creates a new empty `InstrumentedVec` with no capacity
+    ///
+    /// # Arguments
+    ///
+    /// * `growth` - How the capacity grows when the array is full
+    ///
+    /// # Panics
+    ///
+    /// If the growth factor isn't greater than 1, or the additive growth is 0
+    pub fn new(growth: Growth) -> InstrumentedVec<T> {
+        match growth {
+            Growth::Factor(factor) => assert!(factor > 1.0, "the growth factor must be greater than 1"),
+            Growth::Additive(k) => assert!(k > 0, "additive growth must add at least one slot"),
+        }
+        InstrumentedVec { items: Vec::new(), capacity: 0, growth, costs: Vec::new(), reallocations: 0 }
+    }
+
+    /// Number of items in the array
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    /// Checks if the array is empty
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Number of items the array can hold before growing
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Adds an item to the end, growing and copying every item first if the array is full
+    ///
+    /// # Arguments
+    ///
+    /// * `item` - Item to add
+    pub fn push(&mut self, item: T) {
+        let mut cost = 1;
+        if self.items.len() == self.capacity {
+            cost += self.grow();
+        }
+        self.items.push(item);
+        self.costs.push(cost);
+    }
+
+    /// Removes and returns the last item, or None if the array is empty
+    ///
+    /// Never shrinks the array.
+    pub fn pop(&mut self) -> Option<T> {
+        let item = self.items.pop()?;
+        self.costs.push(1);
+        Some(item)
+    }
+
+    /// Gets the item at an index, or None if there are `i` items or fewer
+    ///
+    /// # Arguments
+    ///
+    /// * `i` - Index of the item
+    pub fn get(&self, i: usize) -> Option<&T> {
+        self.items.get(i)
+    }
+
+    /// Cost of every operation so far, in order
+    pub fn costs(&self) -> &[usize] {
+        &self.costs
+    }
+
+    /// Running total of the costs after each operation
+    pub fn cumulative_costs(&self) -> Vec<usize> {
+        self.costs
+            .iter()
+            .scan(0, |total, &cost| {
+                *total += cost;
+                Some(*total)
+            })
+            .collect()
+    }
+
+    /// Totals of the costs so far
+    pub fn report(&self) -> CostReport {
+        let total = self.costs.iter().sum();
+        let operations = self.costs.len();
+        CostReport {
+            operations,
+            total,
+            max: self.costs.iter().copied().max().unwrap_or(0),
+            reallocations: self.reallocations,
+            amortized: if operations == 0 { 0.0 } else { total as f64 / operations as f64 },
+        }
+    }
+
+    /// Clears the recorded costs, keeping the items
+    pub fn reset_costs(&mut self) {
+        self.costs.clear();
+        self.reallocations = 0;
+    }
+
+    /// Internal function to move the items to a bigger allocation, returning the number copied
+    fn grow(&mut self) -> usize {
+        self.capacity = self.growth.next_capacity(self.capacity);
+        let mut items = Vec::with_capacity(self.capacity);
+        let copied = self.items.len();
+        items.append(&mut self.items);
+        self.items = items;
+        self.reallocations += 1;
+        copied
+    }
+}
+
+impl<T> Index<usize> for InstrumentedVec<T> {
+    type Output = T;
+
+    fn index(&self, i: usize) -> &T {
+        &self.items[i]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Pushes `n` items and returns the report
+    fn push_all(growth: Growth, n: usize) -> CostReport {
+        let mut vec = InstrumentedVec::new(growth);
+        for i in 0..n {
+            vec.push(i);
+        }
+        assert_eq!(n, vec.len());
+        assert_eq!(n - 1, vec[n - 1]);
+        vec.report()
+    }
+
+    #[test]
+    fn test_doubling() {
+        let mut vec = InstrumentedVec::new(Growth::Factor(2.0));
+        for i in 0..9 {
+            vec.push(i);
+        }
+        // Grows at 0, 1, 2, 4, and 8 items, copying that many
+        assert_eq!(&[1, 2, 3, 1, 5, 1, 1, 1, 9], vec.costs());
+        assert_eq!(16, vec.capacity());
+        assert_eq!(vec![1, 3, 6, 7, 12, 13, 14, 15, 24], vec.cumulative_costs());
+        assert_eq!(Some(8), vec.pop());
+        assert_eq!(Some(&7), vec.get(7));
+        let report = vec.report();
+        assert_eq!((10, 25, 9, 5), (report.operations, report.total, report.max, report.reallocations));
+        vec.reset_costs();
+        assert_eq!(0.0, vec.report().amortized);
+    }
+
+    #[test]
+    fn test_amortized_bounds() {
+        let n = 100_000;
+        // Every prefix of pushes with doubling costs less than 3 per push
+        let mut vec = InstrumentedVec::new(Growth::Factor(2.0));
+        for i in 0..n {
+            vec.push(i);
+        }
+        for (i, total) in vec.cumulative_costs().into_iter().enumerate() {
+            assert!(total <= 3 * (i + 1));
+        }
+        // A factor of 1.5 copies each item at most 1 / (1.5 - 1) = 2 times on average
+        assert!(push_all(Growth::Factor(1.5), n).amortized <= 4.0);
+    }
+
+    #[test]
+    fn test_additive_is_linear() {
+        let k = 100;
+        let small = push_all(Growth::Additive(k), 10_000);
+        let large = push_all(Growth::Additive(k), 20_000);
+        // Copies add up to about n^2 / 2k, so doubling n doubles the amortized cost
+        assert!(small.amortized > 40.0);
+        assert!(large.amortized / small.amortized > 1.9);
+        assert_eq!(200, large.reallocations);
+    }
+}
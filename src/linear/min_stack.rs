@@ -0,0 +1,204 @@
+/// A Stack that finds its minimum in `O(1)` time
+///
+/// Alongside the items, keeps a stack of the positions of the items that were smaller than
+/// everything below them when pushed. The top of that stack is the current minimum, and it's
+/// popped with its item, uncovering the minimum of the items below. Every operation takes
+/// amortized `O(1)` time, and ties keep the lowest item as the minimum.
+#[derive(Clone, Debug)]
+pub struct MinStack<T: Ord> {
+    items: Vec<T>,
+    mins: Vec<usize>,
+}
+
+impl<T: Ord> MinStack<T> {
+
+    /// Creates a new empty `MinStack`
+    pub fn new() -> MinStack<T> {
+        MinStack { items: Vec::new(), mins: Vec::new() }
+    }
+
+    /// Number of items in the stack
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    /// Checks if the stack is empty
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Pushes an item on top of the stack
+    ///
+    /// # Arguments
+    ///
+    /// * `item` - Item to push
+    pub fn push(&mut self, item: T) {
+        if self.min().is_none_or(|min| item < *min) {
+            self.mins.push(self.items.len());
+        }
+        self.items.push(item);
+    }
+
+    /// Removes and returns the top item, or None if the stack is empty
+    pub fn pop(&mut self) -> Option<T> {
+        let item = self.items.pop()?;
+        if self.mins.last() == Some(&self.items.len()) {
+            self.mins.pop();
+        }
+        Some(item)
+    }
+
+    /// Gets the top item, or None if the stack is empty
+    pub fn peek(&self) -> Option<&T> {
+        self.items.last()
+    }
+
+    /// Gets the smallest item, or None if the stack is empty
+    pub fn min(&self) -> Option<&T> {
+        self.mins.last().map(|&i| &self.items[i])
+    }
+}
+
+impl<T: Ord> Default for MinStack<T> {
+    fn default() -> Self {
+        MinStack::new()
+    }
+}
+
+/// A Queue that finds its minimum in `O(1)` time, built from two `MinStack`s
+///
+/// Items are pushed onto an inbox and popped from an outbox, which is refilled from the inbox
+/// when it's empty, like `TwoStackQueue`. Every item is in one of the two stacks, so the minimum
+/// is the smaller of their minimums. Every operation takes amortized `O(1)` time, which makes it a
+/// sliding window minimum: enqueue the newest value and dequeue the oldest.
+#[derive(Clone, Debug)]
+pub struct MinQueue<T: Ord> {
+    inbox: MinStack<T>,
+    outbox: MinStack<T>,
+}
+
+impl<T: Ord> MinQueue<T> {
+
+    /// Creates a new empty `MinQueue`
+    pub fn new() -> MinQueue<T> {
+        MinQueue { inbox: MinStack::new(), outbox: MinStack::new() }
+    }
+
+    /// Number of items in the queue
+    pub fn len(&self) -> usize {
+        self.inbox.len() + self.outbox.len()
+    }
+
+    /// Checks if the queue is empty
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Adds an item to the back of the queue
+    ///
+    /// # Arguments
+    ///
+    /// * `item` - Item to add
+    pub fn enqueue(&mut self, item: T) {
+        self.inbox.push(item);
+    }
+
+    /// Removes and returns the item at the front of the queue, or None if the queue is empty
+    pub fn dequeue(&mut self) -> Option<T> {
+        self.transfer();
+        self.outbox.pop()
+    }
+
+    /// Gets the item at the front of the queue, or None if the queue is empty
+    ///
+    /// Takes `&mut self` since it may move the inbox over to reach the front.
+    pub fn peek(&mut self) -> Option<&T> {
+        self.transfer();
+        self.outbox.peek()
+    }
+
+    /// Gets the smallest item, or None if the queue is empty
+    pub fn min(&self) -> Option<&T> {
+        match (self.inbox.min(), self.outbox.min()) {
+            (Some(a), Some(b)) => Some(a.min(b)),
+            (a, b) => a.or(b),
+        }
+    }
+
+    /// Internal function to move every inbox item to the outbox when it's empty
+    fn transfer(&mut self) {
+        if self.outbox.is_empty() {
+            while let Some(item) = self.inbox.pop() {
+                self.outbox.push(item);
+            }
+        }
+    }
+}
+
+impl<T: Ord> Default for MinQueue<T> {
+    fn default() -> Self {
+        MinQueue::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::Rng;
+
+    #[test]
+    fn test_min_stack() {
+        let mut stack = MinStack::new();
+        assert_eq!(None, stack.min());
+        for x in [5, 3, 7, 3, 1] {
+            stack.push(x);
+        }
+        assert_eq!(Some(&1), stack.min());
+        assert_eq!(Some(1), stack.pop());
+        assert_eq!(Some(&3), stack.min());
+        assert_eq!(Some(3), stack.pop());
+        assert_eq!(Some(&3), stack.min());
+        stack.pop();
+        stack.pop();
+        assert_eq!(Some(&5), stack.min());
+        assert_eq!(Some(&5), stack.peek());
+        stack.pop();
+        assert!(stack.is_empty());
+        assert_eq!(None, stack.min());
+    }
+
+    #[test]
+    fn test_min_queue() {
+        let mut queue = MinQueue::new();
+        for x in [4, 2, 6, 1, 5] {
+            queue.enqueue(x);
+        }
+        assert_eq!(Some(&1), queue.min());
+        assert_eq!(Some(&4), queue.peek());
+        assert_eq!(Some(4), queue.dequeue());
+        assert_eq!(Some(&1), queue.min());
+        assert_eq!(Some(2), queue.dequeue());
+        assert_eq!(Some(6), queue.dequeue());
+        assert_eq!(Some(1), queue.dequeue());
+        assert_eq!(Some(&5), queue.min());
+        queue.enqueue(0);
+        assert_eq!(Some(&0), queue.min());
+        assert_eq!(2, queue.len());
+    }
+
+    #[test]
+    fn test_sliding_window_min() {
+        let mut rng = Rng::new(83);
+        let values: Vec<u64> = (0..500).map(|_| rng.gen_range(1000)).collect();
+        let k = 7;
+        let mut window = MinQueue::new();
+        for (i, &x) in values.iter().enumerate() {
+            window.enqueue(x);
+            if window.len() > k {
+                window.dequeue();
+            }
+            let start = (i + 1).saturating_sub(k);
+            assert_eq!(values[start..=i].iter().min(), window.min());
+        }
+    }
+}
@@ -0,0 +1,162 @@
+use std::iter::FromIterator;
+
+use crate::deque::{Deque, Iter};
+
+/// A first in, first out Queue on a growable circular buffer
+///
+/// Items are kept in a `Deque`, added at the back and removed from the front, so dequeuing only
+/// advances the start of the circular buffer and takes `O(1)` time, where removing the front of
+/// a `Vec` shifts every other item down. When the buffer is full it doubles, so enqueueing takes
+/// amortized `O(1)` time.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Queue<T> {
+    items: Deque<T>,
+}
+
+impl<T> Queue<T> {
+
+    /// Creates a new empty `Queue`
+    pub fn new() -> Queue<T> {
+        Queue { items: Deque::new() }
+    }
+
+    /// Creates a new empty `Queue` with room for `capacity` items before growing
+    ///
+    /// # Arguments
+    ///
+    /// * `capacity` - Number of items to make room for
+    pub fn with_capacity(capacity: usize) -> Queue<T> {
+        Queue { items: Deque::with_capacity(capacity) }
+    }
+
+    /// Number of items in the queue
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    /// Checks if the queue is empty
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Number of items the queue can hold before growing
+    pub fn capacity(&self) -> usize {
+        self.items.capacity()
+    }
+
+    /// Adds an item to the back of the queue
+    ///
+    /// # Arguments
+    ///
+    /// * `item` - Item to add
+    pub fn enqueue(&mut self, item: T) {
+        self.items.push_back(item);
+    }
+
+    /// Removes and returns the item at the front of the queue, or None if the queue is empty
+    pub fn dequeue(&mut self) -> Option<T> {
+        self.items.pop_front()
+    }
+
+    /// Gets the item at the front of the queue, or None if the queue is empty
+    pub fn peek(&self) -> Option<&T> {
+        self.items.front()
+    }
+
+    /// Removes every item, keeping the buffer
+    pub fn clear(&mut self) {
+        self.items.clear();
+    }
+
+    /// Returns an iterator over the items from the front of the queue to the back
+    pub fn iter(&self) -> Iter<'_, T> {
+        self.items.iter()
+    }
+}
+
+impl<T> Default for Queue<T> {
+    fn default() -> Self {
+        Queue::new()
+    }
+}
+
+impl<T> Extend<T> for Queue<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        self.items.extend(iter);
+    }
+}
+
+impl<T> FromIterator<T> for Queue<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        Queue { items: iter.into_iter().collect() }
+    }
+}
+
+impl<'a, T> IntoIterator for &'a Queue<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::Rng;
+    use std::collections::VecDeque;
+
+    #[test]
+    fn test_enqueue_dequeue() {
+        let mut queue = Queue::new();
+        assert_eq!(None, queue.dequeue());
+        queue.enqueue(1);
+        queue.enqueue(2);
+        queue.enqueue(3);
+        assert_eq!(3, queue.len());
+        assert_eq!(Some(&1), queue.peek());
+        assert_eq!(Some(1), queue.dequeue());
+        assert_eq!(Some(2), queue.dequeue());
+        assert_eq!(Some(3), queue.dequeue());
+        assert_eq!(None, queue.peek());
+        assert!(queue.is_empty());
+    }
+
+    #[test]
+    fn test_wrap_around() {
+        // Dequeue as often as enqueue, so the items wrap around the buffer without growing it
+        let mut queue = Queue::with_capacity(4);
+        queue.extend(0..3);
+        for i in 3..20 {
+            queue.enqueue(i);
+            assert_eq!(Some(i - 3), queue.dequeue());
+        }
+        assert_eq!(4, queue.capacity());
+        assert_eq!(vec![&17, &18, &19], queue.iter().collect::<Vec<_>>());
+
+        // Growing while wrapped keeps the order
+        queue.extend(20..30);
+        assert_eq!((17..30).collect::<Vec<_>>(), queue.iter().copied().collect::<Vec<_>>());
+        assert_eq!(queue, (17..30).collect());
+        queue.clear();
+        assert!(queue.is_empty());
+    }
+
+    #[test]
+    fn test_matches_vec() {
+        let mut rng = Rng::new(71);
+        let mut queue = Queue::new();
+        let mut expected = VecDeque::new();
+        for i in 0..1000 {
+            if rng.gen_range(3) == 0 {
+                assert_eq!(expected.pop_front(), queue.dequeue());
+            } else {
+                queue.enqueue(i);
+                expected.push_back(i);
+            }
+            assert_eq!(expected.len(), queue.len());
+        }
+        assert!(expected.iter().eq(queue.iter()));
+    }
+}
@@ -0,0 +1,173 @@
+use std::cmp::Reverse;
+
+use crate::deque::Deque;
+
+/// Maximum of the last `k` values pushed, kept in a monotonic deque
+///
+/// The deque holds the values that could still be the maximum of some window, in decreasing order
+/// from front to back. A new value pops every smaller or equal value off the back, since they
+/// leave the window before it and can't be the maximum while it's there, and the front is popped
+/// once it leaves the window. The maximum is the front. Each value is pushed and popped at most
+/// once, so pushing takes amortized `O(1)` time, and the deque never holds more than `k` values.
+///
+/// Wrapping values in `std::cmp::Reverse` finds the minimum instead.
+#[derive(Clone, Debug)]
+pub struct SlidingWindowMax<T: Ord> {
+    k: usize,
+    count: usize,
+    deque: Deque<(usize, T)>,
+}
+
+impl<T: Ord> SlidingWindowMax<T> {
+
+    /// Creates a new empty `SlidingWindowMax`
+    ///
+    /// # Arguments
+    ///
+    /// * `k` - Number of values in the window
+    ///
+    /// # Panics
+    ///
+    /// If `k` is zero
+    pub fn new(k: usize) -> SlidingWindowMax<T> {
+        assert!(k > 0, "the window must hold at least one value");
+        SlidingWindowMax { k, count: 0, deque: Deque::new() }
+    }
+
+    /// Number of values in the window, `k` once that many have been pushed
+    pub fn len(&self) -> usize {
+        self.count.min(self.k)
+    }
+
+    /// Checks if no values have been pushed
+    pub fn is_empty(&self) -> bool {
+        self.count == 0
+    }
+
+    /// Pushes a value into the window, and the oldest value out if it was full
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - Value to push
+    pub fn push(&mut self, value: T) {
+        while self.deque.back().is_some_and(|(_, v)| *v <= value) {
+            self.deque.pop_back();
+        }
+        self.deque.push_back((self.count, value));
+        self.count += 1;
+        if self.deque.front().is_some_and(|&(i, _)| i + self.k < self.count) {
+            self.deque.pop_front();
+        }
+    }
+
+    /// Gets the largest value in the window, or None if no values have been pushed
+    pub fn max(&self) -> Option<&T> {
+        self.deque.front().map(|(_, v)| v)
+    }
+}
+
+/// Iterator over the maximum of every window of `k` consecutive values, see `window_max`
+#[derive(Clone, Debug)]
+pub struct WindowMax<I: Iterator>
+where
+    I::Item: Ord,
+{
+    iter: I,
+    window: SlidingWindowMax<I::Item>,
+}
+
+impl<I: Iterator> Iterator for WindowMax<I>
+where
+    I::Item: Ord + Clone,
+{
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<I::Item> {
+        while self.window.len() < self.window.k - 1 {
+            self.window.push(self.iter.next()?);
+        }
+        self.window.push(self.iter.next()?);
+        self.window.max().cloned()
+    }
+}
+
+/// Returns an iterator over the maximum of every window of `k` consecutive values
+///
+/// Yields one maximum per full window, so `n - k + 1` of them for `n` values, or none if there are
+/// fewer than `k`. Takes amortized `O(1)` time per value.
+///
+/// # Arguments
+///
+/// * `iter` - Values to slide the window over
+/// * `k` - Number of values in each window
+///
+/// # Panics
+///
+/// If `k` is zero
+pub fn window_max<I>(iter: I, k: usize) -> WindowMax<I::IntoIter>
+where
+    I: IntoIterator,
+    I::Item: Ord + Clone,
+{
+    WindowMax { iter: iter.into_iter(), window: SlidingWindowMax::new(k) }
+}
+
+/// Returns an iterator over the minimum of every window of `k` consecutive values
+///
+/// The same as `window_max`, with the values wrapped in `Reverse`.
+///
+/// # Arguments
+///
+/// * `iter` - Values to slide the window over
+/// * `k` - Number of values in each window
+///
+/// # Panics
+///
+/// If `k` is zero
+pub fn window_min<I>(iter: I, k: usize) -> impl Iterator<Item = I::Item>
+where
+    I: IntoIterator,
+    I::Item: Ord + Clone,
+{
+    window_max(iter.into_iter().map(Reverse), k).map(|Reverse(x)| x)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::Rng;
+
+    #[test]
+    fn test_sliding_window_max() {
+        let mut window = SlidingWindowMax::new(3);
+        assert_eq!(None, window.max());
+        let mut maxes = Vec::new();
+        for x in [1, 3, -1, -3, 5, 3, 6, 7] {
+            window.push(x);
+            maxes.push(*window.max().unwrap());
+        }
+        assert_eq!(vec![1, 3, 3, 3, 5, 5, 6, 7], maxes);
+        assert_eq!(3, window.len());
+    }
+
+    #[test]
+    fn test_window_iterators() {
+        let values = [1, 3, -1, -3, 5, 3, 6, 7];
+        assert_eq!(vec![3, 3, 5, 5, 6, 7], window_max(values, 3).collect::<Vec<_>>());
+        assert_eq!(vec![-1, -3, -3, -3, 3, 3], window_min(values, 3).collect::<Vec<_>>());
+        assert_eq!(values.to_vec(), window_max(values, 1).collect::<Vec<_>>());
+        assert_eq!(0, window_max(values, 9).count());
+    }
+
+    #[test]
+    fn test_matches_brute_force() {
+        let mut rng = Rng::new(89);
+        let values: Vec<u64> = (0..1000).map(|_| rng.gen_range(50)).collect();
+        for k in [2, 5, 17] {
+            let expected: Vec<u64> = values.windows(k).map(|w| *w.iter().max().unwrap()).collect();
+            assert_eq!(expected, window_max(values.iter().copied(), k).collect::<Vec<_>>());
+            let expected: Vec<u64> = values.windows(k).map(|w| *w.iter().min().unwrap()).collect();
+            assert_eq!(expected, window_min(values.iter().copied(), k).collect::<Vec<_>>());
+        }
+    }
+}
@@ -0,0 +1,150 @@
+use std::iter::{FromIterator, Rev};
+use std::slice::Iter;
+
+/// A last in, first out Stack
+///
+/// Backed by a `Vec` with the top of the stack at the end, so push and pop take amortized `O(1)`
+/// time. The `Vec` is kept private so only stack operations are available, and so the backing can
+/// change without changing the interface.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Stack<T> {
+    items: Vec<T>,
+}
+
+impl<T> Stack<T> {
+
+    /// Creates a new empty `Stack`
+    pub fn new() -> Stack<T> {
+        Stack { items: Vec::new() }
+    }
+
+    /// Creates a new empty `Stack` with room for `capacity` items before growing
+    ///
+    /// # Arguments
+    ///
+    /// * `capacity` - Number of items to make room for
+    pub fn with_capacity(capacity: usize) -> Stack<T> {
+        Stack { items: Vec::with_capacity(capacity) }
+    }
+
+    /// Number of items in the stack
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    /// Checks if the stack is empty
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Pushes an item on top of the stack
+    ///
+    /// # Arguments
+    ///
+    /// * `item` - Item to push
+    pub fn push(&mut self, item: T) {
+        self.items.push(item);
+    }
+
+    /// Removes and returns the top item, or None if the stack is empty
+    pub fn pop(&mut self) -> Option<T> {
+        self.items.pop()
+    }
+
+    /// Gets the top item, or None if the stack is empty
+    pub fn peek(&self) -> Option<&T> {
+        self.items.last()
+    }
+
+    /// Gets the top item mutably, or None if the stack is empty
+    pub fn peek_mut(&mut self) -> Option<&mut T> {
+        self.items.last_mut()
+    }
+
+    /// Removes every item
+    pub fn clear(&mut self) {
+        self.items.clear();
+    }
+
+    /// Returns an iterator over the items from the top of the stack to the bottom
+    pub fn iter(&self) -> Rev<Iter<'_, T>> {
+        self.items.iter().rev()
+    }
+}
+
+impl<T> Default for Stack<T> {
+    fn default() -> Self {
+        Stack::new()
+    }
+}
+
+impl<T> Extend<T> for Stack<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        self.items.extend(iter);
+    }
+}
+
+impl<T> FromIterator<T> for Stack<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        Stack { items: iter.into_iter().collect() }
+    }
+}
+
+impl<'a, T> IntoIterator for &'a Stack<T> {
+    type Item = &'a T;
+    type IntoIter = Rev<Iter<'a, T>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_push_pop() {
+        let mut stack = Stack::new();
+        assert_eq!(None, stack.pop());
+        stack.push(1);
+        stack.push(2);
+        stack.push(3);
+        assert_eq!(3, stack.len());
+        assert_eq!(Some(&3), stack.peek());
+        assert_eq!(Some(3), stack.pop());
+        assert_eq!(Some(2), stack.pop());
+        *stack.peek_mut().unwrap() = 10;
+        assert_eq!(Some(10), stack.pop());
+        assert!(stack.is_empty());
+    }
+
+    #[test]
+    fn test_iter() {
+        let mut stack: Stack<_> = "abc".chars().collect();
+        assert_eq!(vec![&'c', &'b', &'a'], stack.iter().collect::<Vec<_>>());
+        stack.extend("de".chars());
+        assert_eq!("edcba", (&stack).into_iter().collect::<String>());
+        stack.clear();
+        assert_eq!(None, stack.peek());
+    }
+
+    #[test]
+    fn test_balanced_brackets() {
+        let balanced = |s: &str| {
+            let mut stack = Stack::new();
+            for c in s.chars() {
+                match c {
+                    '(' | '[' => stack.push(c),
+                    ')' if stack.pop() != Some('(') => return false,
+                    ']' if stack.pop() != Some('[') => return false,
+                    _ => {}
+                }
+            }
+            stack.is_empty()
+        };
+        assert!(balanced("([()[]])"));
+        assert!(!balanced("([)]"));
+        assert!(!balanced("(("));
+    }
+}
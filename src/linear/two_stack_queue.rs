@@ -0,0 +1,188 @@
+use crate::stack::Stack;
+
+/// A Queue built from two stacks, counting its elementary operations
+///
+/// Items are pushed onto an inbox stack. Dequeuing pops from an outbox stack, and only when the
+/// outbox is empty are all the inbox items popped and pushed onto it, reversing them into queue
+/// order. A transfer of `k` items costs `O(k)`, but each item is transferred at most once, so every
+/// item is pushed and popped at most twice and each operation takes amortized `O(1)` time.
+///
+/// `moves` counts every push and pop on the two stacks, so the amortized bound can be checked.
+#[derive(Clone, Debug, Default)]
+pub struct TwoStackQueue<T> {
+    inbox: Stack<T>,
+    outbox: Stack<T>,
+    moves: usize,
+}
+
+impl<T> TwoStackQueue<T> {
+
+    /// Creates a new empty `TwoStackQueue`
+    pub fn new() -> TwoStackQueue<T> {
+        TwoStackQueue { inbox: Stack::new(), outbox: Stack::new(), moves: 0 }
+    }
+
+    /// Number of items in the queue
+    pub fn len(&self) -> usize {
+        self.inbox.len() + self.outbox.len()
+    }
+
+    /// Checks if the queue is empty
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Number of pushes and pops on the two stacks so far
+    pub fn moves(&self) -> usize {
+        self.moves
+    }
+
+    /// Adds an item to the back of the queue, one push onto the inbox
+    ///
+    /// # Arguments
+    ///
+    /// * `item` - Item to add
+    pub fn enqueue(&mut self, item: T) {
+        self.inbox.push(item);
+        self.moves += 1;
+    }
+
+    /// Removes and returns the item at the front of the queue, or None if the queue is empty
+    ///
+    /// One pop from the outbox, after moving the whole inbox over if the outbox is empty.
+    pub fn dequeue(&mut self) -> Option<T> {
+        self.transfer();
+        let item = self.outbox.pop()?;
+        self.moves += 1;
+        Some(item)
+    }
+
+    /// Gets the item at the front of the queue, or None if the queue is empty
+    ///
+    /// Takes `&mut self` since it may move the inbox over to reach the front.
+    pub fn peek(&mut self) -> Option<&T> {
+        self.transfer();
+        self.outbox.peek()
+    }
+
+    /// Internal function to move every inbox item to the outbox when it's empty
+    fn transfer(&mut self) {
+        if self.outbox.is_empty() {
+            while let Some(item) = self.inbox.pop() {
+                self.outbox.push(item);
+                self.moves += 2;
+            }
+        }
+    }
+}
+
+/// The naive Queue on a `Vec`, shifting every item down on each dequeue
+///
+/// Dequeuing removes the first item and shifts the rest down one place, taking `O(n)` time, for
+/// comparison with `TwoStackQueue`. `moves` counts every item written, by enqueueing or shifting.
+#[derive(Clone, Debug, Default)]
+pub struct ShiftQueue<T> {
+    items: Vec<T>,
+    moves: usize,
+}
+
+impl<T> ShiftQueue<T> {
+
+    /// Creates a new empty `ShiftQueue`
+    pub fn new() -> ShiftQueue<T> {
+        ShiftQueue { items: Vec::new(), moves: 0 }
+    }
+
+    /// Number of items in the queue
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    /// Checks if the queue is empty
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Number of items written so far
+    pub fn moves(&self) -> usize {
+        self.moves
+    }
+
+    /// Adds an item to the back of the queue
+    ///
+    /// # Arguments
+    ///
+    /// * `item` - Item to add
+    pub fn enqueue(&mut self, item: T) {
+        self.items.push(item);
+        self.moves += 1;
+    }
+
+    /// Removes and returns the item at the front of the queue, or None if the queue is empty
+    pub fn dequeue(&mut self) -> Option<T> {
+        if self.items.is_empty() {
+            return None;
+        }
+        self.moves += self.items.len() - 1;
+        Some(self.items.remove(0))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::Rng;
+
+    #[test]
+    fn test_two_stack_queue() {
+        let mut queue = TwoStackQueue::new();
+        queue.enqueue(1);
+        queue.enqueue(2);
+        assert_eq!(Some(&1), queue.peek());
+        queue.enqueue(3);
+        assert_eq!(Some(1), queue.dequeue());
+        assert_eq!(Some(2), queue.dequeue());
+        assert_eq!(Some(3), queue.dequeue());
+        assert_eq!(None, queue.dequeue());
+        assert!(queue.is_empty());
+        // Three pushes, two transfers of two and one item, and three pops
+        assert_eq!(3 + 2 * 3 + 3, queue.moves());
+    }
+
+    #[test]
+    fn test_amortized_cost() {
+        let n = 2000;
+        let mut fast = TwoStackQueue::new();
+        let mut slow = ShiftQueue::new();
+        for i in 0..n {
+            fast.enqueue(i);
+            slow.enqueue(i);
+        }
+        for i in 0..n {
+            assert_eq!(Some(i), fast.dequeue());
+            assert_eq!(Some(i), slow.dequeue());
+        }
+        // Each item is pushed and popped at most twice, while shifting moves n^2 / 2 items
+        assert!(fast.moves() <= 4 * n);
+        assert_eq!(n + n * (n - 1) / 2, slow.moves());
+    }
+
+    #[test]
+    fn test_interleaved() {
+        let mut rng = Rng::new(79);
+        let mut queue = TwoStackQueue::new();
+        let mut expected = ShiftQueue::new();
+        let mut operations = 0;
+        for i in 0..5000 {
+            if rng.gen_range(2) == 0 {
+                assert_eq!(expected.dequeue(), queue.dequeue());
+            } else {
+                queue.enqueue(i);
+                expected.enqueue(i);
+            }
+            operations += 1;
+            assert_eq!(expected.len(), queue.len());
+        }
+        assert!(queue.moves() <= 4 * operations);
+    }
+}
@@ -0,0 +1,369 @@
+use std::iter::FromIterator;
+
+/// Default number of items in each node
+const DEFAULT_NODE_CAPACITY: usize = 64;
+
+/// Internal node storage, a small array of items linked to its neighbours by index
+#[derive(Clone, Debug)]
+struct Node<T> {
+    items: Vec<T>,
+    prev: Option<usize>,
+    next: Option<usize>,
+}
+
+/// A list of items in a doubly linked list of small arrays
+///
+/// Each node holds up to `node_capacity` consecutive items in an array, so walking the list
+/// reads whole arrays at a time instead of chasing a pointer per item, and it takes
+/// `O(n / node_capacity)` jumps to reach an index. Inserting or removing in the middle only shifts
+/// the items of one node. A full node is split into two half full nodes, and a node that falls
+/// below half full is merged with the next one when they fit together, so every node but the
+/// last stays at least half full on a list that only grows, and the list never holds an empty
+/// node.
+///
+/// Nodes are stored in a vector and linked by index, with removed slots reused.
+#[derive(Clone, Debug)]
+pub struct UnrolledList<T> {
+    nodes: Vec<Node<T>>,
+    free: Vec<usize>,
+    head: Option<usize>,
+    tail: Option<usize>,
+    len: usize,
+    node_capacity: usize,
+}
+
+impl<T> UnrolledList<T> {
+
+    /// Creates a new empty `UnrolledList` with 64 items per node
+    pub fn new() -> UnrolledList<T> {
+        UnrolledList::with_node_capacity(DEFAULT_NODE_CAPACITY)
+    }
+
+    /// Creates a new empty `UnrolledList`
+    ///
+    /// # Arguments
+    ///
+    /// * `node_capacity` - Most items in each node
+    ///
+    /// # Panics
+    ///
+    /// If `node_capacity` is less than 2, since a full node has to split into two
+    pub fn with_node_capacity(node_capacity: usize) -> UnrolledList<T> {
+        assert!(node_capacity >= 2, "nodes must hold at least two items");
+        UnrolledList { nodes: Vec::new(), free: Vec::new(), head: None, tail: None, len: 0, node_capacity }
+    }
+
+    /// Number of items in the list
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Checks if the list is empty
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Most items in each node
+    pub fn node_capacity(&self) -> usize {
+        self.node_capacity
+    }
+
+    /// Number of nodes in the list
+    pub fn num_nodes(&self) -> usize {
+        self.nodes.len() - self.free.len()
+    }
+
+    /// Gets the item at an index, or None if there are `i` items or fewer
+    ///
+    /// # Arguments
+    ///
+    /// * `i` - Index of the item
+    pub fn get(&self, i: usize) -> Option<&T> {
+        let (n, offset) = self.locate(i)?;
+        Some(&self.nodes[n].items[offset])
+    }
+
+    /// Gets the item at an index mutably, or None if there are `i` items or fewer
+    ///
+    /// # Arguments
+    ///
+    /// * `i` - Index of the item
+    pub fn get_mut(&mut self, i: usize) -> Option<&mut T> {
+        let (n, offset) = self.locate(i)?;
+        Some(&mut self.nodes[n].items[offset])
+    }
+
+    /// Adds an item to the front of the list
+    ///
+    /// # Arguments
+    ///
+    /// * `item` - Item to add
+    pub fn push_front(&mut self, item: T) {
+        self.insert(0, item);
+    }
+
+    /// Adds an item to the back of the list
+    ///
+    /// # Arguments
+    ///
+    /// * `item` - Item to add
+    pub fn push_back(&mut self, item: T) {
+        self.insert(self.len, item);
+    }
+
+    /// Removes and returns the item at the front of the list, or None if the list is empty
+    pub fn pop_front(&mut self) -> Option<T> {
+        self.remove(0)
+    }
+
+    /// Removes and returns the item at the back of the list, or None if the list is empty
+    pub fn pop_back(&mut self) -> Option<T> {
+        self.remove(self.len.checked_sub(1)?)
+    }
+
+    /// Inserts an item at an index, shifting the items after it back
+    ///
+    /// Splits the node the item lands in if it's full.
+    ///
+    /// # Arguments
+    ///
+    /// * `i` - Index to insert at
+    /// * `item` - Item to insert
+    ///
+    /// # Panics
+    ///
+    /// If `i` is greater than the length of the list
+    pub fn insert(&mut self, i: usize, item: T) {
+        assert!(i <= self.len, "index {} is out of bounds for a list of length {}", i, self.len);
+        let (mut n, mut offset) = match self.tail {
+            None => {
+                let n = self.allocate();
+                self.link_after(None, n);
+                (n, 0)
+            }
+            Some(tail) if i == self.len => (tail, self.nodes[tail].items.len()),
+            Some(_) => self.locate(i).unwrap(),
+        };
+
+        if self.nodes[n].items.len() == self.node_capacity {
+            let split = self.allocate();
+            self.link_after(Some(n), split);
+            if offset == self.node_capacity {
+                // Appending to a full node starts a new one rather than leaving two half full
+                n = split;
+                offset = 0;
+            } else {
+                let half = self.node_capacity / 2;
+                let moved = self.nodes[n].items.split_off(half);
+                self.nodes[split].items.extend(moved);
+                if offset > half {
+                    n = split;
+                    offset -= half;
+                }
+            }
+        }
+        self.nodes[n].items.insert(offset, item);
+        self.len += 1;
+    }
+
+    /// Removes and returns the item at an index, shifting the items after it forward, or None if
+    /// there are `i` items or fewer
+    ///
+    /// Merges the node with the next one if it falls below half full and they fit in one node.
+    ///
+    /// # Arguments
+    ///
+    /// * `i` - Index of the item
+    pub fn remove(&mut self, i: usize) -> Option<T> {
+        let (n, offset) = self.locate(i)?;
+        let item = self.nodes[n].items.remove(offset);
+        self.len -= 1;
+
+        let size = self.nodes[n].items.len();
+        if size == 0 {
+            self.release(n);
+        } else if size < self.node_capacity / 2 {
+            if let Some(next) = self.nodes[n].next {
+                if size + self.nodes[next].items.len() <= self.node_capacity {
+                    let moved = std::mem::take(&mut self.nodes[next].items);
+                    self.nodes[n].items.extend(moved);
+                    self.release(next);
+                }
+            }
+        }
+        Some(item)
+    }
+
+    /// Removes every item
+    pub fn clear(&mut self) {
+        self.nodes.clear();
+        self.free.clear();
+        self.head = None;
+        self.tail = None;
+        self.len = 0;
+    }
+
+    /// Returns an iterator over the items from front to back
+    pub fn iter(&self) -> impl Iterator<Item = &T> + '_ {
+        let mut node = self.head;
+        let mut items = [].iter();
+        std::iter::from_fn(move || loop {
+            if let Some(item) = items.next() {
+                return Some(item);
+            }
+            let n = node?;
+            items = self.nodes[n].items.iter();
+            node = self.nodes[n].next;
+        })
+    }
+
+    /// Internal function to find the node holding index `i` and the offset into it
+    fn locate(&self, mut i: usize) -> Option<(usize, usize)> {
+        if i >= self.len {
+            return None;
+        }
+        let mut node = self.head;
+        while let Some(n) = node {
+            let size = self.nodes[n].items.len();
+            if i < size {
+                return Some((n, i));
+            }
+            i -= size;
+            node = self.nodes[n].next;
+        }
+        None
+    }
+
+    /// Internal function to get an empty unlinked node, reusing a removed one if there is one
+    fn allocate(&mut self) -> usize {
+        match self.free.pop() {
+            Some(n) => n,
+            None => {
+                let items = Vec::with_capacity(self.node_capacity);
+                self.nodes.push(Node { items, prev: None, next: None });
+                self.nodes.len() - 1
+            }
+        }
+    }
+
+    /// Internal function to link node `n` after `prev`, or at the front if `prev` is None
+    fn link_after(&mut self, prev: Option<usize>, n: usize) {
+        let next = match prev {
+            Some(p) => self.nodes[p].next,
+            None => self.head,
+        };
+        self.nodes[n].prev = prev;
+        self.nodes[n].next = next;
+        match prev {
+            Some(p) => self.nodes[p].next = Some(n),
+            None => self.head = Some(n),
+        }
+        match next {
+            Some(x) => self.nodes[x].prev = Some(n),
+            None => self.tail = Some(n),
+        }
+    }
+
+    /// Internal function to unlink an empty node and keep it for reuse
+    fn release(&mut self, n: usize) {
+        let Node { prev, next, .. } = self.nodes[n];
+        match prev {
+            Some(p) => self.nodes[p].next = next,
+            None => self.head = next,
+        }
+        match next {
+            Some(x) => self.nodes[x].prev = prev,
+            None => self.tail = prev,
+        }
+        self.free.push(n);
+    }
+}
+
+impl<T> Default for UnrolledList<T> {
+    fn default() -> Self {
+        UnrolledList::new()
+    }
+}
+
+impl<T> Extend<T> for UnrolledList<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for item in iter {
+            self.push_back(item);
+        }
+    }
+}
+
+impl<T> FromIterator<T> for UnrolledList<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut list = UnrolledList::new();
+        list.extend(iter);
+        list
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::Rng;
+
+    #[test]
+    fn test_push_pop() {
+        let mut list = UnrolledList::with_node_capacity(4);
+        for i in 0..10 {
+            list.push_back(i);
+        }
+        list.push_front(-1);
+        assert_eq!(11, list.len());
+        assert_eq!(Some(&-1), list.get(0));
+        assert_eq!(Some(&9), list.get(10));
+        assert_eq!(None, list.get(11));
+        assert_eq!(Some(-1), list.pop_front());
+        assert_eq!(Some(9), list.pop_back());
+        *list.get_mut(3).unwrap() = 30;
+        assert_eq!(vec![0, 1, 2, 30, 4, 5, 6, 7, 8], list.iter().copied().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_split_merge() {
+        let mut list = UnrolledList::with_node_capacity(4);
+        // Appending fills nodes before starting new ones
+        list.extend(0..8);
+        assert_eq!(2, list.num_nodes());
+        // Inserting into a full node splits it
+        list.insert(1, 100);
+        assert_eq!(3, list.num_nodes());
+        assert_eq!(vec![0, 100, 1, 2, 3, 4, 5, 6, 7], list.iter().copied().collect::<Vec<_>>());
+        // Removing below half full merges with the next node
+        list.remove(0);
+        list.remove(0);
+        assert_eq!(2, list.num_nodes());
+        while list.pop_front().is_some() {}
+        assert_eq!(0, list.num_nodes());
+        list.push_back(1);
+        assert_eq!(1, list.num_nodes());
+    }
+
+    #[test]
+    fn test_matches_vec() {
+        let mut rng = Rng::new(103);
+        for capacity in [2, 3, 8, 64] {
+            let mut list = UnrolledList::with_node_capacity(capacity);
+            let mut expected = Vec::new();
+            for i in 0..3000 {
+                let len = expected.len() as u64;
+                if rng.gen_range(5) < 2 && len > 0 {
+                    let j = rng.gen_range(len) as usize;
+                    assert_eq!(Some(expected.remove(j)), list.remove(j));
+                } else {
+                    let j = rng.gen_range(len + 1) as usize;
+                    expected.insert(j, i);
+                    list.insert(j, i);
+                }
+                assert_eq!(expected.len(), list.len());
+            }
+            assert_eq!(expected, list.iter().copied().collect::<Vec<_>>());
+            // No node is ever left empty
+            assert!(list.num_nodes() <= expected.len());
+        }
+    }
+}
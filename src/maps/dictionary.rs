@@ -0,0 +1,153 @@
+use std::collections::{BTreeMap, HashMap};
+use std::hash::{BuildHasher, Hash};
+
+/// A map from keys to values, with insertion, lookup, and removal by key
+///
+/// Implemented by `SkipListMap`, `IndexableSkipList`, and the stdlib's `HashMap` and `BTreeMap`,
+/// so code, and tests, can be run against any of them. The stdlib maps make good reference models
+/// to check the crate's maps against.
+pub trait Dictionary<K, V> {
+
+    /// Inserts a value under a key, and returns the value it replaced
+    fn insert(&mut self, key: K, value: V) -> Option<V>;
+
+    /// Gets the value under a key
+    fn get(&self, key: &K) -> Option<&V>;
+
+    /// Removes a key, and returns its value
+    fn remove(&mut self, key: &K) -> Option<V>;
+
+    /// Checks if a key is in the map
+    fn contains_key(&self, key: &K) -> bool {
+        self.get(key).is_some()
+    }
+
+    /// Number of keys in the map
+    fn len(&self) -> usize;
+
+    /// Checks if the map is empty
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns an iterator over the keys and values, in ascending order of key if the map is
+    /// ordered, and in no particular order otherwise
+    fn iter<'a>(&'a self) -> impl Iterator<Item = (&'a K, &'a V)>
+    where
+        K: 'a,
+        V: 'a;
+}
+
+impl<K: Hash + Eq, V, S: BuildHasher> Dictionary<K, V> for HashMap<K, V, S> {
+    fn insert(&mut self, key: K, value: V) -> Option<V> {
+        HashMap::insert(self, key, value)
+    }
+
+    fn get(&self, key: &K) -> Option<&V> {
+        HashMap::get(self, key)
+    }
+
+    fn remove(&mut self, key: &K) -> Option<V> {
+        HashMap::remove(self, key)
+    }
+
+    fn contains_key(&self, key: &K) -> bool {
+        HashMap::contains_key(self, key)
+    }
+
+    fn len(&self) -> usize {
+        HashMap::len(self)
+    }
+
+    fn iter<'a>(&'a self) -> impl Iterator<Item = (&'a K, &'a V)>
+    where
+        K: 'a,
+        V: 'a,
+    {
+        HashMap::iter(self)
+    }
+}
+
+impl<K: Ord, V> Dictionary<K, V> for BTreeMap<K, V> {
+    fn insert(&mut self, key: K, value: V) -> Option<V> {
+        BTreeMap::insert(self, key, value)
+    }
+
+    fn get(&self, key: &K) -> Option<&V> {
+        BTreeMap::get(self, key)
+    }
+
+    fn remove(&mut self, key: &K) -> Option<V> {
+        BTreeMap::remove(self, key)
+    }
+
+    fn contains_key(&self, key: &K) -> bool {
+        BTreeMap::contains_key(self, key)
+    }
+
+    fn len(&self) -> usize {
+        BTreeMap::len(self)
+    }
+
+    fn iter<'a>(&'a self) -> impl Iterator<Item = (&'a K, &'a V)>
+    where
+        K: 'a,
+        V: 'a,
+    {
+        BTreeMap::iter(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::indexable_skip_list::IndexableSkipList;
+    use crate::rng::Rng;
+    use crate::skip_list::SkipListMap;
+
+    /// Internal function to run random inserts, lookups, and removals against a map and a
+    /// `BTreeMap` as the model, checking that every result agrees
+    fn check_against_model<D: Dictionary<u64, u64>>(mut map: D, seed: u64) {
+        let mut model = BTreeMap::new();
+        let mut rng = Rng::new(seed);
+        for step in 0..5000 {
+            let key = rng.gen_range(200);
+            match rng.gen_range(4) {
+                0 | 1 => assert_eq!(model.insert(key, step), map.insert(key, step), "insert {} at step {}", key, step),
+                2 => assert_eq!(Dictionary::remove(&mut model, &key), map.remove(&key), "remove {} at step {}", key, step),
+                _ => {
+                    assert_eq!(Dictionary::get(&model, &key), map.get(&key), "get {} at step {}", key, step);
+                    assert_eq!(model.contains_key(&key), map.contains_key(&key));
+                }
+            }
+            assert_eq!(model.len(), map.len());
+        }
+        let mut entries: Vec<_> = map.iter().map(|(&k, &v)| (k, v)).collect();
+        entries.sort_unstable();
+        assert_eq!(model.into_iter().collect::<Vec<_>>(), entries);
+    }
+
+    #[test]
+    fn test_against_model() {
+        check_against_model(SkipListMap::with_seed(461), 463);
+        check_against_model(IndexableSkipList::with_seed(467), 479);
+        check_against_model(HashMap::new(), 487);
+    }
+
+    /// Internal function to insert a few keys out of order into a map and list its entries
+    fn entries<D: Dictionary<u64, char>>(mut map: D) -> Vec<(u64, char)> {
+        assert!(map.is_empty());
+        for (k, v) in [(3, 'c'), (1, 'a'), (2, 'b'), (1, 'z')] {
+            map.insert(k, v);
+        }
+        map.iter().map(|(&k, &v)| (k, v)).collect()
+    }
+
+    #[test]
+    fn test_ordered_iter() {
+        let expected = vec![(1, 'z'), (2, 'b'), (3, 'c')];
+        assert_eq!(expected, entries(SkipListMap::with_seed(491)));
+        assert_eq!(expected, entries(IndexableSkipList::with_seed(499)));
+        assert_eq!(expected, entries(BTreeMap::new()));
+    }
+}
@@ -0,0 +1,446 @@
+use std::cmp::Ordering;
+use std::iter::FromIterator;
+
+use crate::comparator::{Comparator, Natural};
+use crate::dictionary::Dictionary;
+use crate::rng::Rng;
+
+/// Most levels a node can have, enough for `2^32` keys
+const MAX_LEVEL: usize = 32;
+
+/// Internal pointer to the next node on a level, and how many places along the bottom list it is
+#[derive(Clone, Copy, Debug)]
+struct Link {
+    next: Option<usize>,
+    width: usize,
+}
+
+/// Internal node storage, a key and value with a link per level
+#[derive(Clone, Debug)]
+struct Node<K, V> {
+    key: K,
+    value: V,
+    links: Vec<Link>,
+}
+
+/// A sorted map on a skip list that also finds keys by rank
+///
+/// The same as `SkipListMap`, but every link also stores its width, the number of places along
+/// the bottom list it skips. Adding up the widths of the links followed by a search gives the
+/// rank of the key found, and following links while the total stays below `i` finds the key with
+/// rank `i`, both in expected `O(log(n))` time. Links that run off the end of the list have the
+/// width to one place past the last key, so inserting or removing a key only changes the widths
+/// of the links on its search path.
+///
+/// Ranks start at 0 for the smallest key, in their natural order or by a `Comparator`.
+#[derive(Clone, Debug)]
+pub struct IndexableSkipList<K, V, C = Natural> {
+    head: Vec<Link>,
+    nodes: Vec<Option<Node<K, V>>>,
+    free: Vec<usize>,
+    len: usize,
+    rng: Rng,
+    comparator: C,
+}
+
+impl<K: Ord, V> IndexableSkipList<K, V> {
+
+    /// Creates a new empty `IndexableSkipList`, seeded from the operating system's randomness
+    pub fn new() -> IndexableSkipList<K, V> {
+        IndexableSkipList::with_comparator(Natural)
+    }
+
+    /// Creates a new empty `IndexableSkipList` with a seed, equal seeds give equal list shapes
+    ///
+    /// # Arguments
+    ///
+    /// * `seed` - Seed for the levels of the nodes
+    pub fn with_seed(seed: u64) -> IndexableSkipList<K, V> {
+        IndexableSkipList::with_seed_and_comparator(seed, Natural)
+    }
+}
+
+impl<K, V, C: Comparator<K>> IndexableSkipList<K, V, C> {
+
+    /// Creates a new empty `IndexableSkipList` sorted by a comparator, seeded from the operating
+    /// system's randomness
+    ///
+    /// # Arguments
+    ///
+    /// * `comparator` - Order of the keys
+    pub fn with_comparator(comparator: C) -> IndexableSkipList<K, V, C> {
+        IndexableSkipList::with_rng(Rng::from_entropy(), comparator)
+    }
+
+    /// Creates a new empty `IndexableSkipList` sorted by a comparator with a seed
+    ///
+    /// # Arguments
+    ///
+    /// * `seed` - Seed for the levels of the nodes
+    /// * `comparator` - Order of the keys
+    pub fn with_seed_and_comparator(seed: u64, comparator: C) -> IndexableSkipList<K, V, C> {
+        IndexableSkipList::with_rng(Rng::new(seed), comparator)
+    }
+
+    /// Internal function to create an empty `IndexableSkipList` from an `Rng`
+    fn with_rng(rng: Rng, comparator: C) -> IndexableSkipList<K, V, C> {
+        IndexableSkipList { head: Vec::new(), nodes: Vec::new(), free: Vec::new(), len: 0, rng, comparator }
+    }
+
+    /// Number of keys in the map
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Checks if the map is empty
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Inserts a value under a key, and returns the value it replaced
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - Key to insert under
+    /// * `value` - Value to insert
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        let (mut update, mut ranks) = self.predecessors(&key);
+        if let Some(n) = update.first().and_then(|&at| self.link(at, 0).next) {
+            if self.comparator.compare(&self.node(n).key, &key) == Ordering::Equal {
+                return Some(std::mem::replace(&mut self.node_mut(n).value, value));
+            }
+        }
+
+        let level = self.random_level();
+        while self.head.len() < level {
+            self.head.push(Link { next: None, width: self.len + 1 });
+            update.push(None);
+            ranks.push(0);
+        }
+
+        // The new key goes one place after its predecessor on the bottom list
+        let position = ranks.first().map_or(0, |r| r + 1);
+        let mut links = Vec::with_capacity(level);
+        for l in 0..level {
+            let old = self.link(update[l], l);
+            links.push(Link { next: old.next, width: ranks[l] + old.width + 1 - position });
+        }
+        let node = Some(Node { key, value, links });
+        let n = match self.free.pop() {
+            Some(n) => {
+                self.nodes[n] = node;
+                n
+            }
+            None => {
+                self.nodes.push(node);
+                self.nodes.len() - 1
+            }
+        };
+        for l in 0..self.head.len() {
+            let link = self.link_mut(update[l], l);
+            if l < level {
+                *link = Link { next: Some(n), width: position - ranks[l] };
+            } else {
+                link.width += 1;
+            }
+        }
+        self.len += 1;
+        None
+    }
+
+    /// Removes a key, and returns its value
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - Key to remove
+    pub fn remove(&mut self, key: &K) -> Option<V> {
+        let (update, _) = self.predecessors(key);
+        let n = self.link(*update.first()?, 0).next?;
+        if self.comparator.compare(&self.node(n).key, key) != Ordering::Equal {
+            return None;
+        }
+        let node = self.nodes[n].take().unwrap();
+        for (l, &at) in update.iter().enumerate() {
+            let link = self.link_mut(at, l);
+            match node.links.get(l) {
+                Some(removed) => {
+                    link.next = removed.next;
+                    link.width += removed.width - 1;
+                }
+                None => link.width -= 1,
+            }
+        }
+        while self.head.last().is_some_and(|link| link.next.is_none()) {
+            self.head.pop();
+        }
+        self.free.push(n);
+        self.len -= 1;
+        Some(node.value)
+    }
+
+    /// Gets the value under a key
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - Key to look up
+    pub fn get(&self, key: &K) -> Option<&V> {
+        let (n, _) = self.find(key)?;
+        Some(&self.node(n).value)
+    }
+
+    /// Checks if a key is in the map
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - Key to look for
+    pub fn contains_key(&self, key: &K) -> bool {
+        self.find(key).is_some()
+    }
+
+    /// Gets the rank of a key, the number of smaller keys in the map, or None if it isn't in the map
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - Key to rank
+    pub fn rank(&self, key: &K) -> Option<usize> {
+        let (_, rank) = self.find(key)?;
+        Some(rank)
+    }
+
+    /// Gets the key with rank `i` and its value, or None if there are `i` keys or fewer
+    ///
+    /// # Arguments
+    ///
+    /// * `i` - Rank of the key, 0 for the smallest
+    pub fn get_by_rank(&self, i: usize) -> Option<(&K, &V)> {
+        if i >= self.len {
+            return None;
+        }
+        // Positions count from 1, with the head at 0
+        let target = i + 1;
+        let mut at = None;
+        let mut position = 0;
+        for level in (0..self.head.len()).rev() {
+            loop {
+                let link = self.link(at, level);
+                match link.next {
+                    Some(n) if position + link.width <= target => {
+                        position += link.width;
+                        at = Some(n);
+                    }
+                    _ => break,
+                }
+            }
+        }
+        let node = self.node(at?);
+        Some((&node.key, &node.value))
+    }
+
+    /// Returns an iterator over the keys and values, in ascending order of key
+    pub fn iter(&self) -> impl Iterator<Item = (&K, &V)> + '_ {
+        let mut at = self.head.first().and_then(|link| link.next);
+        std::iter::from_fn(move || {
+            let node = self.node(at?);
+            at = node.links[0].next;
+            Some((&node.key, &node.value))
+        })
+    }
+
+    /// Internal function to find the node holding a key and its rank
+    fn find(&self, key: &K) -> Option<(usize, usize)> {
+        let (update, ranks) = self.predecessors(key);
+        let n = self.link(*update.first()?, 0).next?;
+        if self.comparator.compare(&self.node(n).key, key) == Ordering::Equal {
+            Some((n, ranks[0]))
+        } else {
+            None
+        }
+    }
+
+    /// Internal function to find the last node on each level with a key less than `key`, None
+    /// meaning the head, along with the position of each
+    fn predecessors(&self, key: &K) -> (Vec<Option<usize>>, Vec<usize>) {
+        let mut update = vec![None; self.head.len()];
+        let mut ranks = vec![0; self.head.len()];
+        let mut at = None;
+        let mut position = 0;
+        for level in (0..self.head.len()).rev() {
+            loop {
+                let link = self.link(at, level);
+                match link.next {
+                    Some(n) if self.comparator.less(&self.node(n).key, key) => {
+                        position += link.width;
+                        at = Some(n);
+                    }
+                    _ => break,
+                }
+            }
+            update[level] = at;
+            ranks[level] = position;
+        }
+        (update, ranks)
+    }
+
+    /// Internal function to pick a node's level, `l` with probability `2^-l`
+    fn random_level(&mut self) -> usize {
+        let mut level = 1;
+        while level < MAX_LEVEL && self.rng.gen_bool(0.5) {
+            level += 1;
+        }
+        level
+    }
+
+    /// Internal function to get the link on a level, from a node or the head
+    fn link(&self, at: Option<usize>, level: usize) -> Link {
+        match at {
+            Some(n) => self.node(n).links[level],
+            None => self.head[level],
+        }
+    }
+
+    /// Internal function to get the link on a level mutably, from a node or the head
+    fn link_mut(&mut self, at: Option<usize>, level: usize) -> &mut Link {
+        match at {
+            Some(n) => &mut self.node_mut(n).links[level],
+            None => &mut self.head[level],
+        }
+    }
+
+    /// Internal function to get a node that is in the list
+    fn node(&self, n: usize) -> &Node<K, V> {
+        self.nodes[n].as_ref().unwrap()
+    }
+
+    /// Internal function to get a node that is in the list mutably
+    fn node_mut(&mut self, n: usize) -> &mut Node<K, V> {
+        self.nodes[n].as_mut().unwrap()
+    }
+}
+
+impl<K: Ord, V> Default for IndexableSkipList<K, V> {
+    fn default() -> Self {
+        IndexableSkipList::new()
+    }
+}
+
+impl<K, V, C: Comparator<K>> Extend<(K, V)> for IndexableSkipList<K, V, C> {
+    fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+        for (key, value) in iter {
+            self.insert(key, value);
+        }
+    }
+}
+
+impl<K: Ord, V> FromIterator<(K, V)> for IndexableSkipList<K, V> {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut map = IndexableSkipList::new();
+        map.extend(iter);
+        map
+    }
+}
+
+impl<K, V, C: Comparator<K>> Dictionary<K, V> for IndexableSkipList<K, V, C> {
+    fn insert(&mut self, key: K, value: V) -> Option<V> {
+        IndexableSkipList::insert(self, key, value)
+    }
+
+    fn get(&self, key: &K) -> Option<&V> {
+        IndexableSkipList::get(self, key)
+    }
+
+    fn remove(&mut self, key: &K) -> Option<V> {
+        IndexableSkipList::remove(self, key)
+    }
+
+    fn contains_key(&self, key: &K) -> bool {
+        IndexableSkipList::contains_key(self, key)
+    }
+
+    fn len(&self) -> usize {
+        IndexableSkipList::len(self)
+    }
+
+    fn iter<'a>(&'a self) -> impl Iterator<Item = (&'a K, &'a V)>
+    where
+        K: 'a,
+        V: 'a,
+    {
+        IndexableSkipList::iter(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::comparator::by_key;
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn test_rank() {
+        let map: IndexableSkipList<_, _> = [50, 10, 40, 20, 30].iter().map(|&k| (k, k / 10)).collect();
+        assert_eq!(Some(0), map.rank(&10));
+        assert_eq!(Some(2), map.rank(&30));
+        assert_eq!(Some(4), map.rank(&50));
+        assert_eq!(None, map.rank(&35));
+        assert_eq!(Some((&10, &1)), map.get_by_rank(0));
+        assert_eq!(Some((&40, &4)), map.get_by_rank(3));
+        assert_eq!(None, map.get_by_rank(5));
+    }
+
+    #[test]
+    fn test_remove() {
+        let mut map = IndexableSkipList::with_seed(7);
+        for i in 0..100 {
+            map.insert(i, i);
+        }
+        for i in (0..100).step_by(2) {
+            assert_eq!(Some(i), map.remove(&i));
+        }
+        assert_eq!(None, map.remove(&0));
+        assert_eq!(50, map.len());
+        for i in 0..50 {
+            assert_eq!(Some((&(2 * i + 1), &(2 * i + 1))), map.get_by_rank(i));
+            assert_eq!(Some(i), map.rank(&(2 * i + 1)));
+        }
+        for i in 0..100 {
+            map.remove(&i);
+        }
+        assert!(map.is_empty());
+        assert_eq!(None, map.get_by_rank(0));
+    }
+
+    #[test]
+    fn test_comparator() {
+        // Ranked by length, longest first, so rank 0 is the longest word
+        let mut list = IndexableSkipList::with_seed_and_comparator(7, by_key(|s: &&str| s.len()).reversed());
+        list.extend(["fig", "banana", "kiwi", "cherry", "plum"].iter().map(|&s| (s, ())));
+        assert_eq!(3, list.len());
+        // "cherry" is the same key as "banana", which keeps its place
+        assert_eq!(Some((&"banana", &())), list.get_by_rank(0));
+        assert_eq!(Some(1), list.rank(&"pear"));
+        assert_eq!(Some(2), list.rank(&"fig"));
+    }
+
+    #[test]
+    fn test_matches_btree_map() {
+        let mut rng = Rng::new(101);
+        let mut map = IndexableSkipList::with_seed(11);
+        let mut expected = BTreeMap::new();
+        for i in 0..3000 {
+            let key = rng.gen_range(300);
+            if rng.gen_range(3) == 0 {
+                assert_eq!(expected.remove(&key), map.remove(&key));
+            } else {
+                assert_eq!(expected.insert(key, i), map.insert(key, i));
+            }
+            if i % 100 == 0 {
+                for (rank, (k, v)) in expected.iter().enumerate() {
+                    assert_eq!(Some((k, v)), map.get_by_rank(rank));
+                    assert_eq!(Some(rank), map.rank(k));
+                }
+            }
+        }
+        assert!(expected.iter().eq(map.iter()));
+        assert_eq!(expected.get(&17), map.get(&17));
+    }
+}
@@ -0,0 +1,197 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::iter::FromIterator;
+
+/// A map that can hold several values per key
+///
+/// Values under a key keep their insertion order. Stores a `Vec` of values per key, so a key with
+/// no values is removed from the map.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MultiMap<K: Hash + Eq, V> {
+    map: HashMap<K, Vec<V>>,
+    len: usize,
+}
+
+impl<K: Hash + Eq, V> MultiMap<K, V> {
+
+    /// Creates a new empty `MultiMap`
+    pub fn new() -> MultiMap<K, V> {
+        MultiMap { map: HashMap::new(), len: 0 }
+    }
+
+    /// Total number of values, across every key
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Number of distinct keys
+    pub fn keys_len(&self) -> usize {
+        self.map.len()
+    }
+
+    /// Checks if the map is empty
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Inserts a value under a key, after any values already there
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - Key to insert under
+    /// * `value` - Value to insert
+    pub fn insert(&mut self, key: K, value: V) {
+        self.map.entry(key).or_default().push(value);
+        self.len += 1;
+    }
+
+    /// Returns an iterator over the values under a key, in insertion order
+    ///
+    /// The iterator is empty if the key is not in the map.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - Key to look up
+    pub fn get(&self, key: &K) -> std::slice::Iter<'_, V> {
+        self.map.get(key).map(|v| v.as_slice()).unwrap_or(&[]).iter()
+    }
+
+    /// Number of values under a key
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - Key to look up
+    pub fn count(&self, key: &K) -> usize {
+        self.map.get(key).map_or(0, |v| v.len())
+    }
+
+    /// Checks if a key has at least one value
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - Key to look for
+    pub fn contains_key(&self, key: &K) -> bool {
+        self.map.contains_key(key)
+    }
+
+    /// Removes every value under a key, and returns them in insertion order
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - Key to remove
+    pub fn remove_all(&mut self, key: &K) -> Vec<V> {
+        let values = self.map.remove(key).unwrap_or_default();
+        self.len -= values.len();
+        values
+    }
+
+    /// Returns an iterator over every key and value pair, grouped by key
+    pub fn iter(&self) -> impl Iterator<Item = (&K, &V)> {
+        self.map.iter().flat_map(|(k, vs)| vs.iter().map(move |v| (k, v)))
+    }
+
+    /// Returns an iterator over each key and all of its values
+    pub fn groups(&self) -> impl Iterator<Item = (&K, &[V])> {
+        self.map.iter().map(|(k, vs)| (k, vs.as_slice()))
+    }
+
+    /// Returns an iterator over the distinct keys
+    pub fn keys(&self) -> impl Iterator<Item = &K> {
+        self.map.keys()
+    }
+}
+
+impl<K: Hash + Eq, V: PartialEq> MultiMap<K, V> {
+
+    /// Removes the first occurrence of a value under a key
+    ///
+    /// Returns `false` if the value is not under the key.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - Key the value is under
+    /// * `value` - Value to remove
+    pub fn remove(&mut self, key: &K, value: &V) -> bool {
+        let values = match self.map.get_mut(key) {
+            Some(values) => values,
+            None => return false,
+        };
+        let i = match values.iter().position(|v| v == value) {
+            Some(i) => i,
+            None => return false,
+        };
+        values.remove(i);
+        if values.is_empty() {
+            self.map.remove(key);
+        }
+        self.len -= 1;
+        true
+    }
+}
+
+impl<K: Hash + Eq, V> Default for MultiMap<K, V> {
+    fn default() -> Self {
+        MultiMap::new()
+    }
+}
+
+impl<K: Hash + Eq, V> Extend<(K, V)> for MultiMap<K, V> {
+    fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+        for (k, v) in iter {
+            self.insert(k, v);
+        }
+    }
+}
+
+impl<K: Hash + Eq, V> FromIterator<(K, V)> for MultiMap<K, V> {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut map = MultiMap::new();
+        map.extend(iter);
+        map
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_get() {
+        let mut map = MultiMap::new();
+        map.insert("a", 1);
+        map.insert("b", 2);
+        map.insert("a", 3);
+        assert_eq!(vec![&1, &3], map.get(&"a").collect::<Vec<_>>());
+        assert_eq!(vec![&2], map.get(&"b").collect::<Vec<_>>());
+        assert_eq!(0, map.get(&"c").count());
+        assert_eq!(3, map.len());
+        assert_eq!(2, map.keys_len());
+        assert_eq!(2, map.count(&"a"));
+    }
+
+    #[test]
+    fn test_remove() {
+        let mut map: MultiMap<_, _> = vec![(1, 'x'), (1, 'y'), (1, 'x'), (2, 'z')].into_iter().collect();
+        assert!(map.remove(&1, &'x'));
+        assert_eq!(vec![&'y', &'x'], map.get(&1).collect::<Vec<_>>());
+        assert!(!map.remove(&1, &'z'));
+        assert!(!map.remove(&3, &'z'));
+
+        assert!(map.remove(&2, &'z'));
+        assert!(!map.contains_key(&2));
+        assert_eq!(vec!['y', 'x'], map.remove_all(&1));
+        assert!(map.is_empty());
+    }
+
+    #[test]
+    fn test_groups() {
+        let map: MultiMap<_, _> = vec![("even", 2), ("odd", 1), ("even", 4)].into_iter().collect();
+        let mut groups: Vec<_> = map.groups().collect();
+        groups.sort();
+        assert_eq!(vec![(&"even", &[2, 4][..]), (&"odd", &[1][..])], groups);
+
+        let mut pairs: Vec<_> = map.iter().collect();
+        pairs.sort();
+        assert_eq!(vec![(&"even", &2), (&"even", &4), (&"odd", &1)], pairs);
+    }
+}
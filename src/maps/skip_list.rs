@@ -0,0 +1,474 @@
+use std::cmp::Ordering;
+use std::iter::FromIterator;
+use std::ops::{Bound, RangeBounds};
+
+use crate::comparator::{Comparator, Natural};
+use crate::dictionary::Dictionary;
+use crate::rng::Rng;
+
+/// Most levels a node can have, enough for `2^32` keys
+const MAX_LEVEL: usize = 32;
+
+/// Internal node storage, a key and value with a next pointer per level
+#[derive(Clone, Debug)]
+struct Node<K, V> {
+    key: K,
+    value: V,
+    next: Vec<Option<usize>>,
+}
+
+/// A sorted map on a skip list
+///
+/// Every key is in a sorted linked list, and each list level up holds about half the keys of the
+/// one below, chosen by flipping coins when a key is inserted. A search starts on the top level
+/// and drops down a level whenever the next key is too large, so it skips over most keys and takes
+/// expected `O(log(n))` time, the same as a balanced tree but with no rebalancing. The coin flips
+/// come from a seedable `Rng`, so the shape of the list, and the running time, can be reproduced.
+///
+/// Keys are sorted in their natural order, or by a `Comparator`, where keys comparing equal are
+/// the same key. Nodes are stored in a vector and linked by index, with removed slots reused.
+#[derive(Clone, Debug)]
+pub struct SkipListMap<K, V, C = Natural> {
+    head: Vec<Option<usize>>,
+    nodes: Vec<Option<Node<K, V>>>,
+    free: Vec<usize>,
+    len: usize,
+    rng: Rng,
+    comparator: C,
+}
+
+impl<K: Ord, V> SkipListMap<K, V> {
+
+    /// Creates a new empty `SkipListMap`, seeded from the operating system's randomness
+    pub fn new() -> SkipListMap<K, V> {
+        SkipListMap::with_comparator(Natural)
+    }
+
+    /// Creates a new empty `SkipListMap` with a seed, equal seeds give equal list shapes
+    ///
+    /// # Arguments
+    ///
+    /// * `seed` - Seed for the levels of the nodes
+    pub fn with_seed(seed: u64) -> SkipListMap<K, V> {
+        SkipListMap::with_seed_and_comparator(seed, Natural)
+    }
+}
+
+impl<K, V, C: Comparator<K>> SkipListMap<K, V, C> {
+
+    /// Creates a new empty `SkipListMap` sorted by a comparator, seeded from the operating
+    /// system's randomness
+    ///
+    /// # Arguments
+    ///
+    /// * `comparator` - Order of the keys
+    pub fn with_comparator(comparator: C) -> SkipListMap<K, V, C> {
+        SkipListMap::with_rng(Rng::from_entropy(), comparator)
+    }
+
+    /// Creates a new empty `SkipListMap` sorted by a comparator with a seed
+    ///
+    /// # Arguments
+    ///
+    /// * `seed` - Seed for the levels of the nodes
+    /// * `comparator` - Order of the keys
+    pub fn with_seed_and_comparator(seed: u64, comparator: C) -> SkipListMap<K, V, C> {
+        SkipListMap::with_rng(Rng::new(seed), comparator)
+    }
+
+    /// Internal function to create an empty `SkipListMap` from an `Rng`
+    fn with_rng(rng: Rng, comparator: C) -> SkipListMap<K, V, C> {
+        SkipListMap { head: Vec::new(), nodes: Vec::new(), free: Vec::new(), len: 0, rng, comparator }
+    }
+
+    /// Number of keys in the map
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Checks if the map is empty
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Number of levels in the list, expected `O(log(n))`
+    pub fn levels(&self) -> usize {
+        self.head.len()
+    }
+
+    /// Inserts a value under a key, and returns the value it replaced
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - Key to insert under
+    /// * `value` - Value to insert
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        let mut update = self.predecessors(&key);
+        if let Some(n) = self.next(update.first().copied().flatten(), 0) {
+            if self.comparator.compare(&self.node(n).key, &key) == Ordering::Equal {
+                return Some(std::mem::replace(&mut self.node_mut(n).value, value));
+            }
+        }
+
+        let level = self.random_level();
+        while self.head.len() < level {
+            self.head.push(None);
+            update.push(None);
+        }
+        let next = (0..level).map(|l| self.next(update[l], l)).collect();
+        let node = Some(Node { key, value, next });
+        let n = match self.free.pop() {
+            Some(n) => {
+                self.nodes[n] = node;
+                n
+            }
+            None => {
+                self.nodes.push(node);
+                self.nodes.len() - 1
+            }
+        };
+        for (l, &at) in update.iter().enumerate().take(level) {
+            self.set_next(at, l, Some(n));
+        }
+        self.len += 1;
+        None
+    }
+
+    /// Removes a key, and returns its value
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - Key to remove
+    pub fn remove(&mut self, key: &K) -> Option<V> {
+        let update = self.predecessors(key);
+        let n = self.next(update.first().copied().flatten(), 0)?;
+        if self.comparator.compare(&self.node(n).key, key) != Ordering::Equal {
+            return None;
+        }
+        let node = self.nodes[n].take().unwrap();
+        for (l, &next) in node.next.iter().enumerate() {
+            self.set_next(update[l], l, next);
+        }
+        while self.head.last() == Some(&None) {
+            self.head.pop();
+        }
+        self.free.push(n);
+        self.len -= 1;
+        Some(node.value)
+    }
+
+    /// Gets the value under a key
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - Key to look up
+    pub fn get(&self, key: &K) -> Option<&V> {
+        let n = self.seek(|k| self.comparator.less(k, key))?;
+        let node = self.node(n);
+        if self.comparator.compare(&node.key, key) == Ordering::Equal {
+            Some(&node.value)
+        } else {
+            None
+        }
+    }
+
+    /// Gets the value under a key mutably
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - Key to look up
+    pub fn get_mut(&mut self, key: &K) -> Option<&mut V> {
+        let n = self.seek(|k| self.comparator.less(k, key))?;
+        if self.comparator.compare(&self.node(n).key, key) == Ordering::Equal {
+            Some(&mut self.node_mut(n).value)
+        } else {
+            None
+        }
+    }
+
+    /// Checks if a key is in the map
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - Key to look for
+    pub fn contains_key(&self, key: &K) -> bool {
+        self.get(key).is_some()
+    }
+
+    /// Gets the smallest key and its value, or None if the map is empty
+    pub fn first(&self) -> Option<(&K, &V)> {
+        self.iter().next()
+    }
+
+    /// Returns an iterator over the keys and values, in ascending order of key
+    pub fn iter(&self) -> Range<'_, K, V, std::ops::RangeFull, C> {
+        Range { map: self, at: self.next(None, 0), range: .. }
+    }
+
+    /// Returns an iterator over the keys in a range and their values, in ascending order of key
+    ///
+    /// Finds the start of the range in expected `O(log(n))` time, then walks the bottom list.
+    ///
+    /// # Arguments
+    ///
+    /// * `range` - Range of keys to return
+    pub fn range<R: RangeBounds<K>>(&self, range: R) -> Range<'_, K, V, R, C> {
+        let at = match range.start_bound() {
+            Bound::Included(start) => self.seek(|k| self.comparator.less(k, start)),
+            Bound::Excluded(start) => self.seek(|k| !self.comparator.less(start, k)),
+            Bound::Unbounded => self.next(None, 0),
+        };
+        Range { map: self, at, range }
+    }
+
+    /// Internal function to find the first node whose key isn't `before` the one searched for
+    fn seek<F: Fn(&K) -> bool>(&self, before: F) -> Option<usize> {
+        let mut at = None;
+        for level in (0..self.head.len()).rev() {
+            while let Some(n) = self.next(at, level) {
+                if !before(&self.node(n).key) {
+                    break;
+                }
+                at = Some(n);
+            }
+        }
+        self.next(at, 0)
+    }
+
+    /// Internal function to find the last node on each level with a key less than `key`, None
+    /// meaning the head
+    fn predecessors(&self, key: &K) -> Vec<Option<usize>> {
+        let mut update = vec![None; self.head.len()];
+        let mut at = None;
+        for level in (0..self.head.len()).rev() {
+            while let Some(n) = self.next(at, level) {
+                if !self.comparator.less(&self.node(n).key, key) {
+                    break;
+                }
+                at = Some(n);
+            }
+            update[level] = at;
+        }
+        update
+    }
+
+    /// Internal function to pick a node's level, `l` with probability `2^-l`
+    fn random_level(&mut self) -> usize {
+        let mut level = 1;
+        while level < MAX_LEVEL && self.rng.gen_bool(0.5) {
+            level += 1;
+        }
+        level
+    }
+
+    /// Internal function to follow a next pointer on a level, from a node or the head
+    fn next(&self, at: Option<usize>, level: usize) -> Option<usize> {
+        match at {
+            Some(n) => self.node(n).next[level],
+            None => self.head.get(level).copied().flatten(),
+        }
+    }
+
+    /// Internal function to set a next pointer on a level, from a node or the head
+    fn set_next(&mut self, at: Option<usize>, level: usize, next: Option<usize>) {
+        match at {
+            Some(n) => self.node_mut(n).next[level] = next,
+            None => self.head[level] = next,
+        }
+    }
+
+    /// Internal function to get a node that is in the list
+    fn node(&self, n: usize) -> &Node<K, V> {
+        self.nodes[n].as_ref().unwrap()
+    }
+
+    /// Internal function to get a node that is in the list mutably
+    fn node_mut(&mut self, n: usize) -> &mut Node<K, V> {
+        self.nodes[n].as_mut().unwrap()
+    }
+}
+
+/// Iterator over the keys in a range of a `SkipListMap` and their values, in ascending order
+#[derive(Clone, Debug)]
+pub struct Range<'a, K, V, R, C = Natural> {
+    map: &'a SkipListMap<K, V, C>,
+    at: Option<usize>,
+    range: R,
+}
+
+impl<'a, K, V, R: RangeBounds<K>, C: Comparator<K>> Iterator for Range<'a, K, V, R, C> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<(&'a K, &'a V)> {
+        let node = self.map.node(self.at?);
+        // Every key from the start is past the start bound, so only the end needs checking
+        let comparator = &self.map.comparator;
+        let past_end = match self.range.end_bound() {
+            Bound::Included(end) => comparator.less(end, &node.key),
+            Bound::Excluded(end) => !comparator.less(&node.key, end),
+            Bound::Unbounded => false,
+        };
+        if past_end {
+            self.at = None;
+            return None;
+        }
+        self.at = node.next[0];
+        Some((&node.key, &node.value))
+    }
+}
+
+impl<K: Ord, V> Default for SkipListMap<K, V> {
+    fn default() -> Self {
+        SkipListMap::new()
+    }
+}
+
+impl<K, V, C: Comparator<K>> Extend<(K, V)> for SkipListMap<K, V, C> {
+    fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+        for (key, value) in iter {
+            self.insert(key, value);
+        }
+    }
+}
+
+impl<K: Ord, V> FromIterator<(K, V)> for SkipListMap<K, V> {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut map = SkipListMap::new();
+        map.extend(iter);
+        map
+    }
+}
+
+impl<K, V, C: Comparator<K>> Dictionary<K, V> for SkipListMap<K, V, C> {
+    fn insert(&mut self, key: K, value: V) -> Option<V> {
+        SkipListMap::insert(self, key, value)
+    }
+
+    fn get(&self, key: &K) -> Option<&V> {
+        SkipListMap::get(self, key)
+    }
+
+    fn remove(&mut self, key: &K) -> Option<V> {
+        SkipListMap::remove(self, key)
+    }
+
+    fn contains_key(&self, key: &K) -> bool {
+        SkipListMap::contains_key(self, key)
+    }
+
+    fn len(&self) -> usize {
+        SkipListMap::len(self)
+    }
+
+    fn iter<'a>(&'a self) -> impl Iterator<Item = (&'a K, &'a V)>
+    where
+        K: 'a,
+        V: 'a,
+    {
+        SkipListMap::iter(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::comparator::Reverse;
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn test_insert_get() {
+        let mut map = SkipListMap::with_seed(1);
+        assert_eq!(None, map.insert(3, "c"));
+        assert_eq!(None, map.insert(1, "a"));
+        assert_eq!(None, map.insert(2, "b"));
+        assert_eq!(Some("c"), map.insert(3, "C"));
+        assert_eq!(3, map.len());
+        assert_eq!(Some(&"a"), map.get(&1));
+        assert_eq!(Some(&"C"), map.get(&3));
+        assert_eq!(None, map.get(&4));
+        *map.get_mut(&2).unwrap() = "B";
+        assert_eq!(vec![(&1, &"a"), (&2, &"B"), (&3, &"C")], map.iter().collect::<Vec<_>>());
+        assert_eq!(Some((&1, &"a")), map.first());
+    }
+
+    #[test]
+    fn test_remove() {
+        let mut map: SkipListMap<_, _> = (0..10).map(|i| (i, i * i)).collect();
+        assert_eq!(Some(25), map.remove(&5));
+        assert_eq!(None, map.remove(&5));
+        assert!(!map.contains_key(&5));
+        assert_eq!(9, map.len());
+        for i in 0..10 {
+            map.remove(&i);
+        }
+        assert!(map.is_empty());
+        assert_eq!(0, map.levels());
+        map.insert(7, 49);
+        assert_eq!(Some(&49), map.get(&7));
+    }
+
+    #[test]
+    fn test_range() {
+        let map: SkipListMap<_, _> = (0..20).map(|i| (2 * i, ())).collect();
+        let keys = |r: Vec<(&i32, &())>| r.into_iter().map(|(k, _)| *k).collect::<Vec<_>>();
+        assert_eq!(vec![4, 6, 8], keys(map.range(3..10).collect()));
+        assert_eq!(vec![6, 8, 10], keys(map.range((Bound::Excluded(4), Bound::Included(10))).collect()));
+        assert_eq!(vec![36, 38], keys(map.range(35..).collect()));
+        assert_eq!(vec![0, 2], keys(map.range(..=2).collect()));
+        assert_eq!(0, map.range(100..).count());
+    }
+
+    #[test]
+    fn test_comparator() {
+        // Descending keys, with ranges running from high to low
+        let mut map = SkipListMap::with_seed_and_comparator(5, Reverse);
+        map.extend((0..10).map(|i| (i, i * i)));
+        assert_eq!(vec![9, 8, 7], map.iter().take(3).map(|(&k, _)| k).collect::<Vec<_>>());
+        assert_eq!(vec![6, 5, 4], map.range((Bound::Included(6), Bound::Excluded(3))).map(|(&k, _)| k).collect::<Vec<_>>());
+        assert_eq!(Some(&25), map.get(&5));
+
+        // Keys that differ only in case are the same key
+        let mut map = SkipListMap::with_comparator(|a: &&str, b: &&str| a.to_lowercase().cmp(&b.to_lowercase()));
+        assert_eq!(None, map.insert("Apple", 1));
+        assert_eq!(Some(1), map.insert("APPLE", 2));
+        map.insert("banana", 3);
+        assert_eq!(Some(&2), map.get(&"apple"));
+        assert_eq!(2, map.len());
+        assert_eq!(Some(3), map.remove(&"Banana"));
+    }
+
+    #[test]
+    fn test_matches_btree_map() {
+        let mut rng = Rng::new(97);
+        let mut map = SkipListMap::with_seed(5);
+        let mut expected = BTreeMap::new();
+        for i in 0..5000 {
+            let key = rng.gen_range(500);
+            if rng.gen_range(3) == 0 {
+                assert_eq!(expected.remove(&key), map.remove(&key));
+            } else {
+                assert_eq!(expected.insert(key, i), map.insert(key, i));
+            }
+        }
+        assert_eq!(expected.len(), map.len());
+        assert!(expected.iter().eq(map.iter()));
+        assert!(expected.range(100..200).eq(map.range(100..200)));
+        // About log2(n) levels
+        assert!(map.levels() <= 20);
+    }
+
+    #[test]
+    fn test_reproducible() {
+        let mut a = SkipListMap::with_seed(3);
+        let mut b = SkipListMap::with_seed(3);
+        for i in 0..1000 {
+            a.insert(i, ());
+            b.insert(i, ());
+        }
+        let shape = |map: &SkipListMap<i32, ()>| {
+            map.nodes.iter().map(|n| n.as_ref().unwrap().next.len()).collect::<Vec<_>>()
+        };
+        assert_eq!(shape(&a), shape(&b));
+        assert_eq!(a.levels(), b.levels());
+    }
+}
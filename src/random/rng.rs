@@ -0,0 +1,120 @@
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+
+/// A small seedable pseudo-random number generator
+///
+/// Implements the SplitMix64 generator[1]. It is fast, has a 64 bit state, and passes BigCrush,
+/// which is more than enough for randomized data structures and test data. It is **not**
+/// cryptographically secure.
+///
+/// [1] http://prng.di.unimi.it/splitmix64.c
+#[derive(Clone, Debug)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+
+    /// Creates a new `Rng` from a seed
+    ///
+    /// # Arguments
+    ///
+    /// * `seed` - Seed for the generator, equal seeds produce equal sequences
+    pub fn new(seed: u64) -> Rng {
+        Rng { state: seed }
+    }
+
+    /// Creates a new `Rng` seeded from the operating system's randomness
+    ///
+    /// Uses the random keys of the stdlib's `RandomState`, so two calls give different sequences.
+    pub fn from_entropy() -> Rng {
+        Rng::new(RandomState::new().build_hasher().finish())
+    }
+
+    /// Returns the next random `u64`
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// Returns a uniformly random `u64` in the range `[0, n)`
+    ///
+    /// Uses rejection sampling, so there is no modulo bias.
+    ///
+    /// # Arguments
+    ///
+    /// * `n` - Exclusive upper bound, must be greater than 0
+    pub fn gen_range(&mut self, n: u64) -> u64 {
+        assert!(n > 0, "gen_range called with an empty range");
+        let zone = u64::MAX - (u64::MAX % n);
+        loop {
+            let x = self.next_u64();
+            if x < zone {
+                return x % n;
+            }
+        }
+    }
+
+    /// Returns a uniformly random `f64` in the range `[0, 1)`
+    pub fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    /// Returns `true` with probability `p`
+    ///
+    /// # Arguments
+    ///
+    /// * `p` - Probability of returning `true`
+    pub fn gen_bool(&mut self, p: f64) -> bool {
+        self.next_f64() < p
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_seeded() {
+        let mut a = Rng::new(42);
+        let mut b = Rng::new(42);
+        let mut c = Rng::new(43);
+
+        for _ in 0..100 {
+            let x = a.next_u64();
+            assert_eq!(x, b.next_u64());
+            assert_ne!(x, c.next_u64());
+        }
+    }
+
+    #[test]
+    fn test_from_entropy() {
+        let mut a = Rng::from_entropy();
+        let mut b = Rng::from_entropy();
+        assert_ne!(a.next_u64(), b.next_u64());
+    }
+
+    #[test]
+    fn test_gen_range() {
+        let mut rng = Rng::new(7);
+        let mut counts = [0; 6];
+        for _ in 0..6000 {
+            counts[rng.gen_range(6) as usize] += 1;
+        }
+        for &c in counts.iter() {
+            assert!(c > 800 && c < 1200);
+        }
+    }
+
+    #[test]
+    fn test_next_f64() {
+        let mut rng = Rng::new(1);
+        for _ in 0..1000 {
+            let x = rng.next_f64();
+            assert!((0.0..1.0).contains(&x));
+        }
+    }
+}
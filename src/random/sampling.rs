@@ -0,0 +1,252 @@
+use crate::indexed_min_heap::IndexedMinHeap;
+use crate::rng::Rng;
+
+/// Shuffles a slice in place with the Fisher–Yates shuffle
+///
+/// Walks the slice from the back, swapping each position with a uniformly random position at or
+/// before it, in `O(n)` time[1]. Position `i` is filled from `i + 1` choices, so each of the `n!`
+/// orderings comes out with probability `1/n!`. Swapping with any position of the whole slice
+/// instead is a common mistake that makes some orderings more likely than others.
+///
+/// [1] Knuth, Donald E. "The Art of Computer Programming, Volume 2: Seminumerical Algorithms."
+/// <i>Addison-Wesley<i>, 1997.
+///
+/// # Arguments
+///
+/// * `slice` - Slice to shuffle
+/// * `rng` - Source of randomness, seeded for a reproducible shuffle
+pub fn shuffle<T>(slice: &mut [T], rng: &mut Rng) {
+    for i in (1..slice.len()).rev() {
+        let j = rng.gen_range(i as u64 + 1) as usize;
+        slice.swap(i, j);
+    }
+}
+
+/// Chooses `k` items of an iterator uniformly at random with reservoir sampling
+///
+/// Keeps the first `k` items, then replaces a random one of them with the `i`th item with
+/// probability `k/i`, so after any number of items each has been kept with the same probability,
+/// without knowing how many there will be[2]. Takes `O(n)` time and `O(k)` space, so it works on
+/// streams too long to hold. The sample is in no particular order, and holds every item if there
+/// are at most `k`.
+///
+/// [2] Vitter, Jeffrey S. "Random Sampling with a Reservoir." <i>ACM Transactions on Mathematical
+/// Software<i>, 1985.
+///
+/// # Arguments
+///
+/// * `iter` - Items to choose from
+/// * `k` - Number of items to choose
+/// * `rng` - Source of randomness
+pub fn sample_k<T, I: IntoIterator<Item = T>>(iter: I, k: usize, rng: &mut Rng) -> Vec<T> {
+    let mut reservoir = Vec::with_capacity(k);
+    for (i, item) in iter.into_iter().enumerate() {
+        if i < k {
+            reservoir.push(item);
+        } else {
+            let j = rng.gen_range(i as u64 + 1) as usize;
+            if j < k {
+                reservoir[j] = item;
+            }
+        }
+    }
+    reservoir
+}
+
+/// Chooses `k` distinct items of an iterator at random with probability proportional to their
+/// weights, with weighted reservoir sampling
+///
+/// Gives each item the key `u^(1/w)` for a uniformly random `u` in `(0, 1]` and its weight `w`, and
+/// keeps the `k` items with the largest keys in an `IndexedMinHeap` keyed by their slot in the
+/// sample, replacing the smallest when a larger key arrives[3]. Heavier items get larger keys, so
+/// the items come out one at a time in proportion to their weights among those not yet chosen.
+/// Takes `O(n*log(k))` time and `O(k)` space. Items of weight 0 are never chosen.
+///
+/// [3] Efraimidis, Pavlos S., and Paul G. Spirakis. "Weighted Random Sampling with a Reservoir."
+/// <i>Information Processing Letters<i>, 2006.
+///
+/// # Arguments
+///
+/// * `iter` - Items to choose from, each with its weight
+/// * `k` - Number of items to choose
+/// * `rng` - Source of randomness
+///
+/// # Panics
+///
+/// If a weight is negative or isn't finite
+pub fn weighted_sample_k<T, I: IntoIterator<Item = (T, f64)>>(iter: I, k: usize, rng: &mut Rng) -> Vec<T> {
+    let mut reservoir: Vec<T> = Vec::with_capacity(k);
+    let mut keys = IndexedMinHeap::with_capacity(k);
+    for (item, weight) in iter {
+        assert!(weight >= 0.0 && weight.is_finite(), "weight {} isn't a finite non-negative number", weight);
+        if weight == 0.0 || k == 0 {
+            continue;
+        }
+        // The log of u^(1/w), which orders the same without underflowing for small weights
+        let key = (1.0 - rng.next_f64()).ln() / weight;
+        if reservoir.len() < k {
+            keys.push(reservoir.len(), key);
+            reservoir.push(item);
+        } else if keys.peek().is_some_and(|(_, &smallest)| key > smallest) {
+            let (slot, _) = keys.pop().unwrap();
+            reservoir[slot] = item;
+            keys.push(slot, key);
+        }
+    }
+    reservoir
+}
+
+/// A table for drawing indices with probability proportional to fixed weights in `O(1)` time
+///
+/// Vose's alias method[4] splits the probability into `n` columns of equal height, one per index.
+/// Each column holds part of its own index's probability and fills the rest with part of another
+/// index's, its alias. Drawing picks a uniformly random column and flips a biased coin between
+/// its index and its alias. Building the table takes `O(n)` time, which pays off when many
+/// samples are drawn from the same weights, like edges of a random walk.
+///
+/// [4] Vose, Michael D. "A Linear Algorithm for Generating Random Numbers with a Given
+/// Distribution." <i>IEEE Transactions on Software Engineering<i>, 1991.
+#[derive(Clone, Debug)]
+pub struct AliasTable {
+    probability: Vec<f64>,
+    alias: Vec<usize>,
+}
+
+impl AliasTable {
+
+    /// Creates a new `AliasTable` from the weights of the indices `0..n`
+    ///
+    /// # Arguments
+    ///
+    /// * `weights` - Weight of each index, its probability over the total weight
+    ///
+    /// # Panics
+    ///
+    /// If a weight is negative or isn't finite, or there's no positive weight
+    pub fn new(weights: &[f64]) -> AliasTable {
+        assert!(weights.iter().all(|&w| w >= 0.0 && w.is_finite()), "weights must be finite non-negative numbers");
+        let total: f64 = weights.iter().sum();
+        assert!(total > 0.0, "weights must have a positive total");
+        let n = weights.len();
+
+        // Scale the weights so the average column is exactly full
+        let mut probability: Vec<f64> = weights.iter().map(|&w| w * n as f64 / total).collect();
+        let mut alias: Vec<usize> = (0..n).collect();
+        let (mut small, mut large): (Vec<usize>, Vec<usize>) = (0..n).partition(|&i| probability[i] < 1.0);
+        while let (Some(&s), Some(&l)) = (small.last(), large.last()) {
+            // Fill the rest of the short column with part of the tall one
+            small.pop();
+            alias[s] = l;
+            probability[l] -= 1.0 - probability[s];
+            if probability[l] < 1.0 {
+                large.pop();
+                small.push(l);
+            }
+        }
+        // Whatever is left is full, up to rounding
+        for i in small.into_iter().chain(large) {
+            probability[i] = 1.0;
+        }
+        AliasTable { probability, alias }
+    }
+
+    /// Number of indices in the table
+    pub fn len(&self) -> usize {
+        self.probability.len()
+    }
+
+    /// Checks if the table has no indices
+    pub fn is_empty(&self) -> bool {
+        self.probability.is_empty()
+    }
+
+    /// Draws a random index with probability proportional to its weight
+    ///
+    /// # Arguments
+    ///
+    /// * `rng` - Source of randomness
+    pub fn sample(&self, rng: &mut Rng) -> usize {
+        let column = rng.gen_range(self.len() as u64) as usize;
+        if rng.next_f64() < self.probability[column] {
+            column
+        } else {
+            self.alias[column]
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_shuffle() {
+        let mut rng = Rng::new(331);
+        // Each of the 6 orderings of 3 items comes out about equally often
+        let mut counts = [0; 6];
+        for _ in 0..60_000 {
+            let mut v = [0, 1, 2];
+            shuffle(&mut v, &mut rng);
+            let ordering = [[0, 1, 2], [0, 2, 1], [1, 0, 2], [1, 2, 0], [2, 0, 1], [2, 1, 0]].iter().position(|o| *o == v).unwrap();
+            counts[ordering] += 1;
+        }
+        assert!(counts.iter().all(|&c| c > 9500 && c < 10_500), "{:?}", counts);
+        // Seeded shuffles are reproducible and keep the items
+        let mut a: Vec<u64> = (0..100).collect();
+        let mut b = a.clone();
+        shuffle(&mut a, &mut Rng::new(7));
+        shuffle(&mut b, &mut Rng::new(7));
+        assert_eq!(a, b);
+        b.sort();
+        assert_eq!((0..100).collect::<Vec<u64>>(), b);
+        shuffle::<u64>(&mut [], &mut rng);
+    }
+
+    #[test]
+    fn test_sample_k() {
+        let mut rng = Rng::new(337);
+        let mut counts = [0; 20];
+        for _ in 0..10_000 {
+            let sample = sample_k(0..20, 5, &mut rng);
+            assert_eq!(5, sample.len());
+            for x in sample {
+                counts[x] += 1;
+            }
+        }
+        // Each item is chosen with probability 5/20
+        assert!(counts.iter().all(|&c| c > 2300 && c < 2700), "{:?}", counts);
+        assert_eq!(3, sample_k(0..3, 5, &mut rng).len());
+        assert!(sample_k(0..3, 0, &mut rng).is_empty());
+    }
+
+    #[test]
+    fn test_weighted() {
+        let mut rng = Rng::new(347);
+        let weights = [1.0, 0.0, 3.0, 6.0];
+        let table = AliasTable::new(&weights);
+        let mut counts = [0; 4];
+        for _ in 0..100_000 {
+            counts[table.sample(&mut rng)] += 1;
+        }
+        assert_eq!(0, counts[1]);
+        for (&c, &w) in counts.iter().zip(&weights) {
+            assert!((c as f64 - w * 10_000.0).abs() < 600.0, "{:?}", counts);
+        }
+
+        // Drawing one item is proportional to the weights too
+        let mut counts = [0; 4];
+        for _ in 0..20_000 {
+            for i in weighted_sample_k(weights.iter().copied().enumerate(), 1, &mut rng) {
+                counts[i] += 1;
+            }
+        }
+        assert_eq!(0, counts[1]);
+        for (&c, &w) in counts.iter().zip(&weights) {
+            assert!((c as f64 - w * 2000.0).abs() < 300.0, "{:?}", counts);
+        }
+        // Only the three items with a positive weight can be chosen
+        let mut sample = weighted_sample_k(weights.iter().copied().enumerate(), 4, &mut rng);
+        sample.sort();
+        assert_eq!(vec![0, 2, 3], sample);
+    }
+}
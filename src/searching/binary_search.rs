@@ -0,0 +1,189 @@
+use std::cmp::Ordering;
+use std::ops::Range;
+
+/// Finds the first index of a slice whose item fails a predicate, given the predicate holds for a
+/// prefix of the slice and fails for the rest
+///
+/// Every search in this module is a partition point. Keeps the invariant that the predicate holds
+/// before `lo` and fails from `hi` on, and halves the range between them each step, in
+/// `O(log(n))` time. If the predicate doesn't split the slice into a prefix and a suffix, the
+/// result is some index where it changes from true to false, like `partition_point` in the stdlib.
+///
+/// # Arguments
+///
+/// * `slice` - Slice to search, partitioned by the predicate
+/// * `pred` - Predicate that holds for the items before the index to find
+pub fn partition_point<T, P: FnMut(&T) -> bool>(slice: &[T], mut pred: P) -> usize {
+    let (mut lo, mut hi) = (0, slice.len());
+    while lo < hi {
+        let mid = lo + (hi - lo) / 2;
+        if pred(&slice[mid]) {
+            lo = mid + 1;
+        } else {
+            hi = mid;
+        }
+    }
+    lo
+}
+
+/// Finds the first index of a sorted slice whose item isn't less than `x`
+///
+/// It's where `x` would be inserted before any equal items, and the index of the first equal item
+/// if there is one. Returns the length of the slice if every item is less than `x`.
+///
+/// # Arguments
+///
+/// * `slice` - Sorted slice to search
+/// * `x` - Item to search for
+pub fn lower_bound<T: Ord>(slice: &[T], x: &T) -> usize {
+    lower_bound_by(slice, |item| item.cmp(x))
+}
+
+/// Finds the first index of a slice whose item a comparison function doesn't order as `Less`
+///
+/// # Arguments
+///
+/// * `slice` - Slice sorted by the comparison function to search
+/// * `compare` - Function ordering an item of the slice against the item searched for
+pub fn lower_bound_by<T, F: FnMut(&T) -> Ordering>(slice: &[T], mut compare: F) -> usize {
+    partition_point(slice, |item| compare(item) == Ordering::Less)
+}
+
+/// Finds the first index of a slice sorted by a key whose key isn't less than `key`
+///
+/// # Arguments
+///
+/// * `slice` - Slice sorted by the key to search
+/// * `key` - Key to search for
+/// * `f` - Function extracting the key of an item
+pub fn lower_bound_by_key<T, K: Ord, F: FnMut(&T) -> K>(slice: &[T], key: &K, mut f: F) -> usize {
+    lower_bound_by(slice, |item| f(item).cmp(key))
+}
+
+/// Finds the first index of a sorted slice whose item is greater than `x`
+///
+/// It's where `x` would be inserted after any equal items, and one past the last equal item if
+/// there is one. Returns the length of the slice if no item is greater than `x`.
+///
+/// # Arguments
+///
+/// * `slice` - Sorted slice to search
+/// * `x` - Item to search for
+pub fn upper_bound<T: Ord>(slice: &[T], x: &T) -> usize {
+    upper_bound_by(slice, |item| item.cmp(x))
+}
+
+/// Finds the first index of a slice whose item a comparison function orders as `Greater`
+///
+/// # Arguments
+///
+/// * `slice` - Slice sorted by the comparison function to search
+/// * `compare` - Function ordering an item of the slice against the item searched for
+pub fn upper_bound_by<T, F: FnMut(&T) -> Ordering>(slice: &[T], mut compare: F) -> usize {
+    partition_point(slice, |item| compare(item) != Ordering::Greater)
+}
+
+/// Finds the first index of a slice sorted by a key whose key is greater than `key`
+///
+/// # Arguments
+///
+/// * `slice` - Slice sorted by the key to search
+/// * `key` - Key to search for
+/// * `f` - Function extracting the key of an item
+pub fn upper_bound_by_key<T, K: Ord, F: FnMut(&T) -> K>(slice: &[T], key: &K, mut f: F) -> usize {
+    upper_bound_by(slice, |item| f(item).cmp(key))
+}
+
+/// Finds the range of indices of a sorted slice whose items equal `x`
+///
+/// The range runs from the lower bound to the upper bound, so its length is the number of items
+/// equal to `x`, and when there are none it's the empty range where `x` would be inserted. Takes
+/// `O(log(n))` time however many items are equal, unlike scanning out from one match.
+///
+/// # Arguments
+///
+/// * `slice` - Sorted slice to search
+/// * `x` - Item to search for
+pub fn equal_range<T: Ord>(slice: &[T], x: &T) -> Range<usize> {
+    equal_range_by(slice, |item| item.cmp(x))
+}
+
+/// Finds the range of indices of a slice whose items a comparison function orders as `Equal`
+///
+/// # Arguments
+///
+/// * `slice` - Slice sorted by the comparison function to search
+/// * `compare` - Function ordering an item of the slice against the item searched for
+pub fn equal_range_by<T, F: FnMut(&T) -> Ordering>(slice: &[T], mut compare: F) -> Range<usize> {
+    let start = lower_bound_by(slice, &mut compare);
+    // The equal items can only be after the lower bound
+    let end = start + upper_bound_by(&slice[start..], &mut compare);
+    start..end
+}
+
+/// Finds the range of indices of a slice sorted by a key whose keys equal `key`
+///
+/// # Arguments
+///
+/// * `slice` - Slice sorted by the key to search
+/// * `key` - Key to search for
+/// * `f` - Function extracting the key of an item
+pub fn equal_range_by_key<T, K: Ord, F: FnMut(&T) -> K>(slice: &[T], key: &K, mut f: F) -> Range<usize> {
+    equal_range_by(slice, |item| f(item).cmp(key))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::Rng;
+
+    #[test]
+    fn test_bounds() {
+        let v = [1, 2, 2, 2, 5, 8, 8];
+        assert_eq!(0, lower_bound(&v, &0));
+        assert_eq!(0, upper_bound(&v, &0));
+        assert_eq!(1, lower_bound(&v, &2));
+        assert_eq!(4, upper_bound(&v, &2));
+        assert_eq!(4, lower_bound(&v, &3));
+        assert_eq!(4, upper_bound(&v, &3));
+        assert_eq!(5, lower_bound(&v, &8));
+        assert_eq!(7, upper_bound(&v, &8));
+        assert_eq!(7, lower_bound(&v, &9));
+        assert_eq!(1..4, equal_range(&v, &2));
+        assert_eq!(4..4, equal_range(&v, &3));
+        assert_eq!(0..0, equal_range(&[], &3));
+        assert_eq!(4, partition_point(&v, |&x| x < 5));
+        assert_eq!(0, partition_point(&v, |_| false));
+        assert_eq!(7, partition_point(&v, |_| true));
+    }
+
+    #[test]
+    fn test_matches_linear_scan() {
+        let mut rng = Rng::new(311);
+        for n in [0, 1, 2, 100, 1000] {
+            let mut v: Vec<u64> = (0..n).map(|_| rng.gen_range(50)).collect();
+            v.sort();
+            for x in 0..=50 {
+                let lower = v.iter().filter(|&&y| y < x).count();
+                let upper = v.iter().filter(|&&y| y <= x).count();
+                assert_eq!(lower, lower_bound(&v, &x));
+                assert_eq!(upper, upper_bound(&v, &x));
+                assert_eq!(lower..upper, equal_range(&v, &x));
+                assert_eq!(v.partition_point(|&y| y < x), partition_point(&v, |&y| y < x));
+            }
+        }
+    }
+
+    #[test]
+    fn test_by_key() {
+        // Records sorted by score in descending order, then searched with a reversed comparison
+        let records = [("ada", 93), ("bo", 88), ("cy", 88), ("di", 70), ("ed", 61)];
+        assert_eq!(1..3, equal_range_by(&records, |r| 88.cmp(&r.1)));
+        assert_eq!(3, lower_bound_by(&records, |r| 75.cmp(&r.1)));
+        // The same records sorted by name
+        assert_eq!(2, lower_bound_by_key(&records, &"c", |r| r.0));
+        assert_eq!(3, upper_bound_by_key(&records, &"cy", |r| r.0));
+        assert_eq!(4..5, equal_range_by_key(&records, &"ed", |r| r.0));
+        assert_eq!(5..5, equal_range_by_key(&records, &"zed", |r| r.0));
+    }
+}
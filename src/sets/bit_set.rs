@@ -0,0 +1,379 @@
+use std::iter::FromIterator;
+
+use crate::set::Set;
+
+/// Number of bits in a word
+const WORD_BITS: usize = 64;
+
+/// A set of integers stored as one bit each, packed into 64 bit words
+///
+/// Bit `i` is set when `i` is in the set. Setting, clearing, and testing a bit take `O(1)` time,
+/// and the set operations combine whole words at once, so they take `O(n / 64)` time for sets of
+/// `n` bits. Setting a bit past the end grows the set to hold it. The complement is taken within
+/// the current number of bits, since the set has no fixed universe.
+#[derive(Clone, Debug, Default)]
+pub struct BitSet {
+    words: Vec<u64>,
+    num_bits: usize,
+}
+
+impl BitSet {
+
+    /// Creates a new empty `BitSet`
+    pub fn new() -> BitSet {
+        BitSet { words: Vec::new(), num_bits: 0 }
+    }
+
+    /// Creates a new `BitSet` of `num_bits` clear bits
+    ///
+    /// # Arguments
+    ///
+    /// * `num_bits` - Number of bits
+    pub fn with_num_bits(num_bits: usize) -> BitSet {
+        BitSet { words: vec![0; num_bits.div_ceil(WORD_BITS)], num_bits }
+    }
+
+    /// Number of bits, set or clear, one more than the largest element the set can hold without
+    /// growing
+    pub fn num_bits(&self) -> usize {
+        self.num_bits
+    }
+
+    /// Number of set bits, the size of the set
+    pub fn count_ones(&self) -> usize {
+        self.words.iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    /// Checks if no bits are set
+    pub fn is_empty(&self) -> bool {
+        self.words.iter().all(|&w| w == 0)
+    }
+
+    /// Sets bit `i`, growing the set if needed, and returns false if it was already set
+    ///
+    /// # Arguments
+    ///
+    /// * `i` - Bit to set
+    pub fn set(&mut self, i: usize) -> bool {
+        if i >= self.num_bits {
+            self.resize(i + 1);
+        }
+        let (w, mask) = (i / WORD_BITS, 1 << (i % WORD_BITS));
+        let was_set = self.words[w] & mask != 0;
+        self.words[w] |= mask;
+        !was_set
+    }
+
+    /// Clears bit `i`, and returns false if it wasn't set
+    ///
+    /// # Arguments
+    ///
+    /// * `i` - Bit to clear
+    pub fn clear(&mut self, i: usize) -> bool {
+        if !self.test(i) {
+            return false;
+        }
+        self.words[i / WORD_BITS] &= !(1 << (i % WORD_BITS));
+        true
+    }
+
+    /// Checks if bit `i` is set, false for bits past the end
+    ///
+    /// # Arguments
+    ///
+    /// * `i` - Bit to test
+    pub fn test(&self, i: usize) -> bool {
+        i < self.num_bits && self.words[i / WORD_BITS] & (1 << (i % WORD_BITS)) != 0
+    }
+
+    /// Clears every bit, keeping the number of bits
+    pub fn clear_all(&mut self) {
+        self.words.iter_mut().for_each(|w| *w = 0);
+    }
+
+    /// Changes the number of bits, dropping the bits past a smaller size and adding clear bits up
+    /// to a larger one
+    ///
+    /// # Arguments
+    ///
+    /// * `num_bits` - New number of bits
+    pub fn resize(&mut self, num_bits: usize) {
+        self.words.resize(num_bits.div_ceil(WORD_BITS), 0);
+        self.num_bits = num_bits;
+        self.mask_last();
+    }
+
+    /// Sets every bit that is set in another set, growing to its size if it's larger
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - Set to union with
+    pub fn union_with(&mut self, other: &BitSet) {
+        if other.num_bits > self.num_bits {
+            self.resize(other.num_bits);
+        }
+        for (a, b) in self.words.iter_mut().zip(&other.words) {
+            *a |= b;
+        }
+    }
+
+    /// Clears every bit that isn't set in another set
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - Set to intersect with
+    pub fn intersect_with(&mut self, other: &BitSet) {
+        for (i, a) in self.words.iter_mut().enumerate() {
+            *a &= other.words.get(i).copied().unwrap_or(0);
+        }
+    }
+
+    /// Clears every bit that is set in another set
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - Set of bits to clear
+    pub fn difference_with(&mut self, other: &BitSet) {
+        for (a, b) in self.words.iter_mut().zip(&other.words) {
+            *a &= !b;
+        }
+    }
+
+    /// Creates a new set of the bits set in either set
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - Set to union with
+    pub fn union(&self, other: &BitSet) -> BitSet {
+        let mut result = self.clone();
+        result.union_with(other);
+        result
+    }
+
+    /// Creates a new set of the bits set in both sets
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - Set to intersect with
+    pub fn intersection(&self, other: &BitSet) -> BitSet {
+        let mut result = self.clone();
+        result.intersect_with(other);
+        result
+    }
+
+    /// Creates a new set of the bits set in this set but not the other
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - Set of bits to exclude
+    pub fn difference(&self, other: &BitSet) -> BitSet {
+        let mut result = self.clone();
+        result.difference_with(other);
+        result
+    }
+
+    /// Creates a new set of the bits below `num_bits` that aren't set in this one
+    pub fn complement(&self) -> BitSet {
+        let mut result = BitSet { words: self.words.iter().map(|w| !w).collect(), num_bits: self.num_bits };
+        result.mask_last();
+        result
+    }
+
+    /// Checks if every bit set in this set is set in another
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - Possible superset
+    pub fn is_subset(&self, other: &BitSet) -> bool {
+        self.words.iter().enumerate().all(|(i, &a)| a & !other.words.get(i).copied().unwrap_or(0) == 0)
+    }
+
+    /// Returns an iterator over the indices of the set bits, in ascending order
+    ///
+    /// Skips a whole word of clear bits at a time.
+    pub fn iter(&self) -> Ones<'_> {
+        Ones { words: &self.words, index: 0, word: self.words.first().copied().unwrap_or(0) }
+    }
+
+    /// Internal function to clear the unused bits of the last word past `num_bits`
+    fn mask_last(&mut self) {
+        let used = self.num_bits % WORD_BITS;
+        if used > 0 {
+            if let Some(last) = self.words.last_mut() {
+                *last &= (1 << used) - 1;
+            }
+        }
+    }
+}
+
+/// Iterator over the indices of the set bits of a `BitSet`, in ascending order
+#[derive(Clone, Debug)]
+pub struct Ones<'a> {
+    words: &'a [u64],
+    index: usize,
+    word: u64,
+}
+
+impl<'a> Iterator for Ones<'a> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        while self.word == 0 {
+            self.index += 1;
+            self.word = *self.words.get(self.index)?;
+        }
+        let bit = self.word.trailing_zeros() as usize;
+        // Clear the lowest set bit
+        self.word &= self.word - 1;
+        Some(self.index * WORD_BITS + bit)
+    }
+}
+
+impl PartialEq for BitSet {
+    fn eq(&self, other: &BitSet) -> bool {
+        self.is_subset(other) && other.is_subset(self)
+    }
+}
+
+impl Eq for BitSet {}
+
+impl<'a> IntoIterator for &'a BitSet {
+    type Item = usize;
+    type IntoIter = Ones<'a>;
+
+    fn into_iter(self) -> Ones<'a> {
+        self.iter()
+    }
+}
+
+impl Extend<usize> for BitSet {
+    fn extend<I: IntoIterator<Item = usize>>(&mut self, iter: I) {
+        for i in iter {
+            self.set(i);
+        }
+    }
+}
+
+impl FromIterator<usize> for BitSet {
+    fn from_iter<I: IntoIterator<Item = usize>>(iter: I) -> Self {
+        let mut set = BitSet::new();
+        set.extend(iter);
+        set
+    }
+}
+
+impl Set<usize> for BitSet {
+    fn insert(&mut self, item: usize) -> bool {
+        BitSet::set(self, item)
+    }
+
+    fn remove(&mut self, item: &usize) -> bool {
+        BitSet::clear(self, *item)
+    }
+
+    fn contains(&self, item: &usize) -> bool {
+        BitSet::test(self, *item)
+    }
+
+    fn len(&self) -> usize {
+        BitSet::count_ones(self)
+    }
+
+    fn items(&self) -> impl Iterator<Item = usize> {
+        BitSet::iter(self)
+    }
+
+    fn is_subset(&self, other: &Self) -> bool {
+        BitSet::is_subset(self, other)
+    }
+
+    fn union_with(&mut self, other: &Self) {
+        BitSet::union_with(self, other)
+    }
+
+    fn intersect_with(&mut self, other: &Self) {
+        BitSet::intersect_with(self, other)
+    }
+
+    fn difference_with(&mut self, other: &Self) {
+        BitSet::difference_with(self, other)
+    }
+
+    fn union(&self, other: &Self) -> Self {
+        BitSet::union(self, other)
+    }
+
+    fn intersection(&self, other: &Self) -> Self {
+        BitSet::intersection(self, other)
+    }
+
+    fn difference(&self, other: &Self) -> Self {
+        BitSet::difference(self, other)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::Rng;
+    use std::collections::BTreeSet;
+
+    #[test]
+    fn test_set_clear_test() {
+        let mut bits = BitSet::new();
+        assert!(bits.set(3));
+        assert!(!bits.set(3));
+        assert!(bits.set(200));
+        assert_eq!(201, bits.num_bits());
+        assert!(bits.test(3));
+        assert!(!bits.test(4));
+        assert!(!bits.test(1000));
+        assert!(bits.clear(3));
+        assert!(!bits.clear(3));
+        assert_eq!(vec![200], bits.iter().collect::<Vec<_>>());
+        bits.clear_all();
+        assert!(bits.is_empty());
+        assert_eq!(201, bits.num_bits());
+    }
+
+    #[test]
+    fn test_set_operations() {
+        let a: BitSet = [1, 2, 3, 64, 100].iter().copied().collect();
+        let b: BitSet = [2, 3, 4, 130].iter().copied().collect();
+        assert_eq!(vec![1, 2, 3, 4, 64, 100, 130], a.union(&b).iter().collect::<Vec<_>>());
+        assert_eq!(vec![2, 3], a.intersection(&b).iter().collect::<Vec<_>>());
+        assert_eq!(vec![1, 64, 100], a.difference(&b).iter().collect::<Vec<_>>());
+        let c = BitSet::from_iter([0, 2, 4]).complement();
+        assert_eq!(vec![1, 3], c.iter().collect::<Vec<_>>());
+        assert!(a.intersection(&b).is_subset(&a));
+        assert!(!a.is_subset(&b));
+        // Equal sets with different numbers of bits are equal
+        let mut d = a.intersection(&b);
+        d.resize(1000);
+        assert_eq!(a.intersection(&b), d);
+        assert_eq!(5, a.count_ones());
+    }
+
+    #[test]
+    fn test_matches_btree_set() {
+        let mut rng = Rng::new(139);
+        let mut a = BitSet::new();
+        let mut b = BitSet::with_num_bits(300);
+        let (mut x, mut y) = (BTreeSet::new(), BTreeSet::new());
+        for _ in 0..2000 {
+            let i = rng.gen_range(300) as usize;
+            if rng.gen_bool(0.7) {
+                assert_eq!(x.insert(i), a.set(i));
+            } else {
+                assert_eq!(x.remove(&i), a.clear(i));
+            }
+            let j = rng.gen_range(300) as usize;
+            assert_eq!(y.insert(j), b.set(j));
+        }
+        assert!(a.iter().eq(x.iter().copied()));
+        assert!(a.union(&b).iter().eq(x.union(&y).copied()));
+        assert!(a.intersection(&b).iter().eq(x.intersection(&y).copied()));
+        assert!(b.difference(&a).iter().eq(y.difference(&x).copied()));
+        assert!(b.complement().iter().eq((0..300).filter(|i| !y.contains(i))));
+    }
+}
@@ -0,0 +1,280 @@
+use std::collections::hash_map::{self, HashMap, RandomState};
+use std::hash::{BuildHasher, Hash};
+use std::iter::FromIterator;
+
+use crate::set::Set;
+
+/// A set backed by a hash table
+///
+/// A thin wrapper over a hash map with `()` values, so callers don't have to simulate a set
+/// with a unit-valued map. Operations take expected `O(1)` time, and iteration order is arbitrary.
+/// The hasher can be any `BuildHasher`, including the crate's universal hash families.
+#[derive(Clone, Debug)]
+pub struct HashSet<K: Hash + Eq, S: BuildHasher = RandomState> {
+    map: HashMap<K, (), S>,
+}
+
+impl<K: Hash + Eq> HashSet<K> {
+
+    /// Creates a new empty `HashSet`
+    pub fn new() -> HashSet<K> {
+        HashSet { map: HashMap::new() }
+    }
+}
+
+impl<K: Hash + Eq, S: BuildHasher> HashSet<K, S> {
+
+    /// Creates a new empty `HashSet` using a given hasher
+    ///
+    /// # Arguments
+    ///
+    /// * `hasher` - Builds the hash function for keys, e.g. `MultiplyShift::from_seed(64, seed)`
+    pub fn with_hasher(hasher: S) -> HashSet<K, S> {
+        HashSet { map: HashMap::with_hasher(hasher) }
+    }
+
+    /// Number of elements in the set
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    /// Checks if the set is empty
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    /// Inserts an element into the set
+    ///
+    /// Returns `false` if the element was already in the set.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - Element to insert
+    pub fn insert(&mut self, key: K) -> bool {
+        self.map.insert(key, ()).is_none()
+    }
+
+    /// Removes an element from the set
+    ///
+    /// Returns `false` if the element was not in the set.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - Element to remove
+    pub fn remove(&mut self, key: &K) -> bool {
+        self.map.remove(key).is_some()
+    }
+
+    /// Checks if an element is in the set
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - Element to look for
+    pub fn contains(&self, key: &K) -> bool {
+        self.map.contains_key(key)
+    }
+
+    /// Returns an iterator over the elements in arbitrary order
+    pub fn iter(&self) -> hash_map::Keys<'_, K, ()> {
+        self.map.keys()
+    }
+
+    /// Checks if every element of this set is in another set
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - Possible superset
+    pub fn is_subset(&self, other: &HashSet<K, S>) -> bool {
+        self.len() <= other.len() && self.iter().all(|k| other.contains(k))
+    }
+}
+
+impl<K: Hash + Eq + Clone, S: BuildHasher + Clone> HashSet<K, S> {
+
+    /// Creates a new set of the elements in either set
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - Set to union with
+    pub fn union(&self, other: &HashSet<K, S>) -> HashSet<K, S> {
+        let mut result = self.clone();
+        result.extend(other.iter().cloned());
+        result
+    }
+
+    /// Creates a new set of the elements in both sets
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - Set to intersect with
+    pub fn intersection(&self, other: &HashSet<K, S>) -> HashSet<K, S> {
+        // Iterate the smaller set and probe the larger one
+        let (small, large) = if self.len() <= other.len() { (self, other) } else { (other, self) };
+        self.filtered(small, |k| large.contains(k))
+    }
+
+    /// Creates a new set of the elements in this set but not the other
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - Set of elements to exclude
+    pub fn difference(&self, other: &HashSet<K, S>) -> HashSet<K, S> {
+        self.filtered(self, |k| !other.contains(k))
+    }
+
+    /// Creates a new set of the elements in exactly one of the sets
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - Set to compare with
+    pub fn symmetric_difference(&self, other: &HashSet<K, S>) -> HashSet<K, S> {
+        let mut result = self.difference(other);
+        result.extend(other.iter().filter(|k| !self.contains(k)).cloned());
+        result
+    }
+
+    /// Internal function to build a set, with this set's hasher, from the elements of `source` matching `keep`
+    fn filtered<F: Fn(&K) -> bool>(&self, source: &HashSet<K, S>, keep: F) -> HashSet<K, S> {
+        let mut result = HashSet::with_hasher(self.map.hasher().clone());
+        result.extend(source.iter().filter(|k| keep(k)).cloned());
+        result
+    }
+}
+
+impl<K: Hash + Eq> Default for HashSet<K> {
+    fn default() -> Self {
+        HashSet::new()
+    }
+}
+
+impl<K: Hash + Eq, S: BuildHasher> PartialEq for HashSet<K, S> {
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len() && self.is_subset(other)
+    }
+}
+
+impl<K: Hash + Eq, S: BuildHasher> Eq for HashSet<K, S> {}
+
+impl<K: Hash + Eq, S: BuildHasher> Extend<K> for HashSet<K, S> {
+    fn extend<I: IntoIterator<Item = K>>(&mut self, iter: I) {
+        self.map.extend(iter.into_iter().map(|k| (k, ())));
+    }
+}
+
+impl<K: Hash + Eq, S: BuildHasher + Default> FromIterator<K> for HashSet<K, S> {
+    fn from_iter<I: IntoIterator<Item = K>>(iter: I) -> Self {
+        let mut set = HashSet::with_hasher(S::default());
+        set.extend(iter);
+        set
+    }
+}
+
+impl<K: Hash + Eq, S: BuildHasher> IntoIterator for HashSet<K, S> {
+    type Item = K;
+    type IntoIter = hash_map::IntoKeys<K, ()>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.map.into_keys()
+    }
+}
+
+impl<'a, K: Hash + Eq, S: BuildHasher> IntoIterator for &'a HashSet<K, S> {
+    type Item = &'a K;
+    type IntoIter = hash_map::Keys<'a, K, ()>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.map.keys()
+    }
+}
+
+impl<K: Hash + Eq + Clone, S: BuildHasher + Clone> Set<K> for HashSet<K, S> {
+    fn insert(&mut self, item: K) -> bool {
+        HashSet::insert(self, item)
+    }
+
+    fn remove(&mut self, item: &K) -> bool {
+        HashSet::remove(self, item)
+    }
+
+    fn contains(&self, item: &K) -> bool {
+        HashSet::contains(self, item)
+    }
+
+    fn len(&self) -> usize {
+        HashSet::len(self)
+    }
+
+    fn items(&self) -> impl Iterator<Item = K> {
+        HashSet::iter(self).cloned()
+    }
+
+    fn is_subset(&self, other: &Self) -> bool {
+        HashSet::is_subset(self, other)
+    }
+
+    fn union(&self, other: &Self) -> Self {
+        HashSet::union(self, other)
+    }
+
+    fn intersection(&self, other: &Self) -> Self {
+        HashSet::intersection(self, other)
+    }
+
+    fn difference(&self, other: &Self) -> Self {
+        HashSet::difference(self, other)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::universal_hasher::MultiplyShift;
+
+    #[test]
+    fn test_insert_remove() {
+        let mut set = HashSet::new();
+        assert!(set.insert(1));
+        assert!(set.insert(2));
+        assert!(!set.insert(1));
+        assert_eq!(2, set.len());
+        assert!(set.contains(&1));
+        assert!(set.remove(&1));
+        assert!(!set.remove(&1));
+        assert!(!set.contains(&1));
+    }
+
+    #[test]
+    fn test_from_iter() {
+        let set: HashSet<_> = vec![3, 1, 3, 2].into_iter().collect();
+        assert_eq!(3, set.len());
+        let mut v: Vec<_> = set.into_iter().collect();
+        v.sort();
+        assert_eq!(vec![1, 2, 3], v);
+    }
+
+    #[test]
+    fn test_set_operations() {
+        let a: HashSet<_> = (0..6).collect();
+        let b: HashSet<_> = (3..9).collect();
+
+        assert_eq!((0..9).collect::<HashSet<_>>(), a.union(&b));
+        assert_eq!((3..6).collect::<HashSet<_>>(), a.intersection(&b));
+        assert_eq!((0..3).collect::<HashSet<_>>(), a.difference(&b));
+        assert_eq!(vec![0, 1, 2, 6, 7, 8].into_iter().collect::<HashSet<_>>(), a.symmetric_difference(&b));
+        assert!(a.intersection(&b).is_subset(&a));
+        assert!(!a.is_subset(&b));
+    }
+
+    #[test]
+    fn test_with_hasher() {
+        let mut set = HashSet::with_hasher(MultiplyShift::from_seed(64, 1));
+        set.extend(vec!["a", "b"]);
+        assert!(set.contains(&"a"));
+        let other: HashSet<_, _> = {
+            let mut s = HashSet::with_hasher(MultiplyShift::from_seed(64, 1));
+            s.insert("c");
+            s
+        };
+        assert_eq!(3, set.union(&other).len());
+    }
+}
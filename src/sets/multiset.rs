@@ -0,0 +1,264 @@
+use std::collections::{BTreeMap, HashMap};
+use std::hash::Hash;
+use std::iter::FromIterator;
+
+/// A map from elements to their multiplicities, the backing store of a `MultiSet`
+///
+/// Implemented by `HashMap` for an unordered multiset and `BTreeMap` for a sorted one.
+pub trait CountMap<T>: Default {
+
+    /// Multiplicity of an element, or None if it is not in the map
+    fn count(&self, item: &T) -> Option<usize>;
+
+    /// Adds to the multiplicity of an element, inserting it if needed, and returns the new multiplicity
+    fn add(&mut self, item: T, n: usize) -> usize;
+
+    /// Subtracts from the multiplicity of an element, removing it when it reaches 0, and returns the amount subtracted
+    fn subtract(&mut self, item: &T, n: usize) -> usize;
+
+    /// Number of distinct elements
+    fn distinct(&self) -> usize;
+
+    /// Iterator over the elements and their multiplicities
+    fn pairs(&self) -> Box<dyn Iterator<Item = (&T, usize)> + '_>;
+}
+
+impl<T: Hash + Eq> CountMap<T> for HashMap<T, usize> {
+    fn count(&self, item: &T) -> Option<usize> {
+        self.get(item).copied()
+    }
+
+    fn add(&mut self, item: T, n: usize) -> usize {
+        let c = self.entry(item).or_insert(0);
+        *c += n;
+        *c
+    }
+
+    fn subtract(&mut self, item: &T, n: usize) -> usize {
+        let c = match self.get_mut(item) {
+            Some(c) => c,
+            None => return 0,
+        };
+        let removed = n.min(*c);
+        *c -= removed;
+        if *c == 0 {
+            self.remove(item);
+        }
+        removed
+    }
+
+    fn distinct(&self) -> usize {
+        self.len()
+    }
+
+    fn pairs(&self) -> Box<dyn Iterator<Item = (&T, usize)> + '_> {
+        Box::new(self.iter().map(|(k, &c)| (k, c)))
+    }
+}
+
+impl<T: Ord> CountMap<T> for BTreeMap<T, usize> {
+    fn count(&self, item: &T) -> Option<usize> {
+        self.get(item).copied()
+    }
+
+    fn add(&mut self, item: T, n: usize) -> usize {
+        let c = self.entry(item).or_insert(0);
+        *c += n;
+        *c
+    }
+
+    fn subtract(&mut self, item: &T, n: usize) -> usize {
+        let c = match self.get_mut(item) {
+            Some(c) => c,
+            None => return 0,
+        };
+        let removed = n.min(*c);
+        *c -= removed;
+        if *c == 0 {
+            self.remove(item);
+        }
+        removed
+    }
+
+    fn distinct(&self) -> usize {
+        self.len()
+    }
+
+    fn pairs(&self) -> Box<dyn Iterator<Item = (&T, usize)> + '_> {
+        Box::new(self.iter().map(|(k, &c)| (k, c)))
+    }
+}
+
+/// A multiset (bag), a set that can hold several copies of an element
+///
+/// Stores each distinct element once with its multiplicity, so memory is proportional to the
+/// number of distinct elements rather than the total count.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MultiSet<T, M: CountMap<T>> {
+    counts: M,
+    len: usize,
+    marker: std::marker::PhantomData<T>,
+}
+
+/// A hash backed `MultiSet`, with expected `O(1)` operations and arbitrary iteration order
+pub type HashMultiSet<T> = MultiSet<T, HashMap<T, usize>>;
+
+/// A tree backed `MultiSet`, with `O(log(n))` operations and sorted iteration order
+pub type TreeMultiSet<T> = MultiSet<T, BTreeMap<T, usize>>;
+
+impl<T, M: CountMap<T>> MultiSet<T, M> {
+
+    /// Creates a new empty `MultiSet`
+    pub fn new() -> MultiSet<T, M> {
+        MultiSet { counts: M::default(), len: 0, marker: std::marker::PhantomData }
+    }
+
+    /// Total number of elements, counting every copy
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Number of distinct elements
+    pub fn distinct_len(&self) -> usize {
+        self.counts.distinct()
+    }
+
+    /// Checks if the multiset is empty
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Inserts one copy of an element, and returns its new multiplicity
+    ///
+    /// # Arguments
+    ///
+    /// * `item` - Element to insert
+    pub fn insert(&mut self, item: T) -> usize {
+        self.insert_many(item, 1)
+    }
+
+    /// Inserts several copies of an element, and returns its new multiplicity
+    ///
+    /// # Arguments
+    ///
+    /// * `item` - Element to insert
+    /// * `n` - Number of copies
+    pub fn insert_many(&mut self, item: T, n: usize) -> usize {
+        if n == 0 {
+            return self.count(&item);
+        }
+        self.len += n;
+        self.counts.add(item, n)
+    }
+
+    /// Removes one copy of an element
+    ///
+    /// Returns `false` if the element was not in the multiset.
+    ///
+    /// # Arguments
+    ///
+    /// * `item` - Element to remove
+    pub fn remove_one(&mut self, item: &T) -> bool {
+        let removed = self.counts.subtract(item, 1);
+        self.len -= removed;
+        removed == 1
+    }
+
+    /// Removes every copy of an element, and returns how many were removed
+    ///
+    /// # Arguments
+    ///
+    /// * `item` - Element to remove
+    pub fn remove_all(&mut self, item: &T) -> usize {
+        let removed = self.counts.subtract(item, usize::MAX);
+        self.len -= removed;
+        removed
+    }
+
+    /// Number of copies of an element
+    ///
+    /// # Arguments
+    ///
+    /// * `item` - Element to count
+    pub fn count(&self, item: &T) -> usize {
+        self.counts.count(item).unwrap_or(0)
+    }
+
+    /// Checks if at least one copy of an element is in the multiset
+    ///
+    /// # Arguments
+    ///
+    /// * `item` - Element to look for
+    pub fn contains(&self, item: &T) -> bool {
+        self.counts.count(item).is_some()
+    }
+
+    /// Returns an iterator over the distinct elements and their multiplicities
+    pub fn iter(&self) -> Box<dyn Iterator<Item = (&T, usize)> + '_> {
+        self.counts.pairs()
+    }
+}
+
+impl<T, M: CountMap<T>> Default for MultiSet<T, M> {
+    fn default() -> Self {
+        MultiSet::new()
+    }
+}
+
+impl<T, M: CountMap<T>> Extend<T> for MultiSet<T, M> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for item in iter {
+            self.insert(item);
+        }
+    }
+}
+
+impl<T, M: CountMap<T>> FromIterator<T> for MultiSet<T, M> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut set = MultiSet::new();
+        set.extend(iter);
+        set
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_count() {
+        let mut set = HashMultiSet::new();
+        assert_eq!(1, set.insert("a"));
+        assert_eq!(2, set.insert("a"));
+        assert_eq!(1, set.insert("b"));
+        assert_eq!(3, set.insert_many("c", 3));
+        assert_eq!(2, set.count(&"a"));
+        assert_eq!(0, set.count(&"d"));
+        assert_eq!(6, set.len());
+        assert_eq!(3, set.distinct_len());
+    }
+
+    #[test]
+    fn test_remove() {
+        let mut set: HashMultiSet<_> = vec![1, 1, 1, 2].into_iter().collect();
+        assert!(set.remove_one(&1));
+        assert_eq!(2, set.count(&1));
+        assert_eq!(3, set.len());
+        assert!(!set.remove_one(&3));
+
+        assert_eq!(2, set.remove_all(&1));
+        assert!(!set.contains(&1));
+        assert_eq!(0, set.remove_all(&1));
+        assert!(set.remove_one(&2));
+        assert!(set.is_empty());
+        assert_eq!(0, set.distinct_len());
+    }
+
+    #[test]
+    fn test_sorted_iter() {
+        let set: TreeMultiSet<_> = "mississippi".chars().collect();
+        let pairs: Vec<_> = set.iter().collect();
+        assert_eq!(vec![(&'i', 4), (&'m', 1), (&'p', 2), (&'s', 4)], pairs);
+        assert_eq!(11, set.len());
+    }
+}
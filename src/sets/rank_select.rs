@@ -0,0 +1,363 @@
+use std::iter::FromIterator;
+
+use crate::bit_set::BitSet;
+
+/// Number of bits in a word
+const WORD_BITS: usize = 64;
+
+/// Number of words in a superblock, 512 bits
+const SUPERBLOCK_WORDS: usize = 8;
+
+/// Number of ones in a select group
+const GROUP_ONES: usize = 512;
+
+/// Number of ones in a select subgroup of a dense group
+const SUBGROUP_ONES: usize = 64;
+
+/// Span in bits from which a group stores the position of every one
+const SPARSE_GROUP_BITS: usize = 1 << 18;
+
+/// Span in bits from which a subgroup stores the position of every one
+const SPARSE_SUBGROUP_BITS: usize = 1 << 14;
+
+/// Flag marking a subgroup entry as sparse
+const SPARSE_SUBGROUP: u32 = 1 << 31;
+
+/// Internal select entry for a group of `GROUP_ONES` ones
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Group {
+    /// Spread over `SPARSE_GROUP_BITS` or more, with the positions of its ones stored from an
+    /// index into `positions`
+    Sparse(usize),
+    /// Spread over fewer, starting at the position of its first one, with its subgroups stored
+    /// from an index into `subgroups` and the offsets of its sparse subgroups from an index into
+    /// `offsets`
+    ///
+    /// A subgroup entry is the position of its first one relative to `start`, or if it's spread
+    /// over `SPARSE_SUBGROUP_BITS` or more, `SPARSE_SUBGROUP` plus the index of its ones'
+    /// relative positions after the group's first offset.
+    Dense { start: usize, subgroups: usize, offsets: usize },
+}
+
+/// A fixed bit vector that counts and finds set bits in constant time
+///
+/// `rank1(i)` is the number of ones before position `i`, and `select1(k)` is the position of the
+/// `k`th one, counting from 0, the two operations compressed indexes like wavelet trees and FM
+/// indexes are built on. The bits are split into superblocks of 512 bits, each storing the number
+/// of ones before it, and blocks of one 64 bit word, each storing the number of ones before it
+/// within its superblock in 16 bits. A rank is a superblock count plus a block count plus a
+/// `count_ones` on part of one word, in `O(1)` time, using about 37.5% extra space.
+///
+/// Select splits the ones into groups of 512, with the two level scheme of Clark[1]. A group
+/// spread over `2^18` bits or more stores the position of each of its ones, at most an eighth of
+/// a bit per bit of the vector. A denser group is split into subgroups of 64 ones. A subgroup
+/// spread over `2^14` bits or more stores its ones' positions relative to the group in 32 bits
+/// each, again at most an eighth of a bit per bit, and a denser one stores the position of its
+/// first one in 32 bits, from which the rest are under 256 words away. Every select is a few
+/// table lookups and a scan of at most 257 words, `O(1)` time.
+///
+/// [1] Clark, David. "Compact Pat Trees." <i>PhD thesis, University of Waterloo<i>, 1996.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RankSelectBitVector {
+    words: Vec<u64>,
+    len: usize,
+    superblocks: Vec<usize>,
+    blocks: Vec<u16>,
+    groups: Vec<Group>,
+    positions: Vec<usize>,
+    subgroups: Vec<u32>,
+    offsets: Vec<u32>,
+}
+
+impl RankSelectBitVector {
+
+    /// Creates a new `RankSelectBitVector` holding a copy of some bits
+    ///
+    /// Builds the rank and select tables in `O(n)` time.
+    ///
+    /// # Arguments
+    ///
+    /// * `bits` - Bits to store, in order
+    pub fn new<I: IntoIterator<Item = bool>>(bits: I) -> RankSelectBitVector {
+        let mut words = Vec::new();
+        let mut len = 0;
+        for bit in bits {
+            if len % WORD_BITS == 0 {
+                words.push(0);
+            }
+            if bit {
+                words[len / WORD_BITS] |= 1 << (len % WORD_BITS);
+            }
+            len += 1;
+        }
+        RankSelectBitVector::from_words(words, len)
+    }
+
+    /// Internal function to build the rank and select tables over packed words
+    fn from_words(words: Vec<u64>, len: usize) -> RankSelectBitVector {
+        let mut superblocks = Vec::with_capacity(words.len() / SUPERBLOCK_WORDS + 2);
+        let mut blocks = Vec::with_capacity(words.len());
+        let mut ones = 0;
+        let mut superblock_ones = 0;
+        for (i, &word) in words.iter().enumerate() {
+            if i % SUPERBLOCK_WORDS == 0 {
+                superblocks.push(ones);
+                superblock_ones = ones;
+            }
+            blocks.push((ones - superblock_ones) as u16);
+            ones += word.count_ones() as usize;
+        }
+        // A final entry holds the total number of ones
+        superblocks.push(ones);
+
+        let mut vector = RankSelectBitVector {
+            words,
+            len,
+            superblocks,
+            blocks,
+            groups: Vec::with_capacity(ones / GROUP_ONES + 1),
+            positions: Vec::new(),
+            subgroups: Vec::new(),
+            offsets: Vec::new(),
+        };
+        let mut group = Vec::with_capacity(GROUP_ONES);
+        for w in 0..vector.words.len() {
+            let mut word = vector.words[w];
+            while word != 0 {
+                group.push(w * WORD_BITS + word.trailing_zeros() as usize);
+                word &= word - 1;
+                if group.len() == GROUP_ONES {
+                    vector.add_group(&group);
+                    group.clear();
+                }
+            }
+        }
+        if !group.is_empty() {
+            vector.add_group(&group);
+        }
+        vector
+    }
+
+    /// Internal function to add the select entries for the positions of a group's ones
+    fn add_group(&mut self, group: &[usize]) {
+        let start = group[0];
+        if group[group.len() - 1] - start + 1 >= SPARSE_GROUP_BITS {
+            self.groups.push(Group::Sparse(self.positions.len()));
+            self.positions.extend_from_slice(group);
+            return;
+        }
+        let offsets = self.offsets.len();
+        self.groups.push(Group::Dense { start, subgroups: self.subgroups.len(), offsets });
+        // Positions in a dense group are less than `SPARSE_GROUP_BITS` from its start, and a
+        // group has fewer than `GROUP_ONES` offsets, so both fit below `SPARSE_SUBGROUP`
+        for subgroup in group.chunks(SUBGROUP_ONES) {
+            if subgroup[subgroup.len() - 1] - subgroup[0] + 1 >= SPARSE_SUBGROUP_BITS {
+                self.subgroups.push(SPARSE_SUBGROUP | (self.offsets.len() - offsets) as u32);
+                self.offsets.extend(subgroup.iter().map(|&p| (p - start) as u32));
+            } else {
+                self.subgroups.push((subgroup[0] - start) as u32);
+            }
+        }
+    }
+
+    /// Number of bits
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Checks if there are no bits
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Number of ones
+    pub fn count_ones(&self) -> usize {
+        *self.superblocks.last().unwrap()
+    }
+
+    /// Gets the bit at position `i`
+    ///
+    /// # Arguments
+    ///
+    /// * `i` - Position of the bit
+    ///
+    /// # Panics
+    ///
+    /// If `i` isn't less than the number of bits
+    pub fn get(&self, i: usize) -> bool {
+        assert!(i < self.len, "position {} is out of bounds for {} bits", i, self.len);
+        self.words[i / WORD_BITS] & (1 << (i % WORD_BITS)) != 0
+    }
+
+    /// Number of ones before position `i`, in `O(1)` time
+    ///
+    /// # Arguments
+    ///
+    /// * `i` - Position to count up to, not included
+    ///
+    /// # Panics
+    ///
+    /// If `i` is greater than the number of bits
+    pub fn rank1(&self, i: usize) -> usize {
+        assert!(i <= self.len, "position {} is out of bounds for {} bits", i, self.len);
+        if i == self.len {
+            return self.count_ones();
+        }
+        let w = i / WORD_BITS;
+        let below = self.words[w] & ((1 << (i % WORD_BITS)) - 1);
+        self.superblocks[w / SUPERBLOCK_WORDS] + self.blocks[w] as usize + below.count_ones() as usize
+    }
+
+    /// Number of zeros before position `i`, in `O(1)` time
+    ///
+    /// # Arguments
+    ///
+    /// * `i` - Position to count up to, not included
+    ///
+    /// # Panics
+    ///
+    /// If `i` is greater than the number of bits
+    pub fn rank0(&self, i: usize) -> usize {
+        i - self.rank1(i)
+    }
+
+    /// Position of the `k`th one counting from 0, or None if there are `k` ones or fewer, in
+    /// `O(1)` time
+    ///
+    /// # Arguments
+    ///
+    /// * `k` - Number of ones before the one to find
+    pub fn select1(&self, k: usize) -> Option<usize> {
+        if k >= self.count_ones() {
+            return None;
+        }
+        let rest = k % GROUP_ONES;
+        let (start, subgroups, offsets) = match self.groups[k / GROUP_ONES] {
+            Group::Sparse(i) => return Some(self.positions[i + rest]),
+            Group::Dense { start, subgroups, offsets } => (start, subgroups, offsets),
+        };
+        let rest_in_subgroup = rest % SUBGROUP_ONES;
+        let subgroup = self.subgroups[subgroups + rest / SUBGROUP_ONES];
+        if subgroup & SPARSE_SUBGROUP != 0 {
+            let i = offsets + (subgroup & !SPARSE_SUBGROUP) as usize;
+            return Some(start + self.offsets[i + rest_in_subgroup] as usize);
+        }
+        let first = start + subgroup as usize;
+
+        // The subgroup's ones are less than `SPARSE_SUBGROUP_BITS` after its first
+        let mut w = first / WORD_BITS;
+        let mut word = self.words[w] & !((1 << (first % WORD_BITS)) - 1);
+        let mut rest = rest_in_subgroup;
+        loop {
+            let count = word.count_ones() as usize;
+            if rest < count {
+                return Some(w * WORD_BITS + select_in_word(word, rest));
+            }
+            rest -= count;
+            w += 1;
+            word = self.words[w];
+        }
+    }
+
+    /// Returns an iterator over the bits in order
+    pub fn iter(&self) -> impl Iterator<Item = bool> + '_ {
+        (0..self.len).map(move |i| self.get(i))
+    }
+}
+
+/// Internal function to find the position of the `k`th one in a word, which must have more than
+/// `k` ones
+fn select_in_word(mut word: u64, k: usize) -> usize {
+    for _ in 0..k {
+        // Clear the lowest set bit
+        word &= word - 1;
+    }
+    word.trailing_zeros() as usize
+}
+
+impl From<&BitSet> for RankSelectBitVector {
+    fn from(bits: &BitSet) -> Self {
+        RankSelectBitVector::new((0..bits.num_bits()).map(|i| bits.test(i)))
+    }
+}
+
+impl FromIterator<bool> for RankSelectBitVector {
+    fn from_iter<I: IntoIterator<Item = bool>>(iter: I) -> Self {
+        RankSelectBitVector::new(iter)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::Rng;
+
+    #[test]
+    fn test_rank_select() {
+        let bits: RankSelectBitVector = "1011000101".chars().map(|c| c == '1').collect();
+        assert_eq!(10, bits.len());
+        assert_eq!(5, bits.count_ones());
+        assert_eq!(vec![0, 1, 1, 2, 3, 3, 3, 3, 4, 4, 5], (0..=10).map(|i| bits.rank1(i)).collect::<Vec<_>>());
+        assert_eq!(1, bits.rank0(3));
+        assert_eq!(vec![Some(0), Some(2), Some(3), Some(7), Some(9), None], (0..6).map(|k| bits.select1(k)).collect::<Vec<_>>());
+        assert!(bits.get(2));
+        assert!(!bits.get(1));
+    }
+
+    #[test]
+    fn test_from_bit_set() {
+        let set: BitSet = [5, 64, 700, 2000].iter().copied().collect();
+        let bits = RankSelectBitVector::from(&set);
+        assert_eq!(2001, bits.len());
+        assert_eq!(Some(700), bits.select1(2));
+        assert_eq!(3, bits.rank1(2000));
+        assert_eq!(4, bits.rank1(2001));
+        let empty = RankSelectBitVector::new(Vec::new());
+        assert!(empty.is_empty());
+        assert_eq!(0, empty.rank1(0));
+        assert_eq!(None, empty.select1(0));
+    }
+
+    #[test]
+    fn test_select_groups() {
+        // Ones every 8 bits make dense groups and subgroups, every 300 bits dense groups with
+        // sparse subgroups, and every 600 bits sparse groups
+        let mut bits = vec![false; 1_500_000];
+        for (range, gap) in [(0..200_000, 8), (200_000..700_000, 300), (700_000..1_500_000, 600)] {
+            for i in range.step_by(gap) {
+                bits[i] = true;
+            }
+        }
+        let vector = RankSelectBitVector::new(bits.iter().copied());
+        assert!(vector.groups.iter().any(|g| matches!(g, Group::Sparse(_))));
+        assert!(vector.groups.iter().any(|g| matches!(g, Group::Dense { .. })));
+        assert!(vector.subgroups.iter().any(|&s| s & SPARSE_SUBGROUP != 0));
+        assert!(vector.subgroups.iter().any(|&s| s & SPARSE_SUBGROUP == 0));
+        let positions: Vec<usize> = (0..bits.len()).filter(|&i| bits[i]).collect();
+        for (k, &i) in positions.iter().enumerate() {
+            assert_eq!(Some(i), vector.select1(k), "select1({})", k);
+        }
+        assert_eq!(None, vector.select1(positions.len()));
+    }
+
+    #[test]
+    fn test_matches_brute_force() {
+        let mut rng = Rng::new(151);
+        // Dense, sparse, and with a long run of zeros between samples
+        for density in [0.5, 0.02, 0.9] {
+            let mut bits: Vec<bool> = (0..20_000).map(|_| rng.gen_bool(density)).collect();
+            bits[5000..15_000].iter_mut().for_each(|b| *b = false);
+            let vector = RankSelectBitVector::new(bits.iter().copied());
+            let mut ones = 0;
+            for (i, &bit) in bits.iter().enumerate() {
+                assert_eq!(ones, vector.rank1(i));
+                if bit {
+                    assert_eq!(Some(i), vector.select1(ones));
+                    ones += 1;
+                }
+            }
+            assert_eq!(ones, vector.rank1(bits.len()));
+            assert_eq!(None, vector.select1(ones));
+        }
+    }
+}
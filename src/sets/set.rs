@@ -0,0 +1,189 @@
+/// A set of distinct items, with membership, insertion, removal, and the algebraic operations
+///
+/// Implemented by `HashSet`, `TreeSet`, `BitSet`, and `SparseSet`, so code can pick the backing
+/// structure for its workload without changing how it's called. The operations between two sets
+/// have defaults built on the others, which each set replaces with its own where it has a faster
+/// one, like merging sorted keys in `TreeSet` or combining whole words in `BitSet`.
+pub trait Set<T> {
+
+    /// Inserts an item, and returns `false` if it was already in the set
+    fn insert(&mut self, item: T) -> bool;
+
+    /// Removes an item, and returns `false` if it wasn't in the set
+    fn remove(&mut self, item: &T) -> bool;
+
+    /// Checks if an item is in the set
+    fn contains(&self, item: &T) -> bool;
+
+    /// Number of items in the set
+    fn len(&self) -> usize;
+
+    /// Checks if the set is empty
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns an iterator over copies of the items, in the set's own iteration order
+    fn items(&self) -> impl Iterator<Item = T>;
+
+    /// Checks if every item of this set is in the other
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - Set to check against
+    fn is_subset(&self, other: &Self) -> bool {
+        self.len() <= other.len() && self.items().all(|x| other.contains(&x))
+    }
+
+    /// Inserts every item of the other set into this one
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - Set to union with
+    fn union_with(&mut self, other: &Self) {
+        for x in other.items() {
+            self.insert(x);
+        }
+    }
+
+    /// Removes every item of this set that isn't in the other
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - Set to intersect with
+    fn intersect_with(&mut self, other: &Self) {
+        let missing: Vec<T> = self.items().filter(|x| !other.contains(x)).collect();
+        for x in missing {
+            self.remove(&x);
+        }
+    }
+
+    /// Removes every item of the other set from this one
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - Set of items to exclude
+    fn difference_with(&mut self, other: &Self) {
+        for x in other.items() {
+            self.remove(&x);
+        }
+    }
+
+    /// Creates a new set of the items in either set
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - Set to union with
+    fn union(&self, other: &Self) -> Self
+    where
+        Self: Clone,
+    {
+        let mut result = self.clone();
+        result.union_with(other);
+        result
+    }
+
+    /// Creates a new set of the items in both sets
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - Set to intersect with
+    fn intersection(&self, other: &Self) -> Self
+    where
+        Self: Clone,
+    {
+        let mut result = self.clone();
+        result.intersect_with(other);
+        result
+    }
+
+    /// Creates a new set of the items in this set but not the other
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - Set of items to exclude
+    fn difference(&self, other: &Self) -> Self
+    where
+        Self: Clone,
+    {
+        let mut result = self.clone();
+        result.difference_with(other);
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bit_set::BitSet;
+    use crate::hash_set::HashSet;
+    use crate::rng::Rng;
+    use crate::sparse_set::SparseSet;
+    use crate::tree_set::TreeSet;
+    use std::collections::BTreeSet;
+
+    /// Internal function to list the items of a set in sorted order
+    fn sorted<S: Set<usize>>(set: &S) -> Vec<usize> {
+        let mut items: Vec<usize> = set.items().collect();
+        items.sort_unstable();
+        items
+    }
+
+    /// Internal function to run random inserts, removals, and lookups against a set and a
+    /// `BTreeSet` as the model, checking that every result agrees
+    fn check_against_model<S: Set<usize>>(mut set: S, seed: u64) {
+        let mut model = BTreeSet::new();
+        let mut rng = Rng::new(seed);
+        for step in 0..5000 {
+            let x = rng.gen_range(100) as usize;
+            match rng.gen_range(3) {
+                0 => assert_eq!(model.insert(x), set.insert(x), "insert {} at step {}", x, step),
+                1 => assert_eq!(model.remove(&x), set.remove(&x), "remove {} at step {}", x, step),
+                _ => assert_eq!(model.contains(&x), set.contains(&x), "contains {} at step {}", x, step),
+            }
+            assert_eq!(model.len(), set.len());
+        }
+        assert_eq!(model.into_iter().collect::<Vec<_>>(), sorted(&set));
+    }
+
+    /// Internal function to check a set's operations against the same operations on `BTreeSet`s
+    fn check_algebra<S: Set<usize> + Clone>(empty: S, seed: u64) {
+        let mut rng = Rng::new(seed);
+        for _ in 0..50 {
+            let (mut a, mut b) = (empty.clone(), empty.clone());
+            let (mut model_a, mut model_b) = (BTreeSet::new(), BTreeSet::new());
+            for _ in 0..rng.gen_range(40) {
+                let x = rng.gen_range(64) as usize;
+                a.insert(x);
+                model_a.insert(x);
+            }
+            for _ in 0..rng.gen_range(40) {
+                let x = rng.gen_range(64) as usize;
+                b.insert(x);
+                model_b.insert(x);
+            }
+            assert_eq!(model_a.union(&model_b).copied().collect::<Vec<_>>(), sorted(&a.union(&b)));
+            assert_eq!(model_a.intersection(&model_b).copied().collect::<Vec<_>>(), sorted(&a.intersection(&b)));
+            assert_eq!(model_a.difference(&model_b).copied().collect::<Vec<_>>(), sorted(&a.difference(&b)));
+            assert_eq!(model_a.is_subset(&model_b), a.is_subset(&b));
+            let c = a.intersection(&b);
+            assert!(c.is_subset(&a) && c.is_subset(&b));
+        }
+    }
+
+    #[test]
+    fn test_against_model() {
+        check_against_model(HashSet::new(), 503);
+        check_against_model(TreeSet::new(), 509);
+        check_against_model(BitSet::new(), 521);
+        check_against_model(SparseSet::new(100), 523);
+    }
+
+    #[test]
+    fn test_algebra() {
+        check_algebra(HashSet::new(), 541);
+        check_algebra(TreeSet::new(), 547);
+        check_algebra(BitSet::new(), 557);
+        check_algebra(SparseSet::new(64), 563);
+    }
+}
@@ -0,0 +1,203 @@
+use crate::set::Set;
+
+/// A set of integers below a fixed universe size, in a pair of dense and sparse arrays
+///
+/// The elements are packed in order at the start of the dense array, and the sparse array, with a
+/// slot per integer in the universe, holds each element's position in the dense one. An integer is
+/// in the set when its sparse slot points inside the packed elements at a slot holding it, so the
+/// sparse array never has to be cleaned: stale slots just fail the check. That makes insert,
+/// remove, contains, and even clear take `O(1)` time, and iterating takes time in the number of
+/// elements rather than the size of the universe.
+///
+/// Elements iterate in insertion order until something is removed: removal moves the last
+/// element into the removed one's place.
+#[derive(Clone, Debug)]
+pub struct SparseSet {
+    dense: Vec<usize>,
+    sparse: Vec<usize>,
+}
+
+impl SparseSet {
+
+    /// Creates a new empty `SparseSet`
+    ///
+    /// Allocates the sparse array once, in `O(universe)` time.
+    ///
+    /// # Arguments
+    ///
+    /// * `universe` - Elements must be less than this
+    pub fn new(universe: usize) -> SparseSet {
+        SparseSet { dense: Vec::new(), sparse: vec![0; universe] }
+    }
+
+    /// Number of elements in the set
+    pub fn len(&self) -> usize {
+        self.dense.len()
+    }
+
+    /// Checks if the set is empty
+    pub fn is_empty(&self) -> bool {
+        self.dense.is_empty()
+    }
+
+    /// Size of the universe, every element is less than this
+    pub fn universe(&self) -> usize {
+        self.sparse.len()
+    }
+
+    /// Inserts an element into the set
+    ///
+    /// Returns `false` if the element was already in the set.
+    ///
+    /// # Arguments
+    ///
+    /// * `x` - Element to insert
+    ///
+    /// # Panics
+    ///
+    /// If `x` isn't less than the universe size
+    pub fn insert(&mut self, x: usize) -> bool {
+        assert!(x < self.universe(), "{} is outside a universe of size {}", x, self.universe());
+        if self.contains(x) {
+            return false;
+        }
+        self.sparse[x] = self.dense.len();
+        self.dense.push(x);
+        true
+    }
+
+    /// Removes an element from the set, moving the last element into its place
+    ///
+    /// Returns `false` if the element was not in the set.
+    ///
+    /// # Arguments
+    ///
+    /// * `x` - Element to remove
+    pub fn remove(&mut self, x: usize) -> bool {
+        if !self.contains(x) {
+            return false;
+        }
+        let i = self.sparse[x];
+        let last = *self.dense.last().unwrap();
+        self.dense.swap_remove(i);
+        self.sparse[last] = i;
+        true
+    }
+
+    /// Checks if an element is in the set
+    ///
+    /// # Arguments
+    ///
+    /// * `x` - Element to look for
+    pub fn contains(&self, x: usize) -> bool {
+        self.sparse.get(x).is_some_and(|&i| i < self.dense.len() && self.dense[i] == x)
+    }
+
+    /// Removes every element in `O(1)` time, leaving the sparse array as it is
+    pub fn clear(&mut self) {
+        self.dense.clear();
+    }
+
+    /// Gets the elements as a slice, in iteration order
+    pub fn as_slice(&self) -> &[usize] {
+        &self.dense
+    }
+
+    /// Returns an iterator over the elements, in insertion order if none have been removed
+    pub fn iter(&self) -> std::iter::Copied<std::slice::Iter<'_, usize>> {
+        self.dense.iter().copied()
+    }
+}
+
+impl Extend<usize> for SparseSet {
+    fn extend<I: IntoIterator<Item = usize>>(&mut self, iter: I) {
+        for x in iter {
+            self.insert(x);
+        }
+    }
+}
+
+impl Set<usize> for SparseSet {
+    fn insert(&mut self, item: usize) -> bool {
+        SparseSet::insert(self, item)
+    }
+
+    fn remove(&mut self, item: &usize) -> bool {
+        SparseSet::remove(self, *item)
+    }
+
+    fn contains(&self, item: &usize) -> bool {
+        SparseSet::contains(self, *item)
+    }
+
+    fn len(&self) -> usize {
+        SparseSet::len(self)
+    }
+
+    fn items(&self) -> impl Iterator<Item = usize> {
+        SparseSet::iter(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::Rng;
+    use std::collections::BTreeSet;
+
+    #[test]
+    fn test_insert_remove() {
+        let mut set = SparseSet::new(10);
+        assert!(set.insert(7));
+        assert!(set.insert(2));
+        assert!(set.insert(5));
+        assert!(!set.insert(2));
+        assert_eq!(&[7, 2, 5], set.as_slice());
+        assert!(set.remove(7));
+        assert!(!set.remove(7));
+        // 5 moves into the hole left by 7
+        assert_eq!(vec![5, 2], set.iter().collect::<Vec<_>>());
+        assert!(set.contains(5));
+        assert!(!set.contains(7));
+        assert!(!set.contains(100));
+    }
+
+    #[test]
+    fn test_clear() {
+        let mut set = SparseSet::new(100);
+        set.extend(0..50);
+        set.clear();
+        assert!(set.is_empty());
+        // Stale sparse slots don't make cleared elements look present
+        for x in 0..100 {
+            assert!(!set.contains(x));
+        }
+        set.insert(30);
+        assert!(set.contains(30));
+        assert!(!set.contains(0));
+        assert_eq!(1, set.len());
+    }
+
+    #[test]
+    fn test_matches_btree_set() {
+        let mut rng = Rng::new(137);
+        let mut set = SparseSet::new(64);
+        let mut expected = BTreeSet::new();
+        for _ in 0..5000 {
+            let x = rng.gen_range(64) as usize;
+            match rng.gen_range(20) {
+                0 => {
+                    set.clear();
+                    expected.clear();
+                }
+                1..=7 => assert_eq!(expected.remove(&x), set.remove(x)),
+                _ => assert_eq!(expected.insert(x), set.insert(x)),
+            }
+            assert_eq!(expected.len(), set.len());
+            assert_eq!(expected.contains(&x), set.contains(x));
+        }
+        let mut elements: Vec<usize> = set.iter().collect();
+        elements.sort_unstable();
+        assert_eq!(expected.into_iter().collect::<Vec<_>>(), elements);
+    }
+}
@@ -0,0 +1,303 @@
+use std::collections::btree_map::{self, BTreeMap};
+use std::iter::FromIterator;
+use std::ops::RangeBounds;
+
+use crate::set::Set;
+
+/// A sorted set backed by a balanced search tree
+///
+/// A thin wrapper over a tree map with `()` values, so callers don't have to simulate a set with
+/// a unit-valued map. Operations take `O(log(n))` time, and iteration is in ascending order.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TreeSet<K: Ord> {
+    map: BTreeMap<K, ()>,
+}
+
+impl<K: Ord> TreeSet<K> {
+
+    /// Creates a new empty `TreeSet`
+    pub fn new() -> TreeSet<K> {
+        TreeSet { map: BTreeMap::new() }
+    }
+
+    /// Number of elements in the set
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    /// Checks if the set is empty
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    /// Inserts an element into the set
+    ///
+    /// Returns `false` if the element was already in the set.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - Element to insert
+    pub fn insert(&mut self, key: K) -> bool {
+        self.map.insert(key, ()).is_none()
+    }
+
+    /// Removes an element from the set
+    ///
+    /// Returns `false` if the element was not in the set.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - Element to remove
+    pub fn remove(&mut self, key: &K) -> bool {
+        self.map.remove(key).is_some()
+    }
+
+    /// Checks if an element is in the set
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - Element to look for
+    pub fn contains(&self, key: &K) -> bool {
+        self.map.contains_key(key)
+    }
+
+    /// Returns an iterator over the elements in ascending order
+    pub fn iter(&self) -> btree_map::Keys<'_, K, ()> {
+        self.map.keys()
+    }
+
+    /// Returns an iterator over the elements within a range, in ascending order
+    ///
+    /// # Arguments
+    ///
+    /// * `range` - Bounds of the elements to return
+    pub fn range<R: RangeBounds<K>>(&self, range: R) -> impl DoubleEndedIterator<Item = &K> {
+        self.map.range(range).map(|(k, _)| k)
+    }
+
+    /// The smallest element, or None if the set is empty
+    pub fn first(&self) -> Option<&K> {
+        self.map.keys().next()
+    }
+
+    /// The largest element, or None if the set is empty
+    pub fn last(&self) -> Option<&K> {
+        self.map.keys().next_back()
+    }
+
+    /// Checks if every element of this set is in another set
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - Possible superset
+    pub fn is_subset(&self, other: &TreeSet<K>) -> bool {
+        self.len() <= other.len() && self.iter().all(|k| other.contains(k))
+    }
+}
+
+impl<K: Ord + Clone> TreeSet<K> {
+
+    /// Creates a new set of the elements in either set
+    ///
+    /// Merges the two sorted sequences in `O(n + m)` time.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - Set to union with
+    pub fn union(&self, other: &TreeSet<K>) -> TreeSet<K> {
+        self.merge(other, true, true, true)
+    }
+
+    /// Creates a new set of the elements in both sets
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - Set to intersect with
+    pub fn intersection(&self, other: &TreeSet<K>) -> TreeSet<K> {
+        self.merge(other, false, true, false)
+    }
+
+    /// Creates a new set of the elements in this set but not the other
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - Set of elements to exclude
+    pub fn difference(&self, other: &TreeSet<K>) -> TreeSet<K> {
+        self.merge(other, true, false, false)
+    }
+
+    /// Creates a new set of the elements in exactly one of the sets
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - Set to compare with
+    pub fn symmetric_difference(&self, other: &TreeSet<K>) -> TreeSet<K> {
+        self.merge(other, true, false, true)
+    }
+
+    /// Internal function to merge the two sorted sequences, keeping the elements only in
+    /// this set, in both sets, and only in the other set as chosen
+    fn merge(&self, other: &TreeSet<K>, only_self: bool, both: bool, only_other: bool) -> TreeSet<K> {
+        let mut result = Vec::new();
+        let mut a = self.iter().peekable();
+        let mut b = other.iter().peekable();
+        loop {
+            match (a.peek(), b.peek()) {
+                (Some(x), Some(y)) if x < y => {
+                    if only_self {
+                        result.push(a.next().unwrap().clone());
+                    } else {
+                        a.next();
+                    }
+                }
+                (Some(x), Some(y)) if x > y => {
+                    if only_other {
+                        result.push(b.next().unwrap().clone());
+                    } else {
+                        b.next();
+                    }
+                }
+                (Some(_), Some(_)) => {
+                    b.next();
+                    if both {
+                        result.push(a.next().unwrap().clone());
+                    } else {
+                        a.next();
+                    }
+                }
+                (Some(_), None) => {
+                    if only_self {
+                        result.extend(a.cloned());
+                    }
+                    break;
+                }
+                (None, Some(_)) => {
+                    if only_other {
+                        result.extend(b.cloned());
+                    }
+                    break;
+                }
+                (None, None) => break,
+            }
+        }
+        result.into_iter().collect()
+    }
+}
+
+impl<K: Ord> Default for TreeSet<K> {
+    fn default() -> Self {
+        TreeSet::new()
+    }
+}
+
+impl<K: Ord> Extend<K> for TreeSet<K> {
+    fn extend<I: IntoIterator<Item = K>>(&mut self, iter: I) {
+        self.map.extend(iter.into_iter().map(|k| (k, ())));
+    }
+}
+
+impl<K: Ord> FromIterator<K> for TreeSet<K> {
+    fn from_iter<I: IntoIterator<Item = K>>(iter: I) -> Self {
+        TreeSet { map: iter.into_iter().map(|k| (k, ())).collect() }
+    }
+}
+
+impl<K: Ord> IntoIterator for TreeSet<K> {
+    type Item = K;
+    type IntoIter = btree_map::IntoKeys<K, ()>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.map.into_keys()
+    }
+}
+
+impl<'a, K: Ord> IntoIterator for &'a TreeSet<K> {
+    type Item = &'a K;
+    type IntoIter = btree_map::Keys<'a, K, ()>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.map.keys()
+    }
+}
+
+impl<K: Ord + Clone> Set<K> for TreeSet<K> {
+    fn insert(&mut self, item: K) -> bool {
+        TreeSet::insert(self, item)
+    }
+
+    fn remove(&mut self, item: &K) -> bool {
+        TreeSet::remove(self, item)
+    }
+
+    fn contains(&self, item: &K) -> bool {
+        TreeSet::contains(self, item)
+    }
+
+    fn len(&self) -> usize {
+        TreeSet::len(self)
+    }
+
+    fn items(&self) -> impl Iterator<Item = K> {
+        TreeSet::iter(self).cloned()
+    }
+
+    fn is_subset(&self, other: &Self) -> bool {
+        TreeSet::is_subset(self, other)
+    }
+
+    fn union(&self, other: &Self) -> Self {
+        TreeSet::union(self, other)
+    }
+
+    fn intersection(&self, other: &Self) -> Self {
+        TreeSet::intersection(self, other)
+    }
+
+    fn difference(&self, other: &Self) -> Self {
+        TreeSet::difference(self, other)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_insert_remove() {
+        let mut set = TreeSet::new();
+        assert!(set.insert(2));
+        assert!(set.insert(1));
+        assert!(!set.insert(2));
+        assert_eq!(2, set.len());
+        assert!(set.remove(&2));
+        assert!(!set.contains(&2));
+        assert!(set.contains(&1));
+    }
+
+    #[test]
+    fn test_sorted() {
+        let set: TreeSet<_> = vec![5, 1, 4, 1, 3].into_iter().collect();
+        assert_eq!(vec![&1, &3, &4, &5], set.iter().collect::<Vec<_>>());
+        assert_eq!(Some(&1), set.first());
+        assert_eq!(Some(&5), set.last());
+        assert_eq!(vec![&3, &4], set.range(2..5).collect::<Vec<_>>());
+        assert_eq!(vec![1, 3, 4, 5], set.into_iter().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_set_operations() {
+        let a: TreeSet<_> = (0..6).collect();
+        let b: TreeSet<_> = (3..9).collect();
+
+        assert_eq!((0..9).collect::<TreeSet<_>>(), a.union(&b));
+        assert_eq!((3..6).collect::<TreeSet<_>>(), a.intersection(&b));
+        assert_eq!((0..3).collect::<TreeSet<_>>(), a.difference(&b));
+        assert_eq!(vec![0, 1, 2, 6, 7, 8].into_iter().collect::<TreeSet<_>>(), a.symmetric_difference(&b));
+        assert!(a.intersection(&b).is_subset(&b));
+        assert!(!b.is_subset(&a));
+
+        let empty = TreeSet::new();
+        assert_eq!(a, a.union(&empty));
+        assert!(a.intersection(&empty).is_empty());
+    }
+}
@@ -0,0 +1,123 @@
+use crate::counting_sort::place_by_keys;
+use crate::elementary_sorts::insertion_sort_by;
+
+/// How full the buckets of a bucket sort were, to check its assumption that the input is uniform
+///
+/// Insertion sorting a bucket of `m` items takes `O(m^2)` time at worst, so the work of a bucket
+/// sort is proportional to `n` plus the sum of the squared bucket sizes. For `n` uniformly random
+/// items in `n` buckets, that sum is expected to be `2n - 1`[1]. A much larger sum means the items
+/// bunched up in a few buckets.
+///
+/// [1] Cormen, Thomas H.., et al. <i>Introduction to Algorithms<i>.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BucketStats {
+    /// Number of items in each bucket, from the lowest values to the highest
+    pub sizes: Vec<usize>,
+}
+
+impl BucketStats {
+
+    /// Number of buckets with no items
+    pub fn empty_buckets(&self) -> usize {
+        self.sizes.iter().filter(|&&size| size == 0).count()
+    }
+
+    /// Number of items in the fullest bucket
+    pub fn largest(&self) -> usize {
+        self.sizes.iter().copied().max().unwrap_or(0)
+    }
+
+    /// Sum of the squared bucket sizes, proportional to the insertion sorting work
+    pub fn sum_of_squares(&self) -> usize {
+        self.sizes.iter().map(|&size| size * size).sum()
+    }
+}
+
+/// Sorts a slice of floats with bucket sort, and returns how full the buckets were
+///
+/// Splits the range from the smallest value to the largest into equal width buckets, counting
+/// sorts the items into them, and insertion sorts each bucket. When the values are spread
+/// uniformly over their range and there are about as many buckets as items, each bucket holds a
+/// few items and the sort takes expected `O(n)` time. Values bunched into a few buckets take up
+/// to `O(n^2)` time, which the returned stats show.
+///
+/// # Arguments
+///
+/// * `slice` - Slice to sort
+/// * `num_buckets` - Number of buckets to split the range into
+///
+/// # Panics
+///
+/// If `num_buckets` is 0 or the slice has a NaN, which has no place in the order
+pub fn bucket_sort(slice: &mut [f64], num_buckets: usize) -> BucketStats {
+    assert!(num_buckets > 0, "bucket sort needs at least one bucket");
+    assert!(slice.iter().all(|x| !x.is_nan()), "can't bucket sort a NaN");
+    let min = slice.iter().copied().fold(f64::INFINITY, f64::min);
+    let max = slice.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+    let width = (max - min) / num_buckets as f64;
+    let buckets: Vec<usize> = slice
+        .iter()
+        .map(|&x| if width > 0.0 { (((x - min) / width) as usize).min(num_buckets - 1) } else { 0 })
+        .collect();
+
+    let input = slice.to_vec();
+    let ends = place_by_keys(&input, &buckets, num_buckets, slice);
+    let mut sizes = Vec::with_capacity(num_buckets);
+    let mut start = 0;
+    for end in ends {
+        insertion_sort_by(&mut slice[start..end], |a, b| a.partial_cmp(b).unwrap());
+        sizes.push(end - start);
+        start = end;
+    }
+    BucketStats { sizes }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::Rng;
+
+    #[test]
+    fn test_sorts() {
+        let mut rng = Rng::new(199);
+        for n in [0, 1, 2, 100, 1000] {
+            for num_buckets in [1, 10, n.max(1)] {
+                let mut v: Vec<f64> = (0..n).map(|_| rng.next_f64() * 200.0 - 100.0).collect();
+                let mut expected = v.clone();
+                expected.sort_by(f64::total_cmp);
+                let stats = bucket_sort(&mut v, num_buckets);
+                assert_eq!(expected, v);
+                assert_eq!(num_buckets, stats.sizes.len());
+                assert_eq!(n, stats.sizes.iter().sum::<usize>());
+            }
+        }
+        // Equal values have no width to split
+        let mut v = vec![2.5; 10];
+        assert_eq!(10, bucket_sort(&mut v, 4).sizes[0]);
+    }
+
+    #[test]
+    fn test_uniform_occupancy() {
+        let mut rng = Rng::new(211);
+        let n = 10_000;
+        let mut v: Vec<f64> = (0..n).map(|_| rng.next_f64()).collect();
+        let stats = bucket_sort(&mut v, n);
+        // About 2n - 1, with about n/e buckets left empty
+        assert!(stats.sum_of_squares() < 2 * n + n / 10);
+        assert!((stats.empty_buckets() as f64 - n as f64 / std::f64::consts::E).abs() < n as f64 / 50.0);
+        assert!(stats.largest() < 10);
+    }
+
+    #[test]
+    fn test_skewed_occupancy() {
+        let mut rng = Rng::new(223);
+        let n = 10_000;
+        // Raising uniform values to a high power bunches them up near 0
+        let mut v: Vec<f64> = (0..n).map(|_| rng.next_f64().powi(8)).collect();
+        let stats = bucket_sort(&mut v, n);
+        assert!(v.windows(2).all(|w| w[0] <= w[1]));
+        assert!(stats.sum_of_squares() > 50 * n);
+        assert!(stats.largest() > n / 10);
+        assert!(stats.empty_buckets() > n / 2);
+    }
+}
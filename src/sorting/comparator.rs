@@ -0,0 +1,119 @@
+use std::cmp::Ordering;
+
+/// An ordering of items, for the ordered structures to use instead of the items' own order
+///
+/// `MaxHeap`, `SkipListMap`, and `IndexableSkipList` take a comparator, so they can order items
+/// by a key, backwards, or in any other way without wrapping every item in a newtype with its own
+/// `Ord`. `Natural` is the items' own order, `Reverse` is the opposite, and any closure
+/// `Fn(&T, &T) -> Ordering` is a comparator. The sorting functions take closures, which
+/// `as_fn` turns a comparator into.
+pub trait Comparator<T: ?Sized> {
+
+    /// Compares two items
+    fn compare(&self, a: &T, b: &T) -> Ordering;
+
+    /// Checks if the first item comes before the second
+    fn less(&self, a: &T, b: &T) -> bool {
+        self.compare(a, b) == Ordering::Less
+    }
+
+    /// Borrows the comparator as a closure, for the sorting functions
+    fn as_fn(&self) -> impl Fn(&T, &T) -> Ordering + '_ {
+        move |a, b| self.compare(a, b)
+    }
+
+    /// Turns the comparator into one that orders items the opposite way
+    fn reversed(self) -> Reversed<Self>
+    where
+        Self: Sized,
+    {
+        Reversed(self)
+    }
+}
+
+/// The natural order of items, by their `PartialOrd`
+///
+/// Items that don't compare, like `NaN`, are treated as equal, the same as the crate's
+/// `heapsort`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Natural;
+
+impl<T: PartialOrd + ?Sized> Comparator<T> for Natural {
+    fn compare(&self, a: &T, b: &T) -> Ordering {
+        a.partial_cmp(b).unwrap_or(Ordering::Equal)
+    }
+}
+
+/// The opposite of the natural order of items, largest first
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Reverse;
+
+impl<T: PartialOrd + ?Sized> Comparator<T> for Reverse {
+    fn compare(&self, a: &T, b: &T) -> Ordering {
+        Natural.compare(b, a)
+    }
+}
+
+/// The opposite of another comparator's order, made by `Comparator::reversed`
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Reversed<C>(pub C);
+
+impl<T: ?Sized, C: Comparator<T>> Comparator<T> for Reversed<C> {
+    fn compare(&self, a: &T, b: &T) -> Ordering {
+        self.0.compare(b, a)
+    }
+}
+
+/// Orders items by the natural order of a key computed from each, made by `by_key`
+#[derive(Clone, Copy, Debug)]
+pub struct ByKey<F>(F);
+
+impl<T: ?Sized, K: PartialOrd, F: Fn(&T) -> K> Comparator<T> for ByKey<F> {
+    fn compare(&self, a: &T, b: &T) -> Ordering {
+        Natural.compare(&(self.0)(a), &(self.0)(b))
+    }
+}
+
+/// Creates a comparator ordering items by a key computed from each
+///
+/// # Arguments
+///
+/// * `key` - Computes the key of an item
+pub fn by_key<T: ?Sized, K: PartialOrd, F: Fn(&T) -> K>(key: F) -> ByKey<F> {
+    ByKey(key)
+}
+
+impl<T: ?Sized, F: Fn(&T, &T) -> Ordering> Comparator<T> for F {
+    fn compare(&self, a: &T, b: &T) -> Ordering {
+        self(a, b)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::merge_sort::merge_sort_by;
+
+    #[test]
+    fn test_comparators() {
+        assert_eq!(Ordering::Less, Natural.compare(&1, &2));
+        assert_eq!(Ordering::Greater, Reverse.compare(&1, &2));
+        assert_eq!(Ordering::Greater, Reversed(Natural).compare(&1, &2));
+        assert_eq!(Ordering::Equal, Natural.compare(&f64::NAN, &1.0));
+        assert!(Natural.less("apple", "banana"));
+
+        let by_len = |a: &&str, b: &&str| a.len().cmp(&b.len());
+        assert_eq!(Ordering::Greater, by_len.compare(&"three", &"two"));
+        assert_eq!(Ordering::Less, by_len.reversed().compare(&"three", &"two"));
+        assert_eq!(Ordering::Less, by_key(|s: &&str| s.len()).compare(&"two", &"three"));
+    }
+
+    #[test]
+    fn test_as_fn() {
+        let mut words = vec!["pear", "fig", "banana", "kiwi", "apple"];
+        merge_sort_by(&mut words, by_key(|s: &&str| s.len()).as_fn());
+        assert_eq!(vec!["fig", "pear", "kiwi", "apple", "banana"], words);
+        merge_sort_by(&mut words, Reverse.as_fn());
+        assert_eq!(vec!["pear", "kiwi", "fig", "banana", "apple"], words);
+    }
+}
@@ -0,0 +1,134 @@
+use std::fmt;
+
+/// Error for an item whose key isn't less than the number of keys counting sort was given
+///
+/// `index` is the position of the first such item, and the slice is left unchanged.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct KeyOutOfRangeError {
+    pub index: usize,
+    pub key: usize,
+    pub num_keys: usize,
+}
+
+impl fmt::Display for KeyOutOfRangeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "key {} of the item at {} is outside the range 0..{}", self.key, self.index, self.num_keys)
+    }
+}
+
+impl std::error::Error for KeyOutOfRangeError {}
+
+/// Sorts a slice of small unsigned integers with counting sort
+///
+/// Counts how many times each value appears and rewrites the slice from the counts, in
+/// `O(n + k)` time and `O(n + k)` extra space for a largest value of `k - 1`, without comparing
+/// items. That beats comparison sorts when `k` is `O(n)`, like `u8`s or `u16`s, but the counts
+/// take more time and space than the items when the values are spread over a large range.
+///
+/// # Arguments
+///
+/// * `slice` - Slice to sort
+pub fn counting_sort<T: Copy + Into<usize>>(slice: &mut [T]) {
+    let keys: Vec<usize> = slice.iter().map(|&x| x.into()).collect();
+    let num_keys = keys.iter().max().map_or(0, |&k| k + 1);
+    let input = slice.to_vec();
+    place_by_keys(&input, &keys, num_keys, slice);
+}
+
+/// Sorts a slice by integer keys in the range `0..num_keys` with counting sort
+///
+/// Counts the items with each key, turns the counts into the position each key's items start at,
+/// and copies the items to their positions in order, so the sort is stable: items with equal keys
+/// keep their order. Takes `O(n + num_keys)` time and calls `key` once per item. Radix sort sorts
+/// by one digit at a time this way.
+///
+/// # Arguments
+///
+/// * `slice` - Slice to sort
+/// * `num_keys` - Every key must be less than this
+/// * `key` - Function giving an item's key
+pub fn counting_sort_by_key<T: Clone, F: FnMut(&T) -> usize>(slice: &mut [T], num_keys: usize, key: F) -> Result<(), KeyOutOfRangeError> {
+    let keys: Vec<usize> = slice.iter().map(key).collect();
+    if let Some((index, &key)) = keys.iter().enumerate().find(|(_, &key)| key >= num_keys) {
+        return Err(KeyOutOfRangeError { index, key, num_keys });
+    }
+    let input = slice.to_vec();
+    place_by_keys(&input, &keys, num_keys, slice);
+    Ok(())
+}
+
+/// Internal function to copy items into `output` in the stable order of their keys, which must
+/// all be less than `num_keys`, and return where each key's items end
+pub(crate) fn place_by_keys<T: Clone>(input: &[T], keys: &[usize], num_keys: usize, output: &mut [T]) -> Vec<usize> {
+    let mut positions = vec![0; num_keys];
+    for &key in keys {
+        positions[key] += 1;
+    }
+    let mut total = 0;
+    for position in positions.iter_mut() {
+        let count = *position;
+        *position = total;
+        total += count;
+    }
+    for (item, &key) in input.iter().zip(keys) {
+        output[positions[key]] = item.clone();
+        positions[key] += 1;
+    }
+    positions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::Rng;
+
+    #[test]
+    fn test_counting_sort() {
+        let mut rng = Rng::new(179);
+        let mut bytes: Vec<u8> = (0..1000).map(|_| rng.next_u64() as u8).collect();
+        let mut expected = bytes.clone();
+        expected.sort();
+        counting_sort(&mut bytes);
+        assert_eq!(expected, bytes);
+        let mut shorts: Vec<u16> = (0..1000).map(|_| rng.gen_range(5000) as u16).collect();
+        let mut expected = shorts.clone();
+        expected.sort();
+        counting_sort(&mut shorts);
+        assert_eq!(expected, shorts);
+        let mut empty: Vec<u8> = Vec::new();
+        counting_sort(&mut empty);
+        assert!(empty.is_empty());
+    }
+
+    #[test]
+    fn test_by_key_is_stable() {
+        let mut rng = Rng::new(181);
+        // Small signed keys shift into the range 0..21
+        let records: Vec<(i32, usize)> = (0..2000).map(|i| (rng.gen_range(21) as i32 - 10, i)).collect();
+        let mut sorted = records.clone();
+        counting_sort_by_key(&mut sorted, 21, |r| (r.0 + 10) as usize).unwrap();
+        let mut expected = records;
+        expected.sort();
+        assert_eq!(expected, sorted);
+    }
+
+    #[test]
+    fn test_key_range() {
+        let mut v = vec![3, 1, 4, 1, 5, 9, 2, 6];
+        let err = counting_sort_by_key(&mut v, 6, |&x| x).unwrap_err();
+        assert_eq!(KeyOutOfRangeError { index: 5, key: 9, num_keys: 6 }, err);
+        assert_eq!("key 9 of the item at 5 is outside the range 0..6", err.to_string());
+        assert_eq!(vec![3, 1, 4, 1, 5, 9, 2, 6], v);
+        // The key function runs once per item, however many keys there are
+        for num_keys in [10, 1 << 20] {
+            let mut calls = 0;
+            counting_sort_by_key(&mut v, num_keys, |&x| {
+                calls += 1;
+                x
+            })
+            .unwrap();
+            assert_eq!(8, calls);
+            assert_eq!(vec![1, 1, 2, 3, 4, 5, 6, 9], v);
+        }
+    }
+}
@@ -0,0 +1,219 @@
+use std::cmp::Ordering;
+
+/// Gap sequences for shell sort, each ending with a gap of 1
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Gaps {
+    /// Shell's original `n/2, n/4, ..., 1`, `O(n^2)` in the worst case
+    Shell,
+    /// Knuth's `1, 4, 13, 40, ...`, each `3h + 1`, `O(n^(3/2))` in the worst case
+    Knuth,
+    /// Ciura's experimentally tuned `1, 4, 10, 23, 57, 132, 301, 701`, extended by a factor of 2.25
+    Ciura,
+}
+
+impl Gaps {
+
+    /// Gaps to sort `n` items with, largest first and ending with 1
+    ///
+    /// # Arguments
+    ///
+    /// * `n` - Number of items to sort
+    pub fn sequence(self, n: usize) -> Vec<usize> {
+        let mut gaps = match self {
+            Gaps::Shell => std::iter::successors(Some(n / 2), |&h| Some(h / 2)).take_while(|&h| h > 0).collect(),
+            Gaps::Knuth => std::iter::successors(Some(1), |&h| Some(3 * h + 1)).take_while(|&h| h == 1 || h <= n / 3).collect(),
+            Gaps::Ciura => {
+                let mut gaps = vec![1, 4, 10, 23, 57, 132, 301, 701];
+                while *gaps.last().unwrap() < n {
+                    let next = (*gaps.last().unwrap() as f64 * 2.25) as usize;
+                    gaps.push(next);
+                }
+                gaps.retain(|&h| h == 1 || h < n);
+                gaps
+            }
+        };
+        gaps.sort_unstable_by(|a, b| b.cmp(a));
+        if gaps.last() != Some(&1) {
+            gaps.push(1);
+        }
+        gaps
+    }
+}
+
+/// Sorts a slice with insertion sort
+///
+/// Moves each item left past the larger items before it, in `O(n^2)` time, but only `O(n + d)`
+/// time for a slice with `d` pairs out of order, so it's fast on small or nearly sorted slices.
+/// The sort is stable, equal items keep their order.
+///
+/// # Arguments
+///
+/// * `slice` - Slice to sort
+pub fn insertion_sort<T: Ord>(slice: &mut [T]) {
+    insertion_sort_by(slice, T::cmp);
+}
+
+/// Sorts a slice by a comparison function with insertion sort
+///
+/// # Arguments
+///
+/// * `slice` - Slice to sort
+/// * `compare` - Function ordering two items
+pub fn insertion_sort_by<T, F: FnMut(&T, &T) -> Ordering>(slice: &mut [T], mut compare: F) {
+    gapped_insertion_sort(slice, 1, &mut compare);
+}
+
+/// Sorts a slice with selection sort
+///
+/// Swaps the smallest remaining item into place, one position at a time. Always makes
+/// `n(n - 1)/2` comparisons, but at most `n - 1` swaps, the fewest writes of any of these sorts.
+/// The sort isn't stable.
+///
+/// # Arguments
+///
+/// * `slice` - Slice to sort
+pub fn selection_sort<T: Ord>(slice: &mut [T]) {
+    selection_sort_by(slice, T::cmp);
+}
+
+/// Sorts a slice by a comparison function with selection sort
+///
+/// # Arguments
+///
+/// * `slice` - Slice to sort
+/// * `compare` - Function ordering two items
+pub fn selection_sort_by<T, F: FnMut(&T, &T) -> Ordering>(slice: &mut [T], mut compare: F) {
+    for i in 0..slice.len() {
+        let mut smallest = i;
+        for j in i + 1..slice.len() {
+            if compare(&slice[j], &slice[smallest]) == Ordering::Less {
+                smallest = j;
+            }
+        }
+        slice.swap(i, smallest);
+    }
+}
+
+/// Sorts a slice with shell sort
+///
+/// Insertion sorts the items `h` apart for each gap `h` of a decreasing sequence. The large gaps
+/// move items long distances in few swaps, so by the final insertion sort with a gap of 1 the
+/// slice is nearly sorted. The running time depends on the gaps. The sort isn't stable.
+///
+/// # Arguments
+///
+/// * `slice` - Slice to sort
+/// * `gaps` - Gap sequence to use
+pub fn shell_sort<T: Ord>(slice: &mut [T], gaps: Gaps) {
+    shell_sort_by(slice, gaps, T::cmp);
+}
+
+/// Sorts a slice by a comparison function with shell sort
+///
+/// # Arguments
+///
+/// * `slice` - Slice to sort
+/// * `gaps` - Gap sequence to use
+/// * `compare` - Function ordering two items
+pub fn shell_sort_by<T, F: FnMut(&T, &T) -> Ordering>(slice: &mut [T], gaps: Gaps, compare: F) {
+    let gaps = gaps.sequence(slice.len());
+    shell_sort_with_gaps_by(slice, &gaps, compare);
+}
+
+/// Sorts a slice by a comparison function with shell sort, using any gap sequence
+///
+/// # Arguments
+///
+/// * `slice` - Slice to sort
+/// * `gaps` - Gaps in the order to use them
+/// * `compare` - Function ordering two items
+///
+/// # Panics
+///
+/// If the last gap isn't 1, which the slice wouldn't be sorted without
+pub fn shell_sort_with_gaps_by<T, F: FnMut(&T, &T) -> Ordering>(slice: &mut [T], gaps: &[usize], mut compare: F) {
+    assert_eq!(Some(&1), gaps.last(), "the last gap must be 1");
+    for &gap in gaps {
+        gapped_insertion_sort(slice, gap, &mut compare);
+    }
+}
+
+/// Internal function to insertion sort the items `gap` apart
+fn gapped_insertion_sort<T, F: FnMut(&T, &T) -> Ordering>(slice: &mut [T], gap: usize, compare: &mut F) {
+    for i in gap..slice.len() {
+        let mut j = i;
+        while j >= gap && compare(&slice[j], &slice[j - gap]) == Ordering::Less {
+            slice.swap(j, j - gap);
+            j -= gap;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::Rng;
+    use std::cell::Cell;
+
+    #[test]
+    fn test_matches_std_sort() {
+        let mut rng = Rng::new(173);
+        for n in [0, 1, 2, 5, 100, 1000] {
+            let v: Vec<u64> = (0..n).map(|_| rng.gen_range(100)).collect();
+            let mut expected = v.clone();
+            expected.sort();
+            let mut sorted = v.clone();
+            insertion_sort(&mut sorted);
+            assert_eq!(expected, sorted);
+            let mut sorted = v.clone();
+            selection_sort(&mut sorted);
+            assert_eq!(expected, sorted);
+            for gaps in [Gaps::Shell, Gaps::Knuth, Gaps::Ciura] {
+                let mut sorted = v.clone();
+                shell_sort(&mut sorted, gaps);
+                assert_eq!(expected, sorted);
+            }
+            let mut sorted = v.clone();
+            shell_sort_with_gaps_by(&mut sorted, &[7, 3, 1], u64::cmp);
+            assert_eq!(expected, sorted);
+        }
+        // Insertion sort is stable
+        let mut records: Vec<(u64, usize)> = (0..500).map(|i| (rng.gen_range(5), i)).collect();
+        insertion_sort_by(&mut records, |a, b| a.0.cmp(&b.0));
+        assert!(records.windows(2).all(|w| w[0] < w[1]));
+    }
+
+    #[test]
+    fn test_gap_sequences() {
+        assert_eq!(vec![50, 25, 12, 6, 3, 1], Gaps::Shell.sequence(100));
+        assert_eq!(vec![13, 4, 1], Gaps::Knuth.sequence(100));
+        assert_eq!(vec![57, 23, 10, 4, 1], Gaps::Ciura.sequence(100));
+        assert_eq!(1577, Gaps::Ciura.sequence(2000)[0]);
+        for gaps in [Gaps::Shell, Gaps::Knuth, Gaps::Ciura] {
+            assert_eq!(vec![1], gaps.sequence(0));
+        }
+    }
+
+    #[test]
+    fn test_comparisons() {
+        let n = 2000;
+        let count = Cell::new(0);
+        let compare = |a: &u64, b: &u64| {
+            count.set(count.get() + 1);
+            a.cmp(b)
+        };
+        // Insertion sort on sorted input makes one comparison per item
+        let mut v: Vec<u64> = (0..n as u64).collect();
+        insertion_sort_by(&mut v, compare);
+        assert_eq!(n - 1, count.replace(0));
+        selection_sort_by(&mut v, compare);
+        assert_eq!(n * (n - 1) / 2, count.replace(0));
+        // Shell sort on reversed input is far below insertion sort's n(n - 1)/2
+        v.reverse();
+        shell_sort_by(&mut v, Gaps::Ciura, compare);
+        assert!(count.replace(0) < 20 * n);
+        v.reverse();
+        insertion_sort_by(&mut v, compare);
+        assert_eq!(n * (n - 1) / 2, count.get());
+    }
+}
@@ -0,0 +1,125 @@
+use std::cmp::Ordering;
+
+use crate::elementary_sorts::insertion_sort_by;
+use crate::max_heap::heapsort_by;
+use crate::quick_sort::{median_of_three, partition_by};
+
+/// Slices this small are insertion sorted rather than partitioned
+const SMALL_SLICE: usize = 16;
+
+/// Sorts a slice with introsort
+///
+/// Quicksorts with median-of-three pivots, which is fast on almost every input, but counts how
+/// deep the partitions go. A slice still unsorted after `2*log(n)` levels is getting bad pivots,
+/// so it's heapsorted instead, which caps the worst case at `O(n*log(n))`. Slices of 16 items or
+/// fewer are left to insertion sort, which is faster than partitioning on so few. The sort isn't
+/// stable.
+///
+/// # Arguments
+///
+/// * `slice` - Slice to sort
+pub fn intro_sort<T: Ord>(slice: &mut [T]) {
+    intro_sort_by(slice, T::cmp);
+}
+
+/// Sorts a slice by a comparison function with introsort
+///
+/// # Arguments
+///
+/// * `slice` - Slice to sort
+/// * `compare` - Function ordering two items
+pub fn intro_sort_by<T, F: FnMut(&T, &T) -> Ordering>(slice: &mut [T], mut compare: F) {
+    let depth_limit = 2 * (usize::BITS - slice.len().leading_zeros()) as usize;
+    sort(slice, depth_limit, &mut compare);
+}
+
+/// Internal function to introsort a slice, looping on the larger side of each partition
+fn sort<T, F: FnMut(&T, &T) -> Ordering>(mut slice: &mut [T], mut depth_limit: usize, compare: &mut F) {
+    while slice.len() > SMALL_SLICE {
+        if depth_limit == 0 {
+            heapsort_by(slice, &mut *compare);
+            return;
+        }
+        depth_limit -= 1;
+        let p = median_of_three(slice, compare);
+        let i = partition_by(slice, p, compare);
+        let (left, rest) = std::mem::take(&mut slice).split_at_mut(i);
+        let right = &mut rest[1..];
+        if left.len() < right.len() {
+            sort(left, depth_limit, compare);
+            slice = right;
+        } else {
+            sort(right, depth_limit, compare);
+            slice = left;
+        }
+    }
+    insertion_sort_by(slice, compare);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::Rng;
+    use std::cell::{Cell, RefCell};
+
+    #[test]
+    fn test_matches_std_sort() {
+        let mut rng = Rng::new(227);
+        for n in [0, 1, 16, 17, 100, 5000] {
+            let random: Vec<u64> = (0..n).map(|_| rng.gen_range(1000)).collect();
+            let sorted: Vec<u64> = (0..n).collect();
+            let reversed: Vec<u64> = (0..n).rev().collect();
+            let few: Vec<u64> = (0..n).map(|_| rng.gen_range(3)).collect();
+            for v in [random, sorted, reversed, few] {
+                let mut expected = v.clone();
+                expected.sort();
+                let mut v = v;
+                intro_sort(&mut v);
+                assert_eq!(expected, v);
+            }
+        }
+    }
+
+    #[test]
+    fn test_equal_items() {
+        // Every partition of equal items is lopsided, so heapsort takes over
+        let n = 5000;
+        let count = Cell::new(0);
+        let mut v = vec![1; n];
+        intro_sort_by(&mut v, |a: &u64, b| {
+            count.set(count.get() + 1);
+            a.cmp(b)
+        });
+        // n*log(n) is about 13n, and quadratic would be n/2 times n
+        assert!(count.get() < 4 * 13 * n);
+    }
+
+    #[test]
+    fn test_median_of_three_killer() {
+        // McIlroy's adversary makes plain median-of-three quicksort quadratic, see quick_sort.rs,
+        // but introsort falls back to heapsort before that costs much
+        let n = 5000;
+        let gas = n;
+        let values = RefCell::new(vec![gas; n]);
+        let (solid, candidate, count) = (Cell::new(0), Cell::new(0), Cell::new(0));
+        let mut items: Vec<usize> = (0..n).collect();
+        intro_sort_by(&mut items, |&x, &y| {
+            count.set(count.get() + 1);
+            let mut values = values.borrow_mut();
+            if values[x] == gas && values[y] == gas {
+                let frozen = if x == candidate.get() { x } else { y };
+                values[frozen] = solid.get();
+                solid.set(solid.get() + 1);
+            }
+            if values[x] == gas {
+                candidate.set(x);
+            } else if values[y] == gas {
+                candidate.set(y);
+            }
+            values[x].cmp(&values[y])
+        });
+        let values = values.into_inner();
+        assert!(items.windows(2).all(|w| values[w[0]] <= values[w[1]]));
+        assert!(count.get() < 6 * 13 * n);
+    }
+}
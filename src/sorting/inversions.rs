@@ -0,0 +1,175 @@
+use std::cmp::Ordering;
+
+use crate::binary_search::lower_bound;
+use crate::merge_sort::merge_with_buffer;
+
+/// Counts the inversions of a slice, the pairs of items out of order, with mergesort
+///
+/// An inversion is a pair of indices `i < j` with `slice[i] > slice[j]`, so a sorted slice has
+/// none and a reversed one has `n(n - 1)/2`. Checking every pair takes `O(n^2)` time. Instead,
+/// each merge of a mergesort counts the pairs it fixes: an item of the right half is inverted
+/// with every item left in the left half that's greater than it. Counting them takes one pass
+/// over the two sorted halves before they're merged, so it takes `O(n*log(n))` time like the
+/// sort[1]. Sorts a copy, leaving the slice as it is.
+///
+/// [1] Kleinberg, Jon, and Éva Tardos. "Algorithm Design." <i>Addison-Wesley<i>, 2005.
+///
+/// # Arguments
+///
+/// * `slice` - Slice to count the inversions of
+pub fn count_inversions<T: Ord + Clone>(slice: &[T]) -> u64 {
+    count_inversions_by(slice, T::cmp)
+}
+
+/// Counts the pairs of items of a slice out of order by a comparison function, with mergesort
+///
+/// # Arguments
+///
+/// * `slice` - Slice to count the inversions of
+/// * `compare` - Function ordering two items
+pub fn count_inversions_by<T: Clone, F: FnMut(&T, &T) -> Ordering>(slice: &[T], mut compare: F) -> u64 {
+    let mut sorted = slice.to_vec();
+    sort_and_count(&mut sorted, &mut Vec::with_capacity(slice.len() / 2), &mut compare)
+}
+
+/// Counts the inversions of a slice with a Fenwick tree
+///
+/// Replaces each item with its rank among the distinct items, found by binary search in a sorted
+/// copy, then scans the slice keeping a Fenwick tree of how many items of each rank it's seen.
+/// Each item is inverted with the items seen before it of greater rank, which is how many have
+/// been seen minus a prefix sum of the tree. Each prefix sum and update takes `O(log(n))` time,
+/// so it takes `O(n*log(n))` time in total, like the mergesort count, with an extra sort and
+/// searches for the ranks.
+///
+/// # Arguments
+///
+/// * `slice` - Slice to count the inversions of
+pub fn count_inversions_fenwick<T: Ord + Clone>(slice: &[T]) -> u64 {
+    let mut distinct = slice.to_vec();
+    distinct.sort();
+    distinct.dedup();
+    let mut counts = Fenwick::new(distinct.len());
+    let mut inversions = 0;
+    for (seen, item) in slice.iter().enumerate() {
+        let rank = lower_bound(&distinct, item);
+        inversions += (seen - counts.prefix_sum(rank + 1)) as u64;
+        counts.add(rank);
+    }
+    inversions
+}
+
+/// Internal function to mergesort a slice and return the number of inversions it had
+fn sort_and_count<T: Clone, F: FnMut(&T, &T) -> Ordering>(slice: &mut [T], buffer: &mut Vec<T>, compare: &mut F) -> u64 {
+    if slice.len() < 2 {
+        return 0;
+    }
+    let mid = slice.len() / 2;
+    let mut inversions = sort_and_count(&mut slice[..mid], buffer, compare) + sort_and_count(&mut slice[mid..], buffer, compare);
+    // For each item of the right half, count the items of the left half greater than it
+    let (left, right) = slice.split_at(mid);
+    let mut i = 0;
+    for item in right {
+        while i < left.len() && compare(&left[i], item) != Ordering::Greater {
+            i += 1;
+        }
+        inversions += (left.len() - i) as u64;
+    }
+    merge_with_buffer(slice, mid, buffer, compare);
+    inversions
+}
+
+/// Internal Fenwick tree of counts, where `tree[i]` holds the counts of the `i & -i` ranks ending
+/// at rank `i - 1`
+struct Fenwick {
+    tree: Vec<usize>,
+}
+
+impl Fenwick {
+
+    /// Internal function to create a Fenwick tree of `n` zero counts
+    fn new(n: usize) -> Self {
+        Fenwick { tree: vec![0; n + 1] }
+    }
+
+    /// Internal function to add one to the count of a rank
+    fn add(&mut self, rank: usize) {
+        let mut i = rank + 1;
+        while i < self.tree.len() {
+            self.tree[i] += 1;
+            i += i & i.wrapping_neg();
+        }
+    }
+
+    /// Internal function to sum the counts of the ranks below `end`
+    fn prefix_sum(&self, end: usize) -> usize {
+        let mut i = end;
+        let mut sum = 0;
+        while i > 0 {
+            sum += self.tree[i];
+            i -= i & i.wrapping_neg();
+        }
+        sum
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::Rng;
+
+    /// Internal function to count the inversions of a slice by checking every pair
+    fn count_pairs<T: Ord>(slice: &[T]) -> u64 {
+        let mut inversions = 0;
+        for i in 0..slice.len() {
+            for j in i + 1..slice.len() {
+                if slice[i] > slice[j] {
+                    inversions += 1;
+                }
+            }
+        }
+        inversions
+    }
+
+    #[test]
+    fn test_count_inversions() {
+        assert_eq!(0, count_inversions::<u64>(&[]));
+        assert_eq!(0, count_inversions(&[1, 2, 2, 3]));
+        assert_eq!(3, count_inversions(&[2, 4, 1, 3, 5]));
+        assert_eq!(45, count_inversions(&[9, 8, 7, 6, 5, 4, 3, 2, 1, 0]));
+        // Equal items aren't inverted
+        assert_eq!(0, count_inversions(&[7; 50]));
+        assert_eq!(3, count_inversions_fenwick(&[2, 4, 1, 3, 5]));
+        assert_eq!(0, count_inversions_fenwick(&[7; 50]));
+        // Counting by a reversed comparison counts the pairs in order instead
+        assert_eq!(7, count_inversions_by(&[2, 4, 1, 3, 5], |a, b| b.cmp(a)));
+    }
+
+    #[test]
+    fn test_matches_pairs() {
+        let mut rng = Rng::new(313);
+        for n in [1, 2, 3, 10, 100, 500] {
+            for range in [2, 10, 1000] {
+                let v: Vec<u64> = (0..n).map(|_| rng.gen_range(range)).collect();
+                let expected = count_pairs(&v);
+                assert_eq!(expected, count_inversions(&v));
+                assert_eq!(expected, count_inversions_fenwick(&v));
+            }
+        }
+    }
+
+    #[test]
+    fn test_large() {
+        let n: u64 = 200_000;
+        let reversed: Vec<u64> = (0..n).rev().collect();
+        // More inversions than fit in a u32
+        assert_eq!(n * (n - 1) / 2, count_inversions(&reversed));
+        assert_eq!(n * (n - 1) / 2, count_inversions_fenwick(&reversed));
+        let mut rng = Rng::new(317);
+        let random: Vec<u64> = (0..n).map(|_| rng.next_u64()).collect();
+        let inversions = count_inversions(&random);
+        assert_eq!(inversions, count_inversions_fenwick(&random));
+        // A random permutation has n(n - 1)/4 inversions on average
+        let expected = (n * (n - 1) / 4) as f64;
+        assert!((inversions as f64 - expected).abs() < 0.01 * expected, "{} inversions", inversions);
+    }
+}
@@ -0,0 +1,163 @@
+use std::cmp::Ordering;
+
+use crate::elementary_sorts::insertion_sort_by;
+use crate::quick_sort::partition_three_way_by;
+
+/// Slices this small are insertion sorted rather than partitioned
+const SMALL_SLICE: usize = 10;
+
+/// Finds the `k`th smallest item of a slice in worst case linear time, counting from 0
+///
+/// Selects like quickselect, but with a pivot guaranteed to be near the middle: the median of the
+/// medians of groups of 5 items, found recursively[1]. Half the groups have a median no greater
+/// than the pivot, each with 3 items no greater than it, so at least `3n/10` items are on either
+/// side and each partition cuts off at least that many. The time then satisfies
+/// `T(n) <= T(n/5) + T(7n/10) + O(n)`, which is `O(n)` since `1/5 + 7/10 < 1`. The constant is
+/// several times quickselect's, which is why random pivots are used in practice. Leaves the slice
+/// partitioned like `partition_at`.
+///
+/// [1] Blum, Manuel, et al. "Time Bounds for Selection." <i>Journal of Computer and System
+/// Sciences<i>, 1973.
+///
+/// # Arguments
+///
+/// * `slice` - Slice to search
+/// * `k` - Number of items smaller than the one to find, counting equal items in order
+///
+/// # Panics
+///
+/// If `k` isn't less than the length of the slice
+pub fn deterministic_select<T: Ord>(slice: &mut [T], k: usize) -> &T {
+    deterministic_partition_at(slice, k);
+    &slice[k]
+}
+
+/// Rearranges a slice so the item at `k` is the one that would be there if it were sorted, in
+/// worst case linear time
+///
+/// # Arguments
+///
+/// * `slice` - Slice to partition
+/// * `k` - Position to put in its sorted place
+///
+/// # Panics
+///
+/// If `k` isn't less than the length of the slice
+pub fn deterministic_partition_at<T: Ord>(slice: &mut [T], k: usize) {
+    deterministic_partition_at_by(slice, k, T::cmp);
+}
+
+/// Rearranges a slice so the item at `k` is the one that would be there if it were sorted by a
+/// comparison function, in worst case linear time
+///
+/// # Arguments
+///
+/// * `slice` - Slice to partition
+/// * `k` - Position to put in its sorted place
+/// * `compare` - Function ordering two items
+///
+/// # Panics
+///
+/// If `k` isn't less than the length of the slice
+pub fn deterministic_partition_at_by<T, F: FnMut(&T, &T) -> Ordering>(slice: &mut [T], k: usize, mut compare: F) {
+    assert!(k < slice.len(), "position {} is out of bounds for a slice of length {}", k, slice.len());
+    select(slice, k, &mut compare);
+}
+
+/// Internal function to put position `k` of a slice in its sorted place
+fn select<T, F: FnMut(&T, &T) -> Ordering>(slice: &mut [T], k: usize, compare: &mut F) {
+    // Position k is somewhere in slice[lo..hi], and everything outside is on the right side of it
+    let (mut lo, mut hi) = (0, slice.len());
+    loop {
+        let part = &mut slice[lo..hi];
+        if part.len() <= SMALL_SLICE {
+            insertion_sort_by(part, &mut *compare);
+            return;
+        }
+        let pivot = median_of_medians(part, compare);
+        let (lt, gt) = partition_three_way_by(part, pivot, compare);
+        if k < lo + lt {
+            hi = lo + lt;
+        } else if k >= lo + gt {
+            lo += gt;
+        } else {
+            return;
+        }
+    }
+}
+
+/// Internal function to move the medians of each group of 5 to the front of a slice, and return
+/// the index of their median
+fn median_of_medians<T, F: FnMut(&T, &T) -> Ordering>(slice: &mut [T], compare: &mut F) -> usize {
+    let groups = slice.len() / 5;
+    for g in 0..groups {
+        insertion_sort_by(&mut slice[5 * g..5 * g + 5], &mut *compare);
+        // Slot g is in a group that's already been done, or is in this group
+        slice.swap(g, 5 * g + 2);
+    }
+    select(&mut slice[..groups], groups / 2, compare);
+    groups / 2
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::quickselect::partition_at_by;
+    use crate::rng::Rng;
+    use std::cell::Cell;
+
+    #[test]
+    fn test_select() {
+        let mut rng = Rng::new(271);
+        for n in [1, 2, 10, 11, 57, 300] {
+            for range in [3, 1000] {
+                let v: Vec<u64> = (0..n).map(|_| rng.gen_range(range)).collect();
+                let mut sorted = v.clone();
+                sorted.sort();
+                for (k, expected) in sorted.iter().enumerate() {
+                    assert_eq!(expected, deterministic_select(&mut v.clone(), k));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_partition_at() {
+        let mut rng = Rng::new(277);
+        let mut v: Vec<u64> = (0..10_000).map(|_| rng.gen_range(500)).collect();
+        let mut sorted = v.clone();
+        sorted.sort();
+        for k in [0, 17, 5000, 9999] {
+            deterministic_partition_at(&mut v, k);
+            assert_eq!(sorted[k], v[k]);
+            assert!(v[..k].iter().all(|&x| x <= v[k]));
+            assert!(v[k..].iter().all(|&x| x >= v[k]));
+        }
+    }
+
+    #[test]
+    fn test_linear_worst_case() {
+        let mut rng = Rng::new(281);
+        let n = 100_000;
+        let count = Cell::new(0);
+        let compare = |a: &u64, b: &u64| {
+            count.set(count.get() + 1);
+            a.cmp(b)
+        };
+        let random: Vec<u64> = (0..n as u64).map(|_| rng.next_u64()).collect();
+        let sorted: Vec<u64> = (0..n as u64).collect();
+        let reversed: Vec<u64> = (0..n as u64).rev().collect();
+        let organ_pipe: Vec<u64> = (0..n).map(|i| i.min(n - i) as u64).collect();
+        for v in [random.clone(), sorted, reversed, organ_pipe, vec![7; n]] {
+            for k in [0, n / 2, n - 1] {
+                deterministic_partition_at_by(&mut v.clone(), k, compare);
+                assert!(count.replace(0) < 15 * n);
+            }
+        }
+        // Random pivots take a few times fewer comparisons on the same input
+        deterministic_partition_at_by(&mut random.clone(), n / 2, compare);
+        let deterministic = count.replace(0);
+        partition_at_by(&mut random.clone(), n / 2, compare);
+        assert!(count.get() < deterministic);
+    }
+}
@@ -0,0 +1,182 @@
+use std::cmp::Ordering;
+
+/// Merges the two sorted halves `slice[..mid]` and `slice[mid..]` into one sorted slice
+///
+/// Copies the left half out and merges it back with the right half, in `O(n)` time and
+/// `O(mid)` extra space. Equal items keep their order, left half first. If the last item of the
+/// left half is no greater than the first of the right, the halves are already in order and
+/// nothing is copied.
+///
+/// # Arguments
+///
+/// * `slice` - Slice whose two halves are each sorted
+/// * `mid` - Index where the right half starts
+///
+/// # Panics
+///
+/// If `mid` is greater than the length of the slice
+pub fn merge<T: Ord + Clone>(slice: &mut [T], mid: usize) {
+    merge_by(slice, mid, T::cmp);
+}
+
+/// Merges the two halves `slice[..mid]` and `slice[mid..]`, each sorted by a comparison function,
+/// into one sorted slice
+///
+/// # Arguments
+///
+/// * `slice` - Slice whose two halves are each sorted
+/// * `mid` - Index where the right half starts
+/// * `compare` - Function ordering two items
+///
+/// # Panics
+///
+/// If `mid` is greater than the length of the slice
+pub fn merge_by<T: Clone, F: FnMut(&T, &T) -> Ordering>(slice: &mut [T], mid: usize, mut compare: F) {
+    assert!(mid <= slice.len(), "mid {} is out of bounds for a slice of length {}", mid, slice.len());
+    merge_with_buffer(slice, mid, &mut Vec::new(), &mut compare);
+}
+
+/// Sorts a slice with top-down mergesort
+///
+/// Sorts each half recursively and merges them, in `O(n*log(n))` time and `O(n)` extra space.
+/// The sort is stable, equal items keep their order.
+///
+/// # Arguments
+///
+/// * `slice` - Slice to sort
+pub fn merge_sort<T: Ord + Clone>(slice: &mut [T]) {
+    merge_sort_by(slice, T::cmp);
+}
+
+/// Sorts a slice by a comparison function with top-down mergesort
+///
+/// # Arguments
+///
+/// * `slice` - Slice to sort
+/// * `compare` - Function ordering two items
+pub fn merge_sort_by<T: Clone, F: FnMut(&T, &T) -> Ordering>(slice: &mut [T], mut compare: F) {
+    sort_recursive(slice, &mut Vec::with_capacity(slice.len() / 2), &mut compare);
+}
+
+/// Sorts a slice with bottom-up mergesort
+///
+/// Merges runs of 1 item into sorted runs of 2, those into runs of 4, and so on until one run is
+/// left, in `O(n*log(n))` time. Does the same merges as top-down mergesort without recursing.
+/// The sort is stable, equal items keep their order.
+///
+/// # Arguments
+///
+/// * `slice` - Slice to sort
+pub fn bottom_up_merge_sort<T: Ord + Clone>(slice: &mut [T]) {
+    bottom_up_merge_sort_by(slice, T::cmp);
+}
+
+/// Sorts a slice by a comparison function with bottom-up mergesort
+///
+/// # Arguments
+///
+/// * `slice` - Slice to sort
+/// * `compare` - Function ordering two items
+pub fn bottom_up_merge_sort_by<T: Clone, F: FnMut(&T, &T) -> Ordering>(slice: &mut [T], mut compare: F) {
+    let n = slice.len();
+    let mut buffer = Vec::with_capacity(n / 2);
+    let mut width = 1;
+    while width < n {
+        for start in (0..n).step_by(2 * width) {
+            let end = (start + 2 * width).min(n);
+            let mid = (start + width).min(end);
+            merge_with_buffer(&mut slice[start..end], mid - start, &mut buffer, &mut compare);
+        }
+        width *= 2;
+    }
+}
+
+/// Internal function to sort a slice recursively, sharing one merge buffer
+fn sort_recursive<T: Clone, F: FnMut(&T, &T) -> Ordering>(slice: &mut [T], buffer: &mut Vec<T>, compare: &mut F) {
+    if slice.len() <= 1 {
+        return;
+    }
+    let mid = slice.len() / 2;
+    sort_recursive(&mut slice[..mid], buffer, compare);
+    sort_recursive(&mut slice[mid..], buffer, compare);
+    merge_with_buffer(slice, mid, buffer, compare);
+}
+
+/// Internal function to merge the sorted halves of a slice, copying the left half into a buffer
+pub(crate) fn merge_with_buffer<T: Clone, F: FnMut(&T, &T) -> Ordering>(slice: &mut [T], mid: usize, buffer: &mut Vec<T>, compare: &mut F) {
+    if mid == 0 || mid == slice.len() || compare(&slice[mid - 1], &slice[mid]) != Ordering::Greater {
+        return;
+    }
+    buffer.clear();
+    buffer.extend_from_slice(&slice[..mid]);
+    let (mut i, mut j, mut k) = (0, mid, 0);
+    while i < buffer.len() && j < slice.len() {
+        // Only take from the right half when it's strictly smaller, to keep the sort stable
+        if compare(&slice[j], &buffer[i]) == Ordering::Less {
+            slice[k] = slice[j].clone();
+            j += 1;
+        } else {
+            slice[k] = buffer[i].clone();
+            i += 1;
+        }
+        k += 1;
+    }
+    // What's left of the right half is already in place
+    for item in buffer.drain(i..) {
+        slice[k] = item;
+        k += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::Rng;
+
+    #[test]
+    fn test_merge() {
+        let mut v = vec![1, 4, 7, 9, 2, 3, 8];
+        merge(&mut v, 4);
+        assert_eq!(vec![1, 2, 3, 4, 7, 8, 9], v);
+        let mut v = vec![5, 6, 1, 2];
+        merge(&mut v[1..], 1);
+        assert_eq!(vec![5, 1, 2, 6], v);
+        // Empty halves
+        let mut v = vec![3, 1, 2];
+        merge(&mut v, 0);
+        merge(&mut v, 3);
+        assert_eq!(vec![3, 1, 2], v);
+    }
+
+    #[test]
+    fn test_matches_std_sort() {
+        let mut rng = Rng::new(157);
+        for n in [0, 1, 2, 3, 10, 100, 1000, 1025] {
+            let v: Vec<u64> = (0..n).map(|_| rng.gen_range(50)).collect();
+            let mut expected = v.clone();
+            expected.sort();
+            let mut top_down = v.clone();
+            merge_sort(&mut top_down);
+            assert_eq!(expected, top_down);
+            let mut bottom_up = v;
+            bottom_up_merge_sort(&mut bottom_up);
+            assert_eq!(expected, bottom_up);
+        }
+    }
+
+    #[test]
+    fn test_stable() {
+        let mut rng = Rng::new(163);
+        // Records of a key and their original position
+        let records: Vec<(u64, usize)> = (0..2000).map(|i| (rng.gen_range(10), i)).collect();
+        let mut top_down = records.clone();
+        merge_sort_by(&mut top_down, |a, b| a.0.cmp(&b.0));
+        let mut bottom_up = records.clone();
+        bottom_up_merge_sort_by(&mut bottom_up, |a, b| a.0.cmp(&b.0));
+        // A stable sort by key leaves equal keys ordered by position, like sorting by both
+        let mut expected = records;
+        expected.sort();
+        assert_eq!(expected, top_down);
+        assert_eq!(expected, bottom_up);
+    }
+}
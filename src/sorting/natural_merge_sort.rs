@@ -0,0 +1,227 @@
+use std::cmp::Ordering;
+use std::fmt;
+
+use crate::elementary_sorts::insertion_sort_by;
+use crate::merge_sort::merge_with_buffer;
+
+/// Runs a natural mergesort found and how many merges it took to combine them
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RunStats {
+    /// Length of each run in order, after short runs were extended to the minimum run length
+    pub run_lengths: Vec<usize>,
+    /// Number of pairs of runs merged
+    pub merges: usize,
+}
+
+impl RunStats {
+
+    /// Number of runs
+    pub fn num_runs(&self) -> usize {
+        self.run_lengths.len()
+    }
+
+    /// Length of the longest run
+    pub fn longest(&self) -> usize {
+        self.run_lengths.iter().copied().max().unwrap_or(0)
+    }
+}
+
+impl fmt::Display for RunStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let items: usize = self.run_lengths.iter().sum();
+        let mean = if self.run_lengths.is_empty() { 0.0 } else { items as f64 / self.num_runs() as f64 };
+        write!(f, "{} runs of {} items, mean length {:.1}, longest {}, {} merges", self.num_runs(), items, mean, self.longest(), self.merges)
+    }
+}
+
+/// Internal run on the merge stack, `len` items from `start`
+#[derive(Clone, Copy, Debug)]
+struct Run {
+    start: usize,
+    len: usize,
+}
+
+/// Sorts a slice with a natural mergesort that merges the runs already in it, and returns the
+/// runs it found
+///
+/// Scans for runs of items already in order, ascending or strictly descending, reversing the
+/// descending ones. Runs shorter than a minimum length, between 32 and 64, are extended with
+/// insertion sort. Each run is pushed on a stack, and adjacent runs are merged whenever the
+/// lengths on the stack stop shrinking geometrically, as in Timsort[1], so merges stay balanced
+/// and the sort takes `O(n*log(n))` time at worst. On input made of `r` runs it takes
+/// `O(n*log(r))` time, so a sorted or reversed slice takes `O(n)`. The sort is stable.
+///
+/// [1] Peters, Tim. "listsort.txt", CPython, 2002.
+///
+/// # Arguments
+///
+/// * `slice` - Slice to sort
+pub fn natural_merge_sort<T: Ord + Clone>(slice: &mut [T]) -> RunStats {
+    natural_merge_sort_by(slice, T::cmp)
+}
+
+/// Sorts a slice by a comparison function with a natural mergesort, and returns the runs it found
+///
+/// # Arguments
+///
+/// * `slice` - Slice to sort
+/// * `compare` - Function ordering two items
+pub fn natural_merge_sort_by<T: Clone, F: FnMut(&T, &T) -> Ordering>(slice: &mut [T], mut compare: F) -> RunStats {
+    let n = slice.len();
+    let min_run = min_run_length(n);
+    let mut stats = RunStats { run_lengths: Vec::new(), merges: 0 };
+    let mut runs: Vec<Run> = Vec::new();
+    let mut buffer = Vec::new();
+
+    let mut start = 0;
+    while start < n {
+        let mut len = find_run(&mut slice[start..], &mut compare);
+        if len < min_run {
+            len = min_run.min(n - start);
+            insertion_sort_by(&mut slice[start..start + len], &mut compare);
+        }
+        stats.run_lengths.push(len);
+        runs.push(Run { start, len });
+        start += len;
+
+        // Keep each run longer than the two above it, and each longer than the one above it
+        loop {
+            let k = runs.len();
+            let i = if k >= 3 && runs[k - 3].len <= runs[k - 2].len + runs[k - 1].len
+                || k >= 4 && runs[k - 4].len <= runs[k - 3].len + runs[k - 2].len
+            {
+                if runs[k - 3].len < runs[k - 1].len { k - 3 } else { k - 2 }
+            } else if k >= 2 && runs[k - 2].len <= runs[k - 1].len {
+                k - 2
+            } else {
+                break;
+            };
+            merge_at(slice, &mut runs, i, &mut buffer, &mut compare);
+            stats.merges += 1;
+        }
+    }
+    while runs.len() > 1 {
+        let i = runs.len() - 2;
+        merge_at(slice, &mut runs, i, &mut buffer, &mut compare);
+        stats.merges += 1;
+    }
+    stats
+}
+
+/// Internal function to compute the minimum run length for `n` items, `n` itself if it's under
+/// 64, otherwise a length between 32 and 64 that splits `n` into a power of two runs or a few less
+fn min_run_length(mut n: usize) -> usize {
+    let mut rest = 0;
+    while n >= 64 {
+        rest |= n & 1;
+        n >>= 1;
+    }
+    n + rest
+}
+
+/// Internal function to find the length of the run at the start of a slice, reversing it if it's
+/// strictly descending
+///
+/// Only strictly descending runs are reversed, so reversing never reorders equal items.
+fn find_run<T, F: FnMut(&T, &T) -> Ordering>(slice: &mut [T], compare: &mut F) -> usize {
+    if slice.len() < 2 {
+        return slice.len();
+    }
+    let mut end = 2;
+    if compare(&slice[1], &slice[0]) == Ordering::Less {
+        while end < slice.len() && compare(&slice[end], &slice[end - 1]) == Ordering::Less {
+            end += 1;
+        }
+        slice[..end].reverse();
+    } else {
+        while end < slice.len() && compare(&slice[end], &slice[end - 1]) != Ordering::Less {
+            end += 1;
+        }
+    }
+    end
+}
+
+/// Internal function to merge run `i` on the stack with run `i + 1`
+fn merge_at<T: Clone, F: FnMut(&T, &T) -> Ordering>(slice: &mut [T], runs: &mut Vec<Run>, i: usize, buffer: &mut Vec<T>, compare: &mut F) {
+    let (left, right) = (runs[i], runs[i + 1]);
+    merge_with_buffer(&mut slice[left.start..left.start + left.len + right.len], left.len, buffer, compare);
+    runs[i].len += right.len;
+    runs.remove(i + 1);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::Rng;
+    use std::cell::Cell;
+
+    #[test]
+    fn test_matches_std_sort() {
+        let mut rng = Rng::new(229);
+        for n in [0, 1, 2, 63, 64, 65, 1000, 10_000] {
+            let random: Vec<u64> = (0..n).map(|_| rng.gen_range(100)).collect();
+            let sawtooth: Vec<u64> = (0..n).map(|i| (i % 100) as u64).collect();
+            let organ_pipe: Vec<u64> = (0..n).map(|i| i.min(n - i) as u64).collect();
+            for v in [random, sawtooth, organ_pipe] {
+                let mut expected = v.clone();
+                expected.sort();
+                let mut v = v;
+                let stats = natural_merge_sort(&mut v);
+                assert_eq!(expected, v, "{}", stats);
+                assert_eq!(n, stats.run_lengths.iter().sum::<usize>());
+                assert_eq!(stats.num_runs().saturating_sub(1), stats.merges);
+            }
+        }
+        // Stable, even through reversed runs
+        let mut records: Vec<(u64, usize)> = (0..5000).map(|i| (rng.gen_range(20) / (1 + i as u64 / 500), i)).collect();
+        let mut expected = records.clone();
+        expected.sort();
+        natural_merge_sort_by(&mut records, |a, b| a.0.cmp(&b.0));
+        assert_eq!(expected, records);
+    }
+
+    #[test]
+    fn test_presorted_is_linear() {
+        let n = 100_000;
+        let count = Cell::new(0);
+        let compare = |a: &u64, b: &u64| {
+            count.set(count.get() + 1);
+            a.cmp(b)
+        };
+        let mut sorted: Vec<u64> = (0..n as u64).collect();
+        let stats = natural_merge_sort_by(&mut sorted, compare);
+        assert_eq!("1 runs of 100000 items, mean length 100000.0, longest 100000, 0 merges", stats.to_string());
+        assert_eq!(n - 1, count.replace(0));
+        sorted.reverse();
+        let stats = natural_merge_sort_by(&mut sorted, compare);
+        assert_eq!(1, stats.num_runs(), "{}", stats);
+        assert_eq!(n - 1, count.replace(0));
+        assert!(sorted.windows(2).all(|w| w[0] < w[1]));
+        // Ten sorted blocks in descending order of blocks take a few merges
+        let mut blocks: Vec<u64> = (0..n as u64).map(|i| (9 - i / 10_000) * 10_000 + i % 10_000).collect();
+        let stats = natural_merge_sort_by(&mut blocks, compare);
+        assert_eq!(vec![10_000; 10], stats.run_lengths, "{}", stats);
+        assert!(count.get() < 5 * n, "{}", stats);
+    }
+
+    #[test]
+    fn test_nearly_sorted() {
+        let mut rng = Rng::new(233);
+        let n = 100_000;
+        let count = Cell::new(0);
+        // Sorted with 10 random swaps, each breaking a run in two places
+        let mut v: Vec<u64> = (0..n as u64).collect();
+        for _ in 0..10 {
+            let (i, j) = (rng.gen_range(n as u64) as usize, rng.gen_range(n as u64) as usize);
+            v.swap(i, j);
+        }
+        let stats = natural_merge_sort_by(&mut v, |a: &u64, b| {
+            count.set(count.get() + 1);
+            a.cmp(b)
+        });
+        assert!(v.windows(2).all(|w| w[0] < w[1]));
+        // About n*log(r) comparisons for r runs, far fewer than the n*log(n), about 17n, of a full sort
+        assert!(count.get() < 6 * n, "{} comparisons, {}", count.get(), stats);
+        assert!(stats.num_runs() <= 21, "{}", stats);
+    }
+}
@@ -0,0 +1,322 @@
+use std::cmp::Ordering;
+
+use rayon::prelude::*;
+
+use crate::intro_sort::intro_sort_by;
+use crate::max_heap::sift_down_by;
+use crate::merge_sort::merge_sort_by;
+use crate::quick_sort::median_of_three;
+
+/// Slices shorter than this are sorted, merged, partitioned, or heapified on one thread, since
+/// handing work to another thread costs more than it saves on so few items
+pub const SEQUENTIAL_THRESHOLD: usize = 1 << 13;
+
+/// Sorts a slice with mergesort on rayon's threads
+///
+/// Sorts the two halves with `rayon::join`, and merges them in parallel too: the middle item of
+/// the longer half is binary searched for in the other, which splits the merge into two
+/// independent merges of the items before and after it. Parts shorter than
+/// `SEQUENTIAL_THRESHOLD` are sorted or merged sequentially. With `p` threads it takes
+/// `O(n*log(n) / p)` time plus `O(n)` to copy merged halves back. The sort is stable.
+///
+/// Runs on the current rayon thread pool, so `ThreadPool::install` picks the number of threads.
+///
+/// # Arguments
+///
+/// * `slice` - Slice to sort
+pub fn par_merge_sort<T: Ord + Clone + Send + Sync>(slice: &mut [T]) {
+    par_merge_sort_by(slice, T::cmp);
+}
+
+/// Sorts a slice by a comparison function with mergesort on rayon's threads
+///
+/// # Arguments
+///
+/// * `slice` - Slice to sort
+/// * `compare` - Function ordering two items, called from several threads
+pub fn par_merge_sort_by<T: Clone + Send + Sync, F: Fn(&T, &T) -> Ordering + Sync>(slice: &mut [T], compare: F) {
+    let mut buffer = slice.to_vec();
+    merge_sort_parts(slice, &mut buffer, &compare);
+}
+
+/// Sorts a slice with quicksort on rayon's threads
+///
+/// Partitions the slice three ways around a median-of-three pivot in parallel: each thread counts
+/// the items less than, equal to, and greater than the pivot in its chunk, the counts give every
+/// chunk its own range of each part in a buffer, and each thread copies its chunk's items there.
+/// Then the less and greater parts are sorted with `rayon::join`. Parts shorter than
+/// `SEQUENTIAL_THRESHOLD` are sorted with introsort. The sort isn't stable.
+///
+/// # Arguments
+///
+/// * `slice` - Slice to sort
+pub fn par_quick_sort<T: Ord + Clone + Send + Sync>(slice: &mut [T]) {
+    par_quick_sort_by(slice, T::cmp);
+}
+
+/// Sorts a slice by a comparison function with quicksort on rayon's threads
+///
+/// # Arguments
+///
+/// * `slice` - Slice to sort
+/// * `compare` - Function ordering two items, called from several threads
+pub fn par_quick_sort_by<T: Clone + Send + Sync, F: Fn(&T, &T) -> Ordering + Sync>(slice: &mut [T], compare: F) {
+    let mut buffer = slice.to_vec();
+    quick_sort_parts(slice, &mut buffer, &compare);
+}
+
+/// Arranges a vector into a max heap by a comparison function on rayon's threads
+///
+/// The subtrees of an array heap aren't contiguous, but row `d + l` of the array holds the `l`th
+/// rows of the `2^d` subtrees below row `d` one after another. So one pass moves each subtree
+/// into its own vector, already in heap order, the subtrees are heapified in parallel, a second
+/// pass moves them back, and the `2^d - 1` nodes above them are sifted down last. Picks `d` to
+/// make a few subtrees per thread, each of at least `SEQUENTIAL_THRESHOLD` items, and heapifies
+/// sequentially if there aren't enough items. Takes `O(n / p + log(n)^2)` time for heapifying,
+/// plus `O(n)` time to move the items.
+///
+/// # Arguments
+///
+/// * `vec` - Vector to arrange
+/// * `compare` - Function ordering two items, called from several threads, the largest by it
+///   ends up on top
+pub fn par_heapify_by<T: Send, F: Fn(&T, &T) -> Ordering + Sync>(mut vec: Vec<T>, compare: F) -> Vec<T> {
+    let n = vec.len();
+    let mut depth = 0;
+    while (1 << depth) < 4 * rayon::current_num_threads() && n >> (depth + 1) >= SEQUENTIAL_THRESHOLD {
+        depth += 1;
+    }
+    if depth == 0 {
+        heapify(&mut vec, &compare);
+        return vec;
+    }
+
+    let top = (1 << depth) - 1;
+    let mut items = vec.into_iter();
+    let mut heap: Vec<T> = items.by_ref().take(top).collect();
+    let mut subtrees: Vec<Vec<T>> = (0..=top).map(|_| Vec::with_capacity(n / (top + 1) + 1)).collect();
+    let mut row = 1;
+    while items.len() > 0 {
+        for subtree in subtrees.iter_mut() {
+            subtree.extend(items.by_ref().take(row));
+        }
+        row *= 2;
+    }
+
+    subtrees.par_iter_mut().for_each(|subtree| heapify(subtree, &compare));
+
+    heap.reserve(n - top);
+    let mut parts: Vec<_> = subtrees.into_iter().map(Vec::into_iter).collect();
+    let mut row = 1;
+    while heap.len() < n {
+        for part in parts.iter_mut() {
+            heap.extend(part.by_ref().take(row));
+        }
+        row *= 2;
+    }
+    for i in (0..top).rev() {
+        sift_down_by(&mut heap, i, &mut |a: &T, b: &T| compare(a, b));
+    }
+    heap
+}
+
+/// Internal function to arrange a slice into a max heap on one thread
+fn heapify<T, F: Fn(&T, &T) -> Ordering>(slice: &mut [T], compare: &F) {
+    for i in (0..slice.len() / 2).rev() {
+        sift_down_by(slice, i, &mut |a: &T, b: &T| compare(a, b));
+    }
+}
+
+/// Internal function to mergesort a slice with a buffer of the same length
+fn merge_sort_parts<T: Clone + Send + Sync, F: Fn(&T, &T) -> Ordering + Sync>(slice: &mut [T], buffer: &mut [T], compare: &F) {
+    if slice.len() < SEQUENTIAL_THRESHOLD {
+        merge_sort_by(slice, compare);
+        return;
+    }
+    let mid = slice.len() / 2;
+    {
+        let (left, right) = slice.split_at_mut(mid);
+        let (left_buffer, right_buffer) = buffer.split_at_mut(mid);
+        rayon::join(|| merge_sort_parts(left, left_buffer, compare), || merge_sort_parts(right, right_buffer, compare));
+    }
+    let (left, right) = slice.split_at(mid);
+    par_merge(left, right, buffer, compare);
+    slice.clone_from_slice(buffer);
+}
+
+/// Internal function to merge two sorted slices into `out`, splitting the merge across threads
+fn par_merge<T: Clone + Send + Sync, F: Fn(&T, &T) -> Ordering + Sync>(left: &[T], right: &[T], out: &mut [T], compare: &F) {
+    if out.len() < SEQUENTIAL_THRESHOLD {
+        let (mut i, mut j) = (0, 0);
+        for slot in out.iter_mut() {
+            // Take from the right only when it's strictly smaller, to keep the merge stable
+            if j < right.len() && (i == left.len() || compare(&right[j], &left[i]) == Ordering::Less) {
+                *slot = right[j].clone();
+                j += 1;
+            } else {
+                *slot = left[i].clone();
+                i += 1;
+            }
+        }
+        return;
+    }
+    // Split around the middle of the longer slice, equal items from the left going first
+    let (i, j) = if left.len() >= right.len() {
+        let i = left.len() / 2;
+        (i, right.partition_point(|x| compare(x, &left[i]) == Ordering::Less))
+    } else {
+        let j = right.len() / 2;
+        (left.partition_point(|x| compare(x, &right[j]) != Ordering::Greater), j)
+    };
+    let (low, high) = out.split_at_mut(i + j);
+    rayon::join(|| par_merge(&left[..i], &right[..j], low, compare), || par_merge(&left[i..], &right[j..], high, compare));
+}
+
+/// Internal function to quicksort a slice with a buffer of the same length
+fn quick_sort_parts<T: Clone + Send + Sync, F: Fn(&T, &T) -> Ordering + Sync>(slice: &mut [T], buffer: &mut [T], compare: &F) {
+    if slice.len() < SEQUENTIAL_THRESHOLD {
+        intro_sort_by(slice, compare);
+        return;
+    }
+    let pivot = slice[median_of_three(slice, &mut |a: &T, b: &T| compare(a, b))].clone();
+    let (lt, gt) = par_partition(slice, buffer, &pivot, rayon::current_num_threads(), compare);
+    let (left, rest) = slice.split_at_mut(lt);
+    let right = &mut rest[gt - lt..];
+    let (left_buffer, rest_buffer) = buffer.split_at_mut(lt);
+    let right_buffer = &mut rest_buffer[gt - lt..];
+    rayon::join(|| quick_sort_parts(left, left_buffer, compare), || quick_sort_parts(right, right_buffer, compare));
+}
+
+/// Internal function to partition a slice three ways around a pivot in `chunks` parallel chunks,
+/// and return the range of the items equal to it
+fn par_partition<T: Clone + Send + Sync, F: Fn(&T, &T) -> Ordering + Sync>(slice: &mut [T], buffer: &mut [T], pivot: &T, chunks: usize, compare: &F) -> (usize, usize) {
+    let chunk_len = slice.len().div_ceil(chunks.max(1)).max(1);
+    // Count the items less than and equal to the pivot in each chunk
+    let counts: Vec<(usize, usize)> = slice
+        .par_chunks(chunk_len)
+        .map(|chunk| {
+            chunk.iter().fold((0, 0), |(lt, eq), x| match compare(x, pivot) {
+                Ordering::Less => (lt + 1, eq),
+                Ordering::Equal => (lt, eq + 1),
+                Ordering::Greater => (lt, eq),
+            })
+        })
+        .collect();
+    let lt: usize = counts.iter().map(|c| c.0).sum();
+    let eq: usize = counts.iter().map(|c| c.1).sum();
+
+    // Give each chunk its own range of each part of the buffer, and copy its items there
+    let (mut less, rest) = buffer.split_at_mut(lt);
+    let (mut equal, mut greater) = rest.split_at_mut(eq);
+    let mut parts = Vec::with_capacity(counts.len());
+    for (chunk, &(chunk_lt, chunk_eq)) in slice.chunks(chunk_len).zip(&counts) {
+        let (chunk_less, rest) = std::mem::take(&mut less).split_at_mut(chunk_lt);
+        let (chunk_equal, rest_equal) = std::mem::take(&mut equal).split_at_mut(chunk_eq);
+        let (chunk_greater, rest_greater) = std::mem::take(&mut greater).split_at_mut(chunk.len() - chunk_lt - chunk_eq);
+        less = rest;
+        equal = rest_equal;
+        greater = rest_greater;
+        parts.push((chunk, chunk_less, chunk_equal, chunk_greater));
+    }
+    parts.into_par_iter().for_each(|(chunk, chunk_less, chunk_equal, chunk_greater)| {
+        let (mut i, mut j, mut k) = (0, 0, 0);
+        for x in chunk {
+            match compare(x, pivot) {
+                Ordering::Less => {
+                    chunk_less[i] = x.clone();
+                    i += 1;
+                }
+                Ordering::Equal => {
+                    chunk_equal[j] = x.clone();
+                    j += 1;
+                }
+                Ordering::Greater => {
+                    chunk_greater[k] = x.clone();
+                    k += 1;
+                }
+            }
+        }
+    });
+    slice.clone_from_slice(buffer);
+    (lt, lt + eq)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::Rng;
+
+    /// Internal function to run a closure on a rayon thread pool of each size in turn
+    fn on_pools<F: Fn() + Sync>(f: F) {
+        for threads in [1, 2, 3, 8] {
+            rayon::ThreadPoolBuilder::new().num_threads(threads).build().unwrap().install(&f);
+        }
+    }
+
+    #[test]
+    fn test_merge_sort() {
+        let mut rng = Rng::new(239);
+        for n in [0, 1, 100, SEQUENTIAL_THRESHOLD, 100_000] {
+            let v: Vec<u64> = (0..n).map(|_| rng.gen_range(1000)).collect();
+            let mut expected = v.clone();
+            expected.sort();
+            on_pools(|| {
+                let mut sorted = v.clone();
+                par_merge_sort(&mut sorted);
+                assert_eq!(expected, sorted);
+            });
+        }
+        // Stable across the parallel merges
+        let records: Vec<(u64, usize)> = (0..100_000).map(|i| (rng.gen_range(50), i)).collect();
+        let mut sorted = records.clone();
+        par_merge_sort_by(&mut sorted, |a, b| a.0.cmp(&b.0));
+        let mut expected = records;
+        expected.sort();
+        assert_eq!(expected, sorted);
+    }
+
+    #[test]
+    fn test_quick_sort() {
+        let mut rng = Rng::new(241);
+        for n in [0, 1, 100, SEQUENTIAL_THRESHOLD, 100_000] {
+            for range in [2, 1 << 40] {
+                let v: Vec<u64> = (0..n).map(|_| rng.gen_range(range)).collect();
+                let mut expected = v.clone();
+                expected.sort();
+                on_pools(|| {
+                    let mut sorted = v.clone();
+                    par_quick_sort(&mut sorted);
+                    assert_eq!(expected, sorted);
+                });
+            }
+        }
+    }
+
+    #[test]
+    fn test_partition() {
+        let mut rng = Rng::new(251);
+        let mut v: Vec<u64> = (0..50_000).map(|_| rng.gen_range(100)).collect();
+        let mut buffer = v.clone();
+        let (lt, gt) = par_partition(&mut v, &mut buffer, &40, 7, &u64::cmp);
+        assert!(v[..lt].iter().all(|&x| x < 40));
+        assert!(v[lt..gt].iter().all(|&x| x == 40));
+        assert!(v[gt..].iter().all(|&x| x > 40));
+    }
+
+    #[test]
+    fn test_heapify() {
+        let mut rng = Rng::new(571);
+        // Sizes with a full last row, a partial one, and too few items to split
+        for n in [0, 1, 100, (1 << 17) - 1, 100_000, 300_001] {
+            let v: Vec<u64> = (0..n).map(|_| rng.gen_range(1 << 20)).collect();
+            on_pools(|| {
+                let heap = par_heapify_by(v.clone(), u64::cmp);
+                assert!((1..heap.len()).all(|i| heap[(i - 1) / 2] >= heap[i]), "heap of {} items", n);
+                let (mut a, mut b) = (heap, v.clone());
+                a.sort_unstable();
+                b.sort_unstable();
+                assert_eq!(b, a);
+            });
+        }
+    }
+}
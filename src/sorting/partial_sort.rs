@@ -0,0 +1,152 @@
+use std::cmp::Ordering;
+use std::ops::Range;
+
+use crate::intro_sort::intro_sort_by;
+use crate::max_heap::MaxHeap;
+use crate::quickselect::partition_at_by;
+
+/// Puts the `k` smallest items of a slice at its front in sorted order
+///
+/// Partitions around position `k - 1` with quickselect, which leaves the `k` smallest items at
+/// the front in some order in expected `O(n)` time, then sorts just those with introsort, in
+/// `O(n + k*log(k))` time in total. The rest of the slice is left in no particular order. A `k`
+/// of at least the length sorts the whole slice.
+///
+/// # Arguments
+///
+/// * `slice` - Slice to partially sort
+/// * `k` - Number of smallest items to sort
+pub fn partial_sort<T: Ord>(slice: &mut [T], k: usize) {
+    partial_sort_by(slice, k, T::cmp);
+}
+
+/// Puts the `k` smallest items of a slice by a comparison function at its front in sorted order
+///
+/// # Arguments
+///
+/// * `slice` - Slice to partially sort
+/// * `k` - Number of smallest items to sort
+/// * `compare` - Function ordering two items
+pub fn partial_sort_by<T, F: FnMut(&T, &T) -> Ordering>(slice: &mut [T], k: usize, mut compare: F) {
+    let k = k.min(slice.len());
+    if k == 0 {
+        return;
+    }
+    partition_at_by(slice, k - 1, &mut compare);
+    intro_sort_by(&mut slice[..k - 1], compare);
+}
+
+/// Puts the items that would be in a range of positions if the slice were sorted there, in
+/// sorted order, like a page of a sorted list
+///
+/// Partitions around the start of the range, then partially sorts the rest up to its end, in
+/// expected `O(n + m*log(m))` time for a range of `m` positions. Items before the range are no
+/// greater than it and items after are no smaller, in no particular order.
+///
+/// # Arguments
+///
+/// * `slice` - Slice to partially sort
+/// * `range` - Positions to sort
+///
+/// # Panics
+///
+/// If the range ends past the end of the slice, or starts after it ends
+pub fn partial_sort_range<T: Ord>(slice: &mut [T], range: Range<usize>) {
+    assert!(range.start <= range.end && range.end <= slice.len(), "range {:?} is out of bounds for a slice of length {}", range, slice.len());
+    if range.is_empty() {
+        return;
+    }
+    partition_at_by(slice, range.start, T::cmp);
+    partial_sort(&mut slice[range.start..], range.len());
+}
+
+/// Collects the `k` smallest items of an iterator, in sorted order
+///
+/// Keeps the smallest items seen so far in a `MaxHeap` of at most `k` items, replacing its
+/// largest whenever a smaller item arrives, in `O(n*log(k))` time and `O(k)` space. Unlike
+/// `partial_sort` it never holds the whole input, so it works on streams too long to collect.
+///
+/// # Arguments
+///
+/// * `iter` - Items to choose from
+/// * `k` - Number of items to keep
+pub fn smallest_k<T: Ord, I: IntoIterator<Item = T>>(iter: I, k: usize) -> Vec<T> {
+    let mut heap = MaxHeap::new();
+    for item in iter {
+        if heap.len() < k {
+            heap.insert(item);
+        } else if heap.peek().is_some_and(|largest| item < *largest) {
+            heap.pop();
+            heap.insert(item);
+        }
+    }
+    let mut smallest = Vec::with_capacity(heap.len());
+    while let Some(item) = heap.pop() {
+        smallest.push(item);
+    }
+    smallest.reverse();
+    smallest
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::Rng;
+    use std::cell::Cell;
+
+    #[test]
+    fn test_partial_sort() {
+        let mut rng = Rng::new(283);
+        let v: Vec<u64> = (0..1000).map(|_| rng.gen_range(300)).collect();
+        let mut sorted = v.clone();
+        sorted.sort();
+        for k in [0, 1, 10, 999, 1000, 2000] {
+            let mut partial = v.clone();
+            partial_sort(&mut partial, k);
+            let k = k.min(1000);
+            assert_eq!(sorted[..k], partial[..k]);
+            partial[k..].sort();
+            assert_eq!(sorted, partial);
+        }
+        // The largest items first, for a leaderboard
+        let mut scores = v.clone();
+        partial_sort_by(&mut scores, 3, |a, b| b.cmp(a));
+        assert_eq!(sorted[997..].iter().rev().collect::<Vec<_>>(), scores[..3].iter().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_pages() {
+        let mut rng = Rng::new(293);
+        let mut v: Vec<u64> = (0..1000).map(|_| rng.next_u64()).collect();
+        let mut sorted = v.clone();
+        sorted.sort();
+        for page in [0..20, 480..500, 980..1000] {
+            partial_sort_range(&mut v, page.clone());
+            assert_eq!(sorted[page.clone()], v[page.clone()]);
+            assert!(v[..page.start].iter().all(|&x| x <= sorted[page.start]));
+        }
+        // An empty page leaves the slice as it is
+        let before = v.clone();
+        partial_sort_range(&mut v, 5..5);
+        assert_eq!(before, v);
+        let smallest = smallest_k(v.iter().copied(), 20);
+        assert_eq!(sorted[..20], smallest[..]);
+        assert_eq!(sorted, smallest_k(v.iter().copied(), 5000));
+        assert!(smallest_k(v, 0).is_empty());
+    }
+
+    #[test]
+    fn test_comparisons() {
+        let mut rng = Rng::new(307);
+        let n = 100_000;
+        let count = Cell::new(0);
+        let mut v: Vec<u64> = (0..n).map(|_| rng.next_u64()).collect();
+        partial_sort_by(&mut v, 100, |a, b| {
+            count.set(count.get() + 1);
+            a.cmp(b)
+        });
+        // Expected O(n) for the selection and only O(k*log(k)) for the sort
+        assert!(count.get() < 5 * n as usize);
+        assert!(v[..100].windows(2).all(|w| w[0] <= w[1]));
+    }
+}
@@ -0,0 +1,287 @@
+use std::cmp::Ordering;
+
+use crate::rng::Rng;
+
+/// How quicksort picks the pivot to partition a slice around
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Pivot {
+    /// The first item, quadratic on sorted and reverse sorted input
+    First,
+    /// The last item, quadratic on sorted and reverse sorted input
+    Last,
+    /// A uniformly random item, expected `O(n*log(n))` time on every input
+    Random,
+    /// The median of the first, middle, and last items, quadratic only on inputs built to defeat it
+    MedianOfThree,
+}
+
+/// Sorts a slice with quicksort
+///
+/// Picks a pivot, partitions the slice into the items less than it and the rest, and sorts both
+/// sides. Takes `O(n*log(n))` time when the pivots split the slices evenly, but `O(n^2)` when
+/// they keep landing near the ends, which the pivot strategy decides. Every item equal to the
+/// pivot lands on one side, so inputs of many equal items are quadratic too, whatever the pivot.
+/// The sort isn't stable, and recursing on the smaller side keeps the stack `O(log(n))` deep.
+///
+/// # Arguments
+///
+/// * `slice` - Slice to sort
+/// * `pivot` - How to pick each pivot
+pub fn quick_sort<T: Ord>(slice: &mut [T], pivot: Pivot) {
+    quick_sort_by(slice, pivot, T::cmp);
+}
+
+/// Sorts a slice by a comparison function with quicksort
+///
+/// # Arguments
+///
+/// * `slice` - Slice to sort
+/// * `pivot` - How to pick each pivot
+/// * `compare` - Function ordering two items
+pub fn quick_sort_by<T, F: FnMut(&T, &T) -> Ordering>(slice: &mut [T], pivot: Pivot, mut compare: F) {
+    sort(slice, pivot, &mut Rng::from_entropy(), &mut compare, false);
+}
+
+/// Sorts a slice with quicksort, partitioning three ways
+///
+/// Splits each slice into the items less than, equal to, and greater than the pivot, and only
+/// recurses on the less and greater parts. Items equal to the pivot are done after one partition,
+/// so a slice of `k` distinct values takes `O(n*k)` time at worst, and all equal items take `O(n)`.
+///
+/// # Arguments
+///
+/// * `slice` - Slice to sort
+/// * `pivot` - How to pick each pivot
+pub fn three_way_quick_sort<T: Ord>(slice: &mut [T], pivot: Pivot) {
+    three_way_quick_sort_by(slice, pivot, T::cmp);
+}
+
+/// Sorts a slice by a comparison function with quicksort, partitioning three ways
+///
+/// # Arguments
+///
+/// * `slice` - Slice to sort
+/// * `pivot` - How to pick each pivot
+/// * `compare` - Function ordering two items
+pub fn three_way_quick_sort_by<T, F: FnMut(&T, &T) -> Ordering>(slice: &mut [T], pivot: Pivot, mut compare: F) {
+    sort(slice, pivot, &mut Rng::from_entropy(), &mut compare, true);
+}
+
+/// Partitions a slice around the item at an index, and returns the index it ends up at
+///
+/// Afterwards the items before the pivot are less than it, and the items after are no less.
+///
+/// # Arguments
+///
+/// * `slice` - Slice to partition
+/// * `pivot` - Index of the item to partition around
+///
+/// # Panics
+///
+/// If `pivot` isn't less than the length of the slice
+pub fn partition<T: Ord>(slice: &mut [T], pivot: usize) -> usize {
+    partition_by(slice, pivot, &mut T::cmp)
+}
+
+/// Partitions a slice into the items less than, equal to, and greater than the item at an index,
+/// and returns the range of the equal items
+///
+/// # Arguments
+///
+/// * `slice` - Slice to partition
+/// * `pivot` - Index of the item to partition around
+///
+/// # Panics
+///
+/// If `pivot` isn't less than the length of the slice
+pub fn partition_three_way<T: Ord>(slice: &mut [T], pivot: usize) -> (usize, usize) {
+    partition_three_way_by(slice, pivot, &mut T::cmp)
+}
+
+/// Internal function to quicksort a slice, looping on the larger side of each partition
+fn sort<T, F: FnMut(&T, &T) -> Ordering>(mut slice: &mut [T], pivot: Pivot, rng: &mut Rng, compare: &mut F, three_way: bool) {
+    while slice.len() > 1 {
+        let p = choose_pivot(slice, pivot, rng, compare);
+        let (lt, gt) = if three_way {
+            partition_three_way_by(slice, p, compare)
+        } else {
+            let i = partition_by(slice, p, compare);
+            (i, i + 1)
+        };
+        let (left, rest) = std::mem::take(&mut slice).split_at_mut(lt);
+        let right = &mut rest[gt - lt..];
+        if left.len() < right.len() {
+            sort(left, pivot, rng, compare, three_way);
+            slice = right;
+        } else {
+            sort(right, pivot, rng, compare, three_way);
+            slice = left;
+        }
+    }
+}
+
+/// Internal function to pick the index of a pivot in a non-empty slice
+pub(crate) fn choose_pivot<T, F: FnMut(&T, &T) -> Ordering>(slice: &[T], pivot: Pivot, rng: &mut Rng, compare: &mut F) -> usize {
+    let last = slice.len() - 1;
+    match pivot {
+        Pivot::First => 0,
+        Pivot::Last => last,
+        Pivot::Random => rng.gen_range(slice.len() as u64) as usize,
+        Pivot::MedianOfThree => median_of_three(slice, compare),
+    }
+}
+
+/// Internal function to pick the index of the median of the first, middle, and last items of a
+/// non-empty slice
+pub(crate) fn median_of_three<T, F: FnMut(&T, &T) -> Ordering>(slice: &[T], compare: &mut F) -> usize {
+    if slice.len() < 3 {
+        return 0;
+    }
+    let (a, b, c) = (0, (slice.len() - 1) / 2, slice.len() - 1);
+    let mut less = |x: usize, y: usize| compare(&slice[x], &slice[y]) == Ordering::Less;
+    if less(a, b) {
+        if less(b, c) { b } else if less(a, c) { c } else { a }
+    } else if less(a, c) {
+        a
+    } else if less(b, c) {
+        c
+    } else {
+        b
+    }
+}
+
+/// Internal function to partition a slice around the item at an index with Lomuto's scheme
+pub(crate) fn partition_by<T, F: FnMut(&T, &T) -> Ordering>(slice: &mut [T], pivot: usize, compare: &mut F) -> usize {
+    let last = slice.len() - 1;
+    slice.swap(pivot, last);
+    let mut store = 0;
+    for i in 0..last {
+        if compare(&slice[i], &slice[last]) == Ordering::Less {
+            slice.swap(i, store);
+            store += 1;
+        }
+    }
+    slice.swap(store, last);
+    store
+}
+
+/// Internal function to partition a slice three ways around the item at an index, with
+/// Dijkstra's Dutch national flag scheme
+pub(crate) fn partition_three_way_by<T, F: FnMut(&T, &T) -> Ordering>(slice: &mut [T], pivot: usize, compare: &mut F) -> (usize, usize) {
+    slice.swap(0, pivot);
+    // Items in [lt, i) equal the pivot, so slice[lt] is always one to compare with
+    let (mut lt, mut i, mut gt) = (0, 1, slice.len());
+    while i < gt {
+        match compare(&slice[i], &slice[lt]) {
+            Ordering::Less => {
+                slice.swap(lt, i);
+                lt += 1;
+                i += 1;
+            }
+            Ordering::Greater => {
+                gt -= 1;
+                slice.swap(i, gt);
+            }
+            Ordering::Equal => i += 1,
+        }
+    }
+    (lt, gt)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::{Cell, RefCell};
+
+    const PIVOTS: [Pivot; 4] = [Pivot::First, Pivot::Last, Pivot::Random, Pivot::MedianOfThree];
+
+    /// Counts the comparisons a quicksort makes on a slice
+    fn comparisons(mut v: Vec<u64>, pivot: Pivot, three_way: bool) -> usize {
+        let count = Cell::new(0);
+        let compare = |a: &u64, b: &u64| {
+            count.set(count.get() + 1);
+            a.cmp(b)
+        };
+        if three_way {
+            three_way_quick_sort_by(&mut v, pivot, compare);
+        } else {
+            quick_sort_by(&mut v, pivot, compare);
+        }
+        assert!(v.windows(2).all(|w| w[0] <= w[1]));
+        count.get()
+    }
+
+    #[test]
+    fn test_matches_std_sort() {
+        let mut rng = Rng::new(167);
+        for n in [0, 1, 2, 3, 10, 100, 1000] {
+            for range in [3, 1000] {
+                let v: Vec<u64> = (0..n).map(|_| rng.gen_range(range)).collect();
+                let mut expected = v.clone();
+                expected.sort();
+                for pivot in PIVOTS {
+                    let mut sorted = v.clone();
+                    quick_sort(&mut sorted, pivot);
+                    assert_eq!(expected, sorted);
+                    let mut sorted = v.clone();
+                    three_way_quick_sort(&mut sorted, pivot);
+                    assert_eq!(expected, sorted);
+                }
+            }
+        }
+        let mut v = vec![5, 1, 4, 1, 5, 9, 2, 6];
+        let i = partition(&mut v, 2);
+        assert_eq!(4, v[i]);
+        assert!(v[..i].iter().all(|&x| x < 4) && v[i..].iter().all(|&x| x >= 4));
+        let (lt, gt) = partition_three_way(&mut v, 0);
+        assert!(v[lt..gt].iter().all(|&x| x == v[lt]));
+    }
+
+    #[test]
+    fn test_adversarial_inputs() {
+        let n = 2000;
+        let quadratic = n * (n - 1) / 2;
+        let sorted: Vec<u64> = (0..n as u64).collect();
+        // Sorted input makes the first or last item the smallest or largest every time
+        assert_eq!(quadratic, comparisons(sorted.clone(), Pivot::First, false));
+        assert_eq!(quadratic, comparisons(sorted.clone(), Pivot::Last, false));
+        assert!(comparisons(sorted.clone(), Pivot::MedianOfThree, false) < 30 * n);
+        assert!(comparisons(sorted.clone(), Pivot::Random, false) < 30 * n);
+        // Two-way partitioning is quadratic on equal items with any pivot, three-way is linear
+        for pivot in PIVOTS {
+            assert!(comparisons(vec![7; n], pivot, false) >= quadratic);
+            assert!(comparisons(vec![7; n], pivot, true) < 2 * n);
+        }
+    }
+
+    #[test]
+    fn test_median_of_three_killer() {
+        // McIlroy's adversary decides the order of the items as they are compared, freezing an
+        // undecided item only when it must and keeping the pivot candidate undecided and large
+        let n = 2000;
+        let gas = n;
+        let values = RefCell::new(vec![gas; n]);
+        let (solid, candidate, count) = (Cell::new(0), Cell::new(0), Cell::new(0));
+        let mut items: Vec<usize> = (0..n).collect();
+        quick_sort_by(&mut items, Pivot::MedianOfThree, |&x, &y| {
+            count.set(count.get() + 1);
+            let mut values = values.borrow_mut();
+            if values[x] == gas && values[y] == gas {
+                let frozen = if x == candidate.get() { x } else { y };
+                values[frozen] = solid.get();
+                solid.set(solid.get() + 1);
+            }
+            if values[x] == gas {
+                candidate.set(x);
+            } else if values[y] == gas {
+                candidate.set(y);
+            }
+            values[x].cmp(&values[y])
+        });
+        assert!(count.get() > n * n / 8);
+        // Replaying the order the adversary settled on is just as slow, while a random pivot isn't
+        let killer: Vec<u64> = values.into_inner().into_iter().map(|v| v as u64).collect();
+        assert!(comparisons(killer.clone(), Pivot::MedianOfThree, false) > n * n / 8);
+        assert!(comparisons(killer, Pivot::Random, false) < 30 * n);
+    }
+}
@@ -0,0 +1,137 @@
+use std::cmp::Ordering;
+
+use crate::quick_sort::partition_three_way_by;
+use crate::rng::Rng;
+
+/// Finds the `k`th smallest item of a slice with quickselect, counting from 0
+///
+/// Partitions the slice like quicksort around a random pivot, but only continues into the side
+/// holding position `k`, in expected `O(n)` time: each partition is expected to shrink the slice
+/// by a constant fraction, so the work adds up like a geometric series. Any pivot can still be
+/// unlucky, so the worst case is `O(n^2)`. Leaves the slice partitioned like `partition_at`.
+///
+/// # Arguments
+///
+/// * `slice` - Slice to search
+/// * `k` - Number of items smaller than the one to find, counting equal items in order
+///
+/// # Panics
+///
+/// If `k` isn't less than the length of the slice
+pub fn quickselect<T: Ord>(slice: &mut [T], k: usize) -> &T {
+    partition_at(slice, k);
+    &slice[k]
+}
+
+/// Rearranges a slice so the item at `k` is the one that would be there if it were sorted, with
+/// no greater items before it and no smaller items after it
+///
+/// Like `select_nth_unstable` in the stdlib. The items on either side of `k` are left in no
+/// particular order.
+///
+/// # Arguments
+///
+/// * `slice` - Slice to partition
+/// * `k` - Position to put in its sorted place
+///
+/// # Panics
+///
+/// If `k` isn't less than the length of the slice
+pub fn partition_at<T: Ord>(slice: &mut [T], k: usize) {
+    partition_at_by(slice, k, T::cmp);
+}
+
+/// Rearranges a slice so the item at `k` is the one that would be there if it were sorted by a
+/// comparison function
+///
+/// # Arguments
+///
+/// * `slice` - Slice to partition
+/// * `k` - Position to put in its sorted place
+/// * `compare` - Function ordering two items
+///
+/// # Panics
+///
+/// If `k` isn't less than the length of the slice
+pub fn partition_at_by<T, F: FnMut(&T, &T) -> Ordering>(slice: &mut [T], k: usize, mut compare: F) {
+    assert!(k < slice.len(), "position {} is out of bounds for a slice of length {}", k, slice.len());
+    let mut rng = Rng::from_entropy();
+    // Position k is somewhere in slice[lo..hi], and everything outside is on the right side of it
+    let (mut lo, mut hi) = (0, slice.len());
+    while hi - lo > 1 {
+        let part = &mut slice[lo..hi];
+        let pivot = rng.gen_range(part.len() as u64) as usize;
+        let (lt, gt) = partition_three_way_by(part, pivot, &mut compare);
+        if k < lo + lt {
+            hi = lo + lt;
+        } else if k >= lo + gt {
+            lo += gt;
+        } else {
+            return;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+
+    #[test]
+    fn test_quickselect() {
+        let mut rng = Rng::new(257);
+        for n in [1, 2, 10, 101] {
+            for range in [3, 1000] {
+                let v: Vec<u64> = (0..n).map(|_| rng.gen_range(range)).collect();
+                let mut sorted = v.clone();
+                sorted.sort();
+                for (k, expected) in sorted.iter().enumerate() {
+                    assert_eq!(expected, quickselect(&mut v.clone(), k));
+                }
+            }
+        }
+        assert_eq!(&5, quickselect(&mut [5], 0));
+    }
+
+    #[test]
+    fn test_partition_at() {
+        let mut rng = Rng::new(263);
+        let mut v: Vec<u64> = (0..10_000).map(|_| rng.gen_range(500)).collect();
+        let mut sorted = v.clone();
+        sorted.sort();
+        for k in [0, 17, 5000, 9999] {
+            partition_at(&mut v, k);
+            assert_eq!(sorted[k], v[k]);
+            assert!(v[..k].iter().all(|&x| x <= v[k]));
+            assert!(v[k..].iter().all(|&x| x >= v[k]));
+        }
+        // Selecting by a reversed comparison finds the largest items
+        partition_at_by(&mut v, 0, |a, b| b.cmp(a));
+        assert_eq!(sorted[9999], v[0]);
+    }
+
+    #[test]
+    fn test_expected_linear() {
+        let mut rng = Rng::new(269);
+        let n = 100_000;
+        let count = Cell::new(0);
+        let runs = 10;
+        for _ in 0..runs {
+            let mut v: Vec<u64> = (0..n as u64).map(|_| rng.next_u64()).collect();
+            partition_at_by(&mut v, n / 2, |a, b| {
+                count.set(count.get() + 1);
+                a.cmp(b)
+            });
+        }
+        // Finding the median takes about 3.4n comparisons on average
+        assert!(count.get() / runs < 5 * n);
+        // A sorted slice is no harder for a random pivot
+        let mut sorted: Vec<u64> = (0..n as u64).collect();
+        count.set(0);
+        partition_at_by(&mut sorted, n / 2, |a, b| {
+            count.set(count.get() + 1);
+            a.cmp(b)
+        });
+        assert!(count.get() < 10 * n);
+    }
+}
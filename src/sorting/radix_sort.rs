@@ -0,0 +1,210 @@
+use crate::counting_sort::place_by_keys;
+use crate::elementary_sorts::insertion_sort_by;
+
+/// Number of bits per digit that suits most inputs, a radix of 256
+pub const DEFAULT_RADIX_BITS: u32 = 8;
+
+/// Buckets this small are insertion sorted rather than split by another digit
+const SMALL_BUCKET: usize = 32;
+
+/// Item radix sort can sort, by an unsigned integer key in the same order as the items
+///
+/// Implemented for the unsigned integer and floating point types.
+pub trait RadixKey {
+
+    /// Number of significant bits in a key
+    const BITS: u32;
+
+    /// Key that sorts in the same order as the item
+    fn radix_key(&self) -> u64;
+}
+
+macro_rules! impl_radix_key {
+    ($($t:ty),*) => {
+        $(
+            impl RadixKey for $t {
+                const BITS: u32 = <$t>::BITS;
+
+                fn radix_key(&self) -> u64 {
+                    *self as u64
+                }
+            }
+        )*
+    };
+}
+
+impl_radix_key!(u8, u16, u32, u64, usize);
+
+/// Floats compare like their bits as signed magnitudes: flipping every bit of a negative float, and
+/// just the sign bit of a positive one, makes the bits compare as unsigned integers in the order of
+/// the floats. It's the order of `total_cmp`, `-0.0` before `0.0` and NaNs at the ends.
+impl RadixKey for f32 {
+    const BITS: u32 = 32;
+
+    fn radix_key(&self) -> u64 {
+        let bits = self.to_bits();
+        (if bits >> 31 == 1 { !bits } else { bits | 1 << 31 }) as u64
+    }
+}
+
+impl RadixKey for f64 {
+    const BITS: u32 = 64;
+
+    fn radix_key(&self) -> u64 {
+        let bits = self.to_bits();
+        if bits >> 63 == 1 { !bits } else { bits | 1 << 63 }
+    }
+}
+
+/// Sorts a slice with least significant digit first radix sort
+///
+/// Splits the keys into digits of `radix_bits` bits and counting sorts by each digit, starting
+/// with the lowest. Counting sort is stable, so after sorting by a digit the items are in order by
+/// the digits sorted so far. With `d` digits in a radix of `r` it takes `O(d(n + r))` time, which
+/// for fixed size keys is `O(n)`. Digits above the largest key's highest set bit are skipped.
+/// The sort is stable.
+///
+/// # Arguments
+///
+/// * `slice` - Slice to sort
+/// * `radix_bits` - Number of bits per digit
+///
+/// # Panics
+///
+/// If `radix_bits` isn't between 1 and 16
+pub fn lsd_radix_sort<T: RadixKey + Clone>(slice: &mut [T], radix_bits: u32) {
+    assert!((1..=16).contains(&radix_bits), "radix_bits must be between 1 and 16, not {}", radix_bits);
+    let significant = 64 - slice.iter().fold(0, |all, x| all | x.radix_key()).leading_zeros();
+    let passes = significant.div_ceil(radix_bits);
+    let mask = (1 << radix_bits) - 1;
+
+    // Alternate between sorting from the slice into a buffer and back
+    let mut buffer = slice.to_vec();
+    for pass in 0..passes {
+        let (input, output) = if pass % 2 == 0 { (&*slice, &mut buffer[..]) } else { (&buffer[..], &mut *slice) };
+        let digits: Vec<usize> = input.iter().map(|x| (x.radix_key() >> (pass * radix_bits) & mask) as usize).collect();
+        place_by_keys(input, &digits, 1 << radix_bits, output);
+    }
+    if passes % 2 == 1 {
+        slice.clone_from_slice(&buffer);
+    }
+}
+
+/// Sorts a slice with most significant digit first radix sort
+///
+/// Counting sorts by the highest digit, splitting the items into a bucket per digit value, then
+/// sorts each bucket by the next digit, recursively. Buckets of one item, or with no digits left,
+/// are done, so keys that differ in their first few digits finish early, and small buckets are
+/// insertion sorted instead of split further. Takes `O(d(n + r))` time at worst for `d` digits in
+/// a radix of `r`. The sort is stable.
+///
+/// # Arguments
+///
+/// * `slice` - Slice to sort
+/// * `radix_bits` - Number of bits per digit
+///
+/// # Panics
+///
+/// If `radix_bits` isn't between 1 and 16
+pub fn msd_radix_sort<T: RadixKey + Clone>(slice: &mut [T], radix_bits: u32) {
+    assert!((1..=16).contains(&radix_bits), "radix_bits must be between 1 and 16, not {}", radix_bits);
+    let digits = T::BITS.div_ceil(radix_bits);
+    let mut buffer = slice.to_vec();
+    msd(slice, &mut buffer, (digits - 1) * radix_bits, radix_bits);
+}
+
+/// Internal function to sort a bucket by the digit at `shift` and then the digits below it
+fn msd<T: RadixKey + Clone>(slice: &mut [T], buffer: &mut [T], shift: u32, radix_bits: u32) {
+    if slice.len() <= SMALL_BUCKET {
+        insertion_sort_by(slice, |a, b| a.radix_key().cmp(&b.radix_key()));
+        return;
+    }
+    let mask = (1 << radix_bits) - 1;
+    let digits: Vec<usize> = slice.iter().map(|x| (x.radix_key() >> shift & mask) as usize).collect();
+    let buffer = &mut buffer[..slice.len()];
+    buffer.clone_from_slice(slice);
+    let ends = place_by_keys(buffer, &digits, 1 << radix_bits, slice);
+    if shift == 0 {
+        return;
+    }
+    let mut start = 0;
+    for end in ends {
+        if end - start > 1 {
+            msd(&mut slice[start..end], &mut buffer[start..end], shift - radix_bits, radix_bits);
+        }
+        start = end;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::Rng;
+
+    #[test]
+    fn test_integers() {
+        let mut rng = Rng::new(191);
+        for n in [0, 1, 31, 33, 1000, 5000] {
+            let v: Vec<u64> = (0..n).map(|_| rng.next_u64() >> rng.gen_range(64)).collect();
+            let mut expected = v.clone();
+            expected.sort();
+            for radix_bits in [1, 4, 8, 11, 16] {
+                let mut sorted = v.clone();
+                lsd_radix_sort(&mut sorted, radix_bits);
+                assert_eq!(expected, sorted);
+                let mut sorted = v.clone();
+                msd_radix_sort(&mut sorted, radix_bits);
+                assert_eq!(expected, sorted);
+            }
+        }
+        let mut bytes: Vec<u8> = (0..300).map(|_| rng.next_u64() as u8).collect();
+        let mut expected = bytes.clone();
+        expected.sort();
+        msd_radix_sort(&mut bytes, 3);
+        assert_eq!(expected, bytes);
+    }
+
+    #[test]
+    fn test_floats() {
+        let mut rng = Rng::new(193);
+        let mut v: Vec<f64> = (0..2000).map(|_| (rng.next_f64() - 0.5) * 1e6).collect();
+        v.extend([0.0, -0.0, f64::INFINITY, f64::NEG_INFINITY, f64::MIN_POSITIVE, -f64::MIN_POSITIVE, 1e-310]);
+        let mut expected = v.clone();
+        expected.sort_by(f64::total_cmp);
+        let mut sorted = v.clone();
+        lsd_radix_sort(&mut sorted, DEFAULT_RADIX_BITS);
+        assert!(expected.iter().zip(&sorted).all(|(a, b)| a.to_bits() == b.to_bits()));
+        let mut sorted = v;
+        msd_radix_sort(&mut sorted, DEFAULT_RADIX_BITS);
+        assert!(expected.iter().zip(&sorted).all(|(a, b)| a.to_bits() == b.to_bits()));
+
+        let mut singles: Vec<f32> = (0..1000).map(|_| rng.next_f64() as f32 - 0.5).collect();
+        let mut expected = singles.clone();
+        expected.sort_by(f32::total_cmp);
+        lsd_radix_sort(&mut singles, 11);
+        assert_eq!(expected, singles);
+    }
+
+    #[test]
+    fn test_stable() {
+        #[derive(Clone, Debug, PartialEq)]
+        struct Record(u16, usize);
+        impl RadixKey for Record {
+            const BITS: u32 = 16;
+
+            fn radix_key(&self) -> u64 {
+                self.0 as u64
+            }
+        }
+        let mut rng = Rng::new(197);
+        let records: Vec<Record> = (0..3000).map(|i| Record(rng.gen_range(300) as u16, i)).collect();
+        let mut expected = records.clone();
+        expected.sort_by_key(|r| r.0);
+        let mut sorted = records.clone();
+        lsd_radix_sort(&mut sorted, 4);
+        assert_eq!(expected, sorted);
+        let mut sorted = records;
+        msd_radix_sort(&mut sorted, 4);
+        assert_eq!(expected, sorted);
+    }
+}
@@ -0,0 +1,320 @@
+use std::cmp::Ordering;
+use std::iter::{FusedIterator, Peekable};
+
+use crate::comparator::Reverse;
+use crate::max_heap::MaxHeap;
+
+/// Lazy iterator over the items of two sorted iterators in sorted order, made by `merge_sorted`
+pub struct MergeSorted<A: Iterator, B: Iterator<Item = A::Item>> {
+    a: Peekable<A>,
+    b: Peekable<B>,
+}
+
+/// Merges two sorted sequences into one lazy sorted iterator
+///
+/// Takes the smaller of the next items of the two each step, like the merge of mergesort, in
+/// `O(1)` time per item and no extra space. Equal items come out first from `a`, so the merge is
+/// stable. For more than two sequences `merge_k_sorted` keeps a heap of their next items instead,
+/// but with two a single comparison is enough.
+///
+/// # Arguments
+///
+/// * `a` - First sorted sequence
+/// * `b` - Second sorted sequence
+pub fn merge_sorted<T: Ord, A: IntoIterator<Item = T>, B: IntoIterator<Item = T>>(a: A, b: B) -> MergeSorted<A::IntoIter, B::IntoIter> {
+    MergeSorted { a: a.into_iter().peekable(), b: b.into_iter().peekable() }
+}
+
+/// Merges two sorted slices into a new sorted `Vec`
+///
+/// # Arguments
+///
+/// * `a` - First sorted slice
+/// * `b` - Second sorted slice
+pub fn merge_sorted_vec<T: Ord + Clone>(a: &[T], b: &[T]) -> Vec<T> {
+    let mut merged = Vec::with_capacity(a.len() + b.len());
+    merged.extend(merge_sorted(a, b).cloned());
+    merged
+}
+
+impl<T: Ord, A: Iterator<Item = T>, B: Iterator<Item = T>> Iterator for MergeSorted<A, B> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        match (self.a.peek(), self.b.peek()) {
+            // Only take from b when it's strictly smaller, to keep the merge stable
+            (Some(x), Some(y)) if y < x => self.b.next(),
+            (Some(_), _) => self.a.next(),
+            (None, _) => self.b.next(),
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (a_low, a_high) = self.a.size_hint();
+        let (b_low, b_high) = self.b.size_hint();
+        let high = a_high.zip(b_high).and_then(|(a, b)| a.checked_add(b));
+        (a_low.saturating_add(b_low), high)
+    }
+}
+
+impl<T: Ord, A: FusedIterator<Item = T>, B: FusedIterator<Item = T>> FusedIterator for MergeSorted<A, B> {}
+
+/// Lazy iterator over the items of many sorted iterators in sorted order, made by
+/// `merge_k_sorted`
+pub struct MergeKSorted<I: Iterator> {
+    iters: Vec<I>,
+    heads: MaxHeap<(I::Item, usize), Reverse>,
+}
+
+/// Merges any number of sorted sequences into one lazy sorted iterator
+///
+/// Keeps a min-heap of the next item of each sequence, with the index of the sequence it came
+/// from. Each step pops the smallest and replaces it with the next item of the same sequence, so
+/// `k` sequences take `O(log(k))` time per item and `O(k)` space. Ties are broken by the index
+/// of the sequence, so equal items come out in the order of their sequences and the merge is
+/// stable. Only the first item of each sequence is taken up front.
+///
+/// # Arguments
+///
+/// * `iters` - Sorted sequences
+pub fn merge_k_sorted<T: Ord, I: IntoIterator<Item = T>, J: IntoIterator<Item = I>>(iters: J) -> MergeKSorted<I::IntoIter> {
+    let mut iters: Vec<I::IntoIter> = iters.into_iter().map(IntoIterator::into_iter).collect();
+    let heads = iters.iter_mut().enumerate().filter_map(|(i, iter)| iter.next().map(|x| (x, i))).collect();
+    MergeKSorted { iters, heads: MaxHeap::from_vec_with_comparator(heads, Reverse) }
+}
+
+impl<T: Ord, I: Iterator<Item = T>> Iterator for MergeKSorted<I> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        let (x, i) = self.heads.pop()?;
+        if let Some(y) = self.iters[i].next() {
+            self.heads.insert((y, i));
+        }
+        Some(x)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let heads = self.heads.len();
+        self.iters.iter().map(Iterator::size_hint).fold((heads, Some(heads)), |(low, high), (l, h)| {
+            (low.saturating_add(l), high.zip(h).and_then(|(a, b)| a.checked_add(b)))
+        })
+    }
+}
+
+// A sequence is only read again after its head is popped, so an exhausted one is never read again
+impl<T: Ord, I: Iterator<Item = T>> FusedIterator for MergeKSorted<I> {}
+
+/// Lazy iterator over a set operation on two sorted iterators, made by `sorted_union`,
+/// `sorted_intersection`, `sorted_difference`, and `sorted_symmetric_difference`
+pub struct SortedSetOperation<A: Iterator, B: Iterator<Item = A::Item>> {
+    a: Peekable<A>,
+    b: Peekable<B>,
+    only_a: bool,
+    both: bool,
+    only_b: bool,
+}
+
+/// Iterates over the items in either of two sorted sequences, in sorted order
+///
+/// Walks both sequences like a merge, matching equal items one to one, in `O(n + m)` time. An
+/// item in both comes out once, from `a`. With repeated items it's a multiset union: an item `i`
+/// times in `a` and `j` times in `b` comes out `max(i, j)` times.
+///
+/// # Arguments
+///
+/// * `a` - First sorted sequence
+/// * `b` - Second sorted sequence
+pub fn sorted_union<T: Ord, A: IntoIterator<Item = T>, B: IntoIterator<Item = T>>(a: A, b: B) -> SortedSetOperation<A::IntoIter, B::IntoIter> {
+    SortedSetOperation::new(a, b, true, true, true)
+}
+
+/// Iterates over the items in both of two sorted sequences, in sorted order
+///
+/// An item `i` times in `a` and `j` times in `b` comes out `min(i, j)` times, from `a`.
+///
+/// # Arguments
+///
+/// * `a` - First sorted sequence
+/// * `b` - Second sorted sequence
+pub fn sorted_intersection<T: Ord, A: IntoIterator<Item = T>, B: IntoIterator<Item = T>>(a: A, b: B) -> SortedSetOperation<A::IntoIter, B::IntoIter> {
+    SortedSetOperation::new(a, b, false, true, false)
+}
+
+/// Iterates over the items of a sorted sequence that aren't in another, in sorted order
+///
+/// An item `i` times in `a` and `j` times in `b` comes out `i - j` times if that's positive.
+///
+/// # Arguments
+///
+/// * `a` - Sorted sequence to take items from
+/// * `b` - Sorted sequence of items to leave out
+pub fn sorted_difference<T: Ord, A: IntoIterator<Item = T>, B: IntoIterator<Item = T>>(a: A, b: B) -> SortedSetOperation<A::IntoIter, B::IntoIter> {
+    SortedSetOperation::new(a, b, true, false, false)
+}
+
+/// Iterates over the items in exactly one of two sorted sequences, in sorted order
+///
+/// An item `i` times in `a` and `j` times in `b` comes out `|i - j|` times.
+///
+/// # Arguments
+///
+/// * `a` - First sorted sequence
+/// * `b` - Second sorted sequence
+pub fn sorted_symmetric_difference<T: Ord, A: IntoIterator<Item = T>, B: IntoIterator<Item = T>>(a: A, b: B) -> SortedSetOperation<A::IntoIter, B::IntoIter> {
+    SortedSetOperation::new(a, b, true, false, true)
+}
+
+impl<A: Iterator, B: Iterator<Item = A::Item>> SortedSetOperation<A, B> {
+
+    /// Internal function to create an iterator keeping the items only in `a`, in both, and only
+    /// in `b` as chosen
+    fn new<I: IntoIterator<IntoIter = A>, J: IntoIterator<IntoIter = B>>(a: I, b: J, only_a: bool, both: bool, only_b: bool) -> Self {
+        SortedSetOperation { a: a.into_iter().peekable(), b: b.into_iter().peekable(), only_a, both, only_b }
+    }
+}
+
+impl<T: Ord, A: Iterator<Item = T>, B: Iterator<Item = T>> Iterator for SortedSetOperation<A, B> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        loop {
+            let order = match (self.a.peek(), self.b.peek()) {
+                (Some(x), Some(y)) => x.cmp(y),
+                (Some(_), None) => return if self.only_a { self.a.next() } else { None },
+                (None, Some(_)) => return if self.only_b { self.b.next() } else { None },
+                (None, None) => return None,
+            };
+            match order {
+                Ordering::Less => {
+                    let x = self.a.next();
+                    if self.only_a {
+                        return x;
+                    }
+                }
+                Ordering::Greater => {
+                    let y = self.b.next();
+                    if self.only_b {
+                        return y;
+                    }
+                }
+                Ordering::Equal => {
+                    self.b.next();
+                    let x = self.a.next();
+                    if self.both {
+                        return x;
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::Rng;
+
+    #[test]
+    fn test_merge_sorted() {
+        assert_eq!(vec![1, 2, 3, 4, 5, 7, 9], merge_sorted_vec(&[1, 4, 5, 9], &[2, 3, 7]));
+        assert_eq!(vec![1, 2], merge_sorted_vec(&[], &[1, 2]));
+        assert_eq!(Vec::<u64>::new(), merge_sorted_vec::<u64>(&[], &[]));
+        // Lazy, so it can merge infinite sequences
+        let evens = (0..).step_by(2);
+        let squares = (0..).map(|x: u64| x * x);
+        assert_eq!(vec![0, 0, 1, 2, 4, 4, 6, 8, 9, 10], merge_sorted(evens, squares).take(10).collect::<Vec<_>>());
+        assert_eq!((7, Some(7)), merge_sorted(&[1, 4, 5, 9], &[2, 3, 7]).size_hint());
+    }
+
+    #[test]
+    fn test_stable() {
+        let mut rng = Rng::new(349);
+        let mut a: Vec<(u64, char)> = (0..500).map(|_| (rng.gen_range(50), 'a')).collect();
+        let mut b: Vec<(u64, char)> = (0..300).map(|_| (rng.gen_range(50), 'b')).collect();
+        a.sort();
+        b.sort();
+        let merged: Vec<(u64, char)> = merge_sorted(a.iter().map(|&(k, c)| Key(k, c)), b.iter().map(|&(k, c)| Key(k, c))).map(|Key(k, c)| (k, c)).collect();
+        // Equal keys from a come before those from b, which sorting by key then source also does
+        let mut expected = a.clone();
+        expected.extend(b);
+        expected.sort();
+        assert_eq!(expected, merged);
+    }
+
+    #[test]
+    fn test_merge_k_sorted() {
+        let merged: Vec<u64> = merge_k_sorted(vec![vec![1, 5, 9], vec![], vec![2, 3, 10, 11], vec![4]]).collect();
+        assert_eq!(vec![1, 2, 3, 4, 5, 9, 10, 11], merged);
+        assert_eq!(0, merge_k_sorted(Vec::<Vec<u64>>::new()).count());
+        assert_eq!((5, Some(5)), merge_k_sorted([&[1, 4][..], &[2], &[3, 5]]).size_hint());
+        // Lazy, so it can merge infinite sequences
+        let multiples = (2..5).map(|k| (1..).map(move |x: u64| k * x));
+        assert_eq!(vec![2, 3, 4, 4, 6, 6, 8, 8, 9, 10], merge_k_sorted(multiples).take(10).collect::<Vec<_>>());
+
+        let mut rng = Rng::new(569);
+        for _ in 0..50 {
+            let k = rng.gen_range(10) as usize;
+            let sources: Vec<Vec<(u64, usize)>> = (0..k)
+                .map(|i| {
+                    let mut source: Vec<(u64, usize)> = (0..rng.gen_range(40)).map(|_| (rng.gen_range(20), i)).collect();
+                    source.sort();
+                    source
+                })
+                .collect();
+            let merged: Vec<(u64, usize)> = merge_k_sorted(sources.iter().map(|source| source.iter().map(|&(key, i)| Key(key, i))))
+                .map(|Key(key, i)| (key, i))
+                .collect();
+            // Equal keys come out in the order of their sources, like a stable sort of the
+            // concatenation by key
+            let mut expected: Vec<(u64, usize)> = sources.concat();
+            expected.sort_by_key(|&(key, _)| key);
+            assert_eq!(expected, merged, "merging {} sources", k);
+        }
+    }
+
+    /// Internal key compared only by its first field, to tell equal items apart
+    #[derive(Debug)]
+    struct Key<T>(u64, T);
+
+    impl<T> PartialEq for Key<T> {
+        fn eq(&self, other: &Self) -> bool {
+            self.0 == other.0
+        }
+    }
+
+    impl<T> Eq for Key<T> {}
+
+    impl<T> PartialOrd for Key<T> {
+        fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+            Some(self.cmp(other))
+        }
+    }
+
+    impl<T> Ord for Key<T> {
+        fn cmp(&self, other: &Self) -> Ordering {
+            self.0.cmp(&other.0)
+        }
+    }
+
+    #[test]
+    fn test_set_operations() {
+        let a = [1, 2, 2, 2, 4, 6, 8];
+        let b = [2, 2, 3, 6, 9];
+        assert_eq!(vec![1, 2, 2, 2, 3, 4, 6, 8, 9], sorted_union(&a, &b).copied().collect::<Vec<_>>());
+        assert_eq!(vec![2, 2, 6], sorted_intersection(&a, &b).copied().collect::<Vec<_>>());
+        assert_eq!(vec![1, 2, 4, 8], sorted_difference(&a, &b).copied().collect::<Vec<_>>());
+        assert_eq!(vec![3, 9], sorted_difference(&b, &a).copied().collect::<Vec<_>>());
+        assert_eq!(vec![1, 2, 3, 4, 8, 9], sorted_symmetric_difference(&a, &b).copied().collect::<Vec<_>>());
+        assert_eq!(0, sorted_intersection(&a, &[]).count());
+
+        // Matches the set operations of BTreeSet on sets without repeats
+        let mut rng = Rng::new(353);
+        let x: std::collections::BTreeSet<u64> = (0..200).map(|_| rng.gen_range(300)).collect();
+        let y: std::collections::BTreeSet<u64> = (0..200).map(|_| rng.gen_range(300)).collect();
+        assert!(sorted_union(&x, &y).eq(x.union(&y)));
+        assert!(sorted_intersection(&x, &y).eq(x.intersection(&y)));
+        assert!(sorted_difference(&x, &y).eq(x.difference(&y)));
+        assert!(sorted_symmetric_difference(&x, &y).eq(x.symmetric_difference(&y)));
+    }
+}
@@ -0,0 +1,243 @@
+/// One step of an edit script turning one sequence into another, by the indices of the items it
+/// uses
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Edit {
+    /// `a[a]` is kept, and equals `b[b]`
+    Keep { a: usize, b: usize },
+    /// `a[a]` is replaced by `b[b]`
+    Substitute { a: usize, b: usize },
+    /// `b[b]` is inserted
+    Insert { b: usize },
+    /// `a[a]` is deleted
+    Delete { a: usize },
+}
+
+/// A cheapest edit script turning one sequence into another, with its Levenshtein distance
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Alignment {
+    /// Number of insertions, deletions, and substitutions in the script
+    pub distance: usize,
+    /// Steps in order, covering every item of both sequences once
+    pub edits: Vec<Edit>,
+}
+
+impl Alignment {
+
+    /// Applies the edit script to the first sequence, giving the second
+    ///
+    /// # Arguments
+    ///
+    /// * `a` - Sequence the script was found from
+    /// * `b` - Sequence the script turns `a` into, where inserted and substituted items come from
+    pub fn apply<T: Clone>(&self, a: &[T], b: &[T]) -> Vec<T> {
+        self.edits
+            .iter()
+            .filter_map(|&edit| match edit {
+                Edit::Keep { a: i, .. } => Some(a[i].clone()),
+                Edit::Substitute { b: j, .. } | Edit::Insert { b: j } => Some(b[j].clone()),
+                Edit::Delete { .. } => None,
+            })
+            .collect()
+    }
+}
+
+/// Computes the Levenshtein distance between two sequences, the fewest insertions, deletions, and
+/// substitutions of single items turning one into the other
+///
+/// `d[i][j]`, the distance between `a[..i]` and `b[..j]`, is the cheapest of deleting `a[i - 1]`
+/// after `d[i - 1][j]`, inserting `b[j - 1]` after `d[i][j - 1]`, and substituting `a[i - 1]` by
+/// `b[j - 1]`, free if they're equal, after `d[i - 1][j - 1]`[1]. Each row only needs the one
+/// before it, so it takes `O(n*m)` time and keeps just two rows of the shorter sequence, in
+/// `O(min(n, m))` space.
+///
+/// [1] Wagner, Robert A., and Michael J. Fischer. "The String-to-String Correction Problem."
+/// <i>Journal of the ACM<i>, 1974.
+///
+/// # Arguments
+///
+/// * `a` - First sequence
+/// * `b` - Second sequence
+pub fn levenshtein<T: Eq>(a: &[T], b: &[T]) -> usize {
+    // The distance is symmetric, so let the rows run along the shorter sequence
+    let (a, b) = if a.len() < b.len() { (b, a) } else { (a, b) };
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    let mut cur = vec![0; b.len() + 1];
+    for i in 1..=a.len() {
+        cur[0] = i;
+        for j in 1..=b.len() {
+            let substitute = prev[j - 1] + usize::from(a[i - 1] != b[j - 1]);
+            cur[j] = substitute.min(prev[j] + 1).min(cur[j - 1] + 1);
+        }
+        std::mem::swap(&mut prev, &mut cur);
+    }
+    prev[b.len()]
+}
+
+/// Computes the Levenshtein distance between two sequences and a cheapest edit script
+///
+/// Fills in the whole table of distances, in `O(n*m)` time and space, then walks back from the
+/// end choosing a step that gave each distance. Prefers keeping or substituting, then deleting,
+/// then inserting, when several steps are equally cheap.
+///
+/// # Arguments
+///
+/// * `a` - Sequence to edit
+/// * `b` - Sequence to turn it into
+pub fn alignment<T: Eq>(a: &[T], b: &[T]) -> Alignment {
+    let (n, m) = (a.len(), b.len());
+    let mut d = vec![vec![0; m + 1]; n + 1];
+    d[0] = (0..=m).collect();
+    for (i, row) in d.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    for i in 1..=n {
+        for j in 1..=m {
+            let substitute = d[i - 1][j - 1] + usize::from(a[i - 1] != b[j - 1]);
+            d[i][j] = substitute.min(d[i - 1][j] + 1).min(d[i][j - 1] + 1);
+        }
+    }
+
+    let mut edits = Vec::with_capacity(n.max(m));
+    let (mut i, mut j) = (n, m);
+    while i > 0 || j > 0 {
+        if i > 0 && j > 0 && d[i][j] == d[i - 1][j - 1] + usize::from(a[i - 1] != b[j - 1]) {
+            i -= 1;
+            j -= 1;
+            edits.push(if a[i] == b[j] { Edit::Keep { a: i, b: j } } else { Edit::Substitute { a: i, b: j } });
+        } else if i > 0 && d[i][j] == d[i - 1][j] + 1 {
+            i -= 1;
+            edits.push(Edit::Delete { a: i });
+        } else {
+            j -= 1;
+            edits.push(Edit::Insert { b: j });
+        }
+    }
+    edits.reverse();
+    Alignment { distance: d[n][m], edits }
+}
+
+/// Computes the Levenshtein distance between two sequences if it's at most `k`
+///
+/// A cheapest script with at most `k` edits never strays more than `k` places off the diagonal of
+/// the table, so only the band of cells `|i - j| <= k` is filled in, in `O(k*n)` time and `O(m)`
+/// space[2]. Returns `None` as soon as a whole row of the band is over `k`, or right away if the
+/// lengths differ by more than `k`. Checking whether two long sequences are nearly equal, like
+/// for spelling suggestions, is much faster than computing their distance.
+///
+/// [2] Ukkonen, Esko. "Algorithms for Approximate String Matching." <i>Information and
+/// Control<i>, 1985.
+///
+/// # Arguments
+///
+/// * `a` - First sequence
+/// * `b` - Second sequence
+/// * `k` - Largest distance to compute
+pub fn levenshtein_bounded<T: Eq>(a: &[T], b: &[T], k: usize) -> Option<usize> {
+    let (n, m) = (a.len(), b.len());
+    if n.abs_diff(m) > k {
+        return None;
+    }
+    // Any distance over k is as good as k + 1, and cells outside the band hold it
+    let over = k + 1;
+    let mut prev: Vec<usize> = (0..=m).map(|j| if j <= k { j } else { over }).collect();
+    let mut cur = vec![over; m + 1];
+    for i in 1..=n {
+        let (lo, hi) = (i.saturating_sub(k), (i + k).min(m));
+        if lo == 0 {
+            cur[0] = i;
+        } else {
+            cur[lo - 1] = over;
+        }
+        for j in lo.max(1)..=hi {
+            let substitute = prev[j - 1] + usize::from(a[i - 1] != b[j - 1]);
+            cur[j] = substitute.min(prev[j] + 1).min(cur[j - 1] + 1).min(over);
+        }
+        // The next row reads one cell past this row's band
+        if hi < m {
+            cur[hi + 1] = over;
+        }
+        if cur[lo..=hi].iter().all(|&d| d > k) {
+            return None;
+        }
+        std::mem::swap(&mut prev, &mut cur);
+    }
+    Some(prev[m]).filter(|&d| d <= k)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::Rng;
+
+    #[test]
+    fn test_levenshtein() {
+        assert_eq!(3, levenshtein(b"kitten", b"sitting"));
+        assert_eq!(3, levenshtein(b"sitting", b"kitten"));
+        assert_eq!(2, levenshtein(b"flaw", b"lawn"));
+        assert_eq!(5, levenshtein(b"", b"hello"));
+        assert_eq!(0, levenshtein(b"same", b"same"));
+        assert_eq!(0, levenshtein::<u8>(&[], &[]));
+        // Any items that can be compared, like the words of a sentence
+        let a = ["the", "quick", "brown", "fox"];
+        let b = ["the", "slow", "brown", "dog", "barks"];
+        assert_eq!(3, levenshtein(&a, &b));
+    }
+
+    #[test]
+    fn test_alignment() {
+        let result = alignment(b"kitten", b"sitting");
+        assert_eq!(3, result.distance);
+        assert_eq!(
+            vec![
+                Edit::Substitute { a: 0, b: 0 },
+                Edit::Keep { a: 1, b: 1 },
+                Edit::Keep { a: 2, b: 2 },
+                Edit::Keep { a: 3, b: 3 },
+                Edit::Substitute { a: 4, b: 4 },
+                Edit::Keep { a: 5, b: 5 },
+                Edit::Insert { b: 6 },
+            ],
+            result.edits
+        );
+        assert_eq!(b"sitting".to_vec(), result.apply(b"kitten", b"sitting"));
+
+        let mut rng = Rng::new(431);
+        for _ in 0..200 {
+            let a: Vec<u8> = (0..rng.gen_range(30)).map(|_| rng.gen_range(4) as u8).collect();
+            let b: Vec<u8> = (0..rng.gen_range(30)).map(|_| rng.gen_range(4) as u8).collect();
+            let result = alignment(&a, &b);
+            assert_eq!(levenshtein(&a, &b), result.distance);
+            assert_eq!(b, result.apply(&a, &b));
+            let cost = result.edits.iter().filter(|e| !matches!(e, Edit::Keep { .. })).count();
+            assert_eq!(result.distance, cost);
+        }
+    }
+
+    #[test]
+    fn test_bounded() {
+        assert_eq!(Some(3), levenshtein_bounded(b"kitten", b"sitting", 3));
+        assert_eq!(None, levenshtein_bounded(b"kitten", b"sitting", 2));
+        assert_eq!(None, levenshtein_bounded(b"a", b"abcd", 2));
+        assert_eq!(Some(0), levenshtein_bounded(b"", b"", 0));
+        assert_eq!(Some(2), levenshtein_bounded(b"", b"ab", 5));
+        let mut rng = Rng::new(433);
+        for _ in 0..500 {
+            let a: Vec<u8> = (0..rng.gen_range(40)).map(|_| rng.gen_range(3) as u8).collect();
+            let mut b = a.clone();
+            for _ in 0..rng.gen_range(6) {
+                let i = rng.gen_range(b.len() as u64 + 1) as usize;
+                match rng.gen_range(3) {
+                    0 => b.insert(i, rng.gen_range(3) as u8),
+                    _ if i < b.len() => {
+                        b.remove(i);
+                    }
+                    _ => {}
+                }
+            }
+            let distance = levenshtein(&a, &b);
+            for k in 0..8 {
+                assert_eq!(Some(distance).filter(|&d| d <= k), levenshtein_bounded(&a, &b, k), "k = {}", k);
+            }
+        }
+    }
+}
@@ -0,0 +1,170 @@
+/// A byte pattern with its bad character table, for searching texts with the Boyer–Moore–Horspool
+/// algorithm
+///
+/// Compares the pattern against each window of the text, starting from its last byte. Whatever
+/// happens, the next window worth trying is the first that lines the window's last byte up with
+/// the same byte in the pattern, found in a table of 256 shifts built in `O(m)` time[1]. A byte
+/// that isn't in the pattern skips the whole window, so on a large alphabet, like natural
+/// language, most windows cost one comparison and the search reads about `n/m` bytes. It's
+/// `O(n*m)` in the worst case, like searching `aaa...a` for `baa...a`, and on a small alphabet,
+/// like DNA, the shifts are short.
+///
+/// [1] Horspool, R. Nigel. "Practical Fast Searching in Strings." <i>Software: Practice and
+/// Experience<i>, 1980.
+#[derive(Clone, Debug)]
+pub struct Horspool<'p> {
+    pattern: &'p [u8],
+    shift: [usize; 256],
+}
+
+impl<'p> Horspool<'p> {
+
+    /// Creates a new `Horspool` for a pattern
+    ///
+    /// # Arguments
+    ///
+    /// * `pattern` - Pattern to search for
+    pub fn new(pattern: &'p [u8]) -> Horspool<'p> {
+        let m = pattern.len();
+        let mut shift = [m.max(1); 256];
+        // The last byte is left out, or a window ending in it wouldn't move
+        for (i, &b) in pattern.iter().enumerate().take(m.saturating_sub(1)) {
+            shift[b as usize] = m - 1 - i;
+        }
+        Horspool { pattern, shift }
+    }
+
+    /// Pattern the table is for
+    pub fn pattern(&self) -> &'p [u8] {
+        self.pattern
+    }
+
+    /// Number of bytes the search skips after a window ending in a byte
+    ///
+    /// # Arguments
+    ///
+    /// * `b` - Last byte of the window
+    pub fn shift(&self, b: u8) -> usize {
+        self.shift[b as usize]
+    }
+
+    /// Finds the starting index of the first match of the pattern in a text
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - Text to search
+    pub fn find(&self, text: &[u8]) -> Option<usize> {
+        self.search(text, true).first().copied()
+    }
+
+    /// Finds the starting index of every match of the pattern in a text, overlapping matches
+    /// included
+    ///
+    /// An empty pattern matches at every index, up to and including the length of the text.
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - Text to search
+    pub fn find_all(&self, text: &[u8]) -> Vec<usize> {
+        self.search(text, false)
+    }
+
+    /// Internal function to find the matches of the pattern, stopping at the first if asked
+    fn search(&self, text: &[u8], first: bool) -> Vec<usize> {
+        let m = self.pattern.len();
+        if m == 0 {
+            return if first { vec![0] } else { (0..=text.len()).collect() };
+        }
+        let last = self.pattern[m - 1];
+        let mut matches = Vec::new();
+        let mut i = 0;
+        while i + m <= text.len() {
+            // Check the last byte first, since it's the one that decides the shift anyway
+            let b = text[i + m - 1];
+            if b == last && text[i..i + m - 1] == self.pattern[..m - 1] {
+                matches.push(i);
+                if first {
+                    break;
+                }
+            }
+            i += self.shift[b as usize];
+        }
+        matches
+    }
+}
+
+/// Finds the starting index of every match of a byte pattern in a text with the
+/// Boyer–Moore–Horspool algorithm
+///
+/// # Arguments
+///
+/// * `text` - Text to search
+/// * `pattern` - Pattern to search for
+pub fn horspool(text: &[u8], pattern: &[u8]) -> Vec<usize> {
+    Horspool::new(pattern).find_all(text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::kmp::find_all;
+    use crate::rng::Rng;
+
+    #[test]
+    fn test_shifts() {
+        let h = Horspool::new(b"abcab");
+        assert_eq!(1, h.shift(b'a'));
+        assert_eq!(3, h.shift(b'b'));
+        assert_eq!(2, h.shift(b'c'));
+        assert_eq!(5, h.shift(b'z'));
+        assert_eq!(b"abcab", h.pattern());
+        assert_eq!(1, Horspool::new(b"").shift(b'a'));
+    }
+
+    #[test]
+    fn test_search() {
+        let text = "the cat sat on the mat with the other cat".as_bytes();
+        assert_eq!(vec![0, 15, 28, 33], horspool(text, b"the"));
+        assert_eq!(Some(4), Horspool::new(b"cat").find(text));
+        assert_eq!(None, Horspool::new(b"dog").find(text));
+        assert_eq!(vec![0, 1, 2], horspool(b"aaaa", b"aa"));
+        assert_eq!(vec![0, 2, 4], horspool(b"abababa", b"aba"));
+        assert_eq!(vec![0, 1, 2], horspool(b"ab", b""));
+        assert!(horspool(b"ab", b"abc").is_empty());
+        let mut rng = Rng::new(401);
+        for alphabet in [2, 4, 26] {
+            let text: Vec<u8> = (0..5000).map(|_| b'a' + rng.gen_range(alphabet) as u8).collect();
+            for m in 1..10 {
+                let start = rng.gen_range(4990) as usize;
+                let pattern = &text[start..start + m];
+                assert_eq!(find_all(&text, pattern).collect::<Vec<_>>(), horspool(&text, pattern));
+            }
+        }
+    }
+
+    #[test]
+    fn test_skips() {
+        // On a large alphabet the last byte of a window is rarely in the pattern, so the search
+        // tries only a little more than one window in every 16 bytes
+        let mut rng = Rng::new(409);
+        let text: Vec<u8> = (0..100_000).map(|_| rng.gen_range(256) as u8).collect();
+        let pattern: Vec<u8> = (0..16).map(|_| rng.gen_range(256) as u8).collect();
+        let h = Horspool::new(&pattern);
+        let (mut i, mut windows) = (0, 0);
+        while i + 16 <= text.len() {
+            windows += 1;
+            i += h.shift(text[i + 15]);
+        }
+        assert!(windows < text.len() / 12, "{} windows", windows);
+        // On DNA nearly every byte is in the pattern, so the shifts are short
+        let dna: Vec<u8> = text.iter().map(|&b| b"ACGT"[b as usize % 4]).collect();
+        let h = Horspool::new(b"GATTACAGATTACAGA");
+        let mut windows = 0;
+        let mut i = 0;
+        while i + 16 <= dna.len() {
+            windows += 1;
+            i += h.shift(dna[i + 15]);
+        }
+        assert!(windows > dna.len() / 4, "{} windows", windows);
+    }
+}
@@ -0,0 +1,205 @@
+use std::borrow::Cow;
+
+/// Computes the prefix function of a sequence, the length of the longest proper border of each
+/// of its prefixes
+///
+/// A border of a sequence is a proper prefix of it that's also a suffix, like `abab` in
+/// `ababab`. `prefix[i]` is the length of the longest border of `s[..=i]`. Each border of
+/// `s[..=i]` is a border of `s[..i]` extended by one item, so the function tries the borders of
+/// the previous prefix from longest to shortest, following the prefix function itself from one
+/// to the next. Each step extends the border by at most one and each fallback shrinks it, so it
+/// takes `O(n)` time in total[1].
+///
+/// [1] Knuth, Donald E., James H. Morris, and Vaughan R. Pratt. "Fast Pattern Matching in Strings."
+/// <i>SIAM Journal on Computing<i>, 1977.
+///
+/// # Arguments
+///
+/// * `s` - Sequence to compute the prefix function of
+pub fn prefix_function<T: Eq>(s: &[T]) -> Vec<usize> {
+    let mut prefix = vec![0; s.len()];
+    for i in 1..s.len() {
+        let mut border = prefix[i - 1];
+        while border > 0 && s[i] != s[border] {
+            border = prefix[border - 1];
+        }
+        if s[i] == s[border] {
+            border += 1;
+        }
+        prefix[i] = border;
+    }
+    prefix
+}
+
+/// A pattern with its precomputed failure function, for searching many texts for it with the
+/// Knuth–Morris–Pratt algorithm
+///
+/// The failure function is the prefix function of the pattern. After matching `j` items of the
+/// pattern, a mismatch means the next possible match already has its first `failure[j - 1]` items
+/// matched, since those are the longest border of what matched. So the search never moves back
+/// in the text, and finds every match in `O(n)` time after `O(m)` preprocessing.
+#[derive(Clone, Debug)]
+pub struct FailureFunction<'p, T> {
+    pattern: &'p [T],
+    failure: Vec<usize>,
+}
+
+impl<'p, T: Eq> FailureFunction<'p, T> {
+
+    /// Creates a new `FailureFunction` for a pattern in `O(m)` time
+    ///
+    /// # Arguments
+    ///
+    /// * `pattern` - Pattern to search for
+    pub fn new(pattern: &'p [T]) -> FailureFunction<'p, T> {
+        FailureFunction { pattern, failure: prefix_function(pattern) }
+    }
+
+    /// Pattern the failure function is for
+    pub fn pattern(&self) -> &'p [T] {
+        self.pattern
+    }
+
+    /// Failure function of the pattern, the same as its prefix function
+    pub fn as_slice(&self) -> &[usize] {
+        &self.failure
+    }
+
+    /// Lengths of every border of the pattern, longest first
+    ///
+    /// The borders of a border are borders too, so the list follows the failure function from the
+    /// whole pattern down to nothing.
+    pub fn borders(&self) -> Vec<usize> {
+        let mut borders = Vec::new();
+        let mut border = self.failure.last().copied().unwrap_or(0);
+        while border > 0 {
+            borders.push(border);
+            border = self.failure[border - 1];
+        }
+        borders
+    }
+
+    /// Smallest period of the pattern, the smallest `p` where every item equals the one `p` after
+    /// it
+    ///
+    /// The pattern shifted by `p` overlaps itself exactly when it has a border of length `m - p`,
+    /// so the smallest period is the length minus the longest border. The pattern is a repetition
+    /// of a shorter sequence when the period divides its length.
+    pub fn period(&self) -> usize {
+        self.pattern.len() - self.failure.last().copied().unwrap_or(0)
+    }
+
+    /// Finds the starting index of every match of the pattern in a text, overlapping matches
+    /// included, in `O(n)` time
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - Text to search
+    pub fn find_all<'a, 't>(&'a self, text: &'t [T]) -> Matches<'a, 't, T> {
+        Matches { pattern: self.pattern, failure: Cow::Borrowed(&self.failure), text, i: 0, matched: 0 }
+    }
+}
+
+/// Finds the starting index of every match of a pattern in a text with the Knuth–Morris–Pratt
+/// algorithm
+///
+/// Computes the failure function of the pattern and scans the text once, in `O(n + m)` time.
+/// Overlapping matches are included, so `aa` is found at 0, 1, and 2 in `aaaa`. An empty pattern
+/// matches at every index, up to and including the length of the text. To search several texts
+/// for the same pattern, build a `FailureFunction` once.
+///
+/// # Arguments
+///
+/// * `text` - Text to search
+/// * `pattern` - Pattern to search for
+pub fn find_all<'p, 't, T: Eq>(text: &'t [T], pattern: &'p [T]) -> Matches<'p, 't, T> {
+    Matches { pattern, failure: Cow::Owned(prefix_function(pattern)), text, i: 0, matched: 0 }
+}
+
+/// Iterator over the starting indices of the matches of a pattern in a text, made by `find_all`
+#[derive(Clone, Debug)]
+pub struct Matches<'p, 't, T> {
+    pattern: &'p [T],
+    failure: Cow<'p, [usize]>,
+    text: &'t [T],
+    i: usize,
+    matched: usize,
+}
+
+impl<'p, 't, T: Eq> Iterator for Matches<'p, 't, T> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        if self.pattern.is_empty() {
+            self.i += 1;
+            return (self.i <= self.text.len() + 1).then_some(self.i - 1);
+        }
+        while self.i < self.text.len() {
+            let item = &self.text[self.i];
+            while self.matched > 0 && *item != self.pattern[self.matched] {
+                self.matched = self.failure[self.matched - 1];
+            }
+            if *item == self.pattern[self.matched] {
+                self.matched += 1;
+            }
+            self.i += 1;
+            if self.matched == self.pattern.len() {
+                // Carry on from the longest border, so overlapping matches are found too
+                self.matched = self.failure[self.matched - 1];
+                return Some(self.i - self.pattern.len());
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::Rng;
+
+    #[test]
+    fn test_prefix_function() {
+        assert_eq!(vec![0, 0, 1, 2, 3, 4, 0, 1], prefix_function(b"abababca"));
+        assert_eq!(vec![0, 1, 2, 3], prefix_function(b"aaaa"));
+        assert_eq!(Vec::<usize>::new(), prefix_function::<u8>(&[]));
+
+        let f = FailureFunction::new(b"abaababaab");
+        assert_eq!(vec![5, 2], f.borders());
+        assert_eq!(5, f.period());
+        // A repetition of a shorter sequence has a period dividing its length
+        let f = FailureFunction::new(b"abcabcabc");
+        assert_eq!(3, f.period());
+        assert_eq!(0, f.pattern().len() % f.period());
+        assert_eq!(4, FailureFunction::new(b"abcd").period());
+    }
+
+    #[test]
+    fn test_find_all() {
+        let text = "the cat sat on the mat with the other cat".as_bytes();
+        assert_eq!(vec![0, 15, 28, 33], find_all(text, b"the").collect::<Vec<_>>());
+        assert_eq!(vec![4, 38], find_all(text, b"cat").collect::<Vec<_>>());
+        assert_eq!(0, find_all(text, b"dog").count());
+        // Overlapping matches
+        assert_eq!(vec![0, 1, 2], find_all(b"aaaa", b"aa").collect::<Vec<_>>());
+        assert_eq!(vec![0, 2, 4], find_all(b"abababa", b"aba").collect::<Vec<_>>());
+        assert_eq!(vec![0, 1, 2], find_all(b"ab", b"").collect::<Vec<_>>());
+        assert_eq!(0, find_all(b"ab", b"abc").count());
+        // Any items that can be compared, like words
+        let words = ["to", "be", "or", "not", "to", "be"];
+        assert_eq!(vec![0, 4], find_all(&words, &["to", "be"]).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_matches_naive() {
+        let mut rng = Rng::new(359);
+        let text: Vec<u8> = (0..5000).map(|_| b'a' + rng.gen_range(2) as u8).collect();
+        for m in 1..8 {
+            let pattern: Vec<u8> = (0..m).map(|_| b'a' + rng.gen_range(2) as u8).collect();
+            let expected: Vec<usize> = text.windows(m).enumerate().filter(|(_, w)| *w == pattern.as_slice()).map(|(i, _)| i).collect();
+            let f = FailureFunction::new(&pattern);
+            assert_eq!(expected, f.find_all(&text).collect::<Vec<_>>());
+            assert_eq!(expected, find_all(&text, &pattern).collect::<Vec<_>>());
+        }
+    }
+}
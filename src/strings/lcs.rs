@@ -0,0 +1,211 @@
+use crate::edit_distance::Edit;
+
+/// Computes the length of a longest common subsequence of two sequences
+///
+/// A common subsequence is a sequence of items found in both, in order but not necessarily next
+/// to each other. `l[i][j]`, the length for `a[..i]` and `b[..j]`, is `l[i - 1][j - 1] + 1` when
+/// `a[i - 1]` equals `b[j - 1]`, and otherwise the longer of leaving out either item[1]. Keeps
+/// just two rows of the shorter sequence, in `O(n*m)` time and `O(min(n, m))` space.
+///
+/// [1] Cormen, Thomas H., et al. "Introduction to Algorithms." <i>MIT Press<i>, 2009.
+///
+/// # Arguments
+///
+/// * `a` - First sequence
+/// * `b` - Second sequence
+pub fn lcs_length<T: Eq>(a: &[T], b: &[T]) -> usize {
+    let (a, b) = if a.len() < b.len() { (b, a) } else { (a, b) };
+    last_row(a, b, false)[b.len()]
+}
+
+/// Finds a longest common subsequence of two sequences, as the pairs of indices of its items in
+/// each
+///
+/// Fills in the whole table of lengths, in `O(n*m)` time and space, then walks back from the end,
+/// taking each pair of equal items it steps diagonally over.
+///
+/// # Arguments
+///
+/// * `a` - First sequence
+/// * `b` - Second sequence
+pub fn lcs<T: Eq>(a: &[T], b: &[T]) -> Vec<(usize, usize)> {
+    let (n, m) = (a.len(), b.len());
+    let mut l = vec![vec![0; m + 1]; n + 1];
+    for i in 1..=n {
+        for j in 1..=m {
+            l[i][j] = if a[i - 1] == b[j - 1] { l[i - 1][j - 1] + 1 } else { l[i - 1][j].max(l[i][j - 1]) };
+        }
+    }
+    let mut pairs = Vec::with_capacity(l[n][m]);
+    let (mut i, mut j) = (n, m);
+    while i > 0 && j > 0 {
+        if a[i - 1] == b[j - 1] {
+            i -= 1;
+            j -= 1;
+            pairs.push((i, j));
+        } else if l[i - 1][j] >= l[i][j - 1] {
+            i -= 1;
+        } else {
+            j -= 1;
+        }
+    }
+    pairs.reverse();
+    pairs
+}
+
+/// Finds a longest common subsequence of two sequences in linear space with Hirschberg's
+/// algorithm, as the pairs of indices of its items in each
+///
+/// Splits `a` in half and computes, in linear space, the last row of lengths for the first half
+/// against every prefix of `b`, and for the second half against every suffix of `b`, reading both
+/// backwards. A longest common subsequence crosses from one half to the other at the split of `b`
+/// maximizing the sum of the two, so the two halves are solved separately on either side of it[2].
+/// The rows cost `O(n*m)` time at the top, half that at the next level, and so on, so it takes
+/// `O(n*m)` time in total, about twice the table, but only `O(n + m)` space.
+///
+/// [2] Hirschberg, Daniel S. "A Linear Space Algorithm for Computing Maximal Common
+/// Subsequences." <i>Communications of the ACM<i>, 1975.
+///
+/// # Arguments
+///
+/// * `a` - First sequence
+/// * `b` - Second sequence
+pub fn hirschberg<T: Eq>(a: &[T], b: &[T]) -> Vec<(usize, usize)> {
+    let mut pairs = Vec::new();
+    split(a, b, 0, 0, &mut pairs);
+    pairs
+}
+
+/// Finds the edits turning one sequence into another that keep a longest common subsequence,
+/// like a line diff
+///
+/// Items of the subsequence are kept and every other item is deleted from `a` or inserted from
+/// `b`, deletions before insertions between two kept items. Uses Hirschberg's algorithm, so diffing
+/// long sequences only takes linear space.
+///
+/// # Arguments
+///
+/// * `a` - Old sequence
+/// * `b` - New sequence
+pub fn diff<T: Eq>(a: &[T], b: &[T]) -> Vec<Edit> {
+    let mut edits = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    for (next_i, next_j) in hirschberg(a, b).into_iter().chain(std::iter::once((a.len(), b.len()))) {
+        edits.extend((i..next_i).map(|a| Edit::Delete { a }));
+        edits.extend((j..next_j).map(|b| Edit::Insert { b }));
+        if next_i < a.len() {
+            edits.push(Edit::Keep { a: next_i, b: next_j });
+        }
+        i = next_i + 1;
+        j = next_j + 1;
+    }
+    edits
+}
+
+/// Internal function to add the pairs of a longest common subsequence of `a` and `b`, whose
+/// indices start at `i` and `j`, to `pairs`
+fn split<T: Eq>(a: &[T], b: &[T], i: usize, j: usize, pairs: &mut Vec<(usize, usize)>) {
+    if a.is_empty() || b.is_empty() {
+        return;
+    }
+    if a.len() == 1 {
+        if let Some(k) = b.iter().position(|x| *x == a[0]) {
+            pairs.push((i, j + k));
+        }
+        return;
+    }
+    let mid = a.len() / 2;
+    let left = last_row(&a[..mid], b, false);
+    let right = last_row(&a[mid..], b, true);
+    // right[m - k] is the length for a[mid..] and b[k..]
+    let m = b.len();
+    let k = (0..=m).max_by_key(|&k| (left[k] + right[m - k], std::cmp::Reverse(k))).unwrap();
+    split(&a[..mid], &b[..k], i, j, pairs);
+    split(&a[mid..], &b[k..], i + mid, j + k, pairs);
+}
+
+/// Internal function to compute the lengths of a longest common subsequence of `a` and each
+/// prefix of `b`, or of both read backwards, in `O(m)` space
+fn last_row<T: Eq>(a: &[T], b: &[T], backwards: bool) -> Vec<usize> {
+    let (n, m) = (a.len(), b.len());
+    let mut prev = vec![0; m + 1];
+    let mut cur = vec![0; m + 1];
+    for i in 0..n {
+        let x = if backwards { &a[n - 1 - i] } else { &a[i] };
+        for j in 1..=m {
+            let y = if backwards { &b[m - j] } else { &b[j - 1] };
+            cur[j] = if x == y { prev[j - 1] + 1 } else { prev[j].max(cur[j - 1]) };
+        }
+        std::mem::swap(&mut prev, &mut cur);
+    }
+    prev
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::Rng;
+
+    /// Internal function to check that pairs of indices are a common subsequence
+    fn is_common_subsequence<T: Eq>(a: &[T], b: &[T], pairs: &[(usize, usize)]) -> bool {
+        pairs.windows(2).all(|w| w[0].0 < w[1].0 && w[0].1 < w[1].1) && pairs.iter().all(|&(i, j)| a[i] == b[j])
+    }
+
+    #[test]
+    fn test_lcs() {
+        let (a, b) = (b"ABCBDAB", b"BDCABA");
+        assert_eq!(4, lcs_length(a, b));
+        assert_eq!(4, lcs_length(b, a));
+        let pairs = lcs(a, b);
+        assert_eq!(4, pairs.len());
+        assert!(is_common_subsequence(a, b, &pairs));
+        assert_eq!(b"BCBA".to_vec(), pairs.iter().map(|&(i, _)| a[i]).collect::<Vec<_>>());
+        assert_eq!(0, lcs_length(b"abc", b"xyz"));
+        assert!(lcs::<u8>(&[], b"abc").is_empty());
+    }
+
+    #[test]
+    fn test_hirschberg() {
+        let (a, b) = (b"ABCBDAB", b"BDCABA");
+        let pairs = hirschberg(a, b);
+        assert_eq!(4, pairs.len());
+        assert!(is_common_subsequence(a, b, &pairs));
+        assert!(hirschberg::<u8>(b"abc", &[]).is_empty());
+        let mut rng = Rng::new(439);
+        for _ in 0..200 {
+            let a: Vec<u8> = (0..rng.gen_range(60)).map(|_| rng.gen_range(4) as u8).collect();
+            let b: Vec<u8> = (0..rng.gen_range(60)).map(|_| rng.gen_range(4) as u8).collect();
+            let pairs = hirschberg(&a, &b);
+            assert_eq!(lcs(&a, &b).len(), pairs.len());
+            assert_eq!(lcs_length(&a, &b), pairs.len());
+            assert!(is_common_subsequence(&a, &b, &pairs));
+        }
+    }
+
+    #[test]
+    fn test_diff() {
+        let old = ["fn main() {", "    let x = 1;", "    println!(x);", "}"];
+        let new = ["fn main() {", "    let x = 2;", "    println!(x);", "    return;", "}"];
+        assert_eq!(
+            vec![
+                Edit::Keep { a: 0, b: 0 },
+                Edit::Delete { a: 1 },
+                Edit::Insert { b: 1 },
+                Edit::Keep { a: 2, b: 2 },
+                Edit::Insert { b: 3 },
+                Edit::Keep { a: 3, b: 4 },
+            ],
+            diff(&old, &new)
+        );
+        assert_eq!(vec![Edit::Insert { b: 0 }], diff(&[], &[7]));
+        let mut rng = Rng::new(443);
+        for _ in 0..100 {
+            let a: Vec<u8> = (0..rng.gen_range(30)).map(|_| rng.gen_range(3) as u8).collect();
+            let b: Vec<u8> = (0..rng.gen_range(30)).map(|_| rng.gen_range(3) as u8).collect();
+            let edits = diff(&a, &b);
+            let kept = edits.iter().filter(|e| matches!(e, Edit::Keep { .. })).count();
+            assert_eq!(lcs_length(&a, &b), kept);
+            assert_eq!(a.len() + b.len() - 2 * kept, edits.len() - kept);
+        }
+    }
+}
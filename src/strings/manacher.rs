@@ -0,0 +1,144 @@
+use std::ops::Range;
+
+/// Computes the length of the longest palindrome around every center of a sequence with
+/// Manacher's algorithm
+///
+/// A sequence of `n` items has `2n - 1` centers: center `2i` is item `i`, where the odd length
+/// palindromes are, and center `2i + 1` is between items `i` and `i + 1`, where the even length
+/// ones are. The palindrome of length `l` around center `c` starts at `(c + 1 - l)/2`.
+///
+/// Expanding around every center takes `O(n^2)` time. Manacher's algorithm keeps the palindrome
+/// reaching furthest right, and a center inside it starts from the length of its mirror image
+/// around that palindrome's center, capped at its right end, so only items past the right end
+/// are ever compared[1]. The right end only moves right, so it takes `O(n)` time. Puts a `None`
+/// between and around the items, so both kinds of center are handled as one.
+///
+/// [1] Manacher, Glenn. "A New Linear-Time "On-Line" Algorithm for Finding the Smallest Initial
+/// Palindrome of a String." <i>Journal of the ACM<i>, 1975.
+///
+/// # Arguments
+///
+/// * `s` - Sequence to find the palindromes of
+pub fn palindrome_lengths<T: Eq>(s: &[T]) -> Vec<usize> {
+    if s.is_empty() {
+        return Vec::new();
+    }
+    let t: Vec<Option<&T>> = std::iter::once(None).chain(s.iter().flat_map(|x| [Some(x), None])).collect();
+    let m = t.len();
+    // t[j - radius[j]..=j + radius[j]] is the longest palindrome around j
+    let mut radius = vec![0; m];
+    let (mut center, mut right) = (0, 0);
+    for j in 0..m {
+        let mut k = if j < right { radius[2 * center - j].min(right - j) } else { 0 };
+        while j > k && j + k + 1 < m && t[j - k - 1] == t[j + k + 1] {
+            k += 1;
+        }
+        radius[j] = k;
+        if j + k > right {
+            center = j;
+            right = j + k;
+        }
+    }
+    // A longest palindrome of t ends on a None, so its radius counts the items in it
+    radius[1..m - 1].to_vec()
+}
+
+/// Finds the longest palindromic substring of a sequence in `O(n)` time, the first if several are
+/// longest
+///
+/// # Arguments
+///
+/// * `s` - Sequence to search
+pub fn longest_palindrome<T: Eq>(s: &[T]) -> Range<usize> {
+    let lengths = palindrome_lengths(s);
+    match lengths.iter().enumerate().max_by_key(|&(c, &l)| (l, std::cmp::Reverse(c))) {
+        Some((c, &l)) => (c + 1 - l) / 2..(c + 1 + l) / 2,
+        None => 0..0,
+    }
+}
+
+/// Counts the palindromic substrings of a sequence, by position, in `O(n)` time
+///
+/// Every palindrome around a center contains the shorter ones around the same center, so a
+/// center whose longest palindrome has length `l` is the center of `(l + 1)/2` palindromes. Equal
+/// substrings at different positions count separately.
+///
+/// # Arguments
+///
+/// * `s` - Sequence to count the palindromes of
+pub fn count_palindromes<T: Eq>(s: &[T]) -> u64 {
+    palindrome_lengths(s).iter().map(|&l| l.div_ceil(2) as u64).sum()
+}
+
+/// Counts the centers of a sequence with a palindrome of at least a length around them
+///
+/// # Arguments
+///
+/// * `s` - Sequence to search
+/// * `min_len` - Shortest palindrome to count a center for
+pub fn palindromic_centers<T: Eq>(s: &[T], min_len: usize) -> usize {
+    palindrome_lengths(s).iter().filter(|&&l| l >= min_len.max(1)).count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::Rng;
+
+    /// Internal function to find the longest palindrome around each center by expanding it
+    fn expand<T: Eq>(s: &[T]) -> Vec<usize> {
+        let n = s.len();
+        (0..(2 * n).saturating_sub(1))
+            .map(|c| {
+                let (mut lo, mut hi) = ((c / 2) as isize, c.div_ceil(2) as isize);
+                while lo >= 0 && (hi as usize) < n && s[lo as usize] == s[hi as usize] {
+                    lo -= 1;
+                    hi += 1;
+                }
+                (hi - lo - 1) as usize
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_palindrome_lengths() {
+        assert_eq!(vec![1, 0, 3, 0, 1, 0, 7, 0, 1, 0, 3, 0, 1], palindrome_lengths(b"abacaba"));
+        assert_eq!(vec![1, 2, 3, 2, 1], palindrome_lengths(b"aaa"));
+        assert_eq!(vec![1, 0, 1, 4, 1, 0, 1], palindrome_lengths(b"abba"));
+        assert!(palindrome_lengths::<u8>(&[]).is_empty());
+        let mut rng = Rng::new(419);
+        for n in [1, 2, 10, 500] {
+            for alphabet in [1, 2, 3] {
+                let s: Vec<u64> = (0..n).map(|_| rng.gen_range(alphabet)).collect();
+                assert_eq!(expand(&s), palindrome_lengths(&s));
+            }
+        }
+    }
+
+    #[test]
+    fn test_longest_palindrome() {
+        let s = b"forgeeksskeegfor";
+        assert_eq!(b"geeksskeeg", &s[longest_palindrome(s)]);
+        assert_eq!(1..8, longest_palindrome(b"xracecarz"));
+        assert_eq!(0..1, longest_palindrome(b"abc"));
+        assert_eq!(0..0, longest_palindrome::<u8>(&[]));
+        // Any items that can be compared
+        let words = ["step", "on", "no", "pets", "no", "on", "step"];
+        assert_eq!(0..7, longest_palindrome(&words));
+    }
+
+    #[test]
+    fn test_counts() {
+        // 7 single items, aba twice, aca, bacab, and abacaba
+        assert_eq!(12, count_palindromes(b"abacaba"));
+        // 3 of length 1, 2 of length 2, 1 of length 3
+        assert_eq!(6, count_palindromes(b"aaa"));
+        assert_eq!(0, count_palindromes::<u8>(&[]));
+        assert_eq!(3, palindromic_centers(b"abacaba", 3));
+        assert_eq!(7, palindromic_centers(b"abacaba", 0));
+        let mut rng = Rng::new(421);
+        let s: Vec<u8> = (0..200).map(|_| rng.gen_range(2) as u8).collect();
+        let brute = (0..=s.len()).flat_map(|i| (i + 1..=s.len()).map(move |j| (i, j))).filter(|&(i, j)| s[i..j].iter().eq(s[i..j].iter().rev())).count();
+        assert_eq!(brute as u64, count_palindromes(&s));
+    }
+}
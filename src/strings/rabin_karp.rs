@@ -0,0 +1,377 @@
+use std::collections::HashMap;
+use std::ops::Range;
+
+use crate::rng::Rng;
+use crate::universal_hasher::MERSENNE_61;
+
+/// A polynomial hash of fixed length windows that slides one item at a time in `O(1)` time
+///
+/// Hashes a window `s` of `m` items as `(s[0]*b^(m-1) + s[1]*b^(m-2) + ... + s[m-1]) mod p` for a
+/// base `b` and modulus `p`. Sliding the window one item right subtracts the leaving item's term,
+/// multiplies by `b`, and adds the new item, without looking at the rest of the window[1]. Two
+/// different windows collide for at most `m - 1` bases, the roots of their difference, so with a
+/// prime modulus and a random base they collide with probability at most `(m - 1)/p`, whatever
+/// the input.
+///
+/// [1] Karp, Richard M., and Michael O. Rabin. "Efficient Randomized Pattern-Matching
+/// Algorithms." <i>IBM Journal of Research and Development<i>, 1987.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RollingHash {
+    base: u64,
+    modulus: u64,
+    window_len: usize,
+    leading_power: u64,
+}
+
+impl RollingHash {
+
+    /// Creates a new `RollingHash` for windows of a length
+    ///
+    /// # Arguments
+    ///
+    /// * `window_len` - Number of items in a window
+    /// * `base` - Base of the polynomial, between 1 and `modulus - 1`
+    /// * `modulus` - Modulus of the hashes, at least 2 and best prime
+    ///
+    /// # Panics
+    ///
+    /// If the modulus is less than 2 or the base isn't between 1 and `modulus - 1`
+    pub fn new(window_len: usize, base: u64, modulus: u64) -> RollingHash {
+        assert!(modulus >= 2, "modulus must be at least 2");
+        assert!((1..modulus).contains(&base), "base must be between 1 and the modulus - 1");
+        let leading_power = pow_mod(base, window_len.saturating_sub(1) as u64, modulus);
+        RollingHash { base, modulus, window_len, leading_power }
+    }
+
+    /// Creates a new `RollingHash` with the prime modulus `2^61 - 1` and a random base
+    ///
+    /// # Arguments
+    ///
+    /// * `window_len` - Number of items in a window
+    /// * `rng` - Source of the random base
+    pub fn random(window_len: usize, rng: &mut Rng) -> RollingHash {
+        RollingHash::new(window_len, rng.gen_range(MERSENNE_61 - 2) + 2, MERSENNE_61)
+    }
+
+    /// Number of items in a window
+    pub fn window_len(&self) -> usize {
+        self.window_len
+    }
+
+    /// Base of the polynomial
+    pub fn base(&self) -> u64 {
+        self.base
+    }
+
+    /// Modulus of the hashes
+    pub fn modulus(&self) -> u64 {
+        self.modulus
+    }
+
+    /// Hashes a window
+    ///
+    /// # Arguments
+    ///
+    /// * `window` - Items to hash
+    ///
+    /// # Panics
+    ///
+    /// If the window isn't `window_len` items long
+    pub fn hash<T: Copy + Into<u64>>(&self, window: &[T]) -> u64 {
+        assert_eq!(self.window_len, window.len(), "window has the wrong length");
+        window.iter().fold(0, |hash, &x| add_mod(mul_mod(hash, self.base, self.modulus), x.into() % self.modulus, self.modulus))
+    }
+
+    /// Slides a window's hash one item right, dropping its first item and appending another
+    ///
+    /// # Arguments
+    ///
+    /// * `hash` - Hash of the window
+    /// * `outgoing` - First item of the window
+    /// * `incoming` - Item after the window
+    pub fn roll<T: Copy + Into<u64>>(&self, hash: u64, outgoing: T, incoming: T) -> u64 {
+        let leading = mul_mod(outgoing.into() % self.modulus, self.leading_power, self.modulus);
+        let rest = add_mod(hash, self.modulus - leading, self.modulus);
+        add_mod(mul_mod(rest, self.base, self.modulus), incoming.into() % self.modulus, self.modulus)
+    }
+
+    /// Hashes every window of a text, in order, in `O(n)` time
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - Text to slide the window over
+    pub fn windows<'t, T: Copy + Into<u64>>(&self, text: &'t [T]) -> WindowHashes<'t, T> {
+        WindowHashes { hasher: *self, text, start: 0, hash: None }
+    }
+}
+
+/// Iterator over the hashes of every window of a text, made by `RollingHash::windows`
+#[derive(Clone, Debug)]
+pub struct WindowHashes<'t, T> {
+    hasher: RollingHash,
+    text: &'t [T],
+    start: usize,
+    hash: Option<u64>,
+}
+
+impl<'t, T: Copy + Into<u64>> Iterator for WindowHashes<'t, T> {
+    type Item = u64;
+
+    fn next(&mut self) -> Option<u64> {
+        let m = self.hasher.window_len;
+        if self.start + m > self.text.len() {
+            return None;
+        }
+        let hash = match self.hash {
+            Some(hash) if m > 0 => self.hasher.roll(hash, self.text[self.start - 1], self.text[self.start + m - 1]),
+            _ => self.hasher.hash(&self.text[self.start..self.start + m]),
+        };
+        self.hash = Some(hash);
+        self.start += 1;
+        Some(hash)
+    }
+}
+
+/// Finds the starting index of every match of a pattern in a text with the Rabin–Karp algorithm
+///
+/// Slides a `RollingHash` with a random base over the text and compares the pattern with only the
+/// windows whose hash equals its own, in expected `O(n + m)` time. Every candidate is checked, so
+/// a collision costs time but never gives a wrong match. Overlapping matches are included, and an
+/// empty pattern matches at every index, up to and including the length of the text.
+///
+/// # Arguments
+///
+/// * `text` - Text to search
+/// * `pattern` - Pattern to search for
+pub fn rabin_karp<T: Copy + Eq + Into<u64>>(text: &[T], pattern: &[T]) -> Vec<usize> {
+    rabin_karp_multi(text, &[pattern]).into_iter().map(|(i, _)| i).collect()
+}
+
+/// Finds every match of any of several patterns in a text with the Rabin–Karp algorithm, as
+/// pairs of a starting index and the index of the pattern
+///
+/// Puts the hashes of the patterns of each length in a hash map, then slides one window of each
+/// length over the text and looks up every window's hash, in expected `O(n*l + M)` time for `l`
+/// distinct pattern lengths and `M` items in all the patterns. Matches come out in order of
+/// starting index, then pattern index. Repeated patterns each match.
+///
+/// # Arguments
+///
+/// * `text` - Text to search
+/// * `patterns` - Patterns to search for
+pub fn rabin_karp_multi<T: Copy + Eq + Into<u64>>(text: &[T], patterns: &[&[T]]) -> Vec<(usize, usize)> {
+    let mut rng = Rng::from_entropy();
+    let mut by_len: HashMap<usize, Vec<usize>> = HashMap::new();
+    for (p, pattern) in patterns.iter().enumerate() {
+        by_len.entry(pattern.len()).or_default().push(p);
+    }
+
+    let mut matches = Vec::new();
+    for (len, group) in by_len {
+        if len == 0 {
+            matches.extend((0..=text.len()).flat_map(|i| group.iter().map(move |&p| (i, p))));
+            continue;
+        }
+        let hasher = RollingHash::random(len, &mut rng);
+        let mut by_hash: HashMap<u64, Vec<usize>> = HashMap::new();
+        for &p in &group {
+            by_hash.entry(hasher.hash(patterns[p])).or_default().push(p);
+        }
+        for (i, hash) in hasher.windows(text).enumerate() {
+            if let Some(candidates) = by_hash.get(&hash) {
+                // Check the items, so a collision can't give a false match
+                matches.extend(candidates.iter().filter(|&&p| &text[i..i + len] == patterns[p]).map(|&p| (i, p)));
+            }
+        }
+    }
+    matches.sort_unstable();
+    matches
+}
+
+/// Hashes of every prefix of a text, to hash any of its substrings in `O(1)` time
+///
+/// Stores the polynomial hash of `text[..i]` for every `i`, and the powers of the base, in `O(n)`
+/// time. The hash of `text[i..j]` is the hash of the prefix ending at `j` minus the hash of the
+/// prefix ending at `i` shifted up `j - i` places, the same as `RollingHash` gives it. Comparing
+/// hashes then tells if two substrings are equal in `O(1)` time, wrongly with probability at most
+/// `(l - 1)/p` for substrings of length `l`, which is why it's the basis of suffix array and
+/// longest common prefix tricks.
+#[derive(Clone, Debug)]
+pub struct PrefixHashes {
+    base: u64,
+    modulus: u64,
+    prefixes: Vec<u64>,
+    powers: Vec<u64>,
+}
+
+impl PrefixHashes {
+
+    /// Creates a new `PrefixHashes` of a text
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - Text to hash the substrings of
+    /// * `base` - Base of the polynomial, between 1 and `modulus - 1`
+    /// * `modulus` - Modulus of the hashes, at least 2 and best prime
+    ///
+    /// # Panics
+    ///
+    /// If the modulus is less than 2 or the base isn't between 1 and `modulus - 1`
+    pub fn new<T: Copy + Into<u64>>(text: &[T], base: u64, modulus: u64) -> PrefixHashes {
+        assert!(modulus >= 2, "modulus must be at least 2");
+        assert!((1..modulus).contains(&base), "base must be between 1 and the modulus - 1");
+        let mut prefixes = Vec::with_capacity(text.len() + 1);
+        let mut powers = Vec::with_capacity(text.len() + 1);
+        prefixes.push(0);
+        powers.push(1);
+        for (i, &x) in text.iter().enumerate() {
+            prefixes.push(add_mod(mul_mod(prefixes[i], base, modulus), x.into() % modulus, modulus));
+            powers.push(mul_mod(powers[i], base, modulus));
+        }
+        PrefixHashes { base, modulus, prefixes, powers }
+    }
+
+    /// Creates a new `PrefixHashes` of a text with the prime modulus `2^61 - 1` and a random base
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - Text to hash the substrings of
+    /// * `rng` - Source of the random base
+    pub fn random<T: Copy + Into<u64>>(text: &[T], rng: &mut Rng) -> PrefixHashes {
+        PrefixHashes::new(text, rng.gen_range(MERSENNE_61 - 2) + 2, MERSENNE_61)
+    }
+
+    /// Length of the text
+    pub fn len(&self) -> usize {
+        self.prefixes.len() - 1
+    }
+
+    /// Checks if the text is empty
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Hash of a substring, equal to its hash by a `RollingHash` with the same base and modulus
+    ///
+    /// # Arguments
+    ///
+    /// * `range` - Range of the substring
+    ///
+    /// # Panics
+    ///
+    /// If the range is out of bounds of the text
+    pub fn hash(&self, range: Range<usize>) -> u64 {
+        assert!(range.start <= range.end && range.end <= self.len(), "range {:?} is out of bounds for a text of length {}", range, self.len());
+        let shifted = mul_mod(self.prefixes[range.start], self.powers[range.len()], self.modulus);
+        add_mod(self.prefixes[range.end], self.modulus - shifted, self.modulus)
+    }
+
+    /// Checks if two substrings are equal by their hashes, in `O(1)` time
+    ///
+    /// Equal substrings are always reported equal. Different substrings of length `l` are
+    /// reported equal with probability at most `(l - 1)/p` over a random base.
+    ///
+    /// # Arguments
+    ///
+    /// * `a` - Range of the first substring
+    /// * `b` - Range of the second substring
+    ///
+    /// # Panics
+    ///
+    /// If a range is out of bounds of the text
+    pub fn substrings_equal(&self, a: Range<usize>, b: Range<usize>) -> bool {
+        a.len() == b.len() && self.hash(a) == self.hash(b)
+    }
+
+    /// Base of the polynomial
+    pub fn base(&self) -> u64 {
+        self.base
+    }
+}
+
+/// Internal function to add two numbers below a modulus
+fn add_mod(a: u64, b: u64, modulus: u64) -> u64 {
+    ((a as u128 + b as u128) % modulus as u128) as u64
+}
+
+/// Internal function to multiply two numbers below a modulus
+fn mul_mod(a: u64, b: u64, modulus: u64) -> u64 {
+    ((a as u128 * b as u128) % modulus as u128) as u64
+}
+
+/// Internal function to raise a number to a power by repeated squaring
+fn pow_mod(mut base: u64, mut exponent: u64, modulus: u64) -> u64 {
+    let mut result = 1 % modulus;
+    while exponent > 0 {
+        if exponent & 1 == 1 {
+            result = mul_mod(result, base, modulus);
+        }
+        base = mul_mod(base, base, modulus);
+        exponent >>= 1;
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rolling_hash() {
+        let text = b"abracadabra";
+        // Base 256 and a small modulus, so the hashes can be checked by hand
+        let hasher = RollingHash::new(3, 256, 1009);
+        assert_eq!((97 * 256 * 256 + 98 * 256 + 114) % 1009, hasher.hash(b"abr"));
+        let rolled: Vec<u64> = hasher.windows(text).collect();
+        let direct: Vec<u64> = text.windows(3).map(|w| hasher.hash(w)).collect();
+        assert_eq!(direct, rolled);
+        assert_eq!(rolled[0], rolled[7]);
+        assert_eq!(0, hasher.windows(b"ab").count());
+
+        let mut rng = Rng::new(367);
+        let hasher = RollingHash::random(4, &mut rng);
+        let prefixes = PrefixHashes::new(text, hasher.base(), hasher.modulus());
+        for (i, hash) in hasher.windows(text).enumerate() {
+            assert_eq!(hash, prefixes.hash(i..i + 4));
+        }
+    }
+
+    #[test]
+    fn test_search() {
+        let text = "the cat sat on the mat with the other cat".as_bytes();
+        assert_eq!(vec![0, 15, 28, 33], rabin_karp(text, b"the"));
+        assert_eq!(vec![0, 1, 2], rabin_karp(b"aaaa", b"aa"));
+        assert_eq!(vec![0, 1, 2], rabin_karp(b"ab", b""));
+        assert!(rabin_karp(b"ab", b"abc").is_empty());
+        let patterns: [&[u8]; 4] = [b"cat", b"at", b"the", b"dog"];
+        assert_eq!(
+            vec![(0, 2), (4, 0), (5, 1), (9, 1), (15, 2), (20, 1), (28, 2), (33, 2), (38, 0), (39, 1)],
+            rabin_karp_multi(text, &patterns)
+        );
+        // A tiny modulus makes collisions common, but candidates are checked
+        let mut rng = Rng::new(373);
+        let dna: Vec<u8> = (0..3000).map(|_| b"ACGT"[rng.gen_range(4) as usize]).collect();
+        let hasher = RollingHash::new(6, 4, 7);
+        let pattern = &dna[100..106];
+        let expected: Vec<usize> = dna.windows(6).enumerate().filter(|(_, w)| w == &pattern).map(|(i, _)| i).collect();
+        let candidates: Vec<usize> = hasher.windows(&dna).enumerate().filter(|&(_, h)| h == hasher.hash(pattern)).map(|(i, _)| i).collect();
+        assert!(candidates.len() > expected.len());
+        assert_eq!(expected, rabin_karp(&dna, pattern));
+    }
+
+    #[test]
+    fn test_substrings_equal() {
+        let mut rng = Rng::new(379);
+        let text: Vec<u8> = (0..300).map(|_| b'a' + rng.gen_range(2) as u8).collect();
+        let hashes = PrefixHashes::random(&text, &mut rng);
+        assert_eq!(300, hashes.len());
+        for _ in 0..2000 {
+            let len = rng.gen_range(10) as usize;
+            let i = rng.gen_range((300 - len + 1) as u64) as usize;
+            let j = rng.gen_range((300 - len + 1) as u64) as usize;
+            assert_eq!(text[i..i + len] == text[j..j + len], hashes.substrings_equal(i..i + len, j..j + len));
+        }
+        assert!(!hashes.substrings_equal(0..3, 0..4));
+        assert!(PrefixHashes::new::<u8>(&[], 31, 101).is_empty());
+    }
+}
@@ -0,0 +1,128 @@
+/// Computes the Z-array of a sequence, the length of the longest common prefix of the sequence
+/// and each of its suffixes
+///
+/// `z[i]` is the number of items from `i` that match the start of the sequence, and `z[0]` is the
+/// whole length by convention. Keeps the rightmost window `s[l..r]` known to match a prefix. An
+/// index inside it starts out matching as far as the index `l` places earlier, capped at the end
+/// of the window, and only items past `r` are compared, which push `r` right. `r` only moves right,
+/// so it takes `O(n)` time in total[1].
+///
+/// [1] Gusfield, Dan. "Algorithms on Strings, Trees, and Sequences." <i>Cambridge University
+/// Press<i>, 1997.
+///
+/// # Arguments
+///
+/// * `s` - Sequence to compute the Z-array of
+pub fn z_array<T: Eq>(s: &[T]) -> Vec<usize> {
+    let n = s.len();
+    let mut z = vec![0; n];
+    if n == 0 {
+        return z;
+    }
+    z[0] = n;
+    let (mut l, mut r) = (0, 0);
+    for i in 1..n {
+        if i < r {
+            z[i] = z[i - l].min(r - i);
+        }
+        while i + z[i] < n && s[z[i]] == s[i + z[i]] {
+            z[i] += 1;
+        }
+        if i + z[i] > r {
+            l = i;
+            r = i + z[i];
+        }
+    }
+    z
+}
+
+/// Finds the starting index of every match of a pattern in a text with the Z-algorithm
+///
+/// Computes the Z-array of the pattern, a separator, and the text, in `O(n + m)` time. The
+/// separator matches no item, so no Z-value reaches past the pattern, and the text matches at
+/// every index whose Z-value is the whole pattern. The separator is `None` in a sequence of
+/// `Option`s, so any items that can be compared work, not just characters with a spare value
+/// like `$`. Overlapping matches are included, and an empty pattern matches at every index, up to
+/// and including the length of the text.
+///
+/// # Arguments
+///
+/// * `text` - Text to search
+/// * `pattern` - Pattern to search for
+pub fn z_search<T: Eq>(text: &[T], pattern: &[T]) -> Vec<usize> {
+    let m = pattern.len();
+    let joined: Vec<Option<&T>> = pattern.iter().map(Some).chain(std::iter::once(None)).chain(text.iter().map(Some)).collect();
+    let z = z_array(&joined);
+    (0..=text.len()).filter(|&i| m == 0 || z.get(m + 1 + i) == Some(&m)).collect()
+}
+
+/// Finds every period of a sequence, every `p` where each item equals the one `p` after it,
+/// smallest first
+///
+/// Shifting the sequence by `p` lines it up with itself exactly when the suffix from `p` matches
+/// a prefix all the way to the end, that is when `z[p] == n - p`. The periods are the lengths
+/// minus the borders found by the prefix function, in the other order. The length itself always
+/// counts as a period.
+///
+/// # Arguments
+///
+/// * `s` - Sequence to find the periods of
+pub fn periods<T: Eq>(s: &[T]) -> Vec<usize> {
+    let n = s.len();
+    let z = z_array(s);
+    (1..n).filter(|&p| z[p] == n - p).chain(std::iter::once(n).filter(|&n| n > 0)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::kmp::{find_all, FailureFunction};
+    use crate::rng::Rng;
+
+    #[test]
+    fn test_z_array() {
+        assert_eq!(vec![7, 0, 1, 0, 3, 0, 1], z_array(b"abacaba"));
+        assert_eq!(vec![5, 4, 3, 2, 1], z_array(b"aaaaa"));
+        assert_eq!(vec![8, 0, 0, 4, 0, 0, 1, 0], z_array(b"abcabcax"));
+        assert!(z_array::<u8>(&[]).is_empty());
+        // Matches a quadratic computation
+        let mut rng = Rng::new(383);
+        let s: Vec<u8> = (0..500).map(|_| rng.gen_range(3) as u8).collect();
+        for (i, &z) in z_array(&s).iter().enumerate() {
+            assert_eq!(s.iter().zip(&s[i..]).take_while(|(a, b)| a == b).count(), z);
+        }
+    }
+
+    #[test]
+    fn test_z_search() {
+        let text = "the cat sat on the mat with the other cat".as_bytes();
+        assert_eq!(vec![0, 15, 28, 33], z_search(text, b"the"));
+        assert_eq!(vec![0, 1, 2], z_search(b"aaaa", b"aa"));
+        assert_eq!(vec![0, 1, 2], z_search(b"ab", b""));
+        assert!(z_search(b"ab", b"abc").is_empty());
+        let mut rng = Rng::new(389);
+        let text: Vec<u8> = (0..5000).map(|_| rng.gen_range(2) as u8).collect();
+        for m in 1..8 {
+            let pattern = &text[m * 100..m * 101];
+            assert_eq!(find_all(&text, pattern).collect::<Vec<_>>(), z_search(&text, pattern));
+        }
+    }
+
+    #[test]
+    fn test_periods() {
+        assert_eq!(vec![5, 8, 10], periods(b"abaababaab"));
+        assert_eq!(vec![3, 6, 9], periods(b"abcabcabc"));
+        assert_eq!(vec![1, 2, 3], periods(b"aaa"));
+        assert!(periods::<u8>(&[]).is_empty());
+        // The periods are the length minus each border from the prefix function
+        let mut rng = Rng::new(397);
+        for _ in 0..50 {
+            let s: Vec<u8> = (0..rng.gen_range(30) + 1).map(|_| rng.gen_range(2) as u8).collect();
+            let f = FailureFunction::new(&s);
+            let mut expected: Vec<usize> = f.borders().iter().map(|b| s.len() - b).collect();
+            expected.push(s.len());
+            assert_eq!(expected, periods(&s));
+            assert_eq!(f.period(), periods(&s)[0]);
+        }
+    }
+}
@@ -0,0 +1,269 @@
+use std::iter::FromIterator;
+
+/// Smallest gap left after the buffer grows
+const MIN_GAP: usize = 16;
+
+/// A sequence with a cursor, kept as an array with a gap of empty slots at the cursor
+///
+/// Items before the cursor are at the start of the array and items after it are at the end, with
+/// the gap between them. Inserting at the cursor fills the front of the gap and deleting widens
+/// it, both in `O(1)` time, and moving the cursor `d` places shifts `d` items across the gap. Edits
+/// made together in one place, like typing, are cheap, while jumping around a large buffer isn't.
+/// When the gap is used up, the array doubles and the gap grows to fill the new space.
+#[derive(Clone, Debug)]
+pub struct GapBuffer<T> {
+    buffer: Vec<Option<T>>,
+    gap_start: usize,
+    gap_end: usize,
+}
+
+impl<T> GapBuffer<T> {
+
+    /// Creates a new empty `GapBuffer`
+    pub fn new() -> GapBuffer<T> {
+        GapBuffer::with_capacity(0)
+    }
+
+    /// Creates a new empty `GapBuffer` with room for `capacity` items before growing
+    ///
+    /// # Arguments
+    ///
+    /// * `capacity` - Number of items to make room for
+    pub fn with_capacity(capacity: usize) -> GapBuffer<T> {
+        let buffer = (0..capacity).map(|_| None).collect();
+        GapBuffer { buffer, gap_start: 0, gap_end: capacity }
+    }
+
+    /// Number of items in the buffer
+    pub fn len(&self) -> usize {
+        self.buffer.len() - self.gap_len()
+    }
+
+    /// Checks if the buffer is empty
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Number of items the buffer can hold before growing
+    pub fn capacity(&self) -> usize {
+        self.buffer.len()
+    }
+
+    /// Number of empty slots in the gap
+    pub fn gap_len(&self) -> usize {
+        self.gap_end - self.gap_start
+    }
+
+    /// Position of the cursor, the number of items before it
+    pub fn cursor(&self) -> usize {
+        self.gap_start
+    }
+
+    /// Moves the cursor to a position, shifting every item between it and the cursor across the gap
+    ///
+    /// # Arguments
+    ///
+    /// * `position` - Number of items to leave before the cursor
+    ///
+    /// # Panics
+    ///
+    /// If `position` is greater than the length of the buffer
+    pub fn move_to(&mut self, position: usize) {
+        assert!(position <= self.len(), "position {} is past the end of a buffer of length {}", position, self.len());
+        while self.gap_start > position {
+            self.move_left();
+        }
+        while self.gap_start < position {
+            self.move_right();
+        }
+    }
+
+    /// Moves the cursor back one item, and returns false if it's already at the start
+    pub fn move_left(&mut self) -> bool {
+        if self.gap_start == 0 {
+            return false;
+        }
+        self.gap_start -= 1;
+        self.gap_end -= 1;
+        self.buffer[self.gap_end] = self.buffer[self.gap_start].take();
+        true
+    }
+
+    /// Moves the cursor forward one item, and returns false if it's already at the end
+    pub fn move_right(&mut self) -> bool {
+        if self.gap_end == self.buffer.len() {
+            return false;
+        }
+        self.buffer[self.gap_start] = self.buffer[self.gap_end].take();
+        self.gap_start += 1;
+        self.gap_end += 1;
+        true
+    }
+
+    /// Inserts an item at the cursor, leaving the cursor after it
+    ///
+    /// # Arguments
+    ///
+    /// * `item` - Item to insert
+    pub fn insert(&mut self, item: T) {
+        if self.gap_start == self.gap_end {
+            self.grow();
+        }
+        self.buffer[self.gap_start] = Some(item);
+        self.gap_start += 1;
+    }
+
+    /// Removes and returns the item before the cursor, like backspace, or None at the start
+    pub fn delete_before(&mut self) -> Option<T> {
+        if self.gap_start == 0 {
+            return None;
+        }
+        self.gap_start -= 1;
+        self.buffer[self.gap_start].take()
+    }
+
+    /// Removes and returns the item after the cursor, like delete, or None at the end
+    pub fn delete_after(&mut self) -> Option<T> {
+        if self.gap_end == self.buffer.len() {
+            return None;
+        }
+        self.gap_end += 1;
+        self.buffer[self.gap_end - 1].take()
+    }
+
+    /// Gets the item at an index, or None if there are `i` items or fewer
+    ///
+    /// # Arguments
+    ///
+    /// * `i` - Index of the item, not counting the gap
+    pub fn get(&self, i: usize) -> Option<&T> {
+        let slot = if i < self.gap_start { i } else { i + self.gap_len() };
+        self.buffer.get(slot)?.as_ref()
+    }
+
+    /// Returns an iterator over the items in order, skipping the gap
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = &T> + '_ {
+        let (before, rest) = self.buffer.split_at(self.gap_start);
+        let after = &rest[self.gap_len()..];
+        before.iter().chain(after).map(|slot| slot.as_ref().unwrap())
+    }
+
+    /// Internal function to double the buffer, moving the items after the gap to the new end
+    fn grow(&mut self) {
+        let capacity = (2 * self.buffer.len()).max(MIN_GAP);
+        let added = capacity - self.buffer.len();
+        let after = self.buffer.split_off(self.gap_end);
+        self.buffer.extend((0..added).map(|_| None));
+        self.gap_end = self.buffer.len();
+        self.buffer.extend(after);
+    }
+}
+
+impl<T> Default for GapBuffer<T> {
+    fn default() -> Self {
+        GapBuffer::new()
+    }
+}
+
+impl<T> From<Vec<T>> for GapBuffer<T> {
+    fn from(items: Vec<T>) -> Self {
+        let len = items.len();
+        let buffer = items.into_iter().map(Some).collect();
+        GapBuffer { buffer, gap_start: len, gap_end: len }
+    }
+}
+
+impl<T> From<GapBuffer<T>> for Vec<T> {
+    fn from(buffer: GapBuffer<T>) -> Self {
+        buffer.buffer.into_iter().flatten().collect()
+    }
+}
+
+impl<T> Extend<T> for GapBuffer<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for item in iter {
+            self.insert(item);
+        }
+    }
+}
+
+impl<T> FromIterator<T> for GapBuffer<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        GapBuffer::from(iter.into_iter().collect::<Vec<T>>())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::Rng;
+
+    #[test]
+    fn test_editing() {
+        let mut text: GapBuffer<char> = "hello world".chars().collect();
+        assert_eq!(11, text.cursor());
+        text.move_to(5);
+        text.extend(",".chars());
+        text.move_to(text.len());
+        for _ in 0..5 {
+            text.delete_before();
+        }
+        text.extend("there".chars());
+        assert_eq!("hello, there", text.iter().collect::<String>());
+        text.move_to(0);
+        assert_eq!(Some('h'), text.delete_after());
+        text.insert('H');
+        assert_eq!(Some(&'H'), text.get(0));
+        assert_eq!(Some(&'e'), text.get(1));
+        assert_eq!(None, text.get(12));
+        assert_eq!("Hello, there", text.iter().collect::<String>());
+    }
+
+    #[test]
+    fn test_cursor_ends() {
+        let mut buffer = GapBuffer::new();
+        assert!(!buffer.move_left());
+        assert!(!buffer.move_right());
+        assert_eq!(None, buffer.delete_before());
+        assert_eq!(None, buffer.delete_after());
+        buffer.insert(1);
+        buffer.insert(2);
+        assert!(buffer.move_left());
+        assert!(buffer.move_right());
+        assert!(!buffer.move_right());
+        assert_eq!(vec![2, 1], buffer.iter().rev().copied().collect::<Vec<_>>());
+        assert_eq!(vec![1, 2], Vec::from(buffer));
+    }
+
+    #[test]
+    fn test_matches_vec() {
+        let mut rng = Rng::new(127);
+        let mut buffer = GapBuffer::new();
+        let mut expected = Vec::new();
+        let mut cursor = 0;
+        for i in 0..5000 {
+            match rng.gen_range(6) {
+                0 => {
+                    cursor = rng.gen_range(expected.len() as u64 + 1) as usize;
+                    buffer.move_to(cursor);
+                }
+                1 if cursor > 0 => {
+                    cursor -= 1;
+                    assert_eq!(Some(expected.remove(cursor)), buffer.delete_before());
+                }
+                2 if cursor < expected.len() => {
+                    assert_eq!(Some(expected.remove(cursor)), buffer.delete_after());
+                }
+                _ => {
+                    expected.insert(cursor, i);
+                    buffer.insert(i);
+                    cursor += 1;
+                }
+            }
+            assert_eq!(cursor, buffer.cursor());
+            assert_eq!(expected.len(), buffer.len());
+        }
+        assert_eq!(expected, buffer.iter().copied().collect::<Vec<_>>());
+        assert_eq!(expected, Vec::from(buffer));
+    }
+}
@@ -0,0 +1,312 @@
+use std::fmt;
+use std::ops::Range;
+
+/// Internal buffer a piece's text comes from
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Source {
+    Original,
+    Added,
+}
+
+/// Internal span of `len` characters from `start` in one of the buffers
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct Piece {
+    source: Source,
+    start: usize,
+    len: usize,
+}
+
+/// A saved version of a `PieceTable`'s text, to restore later
+///
+/// Only holds the table's list of pieces, since the text they point to is never changed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Version {
+    pieces: Vec<Piece>,
+}
+
+/// A document as a list of pieces of two buffers, with undo and redo
+///
+/// The original text is kept as loaded, and every inserted character is appended to a second
+/// buffer that is only ever added to. The document is a list of pieces, spans of one buffer or
+/// the other, read in order. An insertion appends its text and splits the piece it lands in to
+/// make room for a piece pointing at it, and a deletion splits the pieces at its ends and drops
+/// the ones between, so an edit takes time in the number of pieces, not the length of the text.
+///
+/// Since no text is ever overwritten, an old list of pieces still reads as the old document.
+/// Saving a version copies only the list, which is how undo and redo work: every edit saves the
+/// list it replaces. Offsets count characters, not bytes.
+#[derive(Clone, Debug)]
+pub struct PieceTable {
+    original: Vec<char>,
+    added: Vec<char>,
+    pieces: Vec<Piece>,
+    len: usize,
+    undo: Vec<Version>,
+    redo: Vec<Version>,
+}
+
+impl PieceTable {
+
+    /// Creates a new `PieceTable` holding a document
+    ///
+    /// # Arguments
+    ///
+    /// * `original` - Text of the document as loaded
+    pub fn new(original: &str) -> PieceTable {
+        let original: Vec<char> = original.chars().collect();
+        let len = original.len();
+        let pieces = if len > 0 { vec![Piece { source: Source::Original, start: 0, len }] } else { Vec::new() };
+        PieceTable { original, added: Vec::new(), pieces, len, undo: Vec::new(), redo: Vec::new() }
+    }
+
+    /// Number of characters in the document
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Checks if the document is empty
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Number of pieces the document is made of
+    pub fn num_pieces(&self) -> usize {
+        self.pieces.len()
+    }
+
+    /// Inserts text before the character at an offset
+    ///
+    /// Typing at the end of the last insertion extends its piece rather than adding another.
+    ///
+    /// # Arguments
+    ///
+    /// * `offset` - Number of characters before the new text
+    /// * `text` - Text to insert
+    ///
+    /// # Panics
+    ///
+    /// If `offset` is greater than the length of the document
+    pub fn insert(&mut self, offset: usize, text: &str) {
+        assert!(offset <= self.len, "offset {} is past the end of a document of length {}", offset, self.len);
+        if text.is_empty() {
+            return;
+        }
+        self.save();
+
+        let start = self.added.len();
+        self.added.extend(text.chars());
+        let len = self.added.len() - start;
+        let i = self.split(offset);
+        match i.checked_sub(1).map(|j| &mut self.pieces[j]) {
+            Some(prev) if prev.source == Source::Added && prev.start + prev.len == start => prev.len += len,
+            _ => self.pieces.insert(i, Piece { source: Source::Added, start, len }),
+        }
+        self.len += len;
+    }
+
+    /// Deletes the characters in a range of offsets
+    ///
+    /// # Arguments
+    ///
+    /// * `range` - Offsets of the characters to delete
+    ///
+    /// # Panics
+    ///
+    /// If the range ends past the end of the document, or starts after it ends
+    pub fn delete(&mut self, range: Range<usize>) {
+        assert!(range.start <= range.end && range.end <= self.len, "range {:?} is out of bounds for a document of length {}", range, self.len);
+        if range.is_empty() {
+            return;
+        }
+        self.save();
+
+        let first = self.split(range.start);
+        let last = self.split(range.end);
+        self.pieces.drain(first..last);
+        self.len -= range.len();
+    }
+
+    /// Undoes the last edit, and returns false if there is nothing to undo
+    pub fn undo(&mut self) -> bool {
+        match self.undo.pop() {
+            Some(version) => {
+                let current = self.swap(version);
+                self.redo.push(current);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Redoes the last undone edit, and returns false if there is nothing to redo
+    ///
+    /// Any edit after an undo clears the edits that could be redone.
+    pub fn redo(&mut self) -> bool {
+        match self.redo.pop() {
+            Some(version) => {
+                let current = self.swap(version);
+                self.undo.push(current);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Saves the current version of the document, copying only its list of pieces
+    pub fn snapshot(&self) -> Version {
+        Version { pieces: self.pieces.clone() }
+    }
+
+    /// Restores a saved version of the document, as an edit that can be undone
+    ///
+    /// # Arguments
+    ///
+    /// * `version` - Version from `snapshot` on this table
+    pub fn restore(&mut self, version: &Version) {
+        self.save();
+        self.swap(version.clone());
+    }
+
+    /// Returns an iterator over the characters of the document
+    pub fn chars(&self) -> impl Iterator<Item = char> + '_ {
+        self.pieces.iter().flat_map(move |piece| self.buffer(piece.source)[piece.start..piece.start + piece.len].iter().copied())
+    }
+
+    /// Copies out the text of the document
+    pub fn text(&self) -> String {
+        self.chars().collect()
+    }
+
+    /// Internal function to save the current version for undo, clearing the redo history
+    fn save(&mut self) {
+        self.undo.push(self.snapshot());
+        self.redo.clear();
+    }
+
+    /// Internal function to make a version current, returning the version it replaced
+    fn swap(&mut self, version: Version) -> Version {
+        let old = std::mem::replace(&mut self.pieces, version.pieces);
+        self.len = self.pieces.iter().map(|piece| piece.len).sum();
+        Version { pieces: old }
+    }
+
+    /// Internal function to make a piece start at an offset, splitting the piece it falls inside,
+    /// and return that piece's index
+    fn split(&mut self, offset: usize) -> usize {
+        let mut position = 0;
+        for i in 0..self.pieces.len() {
+            if position == offset {
+                return i;
+            }
+            let piece = self.pieces[i];
+            if offset < position + piece.len {
+                let before = offset - position;
+                self.pieces[i].len = before;
+                self.pieces.insert(i + 1, Piece { start: piece.start + before, len: piece.len - before, ..piece });
+                return i + 1;
+            }
+            position += piece.len;
+        }
+        self.pieces.len()
+    }
+
+    /// Internal function to get the buffer a piece points into
+    fn buffer(&self, source: Source) -> &[char] {
+        match source {
+            Source::Original => &self.original,
+            Source::Added => &self.added,
+        }
+    }
+}
+
+impl fmt::Display for PieceTable {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for c in self.chars() {
+            write!(f, "{}", c)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::Rng;
+
+    #[test]
+    fn test_edit() {
+        let mut doc = PieceTable::new("the quick fox");
+        doc.insert(10, "brown ");
+        assert_eq!("the quick brown fox", doc.text());
+        assert_eq!(3, doc.num_pieces());
+        doc.delete(0..4);
+        doc.insert(0, "A");
+        // Typing on from the last insertion extends its piece
+        doc.insert(1, " very");
+        doc.insert(6, " ");
+        assert_eq!("A very quick brown fox", doc.to_string());
+        assert_eq!(22, doc.len());
+        doc.delete(0..doc.len());
+        assert!(doc.is_empty());
+        assert_eq!(0, doc.num_pieces());
+    }
+
+    #[test]
+    fn test_undo_redo() {
+        let mut doc = PieceTable::new("hello");
+        let saved = doc.snapshot();
+        doc.insert(5, " world");
+        doc.delete(0..1);
+        doc.insert(0, "J");
+        assert_eq!("Jello world", doc.text());
+        assert!(doc.undo());
+        assert!(doc.undo());
+        assert_eq!("hello world", doc.text());
+        assert!(doc.redo());
+        assert_eq!("ello world", doc.text());
+        // A new edit drops what could be redone
+        doc.insert(0, "C");
+        assert!(!doc.redo());
+        doc.restore(&saved);
+        assert_eq!("hello", doc.text());
+        assert!(doc.undo());
+        assert_eq!("Cello world", doc.text());
+        while doc.undo() {}
+        assert_eq!("hello", doc.text());
+        assert_eq!(5, doc.len());
+    }
+
+    #[test]
+    fn test_matches_string() {
+        let mut rng = Rng::new(131);
+        let mut doc = PieceTable::new("lorem ipsum");
+        let mut expected: Vec<char> = "lorem ipsum".chars().collect();
+        let mut history = vec![expected.clone()];
+        for _ in 0..2000 {
+            let len = expected.len() as u64;
+            if rng.gen_bool(0.4) && len > 0 {
+                let start = rng.gen_range(len) as usize;
+                let end = start + rng.gen_range(len - start as u64 + 1) as usize;
+                doc.delete(start..end);
+                expected.drain(start..end);
+            } else {
+                let offset = rng.gen_range(len + 1) as usize;
+                let c = (b'a' + rng.gen_range(26) as u8) as char;
+                let text: String = [c, 'é'].iter().take(1 + rng.gen_range(2) as usize).collect();
+                doc.insert(offset, &text);
+                expected.splice(offset..offset, text.chars());
+            }
+            assert_eq!(expected.len(), doc.len());
+            history.push(expected.clone());
+        }
+        assert_eq!(expected.iter().collect::<String>(), doc.text());
+        // Empty edits aren't saved, so undoing walks back through every distinct version
+        history.dedup();
+        let mut seen = vec![doc.text()];
+        while doc.undo() {
+            seen.push(doc.text());
+        }
+        let history: Vec<String> = history.iter().rev().map(|v| v.iter().collect()).collect();
+        assert_eq!(history, seen);
+    }
+}
@@ -0,0 +1,226 @@
+/// A Disjoint-Set (Union-Find) Data Structure over the elements `0..n`
+///
+/// Each set is a tree of elements stored as a parent array, with the root as the set's
+/// representative. With union by rank and path compression, any sequence of `m` operations on
+/// `n` elements takes `O(m * α(n))` time, where `α` is the inverse Ackermann function.
+#[derive(Clone, Debug)]
+pub struct DisjointSet {
+    parent: Vec<usize>,
+    rank: Vec<u8>,
+    size: Vec<usize>,
+    components: usize,
+}
+
+impl DisjointSet {
+
+    /// Creates a new empty `DisjointSet`
+    pub fn new() -> DisjointSet {
+        DisjointSet { parent: Vec::new(), rank: Vec::new(), size: Vec::new(), components: 0 }
+    }
+
+    /// Creates a new `DisjointSet` of `n` singleton sets, `{0}, {1}, ..., {n - 1}`
+    ///
+    /// # Arguments
+    ///
+    /// * `n` - Number of elements
+    pub fn with_size(n: usize) -> DisjointSet {
+        DisjointSet { parent: (0..n).collect(), rank: vec![0; n], size: vec![1; n], components: n }
+    }
+
+    /// Number of elements
+    pub fn len(&self) -> usize {
+        self.parent.len()
+    }
+
+    /// Checks if there are no elements
+    pub fn is_empty(&self) -> bool {
+        self.parent.is_empty()
+    }
+
+    /// Adds a new element in a singleton set, and returns the element
+    pub fn make_set(&mut self) -> usize {
+        let x = self.parent.len();
+        self.parent.push(x);
+        self.rank.push(0);
+        self.size.push(1);
+        self.components += 1;
+        x
+    }
+
+    /// Finds the representative of the set containing `x`
+    ///
+    /// Compresses the path, pointing every element on the way directly at the root.
+    ///
+    /// # Arguments
+    ///
+    /// * `x` - Element to find the set of
+    ///
+    /// # Panics
+    ///
+    /// If `x` is not an element
+    pub fn find(&mut self, x: usize) -> usize {
+        let mut root = x;
+        while self.parent[root] != root {
+            root = self.parent[root];
+        }
+
+        let mut x = x;
+        while self.parent[x] != root {
+            let next = self.parent[x];
+            self.parent[x] = root;
+            x = next;
+        }
+        root
+    }
+
+    /// Merges the sets containing `a` and `b`
+    ///
+    /// The root of lower rank is attached under the root of higher rank, so trees stay
+    /// `O(log(n))` tall. Returns `false` if `a` and `b` were already in the same set.
+    ///
+    /// # Arguments
+    ///
+    /// * `a` - Element of the first set
+    /// * `b` - Element of the second set
+    pub fn union(&mut self, a: usize, b: usize) -> bool {
+        let a = self.find(a);
+        let b = self.find(b);
+        if a == b {
+            return false;
+        }
+
+        let (root, child) = if self.rank[a] < self.rank[b] { (b, a) } else { (a, b) };
+        if self.rank[a] == self.rank[b] {
+            self.rank[root] += 1;
+        }
+        self.parent[child] = root;
+        self.size[root] += self.size[child];
+        self.components -= 1;
+        true
+    }
+
+    /// Checks if `a` and `b` are in the same set
+    ///
+    /// # Arguments
+    ///
+    /// * `a` - First element
+    /// * `b` - Second element
+    pub fn connected(&mut self, a: usize, b: usize) -> bool {
+        self.find(a) == self.find(b)
+    }
+
+    /// Number of elements in the set containing `x`
+    ///
+    /// # Arguments
+    ///
+    /// * `x` - Element of the set
+    pub fn size_of(&mut self, x: usize) -> usize {
+        let root = self.find(x);
+        self.size[root]
+    }
+
+    /// Number of disjoint sets
+    pub fn num_components(&self) -> usize {
+        self.components
+    }
+
+    /// Returns an iterator over the sets, each as a vector of its elements
+    ///
+    /// Sets are ordered by their smallest element, and the elements of a set are in ascending order.
+    pub fn components(&mut self) -> std::vec::IntoIter<Vec<usize>> {
+        let mut group = vec![usize::MAX; self.len()];
+        let mut groups: Vec<Vec<usize>> = Vec::with_capacity(self.components);
+        for x in 0..self.len() {
+            let root = self.find(x);
+            if group[root] == usize::MAX {
+                group[root] = groups.len();
+                groups.push(Vec::with_capacity(self.size[root]));
+            }
+            groups[group[root]].push(x);
+        }
+        groups.into_iter()
+    }
+}
+
+impl Default for DisjointSet {
+    fn default() -> Self {
+        DisjointSet::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_make_set() {
+        let mut set = DisjointSet::new();
+        assert_eq!(0, set.make_set());
+        assert_eq!(1, set.make_set());
+        assert_eq!(2, set.len());
+        assert!(!set.connected(0, 1));
+        assert_eq!(1, set.find(1));
+    }
+
+    #[test]
+    fn test_union() {
+        let mut set = DisjointSet::with_size(6);
+        assert!(set.union(0, 1));
+        assert!(set.union(2, 3));
+        assert!(set.union(1, 3));
+        assert!(!set.union(0, 2));
+
+        assert!(set.connected(0, 3));
+        assert!(set.connected(2, 1));
+        assert!(!set.connected(0, 4));
+        assert!(!set.connected(4, 5));
+    }
+
+    #[test]
+    fn test_union_by_rank() {
+        let mut set = DisjointSet::with_size(4);
+        set.union(0, 1);
+        // Attaching a singleton under a rank 1 root doesn't increase its rank
+        set.union(2, 0);
+        assert_eq!(1, set.rank[0]);
+        assert_eq!(0, set.find(2));
+        set.union(3, 2);
+        assert_eq!(1, set.rank[0]);
+    }
+
+    #[test]
+    fn test_sizes() {
+        let mut set = DisjointSet::with_size(5);
+        assert_eq!(5, set.num_components());
+        set.union(0, 1);
+        set.union(1, 2);
+        set.union(0, 2);
+        assert_eq!(3, set.size_of(2));
+        assert_eq!(1, set.size_of(3));
+        assert_eq!(3, set.num_components());
+
+        set.make_set();
+        assert_eq!(4, set.num_components());
+    }
+
+    #[test]
+    fn test_components() {
+        let mut set = DisjointSet::with_size(6);
+        set.union(4, 1);
+        set.union(5, 0);
+        set.union(3, 1);
+        let components: Vec<_> = set.components().collect();
+        assert_eq!(vec![vec![0, 5], vec![1, 3, 4], vec![2]], components);
+
+        assert_eq!(0, DisjointSet::new().components().count());
+    }
+
+    #[test]
+    fn test_path_compression() {
+        let mut set = DisjointSet::with_size(4);
+        // Build the chain 3 -> 2 -> 1 -> 0 by hand
+        set.parent = vec![0, 0, 1, 2];
+        assert_eq!(0, set.find(3));
+        assert_eq!(vec![0, 0, 0, 0], set.parent);
+    }
+}
@@ -0,0 +1,193 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use crate::disjoint_set::DisjointSet;
+
+/// A Disjoint-Set over arbitrary hashable keys
+///
+/// Wraps a `DisjointSet` with a map from keys to element indexes, so keys like strings or IDs
+/// can be unioned without manual index bookkeeping. Keys are added on their first `make_set`
+/// or `union`.
+#[derive(Clone, Debug)]
+pub struct DisjointSetMap<K: Hash + Eq + Clone> {
+    sets: DisjointSet,
+    index: HashMap<K, usize>,
+    keys: Vec<K>,
+}
+
+impl<K: Hash + Eq + Clone> DisjointSetMap<K> {
+
+    /// Creates a new empty `DisjointSetMap`
+    pub fn new() -> DisjointSetMap<K> {
+        DisjointSetMap { sets: DisjointSet::new(), index: HashMap::new(), keys: Vec::new() }
+    }
+
+    /// Number of keys
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    /// Checks if there are no keys
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    /// Checks if a key has been added
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - Key to look for
+    pub fn contains(&self, key: &K) -> bool {
+        self.index.contains_key(key)
+    }
+
+    /// Adds a key in a singleton set
+    ///
+    /// Returns `false` and leaves the key's set unchanged if the key was already added.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - Key to add
+    pub fn make_set(&mut self, key: K) -> bool {
+        if self.index.contains_key(&key) {
+            return false;
+        }
+        self.id(key);
+        true
+    }
+
+    /// Finds the representative key of the set containing `key`
+    ///
+    /// Returns None if the key has not been added.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - Key to find the set of
+    pub fn find(&mut self, key: &K) -> Option<&K> {
+        let i = *self.index.get(key)?;
+        let root = self.sets.find(i);
+        Some(&self.keys[root])
+    }
+
+    /// Merges the sets containing `a` and `b`, adding either key if needed
+    ///
+    /// Returns `false` if `a` and `b` were already in the same set.
+    ///
+    /// # Arguments
+    ///
+    /// * `a` - Key in the first set
+    /// * `b` - Key in the second set
+    pub fn union(&mut self, a: K, b: K) -> bool {
+        let a = self.id(a);
+        let b = self.id(b);
+        self.sets.union(a, b)
+    }
+
+    /// Checks if `a` and `b` are in the same set
+    ///
+    /// Keys that have not been added are only connected to themselves.
+    ///
+    /// # Arguments
+    ///
+    /// * `a` - First key
+    /// * `b` - Second key
+    pub fn connected(&mut self, a: &K, b: &K) -> bool {
+        match (self.index.get(a), self.index.get(b)) {
+            (Some(&a), Some(&b)) => self.sets.connected(a, b),
+            _ => a == b,
+        }
+    }
+
+    /// Number of keys in the set containing `key`
+    ///
+    /// Returns None if the key has not been added.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - Key in the set
+    pub fn size_of(&mut self, key: &K) -> Option<usize> {
+        let i = *self.index.get(key)?;
+        Some(self.sets.size_of(i))
+    }
+
+    /// Number of disjoint sets
+    pub fn num_components(&self) -> usize {
+        self.sets.num_components()
+    }
+
+    /// Returns an iterator over the sets, each as a vector of its keys
+    ///
+    /// Sets, and the keys within a set, are in the order their keys were first added.
+    pub fn components(&mut self) -> impl Iterator<Item = Vec<&K>> {
+        let keys = &self.keys;
+        self.sets.components().map(move |c| c.into_iter().map(|i| &keys[i]).collect())
+    }
+
+    /// Internal function to get the element index of a key, adding it if needed
+    fn id(&mut self, key: K) -> usize {
+        if let Some(&i) = self.index.get(&key) {
+            return i;
+        }
+        let i = self.sets.make_set();
+        self.index.insert(key.clone(), i);
+        self.keys.push(key);
+        i
+    }
+}
+
+impl<K: Hash + Eq + Clone> Default for DisjointSetMap<K> {
+    fn default() -> Self {
+        DisjointSetMap::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_make_set() {
+        let mut sets = DisjointSetMap::new();
+        assert!(sets.make_set("a"));
+        assert!(!sets.make_set("a"));
+        assert_eq!(Some(&"a"), sets.find(&"a"));
+        assert_eq!(None, sets.find(&"b"));
+        assert_eq!(1, sets.len());
+    }
+
+    #[test]
+    fn test_union() {
+        let mut sets = DisjointSetMap::new();
+        assert!(sets.union(String::from("toronto"), String::from("montreal")));
+        assert!(sets.union(String::from("vancouver"), String::from("calgary")));
+        assert!(sets.union(String::from("montreal"), String::from("calgary")));
+        assert!(!sets.union(String::from("toronto"), String::from("vancouver")));
+        assert_eq!(4, sets.len());
+
+        let root = sets.find(&String::from("toronto")).cloned();
+        assert_eq!(root.as_ref(), sets.find(&String::from("calgary")));
+    }
+
+    #[test]
+    fn test_components() {
+        let mut sets = DisjointSetMap::new();
+        sets.union('a', 'b');
+        sets.make_set('c');
+        sets.union('d', 'a');
+        assert_eq!(Some(3), sets.size_of(&'d'));
+        assert_eq!(None, sets.size_of(&'e'));
+        assert_eq!(2, sets.num_components());
+        assert_eq!(vec![vec![&'a', &'b', &'d'], vec![&'c']], sets.components().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_connected() {
+        let mut sets = DisjointSetMap::new();
+        sets.union((1, 2), (3, 4));
+        sets.make_set((5, 6));
+        assert!(sets.connected(&(1, 2), &(3, 4)));
+        assert!(!sets.connected(&(1, 2), &(5, 6)));
+        assert!(sets.connected(&(7, 8), &(7, 8)));
+        assert!(!sets.connected(&(7, 8), &(1, 2)));
+    }
+}
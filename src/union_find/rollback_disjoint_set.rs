@@ -0,0 +1,205 @@
+/// A Disjoint-Set that can undo unions and answer queries about the past
+///
+/// Uses union by rank without path compression, so every union changes exactly one parent
+/// pointer and trees stay `O(log(n))` tall. Every call to `union` is a time step: recording the
+/// changed pointer lets `rollback` undo the latest unions, and stamping each pointer with its
+/// time lets `connected_at` ask whether two elements were connected after the first `t` unions.
+/// This is the building block of offline dynamic connectivity algorithms.
+#[derive(Clone, Debug)]
+pub struct RollbackDisjointSet {
+    parent: Vec<usize>,
+    rank: Vec<u8>,
+    since: Vec<usize>,
+    history: Vec<Option<(usize, bool)>>,
+    components: usize,
+}
+
+impl RollbackDisjointSet {
+
+    /// Creates a new `RollbackDisjointSet` of `n` singleton sets, `{0}, {1}, ..., {n - 1}`
+    ///
+    /// # Arguments
+    ///
+    /// * `n` - Number of elements
+    pub fn with_size(n: usize) -> RollbackDisjointSet {
+        RollbackDisjointSet {
+            parent: (0..n).collect(),
+            rank: vec![0; n],
+            since: vec![usize::MAX; n],
+            history: Vec::new(),
+            components: n,
+        }
+    }
+
+    /// Number of elements
+    pub fn len(&self) -> usize {
+        self.parent.len()
+    }
+
+    /// Checks if there are no elements
+    pub fn is_empty(&self) -> bool {
+        self.parent.is_empty()
+    }
+
+    /// Number of unions performed so far, the current time
+    pub fn time(&self) -> usize {
+        self.history.len()
+    }
+
+    /// Number of disjoint sets
+    pub fn num_components(&self) -> usize {
+        self.components
+    }
+
+    /// Finds the representative of the set containing `x`, in `O(log(n))` time
+    ///
+    /// # Arguments
+    ///
+    /// * `x` - Element to find the set of
+    pub fn find(&self, x: usize) -> usize {
+        let mut x = x;
+        while self.parent[x] != x {
+            x = self.parent[x];
+        }
+        x
+    }
+
+    /// Merges the sets containing `a` and `b`, and advances the time by one
+    ///
+    /// Returns `false` if `a` and `b` were already in the same set. The call is still recorded,
+    /// so `rollback` undoes calls one for one.
+    ///
+    /// # Arguments
+    ///
+    /// * `a` - Element of the first set
+    /// * `b` - Element of the second set
+    pub fn union(&mut self, a: usize, b: usize) -> bool {
+        let a = self.find(a);
+        let b = self.find(b);
+        if a == b {
+            self.history.push(None);
+            return false;
+        }
+
+        let (root, child) = if self.rank[a] < self.rank[b] { (b, a) } else { (a, b) };
+        let bumped = self.rank[a] == self.rank[b];
+        if bumped {
+            self.rank[root] += 1;
+        }
+        self.parent[child] = root;
+        self.since[child] = self.history.len() + 1;
+        self.history.push(Some((child, bumped)));
+        self.components -= 1;
+        true
+    }
+
+    /// Checks if `a` and `b` are in the same set
+    ///
+    /// # Arguments
+    ///
+    /// * `a` - First element
+    /// * `b` - Second element
+    pub fn connected(&self, a: usize, b: usize) -> bool {
+        self.find(a) == self.find(b)
+    }
+
+    /// Undoes the latest `k` unions, and returns how many were undone
+    ///
+    /// # Arguments
+    ///
+    /// * `k` - Number of unions to undo
+    pub fn rollback(&mut self, k: usize) -> usize {
+        let k = k.min(self.history.len());
+        for _ in 0..k {
+            if let Some((child, bumped)) = self.history.pop().unwrap() {
+                let root = self.parent[child];
+                if bumped {
+                    self.rank[root] -= 1;
+                }
+                self.parent[child] = child;
+                self.since[child] = usize::MAX;
+                self.components += 1;
+            }
+        }
+        k
+    }
+
+    /// Checks if `a` and `b` were in the same set after the first `t` unions
+    ///
+    /// Only follows parent pointers that existed at time `t`.
+    ///
+    /// # Arguments
+    ///
+    /// * `a` - First element
+    /// * `b` - Second element
+    /// * `t` - Time to query, from 0 to `time()`
+    pub fn connected_at(&self, a: usize, b: usize, t: usize) -> bool {
+        self.find_at(a, t) == self.find_at(b, t)
+    }
+
+    /// Internal function to find the representative of `x` after the first `t` unions
+    fn find_at(&self, x: usize, t: usize) -> usize {
+        let mut x = x;
+        while self.parent[x] != x && self.since[x] <= t {
+            x = self.parent[x];
+        }
+        x
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_union() {
+        let mut set = RollbackDisjointSet::with_size(4);
+        assert!(set.union(0, 1));
+        assert!(set.union(2, 3));
+        assert!(!set.union(1, 0));
+        assert!(set.connected(0, 1));
+        assert!(!set.connected(0, 2));
+        assert_eq!(3, set.time());
+        assert_eq!(2, set.num_components());
+    }
+
+    #[test]
+    fn test_rollback() {
+        let mut set = RollbackDisjointSet::with_size(5);
+        set.union(0, 1);
+        set.union(1, 2);
+        set.union(0, 2);
+        set.union(3, 4);
+        set.union(2, 4);
+        assert_eq!(1, set.num_components());
+
+        assert_eq!(2, set.rollback(2));
+        assert!(set.connected(0, 2));
+        assert!(!set.connected(3, 4));
+        assert_eq!(3, set.num_components());
+
+        // The no-op union at time 3 is undone one for one
+        assert_eq!(1, set.rollback(1));
+        assert!(set.connected(0, 2));
+        assert_eq!(2, set.rollback(10));
+        assert_eq!(5, set.num_components());
+        assert_eq!(0, set.time());
+        assert_eq!(vec![0, 0, 0, 0, 0], set.rank);
+    }
+
+    #[test]
+    fn test_connected_at() {
+        let mut set = RollbackDisjointSet::with_size(4);
+        set.union(0, 1);
+        set.union(2, 3);
+        set.union(1, 3);
+
+        assert!(!set.connected_at(0, 1, 0));
+        assert!(set.connected_at(0, 1, 1));
+        assert!(!set.connected_at(2, 3, 1));
+        assert!(set.connected_at(2, 3, 2));
+        assert!(!set.connected_at(0, 3, 2));
+        assert!(set.connected_at(0, 3, 3));
+        assert!(set.connected_at(0, 3, 100));
+    }
+}
@@ -0,0 +1,194 @@
+use std::fmt;
+use std::ops::{Add, Sub};
+
+/// Error for a relation that contradicts the relations already known
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct InconsistentError<W> {
+    /// Difference `a - b` implied by the earlier relations
+    pub known: W,
+    /// Difference `a - b` given to `union_with`
+    pub given: W,
+}
+
+impl<W: fmt::Debug> fmt::Display for InconsistentError<W> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "inconsistent relation: difference is {:?} but {:?} was given", self.known, self.given)
+    }
+}
+
+impl<W: fmt::Debug> std::error::Error for InconsistentError<W> {}
+
+/// A Disjoint-Set where each element has a potential relative to the other elements in its set
+///
+/// Stores, for every element, its offset from its parent, so the offset to the root is the sum
+/// along the path. `union_with(a, b, w)` records the constraint `a - b = w`, and `diff(a, b)`
+/// answers `a - b` for any two connected elements, which solves systems of constraints like
+/// "a is w more than b". Uses union by rank and path compression, keeping offsets up to date as
+/// paths are compressed.
+#[derive(Clone, Debug)]
+pub struct WeightedDisjointSet<W> {
+    parent: Vec<usize>,
+    rank: Vec<u8>,
+    offset: Vec<W>,
+}
+
+impl<W: Copy + Default + PartialEq + Add<Output = W> + Sub<Output = W>> WeightedDisjointSet<W> {
+
+    /// Creates a new `WeightedDisjointSet` of `n` singleton sets
+    ///
+    /// # Arguments
+    ///
+    /// * `n` - Number of elements
+    pub fn with_size(n: usize) -> WeightedDisjointSet<W> {
+        WeightedDisjointSet { parent: (0..n).collect(), rank: vec![0; n], offset: vec![W::default(); n] }
+    }
+
+    /// Number of elements
+    pub fn len(&self) -> usize {
+        self.parent.len()
+    }
+
+    /// Checks if there are no elements
+    pub fn is_empty(&self) -> bool {
+        self.parent.is_empty()
+    }
+
+    /// Finds the representative of the set containing `x`, and `x`'s potential relative to it
+    ///
+    /// # Arguments
+    ///
+    /// * `x` - Element to find the set of
+    pub fn find(&mut self, x: usize) -> (usize, W) {
+        let mut path = Vec::new();
+        let mut root = x;
+        while self.parent[root] != root {
+            path.push(root);
+            root = self.parent[root];
+        }
+
+        // Walk back down from the element nearest the root, so each parent's offset is already
+        // relative to the root when its child is updated
+        for &y in path.iter().rev() {
+            let p = self.parent[y];
+            if p != root {
+                self.offset[y] = self.offset[y] + self.offset[p];
+            }
+            self.parent[y] = root;
+        }
+        (root, self.offset[x])
+    }
+
+    /// Records the constraint `a - b = w`, merging the sets of `a` and `b`
+    ///
+    /// Returns `Ok(false)` if the constraint was already implied, `Ok(true)` if the sets were
+    /// merged, and an error if the constraint contradicts the known difference.
+    ///
+    /// # Arguments
+    ///
+    /// * `a` - First element
+    /// * `b` - Second element
+    /// * `w` - Difference `a - b`
+    pub fn union_with(&mut self, a: usize, b: usize, w: W) -> Result<bool, InconsistentError<W>> {
+        let (ra, wa) = self.find(a);
+        let (rb, wb) = self.find(b);
+        if ra == rb {
+            let known = wa - wb;
+            return if known == w { Ok(false) } else { Err(InconsistentError { known, given: w }) };
+        }
+
+        // a = ra + wa and b = rb + wb, so a - b = w gives ra - rb = w - wa + wb
+        let d = w - wa + wb;
+        if self.rank[ra] < self.rank[rb] {
+            self.parent[ra] = rb;
+            self.offset[ra] = d;
+        } else {
+            if self.rank[ra] == self.rank[rb] {
+                self.rank[ra] += 1;
+            }
+            self.parent[rb] = ra;
+            self.offset[rb] = W::default() - d;
+        }
+        Ok(true)
+    }
+
+    /// The difference `a - b`, or None if `a` and `b` are not in the same set
+    ///
+    /// # Arguments
+    ///
+    /// * `a` - First element
+    /// * `b` - Second element
+    pub fn diff(&mut self, a: usize, b: usize) -> Option<W> {
+        let (ra, wa) = self.find(a);
+        let (rb, wb) = self.find(b);
+        if ra == rb {
+            Some(wa - wb)
+        } else {
+            None
+        }
+    }
+
+    /// Checks if `a` and `b` are in the same set
+    ///
+    /// # Arguments
+    ///
+    /// * `a` - First element
+    /// * `b` - Second element
+    pub fn connected(&mut self, a: usize, b: usize) -> bool {
+        self.find(a).0 == self.find(b).0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_diff() {
+        let mut set = WeightedDisjointSet::with_size(4);
+        assert_eq!(Ok(true), set.union_with(0, 1, 5));
+        assert_eq!(Ok(true), set.union_with(1, 2, 3));
+        assert_eq!(Some(5), set.diff(0, 1));
+        assert_eq!(Some(-5), set.diff(1, 0));
+        assert_eq!(Some(8), set.diff(0, 2));
+        assert_eq!(Some(0), set.diff(2, 2));
+        assert_eq!(None, set.diff(0, 3));
+    }
+
+    #[test]
+    fn test_inconsistent() {
+        let mut set = WeightedDisjointSet::with_size(3);
+        set.union_with(0, 1, 2).unwrap();
+        set.union_with(1, 2, 2).unwrap();
+        assert_eq!(Ok(false), set.union_with(0, 2, 4));
+
+        let err = set.union_with(2, 0, 1).unwrap_err();
+        assert_eq!(InconsistentError { known: -4, given: 1 }, err);
+        assert_eq!("inconsistent relation: difference is -4 but 1 was given", err.to_string());
+    }
+
+    #[test]
+    fn test_path_compression() {
+        let mut set = WeightedDisjointSet::with_size(6);
+        // Chain merges of equal rank trees so paths get long before they are compressed
+        set.union_with(0, 1, 1).unwrap();
+        set.union_with(2, 3, 1).unwrap();
+        set.union_with(4, 5, 1).unwrap();
+        set.union_with(1, 3, 10).unwrap();
+        set.union_with(5, 0, 100).unwrap();
+        for x in 0..6 {
+            for y in 0..6 {
+                assert_eq!(Some(set.diff(x, 0).unwrap() - set.diff(y, 0).unwrap()), set.diff(x, y));
+            }
+        }
+        // 4 - 5 = 1, 5 - 0 = 100, 0 - 1 = 1, 1 - 3 = 10
+        assert_eq!(Some(112), set.diff(4, 3));
+    }
+
+    #[test]
+    fn test_floats() {
+        let mut set = WeightedDisjointSet::with_size(3);
+        set.union_with(0, 1, 0.5).unwrap();
+        set.union_with(2, 1, 1.5).unwrap();
+        assert_eq!(Some(-1.0), set.diff(0, 2));
+    }
+}